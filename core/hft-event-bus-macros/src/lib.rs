@@ -0,0 +1,140 @@
+//! Procedural macros for `hft-event-bus`
+//!
+//! Use them through the re-exports in `hft_event_bus`; the expansions refer
+//! to `::hft_event_bus` paths.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, LitInt, LitStr};
+
+/// Turn a struct or enum into an event type known to the `EventRegistry`
+///
+/// Derives `Serialize` and `Deserialize`, implements `StaticEventType` and
+/// `Event`, and submits a deserializer that the global `EventRegistry` picks
+/// up on first use, so no `register_event_type!` call is needed at startup.
+/// The type must also implement `Debug`, `Send` and `Sync`.
+///
+/// The event type defaults to the type name in snake_case without an
+/// `Event` suffix (`SpreadAlertEvent` becomes `"spread_alert"`):
+///
+/// ```rust,ignore
+/// #[register_event]
+/// #[derive(Debug, Clone)]
+/// pub struct SpreadAlertEvent {
+///     pub symbol: String,
+///     pub spread_bps: f64,
+/// }
+///
+/// #[register_event(name = "venue_halt", priority = 0)]
+/// #[derive(Debug, Clone)]
+/// pub struct VenueHalted {
+///     pub venue: String,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn register_event(args: TokenStream, input: TokenStream) -> TokenStream {
+    let mut name: Option<LitStr> = None;
+    let mut priority: Option<LitInt> = None;
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("name") {
+            name = Some(meta.value()?.parse()?);
+            Ok(())
+        } else if meta.path.is_ident("priority") {
+            let value: LitInt = meta.value()?.parse()?;
+            value.base10_parse::<u8>()?;
+            priority = Some(value);
+            Ok(())
+        } else {
+            Err(meta.error("expected `name` or `priority`"))
+        }
+    });
+    parse_macro_input!(args with parser);
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(input, name, priority)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput, name: Option<LitStr>, priority: Option<LitInt>) -> syn::Result<proc_macro2::TokenStream> {
+    if let Data::Union(_) = input.data {
+        return Err(syn::Error::new_spanned(&input.ident, "#[register_event] does not support unions"));
+    }
+    // The registry stores one deserializer per name, so the type must be concrete
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(&input.generics, "#[register_event] does not support generic types"));
+    }
+
+    let ident = &input.ident;
+    let name = name.unwrap_or_else(|| LitStr::new(&default_event_type(&ident.to_string()), ident.span()));
+    let priority = priority.map(|priority| quote! {
+        fn priority(&self) -> u8 { #priority }
+    });
+
+    Ok(quote! {
+        #[derive(
+            ::hft_event_bus::serde_support::serde::Serialize,
+            ::hft_event_bus::serde_support::serde::Deserialize
+        )]
+        #[serde(crate = "::hft_event_bus::serde_support::serde")]
+        #input
+
+        impl ::hft_event_bus::events::StaticEventType for #ident {
+            const EVENT_TYPE: &'static str = #name;
+        }
+
+        impl ::hft_event_bus::events::Event for #ident {
+            fn event_type(&self) -> &'static str { #name }
+
+            #priority
+
+            fn to_json(&self) -> ::std::option::Option<::hft_event_bus::serde_support::serde_json::Value> {
+                ::hft_event_bus::serde_support::serde_json::to_value(self).ok()
+            }
+        }
+
+        ::hft_event_bus::serde_support::inventory::submit! {
+            ::hft_event_bus::serde_support::EventRegistration::of::<#ident>()
+        }
+    })
+}
+
+/// `MarketDataEvent` -> `market_data`, `VWAPCross` -> `vwap_cross`
+fn default_event_type(ident: &str) -> String {
+    let base = match ident.strip_suffix("Event") {
+        Some(base) if !base.is_empty() => base,
+        _ => ident,
+    };
+
+    let chars: Vec<char> = base.chars().collect();
+    let mut name = String::with_capacity(base.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let after_lower = i > 0 && !chars[i - 1].is_uppercase();
+            let ends_acronym = i > 0
+                && chars[i - 1].is_uppercase()
+                && chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            if after_lower || ends_acronym {
+                name.push('_');
+            }
+            name.extend(c.to_lowercase());
+        } else {
+            name.push(c);
+        }
+    }
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_event_type() {
+        assert_eq!(default_event_type("MarketDataEvent"), "market_data");
+        assert_eq!(default_event_type("FillEvent"), "fill");
+        assert_eq!(default_event_type("VWAPCrossEvent"), "vwap_cross");
+        assert_eq!(default_event_type("Level2Update"), "level2_update");
+        assert_eq!(default_event_type("Event"), "event");
+    }
+}
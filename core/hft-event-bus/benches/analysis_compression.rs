@@ -0,0 +1,68 @@
+//! zstd compression of `AnalysisResults` with a 10 000-entry IC series
+//!
+//! Run with `--features compress`. Prints the compressed size against the
+//! raw JSON once per level before timing.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use hft_event_bus::research_topic::{AnalysisResults, CompressedAnalysisResults, IcResults};
+
+const POINTS: usize = 10_000;
+
+/// Daily IC values in [-0.1, 0.1) from a deterministic generator
+fn results() -> AnalysisResults {
+    let mut state = 0x9e37_79b9_7f4a_7c15u64;
+    let ic_series = (0..POINTS)
+        .map(|i| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let ic = ((state >> 11) as f64 / (1u64 << 53) as f64 - 0.5) * 0.2;
+            (1_600_000_000_000_000_000 + i as i64 * 86_400_000_000_000, ic)
+        })
+        .collect();
+    AnalysisResults {
+        ic_results: Some(IcResults {
+            mean_ic: 0.01,
+            std_ic: 0.05,
+            ir: 0.2,
+            hit_rate: 0.52,
+            ic_series,
+        }),
+        ..AnalysisResults::default()
+    }
+}
+
+fn bench_compression(c: &mut Criterion) {
+    let mut group = c.benchmark_group("analysis_compression");
+    let results = results();
+    let raw = serde_json::to_vec(&results).unwrap().len();
+
+    for level in [1, 3, 9] {
+        let compressed = CompressedAnalysisResults::from_results(&results, level).unwrap();
+        let size = compressed.to_bytes().unwrap().len();
+        println!(
+            "level {}: {} -> {} bytes ({:.1}% smaller, {} as base64)",
+            level,
+            raw,
+            size,
+            100.0 * (1.0 - size as f64 / raw as f64),
+            compressed.as_encoded().len()
+        );
+
+        group.bench_with_input(BenchmarkId::new("compress", level), &level, |b, &level| {
+            b.iter(|| black_box(CompressedAnalysisResults::from_results(&results, level).unwrap()))
+        });
+        group.bench_with_input(BenchmarkId::new("decompress", level), &compressed, |b, compressed| {
+            b.iter(|| black_box(compressed.to_results().unwrap()))
+        });
+    }
+
+    group.bench_function("raw_json", |b| {
+        b.iter(|| black_box(serde_json::to_vec(&results).unwrap()))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_compression);
+criterion_main!(benches);
@@ -0,0 +1,103 @@
+//! EnvelopePool benchmarks: pooled boxes vs Box::new per event, and
+//! EventBus::publish_pooled vs publish
+//!
+//! Each allocation iteration processes one second's worth of events at 1M,
+//! 5M and 10M events/sec; an iteration time under one second means the rate
+//! is sustainable. A window of in-flight events models subscribers that
+//! still hold recent envelopes.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use hft_event_bus::{EnvelopePool, Event, EventBus};
+use std::collections::VecDeque;
+use tokio::runtime::Runtime;
+
+/// Events still held by consumers at any time
+const IN_FLIGHT: usize = 1024;
+
+/// Heap-free payload so only the box allocation is measured
+#[derive(Debug, Clone, Copy, Default)]
+struct Tick {
+    timestamp: i64,
+    price: f64,
+    size: f64,
+}
+
+impl Event for Tick {
+    fn event_type(&self) -> &'static str {
+        "tick"
+    }
+}
+
+fn tick(i: usize) -> Tick {
+    Tick {
+        timestamp: i as i64,
+        price: 6000.0 + (i % 64) as f64 * 0.25,
+        size: 1.0,
+    }
+}
+
+fn bench_allocation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("envelope_allocation");
+    group.sample_size(10);
+    
+    for rate in [1_000_000usize, 5_000_000, 10_000_000] {
+        group.throughput(Throughput::Elements(rate as u64));
+        let label = format!("{}M_per_sec", rate / 1_000_000);
+        
+        group.bench_with_input(BenchmarkId::new("box_new", &label), &rate, |b, &rate| {
+            b.iter(|| {
+                let mut window = VecDeque::with_capacity(IN_FLIGHT);
+                for i in 0..rate {
+                    if window.len() == IN_FLIGHT {
+                        window.pop_front();
+                    }
+                    window.push_back(Box::new(tick(i)));
+                }
+                black_box(window.back().map(|t| (t.timestamp, t.price, t.size)))
+            })
+        });
+        
+        let pool = EnvelopePool::<Tick>::new(IN_FLIGHT * 2);
+        pool.prefill(IN_FLIGHT * 2);
+        group.bench_with_input(BenchmarkId::new("pooled", &label), &rate, |b, &rate| {
+            b.iter(|| {
+                let mut window = VecDeque::with_capacity(IN_FLIGHT);
+                for i in 0..rate {
+                    if window.len() == IN_FLIGHT {
+                        window.pop_front();
+                    }
+                    window.push_back(pool.acquire(tick(i)));
+                }
+                black_box(window.back().map(|t| (t.timestamp, t.price, t.size)))
+            })
+        });
+    }
+    
+    group.finish();
+}
+
+/// Publish through the bus to one subscriber that never reads, so once its
+/// queue is full every publish evicts (and returns to the pool) the oldest
+/// envelope
+fn bench_publish(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let mut group = c.benchmark_group("bus_publish");
+    
+    let bus = EventBus::new();
+    let _rx = rt.block_on(bus.subscribe("tick"));
+    let pool = EnvelopePool::<Tick>::new(16_384);
+    pool.prefill(16_384);
+    
+    group.bench_function("publish", |b| {
+        b.to_async(&rt).iter(|| async { black_box(bus.publish(tick(0)).await.unwrap()) })
+    });
+    
+    group.bench_function("publish_pooled", |b| {
+        b.to_async(&rt).iter(|| async { bus.publish_pooled(pool.acquire(tick(0))).await.unwrap() })
+    });
+    
+    group.finish();
+}
+
+criterion_group!(benches, bench_allocation, bench_publish);
+criterion_main!(benches);
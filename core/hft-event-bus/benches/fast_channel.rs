@@ -0,0 +1,196 @@
+//! FastChannel benchmarks: flume-backed bounded channel vs SPSC ring buffer
+//! vs Disruptor ring, and rate limiter accuracy
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use hft_event_bus::fast_channel::{DisruptorChannel, FastChannel};
+use market_data_engine::types::{InstrumentId, Price, Quantity, SideV2, Timestamp, TradeFlags, TradeV2};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const EVENTS: u64 = 1_000_000;
+const CAPACITY: usize = 65_536;
+const BROADCAST_EVENTS: u64 = 10_000_000;
+
+fn create_trade(id: u64) -> TradeV2 {
+    TradeV2 {
+        timestamp: Timestamp::from_nanos(id as i64),
+        instrument_id: InstrumentId::from_raw(1),
+        price: Price::from_float(100.0),
+        quantity: Quantity::new(10),
+        side: SideV2::Buy,
+        trade_id: id,
+        exchange: 1,
+        flags: TradeFlags::new(0),
+        _padding: [0; 12],
+    }
+}
+
+/// Producer thread -> consumer thread, 1M events
+fn bench_throughput(c: &mut Criterion) {
+    let mut group = c.benchmark_group("channel_throughput");
+    group.throughput(Throughput::Elements(EVENTS));
+    group.sample_size(10);
+    
+    group.bench_function(BenchmarkId::new("flume_bounded", EVENTS), |b| {
+        b.iter(|| {
+            let channel = FastChannel::<TradeV2>::bounded(CAPACITY);
+            let tx = channel.sender();
+            let producer = thread::spawn(move || {
+                for i in 0..EVENTS {
+                    tx.send(create_trade(i)).unwrap();
+                }
+            });
+            for _ in 0..EVENTS {
+                black_box(channel.recv().unwrap());
+            }
+            producer.join().unwrap();
+        })
+    });
+    
+    group.bench_function(BenchmarkId::new("spsc_ring", EVENTS), |b| {
+        b.iter(|| {
+            let (mut tx, mut rx) = FastChannel::<TradeV2>::spsc(CAPACITY).unwrap();
+            let producer = thread::spawn(move || {
+                for i in 0..EVENTS {
+                    tx.send(create_trade(i)).unwrap();
+                }
+            });
+            for _ in 0..EVENTS {
+                black_box(rx.recv().unwrap());
+            }
+            producer.join().unwrap();
+        })
+    });
+    
+    group.finish();
+}
+
+/// Producer thread -> consumer thread, 10M events
+fn bench_disruptor_throughput(c: &mut Criterion) {
+    let mut group = c.benchmark_group("disruptor_throughput");
+    group.throughput(Throughput::Elements(BROADCAST_EVENTS));
+    group.sample_size(10);
+    
+    group.bench_function(BenchmarkId::new("flume_bounded", BROADCAST_EVENTS), |b| {
+        b.iter(|| {
+            let channel = FastChannel::<TradeV2>::bounded(CAPACITY);
+            let tx = channel.sender();
+            let producer = thread::spawn(move || {
+                for i in 0..BROADCAST_EVENTS {
+                    tx.send(create_trade(i)).unwrap();
+                }
+            });
+            for _ in 0..BROADCAST_EVENTS {
+                black_box(channel.recv().unwrap());
+            }
+            producer.join().unwrap();
+        })
+    });
+    
+    for consumers in [1, 2] {
+        group.bench_function(BenchmarkId::new("disruptor", consumers), |b| {
+            b.iter(|| {
+                let channel = DisruptorChannel::<TradeV2, CAPACITY>::new();
+                let mut tx = channel.new_producer().unwrap();
+                let handles: Vec<_> = (0..consumers)
+                    .map(|_| {
+                        let mut rx = channel.add_consumer().unwrap();
+                        thread::spawn(move || {
+                            for _ in 0..BROADCAST_EVENTS {
+                                black_box(rx.consume());
+                            }
+                        })
+                    })
+                    .collect();
+                for i in 0..BROADCAST_EVENTS {
+                    tx.publish(create_trade(i));
+                }
+                for handle in handles {
+                    handle.join().unwrap();
+                }
+            })
+        });
+    }
+    
+    group.finish();
+}
+
+/// Rate-limited send + recv at fixed targets; panics if the achieved rate
+/// is more than 1% off target
+fn bench_rate_limit(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rate_limit_accuracy");
+    group.sample_size(10);
+    
+    for target in [100_000u64, 1_000_000, 5_000_000] {
+        // 100 ms worth of events per iteration
+        let events = target / 10;
+        group.throughput(Throughput::Elements(events));
+        group.bench_function(BenchmarkId::new("token_bucket", target), |b| {
+            b.iter_custom(|iters| {
+                let mut total = Duration::ZERO;
+                for _ in 0..iters {
+                    let channel = FastChannel::<TradeV2>::bounded(CAPACITY).with_rate_limit(target);
+                    // Spend the initial burst so only refills are measured
+                    while channel.bucket().try_acquire() {}
+                    
+                    let start = Instant::now();
+                    for i in 0..events {
+                        channel.send_blocking(create_trade(i), u64::MAX).unwrap();
+                        black_box(channel.recv().unwrap());
+                    }
+                    let elapsed = start.elapsed();
+                    
+                    let rate = events as f64 / elapsed.as_secs_f64();
+                    let error = (rate - target as f64).abs() / target as f64;
+                    assert!(error < 0.01, "target {}/s, achieved {:.0}/s", target, rate);
+                    total += elapsed;
+                }
+                total
+            })
+        });
+    }
+    
+    group.finish();
+}
+
+/// Single-thread send + recv round trip (per-event latency)
+fn bench_latency(c: &mut Criterion) {
+    let mut group = c.benchmark_group("channel_latency");
+    
+    let channel = FastChannel::<TradeV2>::bounded(CAPACITY);
+    group.bench_function("flume_bounded", |b| {
+        let mut id = 0;
+        b.iter(|| {
+            id += 1;
+            channel.send(create_trade(id)).unwrap();
+            black_box(channel.recv().unwrap())
+        })
+    });
+    
+    let (mut tx, mut rx) = FastChannel::<TradeV2>::spsc(CAPACITY).unwrap();
+    group.bench_function("spsc_ring", |b| {
+        let mut id = 0;
+        b.iter(|| {
+            id += 1;
+            tx.send(create_trade(id)).unwrap();
+            black_box(rx.recv().unwrap())
+        })
+    });
+    
+    let channel = DisruptorChannel::<TradeV2, CAPACITY>::new();
+    let mut tx = channel.new_producer().unwrap();
+    let mut rx = channel.add_consumer().unwrap();
+    group.bench_function("disruptor", |b| {
+        let mut id = 0;
+        b.iter(|| {
+            id += 1;
+            tx.publish(create_trade(id));
+            black_box(rx.consume())
+        })
+    });
+    
+    group.finish();
+}
+
+criterion_group!(benches, bench_throughput, bench_disruptor_throughput, bench_rate_limit, bench_latency);
+criterion_main!(benches);
@@ -0,0 +1,62 @@
+//! Rolling IC over 10 000 signal/return pairs: incremental ranks vs
+//! re-ranking every window from scratch
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use hft_event_bus::research_topic::{spearman_correlation, IcStreamCalculator};
+use uuid::Uuid;
+
+const POINTS: usize = 10_000;
+
+/// Deterministic pseudo-random pairs with a weak positive relationship
+fn pairs() -> Vec<(f64, f64)> {
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        (state >> 11) as f64 / (1u64 << 53) as f64 - 0.5
+    };
+    (0..POINTS)
+        .map(|_| {
+            let signal = next();
+            (signal, 0.1 * signal + next())
+        })
+        .collect()
+}
+
+fn bench_rolling_ic(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rolling_ic");
+    let data = pairs();
+    
+    for window in [50, 250, 1_000] {
+        group.bench_with_input(BenchmarkId::new("incremental", window), &window, |b, &window| {
+            b.iter(|| {
+                let mut ic = IcStreamCalculator::new(Uuid::nil(), window);
+                let mut last = None;
+                for (i, &(signal, ret)) in data.iter().enumerate() {
+                    ic.update_signal(signal, i as i64);
+                    if ic.update_return(ret, i as i64).is_some() {
+                        last = ic.current_ic();
+                    }
+                }
+                black_box(last)
+            })
+        });
+        
+        group.bench_with_input(BenchmarkId::new("batch", window), &window, |b, &window| {
+            let (signals, returns): (Vec<f64>, Vec<f64>) = data.iter().copied().unzip();
+            b.iter(|| {
+                let mut last = 0.0;
+                for end in window..=POINTS {
+                    last = spearman_correlation(&signals[end - window..end], &returns[end - window..end]);
+                }
+                black_box(last)
+            })
+        });
+    }
+    
+    group.finish();
+}
+
+criterion_group!(benches, bench_rolling_ic);
+criterion_main!(benches);
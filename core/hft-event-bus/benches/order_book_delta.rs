@@ -0,0 +1,72 @@
+//! Order book benchmarks: full 1 000-level snapshot vs delta encoding
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use hft_event_bus::{OrderBook, OrderBookDeltaEvent, OrderBookEvent, PriceLevel};
+
+const LEVELS: usize = 1_000;
+
+fn full_book() -> OrderBookEvent {
+    OrderBookEvent {
+        timestamp: 0,
+        symbol: "ES".to_string(),
+        bids: (0..LEVELS).map(|i| (5000.0 - i as f64 * 0.25, 1.0 + i as f64)).collect(),
+        asks: (0..LEVELS).map(|i| (5000.25 + i as f64 * 0.25, 1.0 + i as f64)).collect(),
+    }
+}
+
+/// Typical tick: a handful of levels near the touch change
+fn tick_delta(seq: i64) -> OrderBookDeltaEvent {
+    OrderBookDeltaEvent {
+        timestamp: seq,
+        symbol: "ES".to_string(),
+        bid_changes: vec![
+            PriceLevel { price: 5000.0, new_size: 3.0 + (seq % 5) as f64 },
+            PriceLevel { price: 4999.75, new_size: 0.0 },
+        ],
+        ask_changes: vec![PriceLevel { price: 5000.5, new_size: 7.0 }],
+    }
+}
+
+fn bench_encoding(c: &mut Criterion) {
+    let mut group = c.benchmark_group("order_book_encode");
+    let snapshot = full_book();
+    let delta = tick_delta(1);
+    
+    println!(
+        "encoded size: snapshot {} bytes, delta {} bytes",
+        bincode::serialize(&snapshot).unwrap().len(),
+        bincode::serialize(&delta).unwrap().len(),
+    );
+    
+    group.bench_function("full_snapshot", |b| {
+        b.iter(|| black_box(bincode::serialize(black_box(&snapshot)).unwrap()))
+    });
+    group.bench_function("delta", |b| {
+        b.iter(|| black_box(bincode::serialize(black_box(&delta)).unwrap()))
+    });
+    
+    group.finish();
+}
+
+fn bench_apply(c: &mut Criterion) {
+    let mut group = c.benchmark_group("order_book_apply");
+    let snapshot = full_book();
+    
+    group.bench_function("replace_snapshot", |b| {
+        b.iter(|| black_box(OrderBook::from_snapshot(black_box(&snapshot))))
+    });
+    
+    let mut book = OrderBook::from_snapshot(&snapshot);
+    let mut seq = 0;
+    group.bench_function("apply_delta", |b| {
+        b.iter(|| {
+            seq += 1;
+            book.apply_delta(black_box(&tick_delta(seq)));
+        })
+    });
+    
+    group.finish();
+}
+
+criterion_group!(benches, bench_encoding, bench_apply);
+criterion_main!(benches);
@@ -0,0 +1,53 @@
+//! EventReplay::run_parallel throughput at 1, 4, 8 and 16 threads
+//!
+//! 100 000 market data events over 64 symbols, one idle subscriber.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use hft_event_bus::{EventBus, EventEnvelope, EventReplay, MarketDataEvent, ReplaySpeed};
+
+const EVENTS: usize = 100_000;
+const SYMBOLS: usize = 64;
+
+fn events() -> Vec<EventEnvelope> {
+    (0..EVENTS)
+        .map(|i| {
+            let price = 100.0 + (i % 100) as f64;
+            let mut envelope = EventEnvelope::new(
+                MarketDataEvent {
+                    timestamp: i as i64,
+                    symbol: format!("SYM{}", i % SYMBOLS),
+                    price,
+                    volume: 1.0,
+                    bid_price: price - 0.01,
+                    bid_size: 1.0,
+                    ask_price: price + 0.01,
+                    ask_size: 1.0,
+                },
+                5,
+            );
+            envelope.timestamp_ns = i as i64;
+            envelope
+        })
+        .collect()
+}
+
+fn bench_run_parallel(c: &mut Criterion) {
+    let rt = tokio::runtime::Builder::new_multi_thread().enable_all().build().unwrap();
+    let bus = EventBus::new();
+    let _receiver = rt.block_on(bus.subscribe_market_data());
+    let mut replay = EventReplay::new(bus, ReplaySpeed::Max);
+    replay.load_events(events()).unwrap();
+
+    let mut group = c.benchmark_group("run_parallel");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(EVENTS as u64));
+    for threads in [1, 4, 8, 16] {
+        group.bench_with_input(BenchmarkId::from_parameter(threads), &threads, |b, &threads| {
+            b.iter(|| rt.block_on(replay.run_parallel(threads)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_run_parallel);
+criterion_main!(benches);
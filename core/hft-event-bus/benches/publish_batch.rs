@@ -0,0 +1,53 @@
+//! EventBus benchmarks: per-event publish vs publish_batch
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use hft_event_bus::{EventBus, MarketDataEvent};
+use tokio::runtime::Runtime;
+
+fn create_market_data(i: usize) -> MarketDataEvent {
+    MarketDataEvent {
+        timestamp: i as i64,
+        symbol: "ES".to_string(),
+        price: 6000.0 + i as f64 * 0.25,
+        volume: 1.0,
+        bid_price: 5999.75,
+        bid_size: 10.0,
+        ask_price: 6000.25,
+        ask_size: 10.0,
+    }
+}
+
+fn bench_publish(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let mut group = c.benchmark_group("event_bus_publish");
+    
+    for size in [8usize, 64, 512] {
+        let events: Vec<MarketDataEvent> = (0..size).map(create_market_data).collect();
+        group.throughput(Throughput::Elements(size as u64));
+        
+        let bus = EventBus::new();
+        let _rx = rt.block_on(bus.subscribe_market_data());
+        
+        group.bench_with_input(BenchmarkId::new("single", size), &events, |b, events| {
+            b.iter(|| {
+                rt.block_on(async {
+                    for event in events {
+                        bus.publish(event.clone()).await.unwrap();
+                    }
+                })
+            })
+        });
+        
+        group.bench_with_input(BenchmarkId::new("batch", size), &events, |b, events| {
+            b.iter(|| {
+                let delivered = rt.block_on(bus.publish_batch(events)).unwrap();
+                black_box(delivered)
+            })
+        });
+    }
+    
+    group.finish();
+}
+
+criterion_group!(benches, bench_publish);
+criterion_main!(benches);
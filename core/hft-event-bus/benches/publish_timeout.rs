@@ -0,0 +1,59 @@
+//! BackpressureEventBus: publish_with_timeout vs try_publish with room in the
+//! queue, against EventBus::publish_fire_and_forget as a baseline
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use hft_event_bus::{BackpressureEventBus, EventBus, MarketDataEvent};
+use std::time::Duration;
+use tokio::runtime::Runtime;
+use tokio::sync::broadcast::error::RecvError;
+
+fn market_data() -> MarketDataEvent {
+    MarketDataEvent {
+        timestamp: 0,
+        symbol: "ES".to_string(),
+        price: 6000.0,
+        volume: 1.0,
+        bid_price: 5999.75,
+        bid_size: 10.0,
+        ask_price: 6000.25,
+        ask_size: 10.0,
+    }
+}
+
+fn bench_publish_timeout(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let mut group = c.benchmark_group("backpressure_publish");
+    
+    // A consumer task drains the queue so it never fills
+    let bus = BackpressureEventBus::new(1024);
+    let mut rx = rt.block_on(bus.subscribe("market_data"));
+    rt.spawn(async move { while rx.recv().await.is_some() {} });
+    
+    let baseline = EventBus::new();
+    let mut baseline_rx = rt.block_on(baseline.subscribe_market_data());
+    rt.spawn(async move {
+        while !matches!(baseline_rx.recv().await, Err(RecvError::Closed)) {}
+    });
+    
+    group.bench_function("publish_fire_and_forget", |b| {
+        b.to_async(&rt).iter(|| async {
+            black_box(baseline.publish_fire_and_forget(market_data()).await.unwrap())
+        })
+    });
+    
+    group.bench_function("try_publish", |b| {
+        b.to_async(&rt).iter(|| async { black_box(bus.try_publish(market_data())) })
+    });
+    
+    group.bench_function("publish_with_timeout", |b| {
+        b.to_async(&rt).iter(|| async {
+            let receipt = bus.publish_with_timeout(market_data(), Duration::from_millis(1)).await;
+            black_box(receipt.unwrap())
+        })
+    });
+    
+    group.finish();
+}
+
+criterion_group!(benches, bench_publish_timeout);
+criterion_main!(benches);
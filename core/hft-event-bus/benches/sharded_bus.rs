@@ -0,0 +1,72 @@
+//! EventBus vs ShardedEventBus: concurrent publishers over 10 event types
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use hft_event_bus::{Event, EventBus, EventBusTrait, ShardedEventBus};
+use std::time::{Duration, Instant};
+use tokio::runtime::Runtime;
+
+const EVENT_TYPES: [&str; 10] = [
+    "bench_0", "bench_1", "bench_2", "bench_3", "bench_4", "bench_5", "bench_6", "bench_7", "bench_8", "bench_9",
+];
+const EVENTS_PER_PUBLISHER: u64 = 10_000;
+
+#[derive(Debug)]
+struct BenchEvent {
+    event_type: &'static str,
+}
+
+impl Event for BenchEvent {
+    fn event_type(&self) -> &'static str {
+        self.event_type
+    }
+}
+
+/// Run `publishers` tasks, each cycling through all event types
+async fn publish_concurrently<B: EventBusTrait>(bus: &B, publishers: u64) {
+    let tasks: Vec<_> = (0..publishers)
+        .map(|p| {
+            let bus = bus.clone();
+            tokio::spawn(async move {
+                for i in 0..EVENTS_PER_PUBLISHER {
+                    let event_type = EVENT_TYPES[((p + i) % EVENT_TYPES.len() as u64) as usize];
+                    bus.publish(BenchEvent { event_type }).await.unwrap();
+                }
+            })
+        })
+        .collect();
+    for task in tasks {
+        task.await.unwrap();
+    }
+}
+
+fn bench_bus<B: EventBusTrait>(c: &mut Criterion, rt: &Runtime, name: &str, bus: B) {
+    // One idle subscriber per type so every publish is delivered
+    let _receivers: Vec<_> = EVENT_TYPES.iter().map(|event_type| rt.block_on(bus.subscribe(event_type))).collect();
+
+    let mut group = c.benchmark_group("sharded_publish");
+    group.sample_size(10);
+    for publishers in [1u64, 8, 32] {
+        group.throughput(Throughput::Elements(publishers * EVENTS_PER_PUBLISHER));
+        group.bench_function(BenchmarkId::new(name, publishers), |b| {
+            b.iter_custom(|iters| {
+                let mut total = Duration::ZERO;
+                for _ in 0..iters {
+                    let start = Instant::now();
+                    rt.block_on(publish_concurrently(&bus, publishers));
+                    total += start.elapsed();
+                }
+                total
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_sharding(c: &mut Criterion) {
+    let rt = tokio::runtime::Builder::new_multi_thread().enable_all().build().unwrap();
+    bench_bus(c, &rt, "event_bus", EventBus::new());
+    bench_bus(c, &rt, "sharded_16", ShardedEventBus::default());
+}
+
+criterion_group!(benches, bench_sharding);
+criterion_main!(benches);
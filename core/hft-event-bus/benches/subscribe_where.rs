@@ -0,0 +1,49 @@
+//! EventBus benchmarks: publish overhead of predicate-routed subscriptions
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use hft_event_bus::{EventBus, MarketDataEvent};
+use tokio::runtime::Runtime;
+
+fn create_market_data(i: usize) -> MarketDataEvent {
+    MarketDataEvent {
+        timestamp: i as i64,
+        symbol: format!("SYM{}", i % 500),
+        price: 6000.0 + i as f64 * 0.25,
+        volume: 1.0,
+        bid_price: 5999.75,
+        bid_size: 10.0,
+        ask_price: 6000.25,
+        ask_size: 10.0,
+    }
+}
+
+fn bench_predicate_routing(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let mut group = c.benchmark_group("event_bus_subscribe_where");
+    let events: Vec<MarketDataEvent> = (0..500).map(create_market_data).collect();
+    
+    for predicates in [0usize, 10, 100] {
+        let bus = EventBus::new();
+        let _all = rt.block_on(bus.subscribe_market_data());
+        // One symbol per predicate, so each event matches at most one
+        let _receivers: Vec<_> = (0..predicates)
+            .map(|i| {
+                let symbol = format!("SYM{}", i);
+                bus.subscribe_where(move |e: &MarketDataEvent| e.symbol == symbol)
+            })
+            .collect();
+        
+        let mut i = 0;
+        group.bench_with_input(BenchmarkId::from_parameter(predicates), &predicates, |b, _| {
+            b.iter(|| {
+                i = (i + 1) % events.len();
+                rt.block_on(bus.publish(events[i].clone())).unwrap();
+            })
+        });
+    }
+    
+    group.finish();
+}
+
+criterion_group!(benches, bench_predicate_routing);
+criterion_main!(benches);
@@ -0,0 +1,56 @@
+//! TypedEventBus benchmarks: per-event publish vs publish_batch
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use hft_event_bus::TypedEventBus;
+use market_data_engine::types::{InstrumentId, Price, Quantity, SideV2, Timestamp, TradeFlags, TradeV2};
+
+fn create_trade(id: u64) -> TradeV2 {
+    TradeV2 {
+        timestamp: Timestamp::from_nanos(id as i64),
+        instrument_id: InstrumentId::from_raw(1),
+        price: Price::from_float(100.0),
+        quantity: Quantity::new(10),
+        side: SideV2::Buy,
+        trade_id: id,
+        exchange: 1,
+        flags: TradeFlags::new(0),
+        _padding: [0; 12],
+    }
+}
+
+fn bench_publish(c: &mut Criterion) {
+    let mut group = c.benchmark_group("typed_bus_publish");
+    
+    for size in [16u64, 256, 4096] {
+        let trades: Vec<TradeV2> = (0..size).map(create_trade).collect();
+        group.throughput(Throughput::Elements(size));
+        
+        let bus = TypedEventBus::new();
+        let _rx = bus.subscribe::<TradeV2>();
+        
+        group.bench_with_input(BenchmarkId::new("single", size), &trades, |b, trades| {
+            b.iter(|| {
+                for trade in trades {
+                    bus.publish(*trade).unwrap();
+                }
+                black_box(bus.subscribe_batch::<TradeV2>(trades.len()))
+            })
+        });
+        
+        group.bench_with_input(BenchmarkId::new("batch", size), &trades, |b, trades| {
+            b.iter_batched(
+                || trades.clone(),
+                |batch| {
+                    bus.publish_batch(batch).unwrap();
+                    black_box(bus.subscribe_batch::<TradeV2>(trades.len()))
+                },
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    
+    group.finish();
+}
+
+criterion_group!(benches, bench_publish);
+criterion_main!(benches);
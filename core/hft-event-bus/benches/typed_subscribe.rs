@@ -0,0 +1,54 @@
+//! EventBus benchmarks: envelope receive + downcast vs subscribe_typed
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use hft_event_bus::{EventBus, MarketDataEvent};
+use tokio::runtime::Runtime;
+
+fn create_market_data(i: usize) -> MarketDataEvent {
+    MarketDataEvent {
+        timestamp: i as i64,
+        symbol: "ES".to_string(),
+        price: 6000.0 + i as f64 * 0.25,
+        volume: 1.0,
+        bid_price: 5999.75,
+        bid_size: 10.0,
+        ask_price: 6000.25,
+        ask_size: 10.0,
+    }
+}
+
+fn bench_receive(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let mut group = c.benchmark_group("event_bus_receive");
+    let event = create_market_data(1);
+    
+    let bus = EventBus::new();
+    let mut envelopes = rt.block_on(bus.subscribe_market_data());
+    group.bench_function("envelope", |b| {
+        b.iter(|| {
+            rt.block_on(async {
+                bus.publish(event.clone()).await.unwrap();
+                let envelope = envelopes.recv().await.unwrap();
+                let data = envelope.event.downcast_ref::<MarketDataEvent>().unwrap();
+                black_box(data.price)
+            })
+        })
+    });
+    
+    let bus = EventBus::new();
+    let mut typed = bus.subscribe_typed::<MarketDataEvent>();
+    group.bench_function("typed", |b| {
+        b.iter(|| {
+            rt.block_on(async {
+                bus.publish(event.clone()).await.unwrap();
+                let data = typed.recv().await.unwrap();
+                black_box(data.price)
+            })
+        })
+    });
+    
+    group.finish();
+}
+
+criterion_group!(benches, bench_receive);
+criterion_main!(benches);
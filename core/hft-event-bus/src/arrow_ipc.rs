@@ -0,0 +1,237 @@
+//! Arrow IPC export/import for recorded events (requires the `arrow` feature)
+//!
+//! Each envelope becomes one row:
+//!
+//! | column         | type   |
+//! |----------------|--------|
+//! | `id`           | utf8   |
+//! | `timestamp_ns` | int64  |
+//! | `priority`     | uint8  |
+//! | `event_type`   | utf8   |
+//! | `payload`      | binary |
+//!
+//! `payload` is a bincode-encoded `IpcPayload`: the causation and
+//! correlation IDs (as optional u128) followed by the JSON-encoded event.
+//! Files are uncompressed so PyArrow and Polars can memory-map them.
+
+use crate::events::EventEnvelope;
+use crate::replay::EventRecorder;
+use crate::serde_support::SerializableEnvelope;
+use anyhow::{anyhow, Result};
+use arrow2::array::{Array, BinaryArray, Int64Array, UInt8Array, Utf8Array};
+use arrow2::chunk::Chunk;
+use arrow2::datatypes::{DataType, Field, Schema};
+use arrow2::io::ipc::read::{read_file_metadata, FileReader};
+use arrow2::io::ipc::write::{FileWriter, WriteOptions};
+use serde::{Deserialize, Serialize};
+use std::io::{Cursor, Read, Write};
+use uuid::Uuid;
+
+/// Columns of one export, laid out as in `recording_schema`
+pub type RecordBatch = Chunk<Box<dyn Array>>;
+
+/// Contents of the `payload` column
+#[derive(Debug, Serialize, Deserialize)]
+struct IpcPayload {
+    causation_id: Option<u128>,
+    correlation_id: Option<u128>,
+    /// JSON-encoded event
+    event: Vec<u8>,
+}
+
+/// Schema of exported recordings
+pub fn recording_schema() -> Schema {
+    Schema::from(vec![
+        Field::new("id", DataType::Utf8, false),
+        Field::new("timestamp_ns", DataType::Int64, false),
+        Field::new("priority", DataType::UInt8, false),
+        Field::new("event_type", DataType::Utf8, false),
+        Field::new("payload", DataType::Binary, false),
+    ])
+}
+
+impl EventRecorder {
+    /// Recorded events as Arrow columns, oldest first
+    ///
+    /// Events without a JSON form are skipped.
+    pub async fn to_arrow_record_batch(&self) -> Result<RecordBatch> {
+        let rows: Vec<SerializableEnvelope> = self
+            .get_events_ordered()
+            .await
+            .iter()
+            .filter_map(EventEnvelope::to_serializable)
+            .collect();
+
+        let ids: Vec<String> = rows.iter().map(|r| r.id.to_string()).collect();
+        let payloads = rows
+            .iter()
+            .map(|r| {
+                bincode::serialize(&IpcPayload {
+                    causation_id: r.causation_id.map(|id| id.as_u128()),
+                    correlation_id: r.correlation_id.map(|id| id.as_u128()),
+                    event: serde_json::to_vec(&r.payload)?,
+                })
+                .map_err(anyhow::Error::from)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let columns: Vec<Box<dyn Array>> = vec![
+            Utf8Array::<i32>::from_slice(&ids).boxed(),
+            Int64Array::from_vec(rows.iter().map(|r| r.timestamp_ns).collect()).boxed(),
+            UInt8Array::from_vec(rows.iter().map(|r| r.priority).collect()).boxed(),
+            Utf8Array::<i32>::from_iter_values(rows.iter().map(|r| r.event_type.as_str())).boxed(),
+            BinaryArray::<i32>::from_slice(&payloads).boxed(),
+        ];
+        Ok(Chunk::try_new(columns)?)
+    }
+
+    /// Write recorded events as an Arrow IPC file, returning the number of rows
+    ///
+    /// Events without a JSON form are skipped.
+    pub async fn to_arrow_ipc(&self, writer: impl Write) -> Result<usize> {
+        let batch = self.to_arrow_record_batch().await?;
+        let mut writer = FileWriter::try_new(writer, recording_schema(), None, WriteOptions { compression: None })?;
+        writer.write(&batch, None)?;
+        writer.finish()?;
+        Ok(batch.len())
+    }
+
+    /// Create a recorder holding the events of a file written by `to_arrow_ipc`
+    ///
+    /// The recorder's capacity is the number of events. Payloads are rebuilt
+    /// through the `EventRegistry`; unregistered event types come back as
+    /// `RawEvent`.
+    pub fn from_arrow_ipc(mut reader: impl Read) -> Result<Self> {
+        // The IPC file footer is at the end, so the reader needs to seek
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        let mut cursor = Cursor::new(bytes);
+        let metadata = read_file_metadata(&mut cursor)?;
+        if metadata.schema.fields != recording_schema().fields {
+            return Err(anyhow!("Arrow IPC file does not have the recording schema"));
+        }
+
+        let mut events = Vec::new();
+        for chunk in FileReader::new(cursor, metadata, None, None) {
+            let chunk = chunk?;
+            let arrays = chunk.arrays();
+            let ids = column::<Utf8Array<i32>>(arrays, 0, "id")?;
+            let timestamps = column::<Int64Array>(arrays, 1, "timestamp_ns")?;
+            let priorities = column::<UInt8Array>(arrays, 2, "priority")?;
+            let event_types = column::<Utf8Array<i32>>(arrays, 3, "event_type")?;
+            let payloads = column::<BinaryArray<i32>>(arrays, 4, "payload")?;
+
+            for row in 0..chunk.len() {
+                let payload: IpcPayload = bincode::deserialize(payloads.value(row))?;
+                let envelope = SerializableEnvelope {
+                    id: Uuid::parse_str(ids.value(row))?,
+                    timestamp_ns: timestamps.value(row),
+                    priority: priorities.value(row),
+                    causation_id: payload.causation_id.map(Uuid::from_u128),
+                    correlation_id: payload.correlation_id.map(Uuid::from_u128),
+                    event_type: event_types.value(row).to_string(),
+                    payload: serde_json::from_slice(&payload.event)?,
+                };
+                events.push(envelope.into_envelope_or_raw()?);
+            }
+        }
+        Ok(Self::from_events(events))
+    }
+}
+
+/// Downcast column `index` to its expected array type
+fn column<'a, A: 'static>(arrays: &'a [Box<dyn Array>], index: usize, name: &str) -> Result<&'a A> {
+    arrays
+        .get(index)
+        .and_then(|array| array.as_any().downcast_ref::<A>())
+        .ok_or_else(|| anyhow!("Arrow column {} missing or has unexpected type", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{HealthEvent, HealthStatus, MarketDataEvent};
+
+    fn market_data(i: i64) -> MarketDataEvent {
+        MarketDataEvent {
+            timestamp: i,
+            symbol: if i % 2 == 0 { "ES" } else { "NQ" }.to_string(),
+            price: 6000.0 + i as f64 * 0.25,
+            volume: 1.0 + (i % 7) as f64,
+            bid_price: 5999.75 + i as f64 * 0.25,
+            bid_size: 3.0,
+            ask_price: 6000.25 + i as f64 * 0.25,
+            ask_size: 4.0,
+        }
+    }
+
+    async fn recorder() -> (EventRecorder, Vec<EventEnvelope>) {
+        let recorder = EventRecorder::new(2_000);
+        let mut events: Vec<EventEnvelope> =
+            (0..1_000).map(|i| EventEnvelope::new(market_data(i), (i % 10) as u8)).collect();
+        let health = EventEnvelope::new(
+            HealthEvent {
+                timestamp: 0,
+                component: "gateway".to_string(),
+                status: HealthStatus::Degraded,
+                message: "slow".to_string(),
+            },
+            1,
+        );
+        events.push(health.caused_by(&events[0]));
+        for event in &events {
+            recorder.record(event.clone()).await;
+        }
+        (recorder, events)
+    }
+
+    #[tokio::test]
+    async fn test_record_batch_layout() {
+        let (recorder, events) = recorder().await;
+        let batch = recorder.to_arrow_record_batch().await.unwrap();
+        assert_eq!(batch.len(), events.len());
+        assert_eq!(batch.arrays().len(), recording_schema().fields.len());
+        for (array, field) in batch.arrays().iter().zip(&recording_schema().fields) {
+            assert_eq!(array.data_type(), &field.data_type);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_arrow_ipc_roundtrip() {
+        let (recorder, events) = recorder().await;
+        let mut file = Vec::new();
+        assert_eq!(recorder.to_arrow_ipc(&mut file).await.unwrap(), events.len());
+
+        // Readable with the plain arrow2 reader
+        let mut cursor = Cursor::new(file.clone());
+        let metadata = read_file_metadata(&mut cursor).unwrap();
+        assert_eq!(metadata.schema.fields, recording_schema().fields);
+        let rows: usize = FileReader::new(cursor, metadata, None, None).map(|chunk| chunk.unwrap().len()).sum();
+        assert_eq!(rows, events.len());
+
+        let loaded = EventRecorder::from_arrow_ipc(file.as_slice()).unwrap();
+        assert_eq!(loaded.capacity(), events.len());
+        let restored = loaded.get_events_ordered().await;
+        assert_eq!(restored.len(), events.len());
+        for (original, restored) in events.iter().zip(&restored) {
+            assert_eq!(restored.id, original.id);
+            assert_eq!(restored.timestamp_ns, original.timestamp_ns);
+            assert_eq!(restored.priority, original.priority);
+            assert_eq!(restored.causation_id, original.causation_id);
+            assert_eq!(restored.correlation_id, original.correlation_id);
+            assert_eq!(restored.event.to_json(), original.event.to_json());
+        }
+        assert!(restored.last().unwrap().event.downcast_ref::<HealthEvent>().is_some());
+        assert_eq!(loaded.get_events_by_type("health").await.len(), 1);
+    }
+
+    #[test]
+    fn test_rejects_other_schema() {
+        let schema = Schema::from(vec![Field::new("id", DataType::Utf8, false)]);
+        let mut file = Vec::new();
+        let mut writer = FileWriter::try_new(&mut file, schema, None, WriteOptions { compression: None }).unwrap();
+        writer.write(&Chunk::try_new(vec![Utf8Array::<i32>::from_slice(["a"]).boxed()]).unwrap(), None).unwrap();
+        writer.finish().unwrap();
+        assert!(EventRecorder::from_arrow_ipc(file.as_slice()).is_err());
+    }
+}
@@ -0,0 +1,277 @@
+//! Load shedding for slow subscribers
+//!
+//! A plain broadcast receiver that falls behind silently loses the oldest
+//! events (`RecvError::Lagged`). `EventBus::subscribe_with_backpressure`
+//! puts a bounded queue in front of the consumer instead and lets it choose
+//! what gives way when the queue is full.
+
+use crate::bus::EventBus;
+use crate::events::EventEnvelope;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, oneshot};
+
+/// What to do with an event when the subscriber's queue is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackpressureStrategy {
+    /// Evict the oldest queued event to make room
+    DropOldest,
+    /// Discard the incoming event
+    DropNewest,
+    /// Wait up to the given time for room, then discard the incoming event
+    Block(Duration),
+    /// Forward only every N-th event (the 1st, N+1-th, ...); others are
+    /// skipped whether or not the queue is full. Full queues drop the newest.
+    Sample(u64),
+}
+
+/// Bounded subscription returned by `subscribe_with_backpressure`
+///
+/// Fed by a background task that exits when the source channel closes or
+/// this receiver is dropped.
+pub struct BackpressureReceiver {
+    receiver: flume::Receiver<EventEnvelope>,
+    dropped: Arc<AtomicU64>,
+    sampled: Arc<AtomicU64>,
+    /// Dropping this stops the forwarding task
+    _shutdown: oneshot::Sender<()>,
+}
+
+impl BackpressureReceiver {
+    /// Receive next event (`None` once the source channel closes)
+    pub async fn recv(&mut self) -> Option<EventEnvelope> {
+        self.receiver.recv_async().await.ok()
+    }
+
+    /// Try to receive without blocking
+    pub fn try_recv(&mut self) -> Option<EventEnvelope> {
+        self.receiver.try_recv().ok()
+    }
+
+    /// Number of events waiting in the queue
+    pub fn len(&self) -> usize {
+        self.receiver.len()
+    }
+
+    /// Check if the queue is empty
+    pub fn is_empty(&self) -> bool {
+        self.receiver.is_empty()
+    }
+
+    /// Events shed because the queue was full, plus any lost to broadcast lag
+    pub fn dropped_count(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Events skipped by `BackpressureStrategy::Sample`
+    pub fn sampled_count(&self) -> u64 {
+        self.sampled.load(Ordering::Relaxed)
+    }
+}
+
+impl EventBus {
+    /// Subscribe through a queue of `capacity` events that sheds load per `strategy`
+    ///
+    /// The forwarding task drains the bus channel (itself buffering up to
+    /// `CHANNEL_CAPACITY` events) as fast as it can, so only this queue
+    /// fills up when the consumer is slow.
+    pub async fn subscribe_with_backpressure(
+        &self,
+        event_type: &str,
+        capacity: usize,
+        strategy: BackpressureStrategy,
+    ) -> BackpressureReceiver {
+        let source = self.subscribe(event_type).await;
+        let (tx, receiver) = flume::bounded(capacity.max(1));
+        let (shutdown_tx, shutdown_rx) = oneshot::channel();
+        let dropped = Arc::new(AtomicU64::new(0));
+        let sampled = Arc::new(AtomicU64::new(0));
+
+        tokio::spawn(shed_load(
+            source,
+            tx,
+            receiver.clone(),
+            strategy,
+            dropped.clone(),
+            sampled.clone(),
+            shutdown_rx,
+        ));
+
+        BackpressureReceiver {
+            receiver,
+            dropped,
+            sampled,
+            _shutdown: shutdown_tx,
+        }
+    }
+}
+
+/// Forward `source` into `tx` per `strategy` until the source closes or `shutdown` fires
+///
+/// `evict` is a second handle on the queue, used by `DropOldest`.
+async fn shed_load(
+    mut source: broadcast::Receiver<EventEnvelope>,
+    tx: flume::Sender<EventEnvelope>,
+    evict: flume::Receiver<EventEnvelope>,
+    strategy: BackpressureStrategy,
+    dropped: Arc<AtomicU64>,
+    sampled: Arc<AtomicU64>,
+    mut shutdown: oneshot::Receiver<()>,
+) {
+    let mut seen = 0u64;
+    loop {
+        let mut envelope = tokio::select! {
+            received = source.recv() => match received {
+                Ok(envelope) => envelope,
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    dropped.fetch_add(skipped, Ordering::Relaxed);
+                    continue;
+                }
+                Err(broadcast::error::RecvError::Closed) => return,
+            },
+            _ = &mut shutdown => return,
+        };
+
+        match strategy {
+            BackpressureStrategy::DropOldest => loop {
+                match tx.try_send(envelope) {
+                    Ok(()) => break,
+                    Err(flume::TrySendError::Full(returned)) => {
+                        // The consumer may have made room in the meantime
+                        if evict.try_recv().is_ok() {
+                            dropped.fetch_add(1, Ordering::Relaxed);
+                        }
+                        envelope = returned;
+                    }
+                    Err(flume::TrySendError::Disconnected(_)) => return,
+                }
+            },
+            BackpressureStrategy::DropNewest => {
+                if tx.try_send(envelope).is_err() {
+                    dropped.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            BackpressureStrategy::Block(timeout) => {
+                tokio::select! {
+                    sent = tokio::time::timeout(timeout, tx.send_async(envelope)) => {
+                        if sent.is_err() {
+                            dropped.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                    _ = &mut shutdown => return,
+                }
+            }
+            BackpressureStrategy::Sample(every) => {
+                seen += 1;
+                if (seen - 1) % every.max(1) != 0 {
+                    sampled.fetch_add(1, Ordering::Relaxed);
+                } else if tx.try_send(envelope).is_err() {
+                    dropped.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::MarketDataEvent;
+    use crate::test_fixtures::quote;
+
+    /// Publish ticks priced 0..count without giving the consumer a chance to read
+    async fn overload(bus: &EventBus, count: usize) {
+        for i in 0..count {
+            bus.publish(quote("ES", i as f64)).await.unwrap();
+        }
+        // Let the forwarding task work through the backlog
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+
+    fn drain(rx: &mut BackpressureReceiver) -> Vec<f64> {
+        std::iter::from_fn(|| rx.try_recv())
+            .map(|e| e.event.downcast_ref::<MarketDataEvent>().unwrap().price)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_drop_oldest_keeps_latest() {
+        let bus = EventBus::new();
+        let mut rx = bus.subscribe_with_backpressure("market_data", 3, BackpressureStrategy::DropOldest).await;
+        overload(&bus, 10).await;
+        assert_eq!(drain(&mut rx), vec![7.0, 8.0, 9.0]);
+        assert_eq!(rx.dropped_count(), 7);
+        assert_eq!(rx.sampled_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_drop_newest_keeps_earliest() {
+        let bus = EventBus::new();
+        let mut rx = bus.subscribe_with_backpressure("market_data", 3, BackpressureStrategy::DropNewest).await;
+        overload(&bus, 10).await;
+        assert_eq!(drain(&mut rx), vec![0.0, 1.0, 2.0]);
+        assert_eq!(rx.dropped_count(), 7);
+    }
+
+    #[tokio::test]
+    async fn test_block_waits_for_consumer() {
+        let bus = EventBus::new();
+        let strategy = BackpressureStrategy::Block(Duration::from_secs(1));
+        let mut rx = bus.subscribe_with_backpressure("market_data", 2, strategy).await;
+        for i in 0..10 {
+            bus.publish(quote("ES", i as f64)).await.unwrap();
+        }
+
+        // A consumer that keeps up within the timeout loses nothing
+        let mut received = Vec::new();
+        while received.len() < 10 {
+            let envelope = rx.recv().await.unwrap();
+            received.push(envelope.event.downcast_ref::<MarketDataEvent>().unwrap().price);
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+        assert_eq!(received, (0..10).map(|i| i as f64).collect::<Vec<_>>());
+        assert_eq!(rx.dropped_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_block_times_out() {
+        let bus = EventBus::new();
+        let strategy = BackpressureStrategy::Block(Duration::from_millis(1));
+        let mut rx = bus.subscribe_with_backpressure("market_data", 2, strategy).await;
+        overload(&bus, 6).await;
+        assert_eq!(drain(&mut rx), vec![0.0, 1.0]);
+        assert_eq!(rx.dropped_count(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_sample_keeps_every_nth() {
+        let bus = EventBus::new();
+        let mut rx = bus.subscribe_with_backpressure("market_data", 16, BackpressureStrategy::Sample(3)).await;
+        overload(&bus, 10).await;
+        assert_eq!(drain(&mut rx), vec![0.0, 3.0, 6.0, 9.0]);
+        assert_eq!(rx.sampled_count(), 6);
+        assert_eq!(rx.dropped_count(), 0);
+
+        // Sampled events still shed the newest once the queue fills
+        let mut rx = bus.subscribe_with_backpressure("market_data", 2, BackpressureStrategy::Sample(2)).await;
+        overload(&bus, 10).await;
+        assert_eq!(drain(&mut rx), vec![0.0, 2.0]);
+        assert_eq!(rx.sampled_count(), 5);
+        assert_eq!(rx.dropped_count(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_receiver_drop_stops_task() {
+        let bus = EventBus::new();
+        let rx = bus.subscribe_with_backpressure("market_data", 1, BackpressureStrategy::Block(Duration::from_secs(60))).await;
+        bus.publish(quote("ES", 1.0)).await.unwrap();
+        bus.publish(quote("ES", 2.0)).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert_eq!(bus.channel_stats_snapshot()["market_data"].subscriber_count, 1);
+
+        drop(rx);
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert_eq!(bus.channel_stats_snapshot()["market_data"].subscriber_count, 0);
+    }
+}
@@ -0,0 +1,251 @@
+//! Publisher-side backpressure
+//!
+//! `EventBus` channels are `broadcast` queues: a full queue evicts the
+//! oldest event rather than making the publisher wait. `BackpressureEventBus`
+//! gives every subscriber its own bounded `mpsc` queue instead, so a
+//! publisher can wait (up to a timeout) for slow subscribers to make room.
+
+use crate::bus::{EventBus, PublishReceipt, CHANNEL_CAPACITY};
+use crate::events::{Event, EventEnvelope};
+use crate::replay_mode::SharedClock;
+use dashmap::DashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc::{self, error::SendTimeoutError, error::TrySendError};
+use tokio::time::Instant;
+use tracing::debug;
+
+/// Some subscribers of an event still had no room when the timeout expired
+///
+/// The event was delivered to the other subscribers.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("timed out after {timeout:?} publishing {event_type}: {blocked} of {subscribers} subscribers full")]
+pub struct PublishTimeoutError {
+    pub event_type: &'static str,
+    pub timeout: Duration,
+    pub subscribers: usize,
+    pub blocked: usize,
+}
+
+/// Event bus whose publishers wait for room in subscriber queues
+///
+/// Each subscriber owns a queue of `capacity` events. Receipts report how
+/// many subscribers the event was queued for but do not track consumption
+/// (`PublishReceipt::is_delivered` is always true). Cloning yields a handle
+/// to the same bus.
+#[derive(Clone)]
+pub struct BackpressureEventBus {
+    /// Subscriber queues per event type, replaced wholesale on change so
+    /// publishers can hold a snapshot across awaits
+    channels: Arc<DashMap<String, Arc<Vec<mpsc::Sender<EventEnvelope>>>>>,
+    capacity: usize,
+    /// Events `try_publish` could not queue for a full subscriber
+    dropped: Arc<AtomicU64>,
+    /// Virtual clock used to stamp envelopes (optional)
+    clock: Option<SharedClock>,
+}
+
+impl BackpressureEventBus {
+    /// Create bus whose subscriber queues hold `capacity` events (at least 1)
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            channels: Arc::new(DashMap::new()),
+            capacity: capacity.max(1),
+            dropped: Arc::new(AtomicU64::new(0)),
+            clock: None,
+        }
+    }
+
+    /// Subscribe to a specific event type
+    pub async fn subscribe(&self, event_type: &str) -> mpsc::Receiver<EventEnvelope> {
+        let (tx, rx) = mpsc::channel(self.capacity);
+        let mut entry = self.channels.entry(event_type.to_string()).or_insert_with(|| {
+            debug!("Creating new backpressure channel for subscription: {}", event_type);
+            Arc::new(Vec::new())
+        });
+        let mut senders = Vec::clone(&entry);
+        senders.push(tx);
+        *entry = Arc::new(senders);
+        rx
+    }
+
+    /// Publish, waiting up to `timeout` in total for full subscriber queues
+    ///
+    /// Subscribers with room get the event immediately; the timeout is only
+    /// started if one is full. On timeout the event stays delivered to the
+    /// subscribers that had room.
+    pub async fn publish_with_timeout<T: Event + Send + 'static>(
+        &self,
+        event: T,
+        timeout: Duration,
+    ) -> Result<PublishReceipt, PublishTimeoutError> {
+        let envelope = self.stamp(EventEnvelope::new(event, 5));
+        let event_type = envelope.event.event_type();
+        let Some(senders) = self.senders(event_type) else {
+            return Ok(PublishReceipt::untracked(0, envelope.id, envelope.timestamp_ns));
+        };
+
+        let mut deadline = None;
+        let (mut queued, mut blocked, mut closed) = (0, 0, false);
+        for sender in senders.iter() {
+            match sender.try_send(envelope.clone()) {
+                Ok(()) => queued += 1,
+                Err(TrySendError::Closed(_)) => closed = true,
+                Err(TrySendError::Full(envelope)) => {
+                    let deadline = *deadline.get_or_insert_with(|| Instant::now() + timeout);
+                    match sender.send_timeout(envelope, deadline.saturating_duration_since(Instant::now())).await {
+                        Ok(()) => queued += 1,
+                        Err(SendTimeoutError::Timeout(_)) => blocked += 1,
+                        Err(SendTimeoutError::Closed(_)) => closed = true,
+                    }
+                }
+            }
+        }
+        if closed {
+            self.prune(event_type);
+        }
+
+        if blocked > 0 {
+            return Err(PublishTimeoutError {
+                event_type,
+                timeout,
+                subscribers: queued + blocked,
+                blocked,
+            });
+        }
+        Ok(PublishReceipt::untracked(queued, envelope.id, envelope.timestamp_ns))
+    }
+
+    /// Publish without waiting; full subscribers miss the event
+    ///
+    /// Missed deliveries are counted in `dropped_count`.
+    pub fn try_publish<T: Event + Send + 'static>(&self, event: T) -> PublishReceipt {
+        let envelope = self.stamp(EventEnvelope::new(event, 5));
+        let event_type = envelope.event.event_type();
+        let Some(senders) = self.senders(event_type) else {
+            return PublishReceipt::untracked(0, envelope.id, envelope.timestamp_ns);
+        };
+
+        let (mut queued, mut closed) = (0, false);
+        for sender in senders.iter() {
+            match sender.try_send(envelope.clone()) {
+                Ok(()) => queued += 1,
+                Err(TrySendError::Full(_)) => {
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                }
+                Err(TrySendError::Closed(_)) => closed = true,
+            }
+        }
+        if closed {
+            self.prune(event_type);
+        }
+        PublishReceipt::untracked(queued, envelope.id, envelope.timestamp_ns)
+    }
+
+    /// Deliveries `try_publish` skipped because a subscriber queue was full
+    pub fn dropped_count(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Number of live subscribers for `event_type`
+    pub fn subscriber_count(&self, event_type: &str) -> usize {
+        self.senders(event_type)
+            .map_or(0, |senders| senders.iter().filter(|sender| !sender.is_closed()).count())
+    }
+
+    fn senders(&self, event_type: &str) -> Option<Arc<Vec<mpsc::Sender<EventEnvelope>>>> {
+        self.channels.get(event_type).map(|senders| senders.clone())
+    }
+
+    /// Drop queues whose receiver is gone
+    fn prune(&self, event_type: &str) {
+        if let Some(mut entry) = self.channels.get_mut(event_type) {
+            let live: Vec<_> = entry.iter().filter(|sender| !sender.is_closed()).cloned().collect();
+            *entry = Arc::new(live);
+        }
+    }
+
+    fn stamp(&self, mut envelope: EventEnvelope) -> EventEnvelope {
+        if let Some(clock) = &self.clock {
+            envelope.timestamp_ns = clock.now();
+        }
+        envelope
+    }
+}
+
+impl Default for BackpressureEventBus {
+    fn default() -> Self {
+        Self::new(CHANNEL_CAPACITY)
+    }
+}
+
+/// Bus with `EventBus`'s channel capacity and virtual clock
+///
+/// Subscriptions and other per-bus state are not carried over.
+impl From<EventBus> for BackpressureEventBus {
+    fn from(bus: EventBus) -> Self {
+        Self {
+            clock: bus.clock().cloned(),
+            ..Self::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{HealthEvent, HealthStatus};
+
+    fn health() -> HealthEvent {
+        HealthEvent {
+            timestamp: 0,
+            component: "gateway".to_string(),
+            status: HealthStatus::Healthy,
+            message: String::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_try_publish_counts_drops() {
+        let bus = BackpressureEventBus::new(1);
+        let mut rx = bus.subscribe("health").await;
+        let _idle = bus.subscribe("health").await;
+
+        assert_eq!(bus.try_publish(health()).subscriber_count, 2);
+        assert_eq!(bus.try_publish(health()).subscriber_count, 0);
+        assert_eq!(bus.dropped_count(), 2);
+
+        // Draining one queue makes room in that queue only
+        assert!(rx.recv().await.is_some());
+        assert_eq!(bus.try_publish(health()).subscriber_count, 1);
+        assert_eq!(bus.dropped_count(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_dropped_subscribers_pruned() {
+        let bus = BackpressureEventBus::new(4);
+        let rx = bus.subscribe("health").await;
+        let _kept = bus.subscribe("health").await;
+        assert_eq!(bus.subscriber_count("health"), 2);
+
+        drop(rx);
+        let receipt = bus.publish_with_timeout(health(), Duration::from_millis(10)).await.unwrap();
+        assert_eq!(receipt.subscriber_count, 1);
+        assert_eq!(bus.subscriber_count("health"), 1);
+        assert_eq!(bus.channels.get("health").unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_from_event_bus_keeps_clock() {
+        let clock = SharedClock::new();
+        clock.advance_to(42);
+        let bus = BackpressureEventBus::from(EventBus::with_shared_clock(clock));
+        let mut rx = bus.subscribe("health").await;
+
+        let receipt = bus.publish_with_timeout(health(), Duration::from_millis(10)).await.unwrap();
+        assert_eq!(receipt.timestamp_ns, 42);
+        assert_eq!(rx.recv().await.unwrap().timestamp_ns, 42);
+        assert_eq!(bus.capacity, CHANNEL_CAPACITY);
+    }
+}
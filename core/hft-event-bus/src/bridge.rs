@@ -0,0 +1,276 @@
+//! WebSocket bridge for external consumers (requires the `websocket` feature)
+//!
+//! Dashboards connect over WebSocket and send a subscription message:
+//!
+//! ```json
+//! {"subscribe": ["visualization_update", "analysis_progress"]}
+//! ```
+//!
+//! The bridge acknowledges with `{"subscribed": [...]}` and then streams one
+//! newline-terminated JSON envelope per text message:
+//!
+//! ```json
+//! {"id": "...", "timestamp_ns": 0, "priority": 5, "event_type": "...", "payload": {...}}
+//! ```
+//!
+//! Each client has a bounded buffer. When a client falls behind, new events
+//! for it are dropped so a slow consumer never blocks the bus.
+
+use crate::bus::EventBus;
+use crate::events::EventEnvelope;
+use anyhow::{bail, Result};
+use futures_util::{SinkExt, StreamExt};
+use serde::Deserialize;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{broadcast, mpsc};
+use tokio_tungstenite::tungstenite::Message;
+use tracing::{debug, info, warn};
+
+/// Default number of messages buffered per client
+pub const DEFAULT_CLIENT_BUFFER_SIZE: usize = 1024;
+
+/// First message sent by a client
+#[derive(Debug, Deserialize)]
+struct SubscriptionRequest {
+    subscribe: Vec<String>,
+}
+
+/// Serves EventBus events to WebSocket clients
+pub struct WebSocketBridge {
+    bus: EventBus,
+    addr: SocketAddr,
+    client_buffer_size: usize,
+    dropped: Arc<AtomicU64>,
+}
+
+impl WebSocketBridge {
+    /// Create bridge serving `bus` on `addr`
+    pub fn new(bus: EventBus, addr: SocketAddr) -> Self {
+        Self {
+            bus,
+            addr,
+            client_buffer_size: DEFAULT_CLIENT_BUFFER_SIZE,
+            dropped: Arc::new(AtomicU64::new(0)),
+        }
+    }
+    
+    /// Set messages buffered per client before events are dropped
+    pub fn client_buffer_size(mut self, size: usize) -> Self {
+        self.client_buffer_size = size.max(1);
+        self
+    }
+    
+    /// Total events dropped for slow clients
+    pub fn dropped_events(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+    
+    /// Shared counter of dropped events (stays valid after `run` takes the bridge)
+    pub fn dropped_counter(&self) -> Arc<AtomicU64> {
+        self.dropped.clone()
+    }
+    
+    /// Bind the configured address and serve clients until an error occurs
+    pub async fn run(self) -> Result<()> {
+        let listener = TcpListener::bind(self.addr).await?;
+        self.serve(listener).await
+    }
+    
+    /// Serve clients on an already bound listener
+    pub async fn serve(self, listener: TcpListener) -> Result<()> {
+        info!("WebSocket bridge listening on {}", listener.local_addr()?);
+        
+        loop {
+            let (stream, peer) = listener.accept().await?;
+            let bus = self.bus.clone();
+            let buffer = self.client_buffer_size;
+            let dropped = self.dropped.clone();
+            
+            tokio::spawn(async move {
+                if let Err(e) = handle_client(bus, stream, buffer, dropped).await {
+                    debug!("WebSocket client {} disconnected: {}", peer, e);
+                }
+            });
+        }
+    }
+}
+
+/// Serialize an envelope as one newline-terminated JSON line
+fn envelope_line(envelope: &EventEnvelope) -> String {
+    let json = serde_json::json!({
+        "id": envelope.id,
+        "timestamp_ns": envelope.timestamp_ns,
+        "priority": envelope.priority,
+        "event_type": envelope.event.event_type(),
+        "payload": envelope.event.to_json(),
+    });
+    let mut line = json.to_string();
+    line.push('\n');
+    line
+}
+
+async fn handle_client(
+    bus: EventBus,
+    stream: TcpStream,
+    buffer: usize,
+    dropped: Arc<AtomicU64>,
+) -> Result<()> {
+    let ws = tokio_tungstenite::accept_async(stream).await?;
+    let (mut sink, mut source) = ws.split();
+    
+    // Wait for the subscription message
+    let request: SubscriptionRequest = loop {
+        match source.next().await {
+            Some(Ok(Message::Text(text))) => break serde_json::from_str(&text)?,
+            Some(Ok(Message::Ping(_))) | Some(Ok(Message::Pong(_))) => continue,
+            Some(Ok(Message::Close(_))) | None => return Ok(()),
+            Some(Ok(other)) => bail!("Expected subscription message, got {:?}", other),
+            Some(Err(e)) => return Err(e.into()),
+        }
+    };
+    
+    // One forwarder per event type, all feeding the client's bounded buffer
+    let (tx, mut rx) = mpsc::channel::<String>(buffer);
+    let mut forwarders = Vec::with_capacity(request.subscribe.len());
+    for event_type in &request.subscribe {
+        let receiver = bus.subscribe(event_type).await;
+        forwarders.push(tokio::spawn(forward(receiver, tx.clone(), dropped.clone())));
+    }
+    drop(tx);
+    
+    let ack = serde_json::json!({ "subscribed": request.subscribe });
+    sink.send(Message::Text(format!("{}\n", ack))).await?;
+    
+    let result = loop {
+        tokio::select! {
+            line = rx.recv() => match line {
+                Some(line) => {
+                    if let Err(e) = sink.send(Message::Text(line)).await {
+                        break Err(e.into());
+                    }
+                }
+                None => break Ok(()),
+            },
+            message = source.next() => match message {
+                Some(Ok(Message::Close(_))) | None => break Ok(()),
+                Some(Err(e)) => break Err(e.into()),
+                Some(Ok(_)) => {}
+            },
+        }
+    };
+    
+    for forwarder in forwarders {
+        forwarder.abort();
+    }
+    result
+}
+
+/// Move events from a bus subscription into a client buffer without blocking
+async fn forward(
+    mut receiver: broadcast::Receiver<EventEnvelope>,
+    tx: mpsc::Sender<String>,
+    dropped: Arc<AtomicU64>,
+) {
+    loop {
+        match receiver.recv().await {
+            Ok(envelope) => match tx.try_send(envelope_line(&envelope)) {
+                Ok(()) => {}
+                Err(mpsc::error::TrySendError::Full(_)) => {
+                    dropped.fetch_add(1, Ordering::Relaxed);
+                }
+                Err(mpsc::error::TrySendError::Closed(_)) => return,
+            },
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                warn!("WebSocket forwarder lagged, skipped {} events", skipped);
+                dropped.fetch_add(skipped, Ordering::Relaxed);
+            }
+            Err(broadcast::error::RecvError::Closed) => return,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{OrderStatus, OrderUpdateEvent};
+    use crate::test_fixtures::quote;
+    use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+    
+    type Client = WebSocketStream<MaybeTlsStream<TcpStream>>;
+    
+    async fn start_bridge(bridge: WebSocketBridge) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(bridge.serve(listener));
+        addr
+    }
+    
+    async fn connect(addr: SocketAddr, event_types: &[&str]) -> Client {
+        let (mut client, _) = connect_async(format!("ws://{}", addr)).await.unwrap();
+        let request = serde_json::json!({ "subscribe": event_types });
+        client.send(Message::Text(request.to_string())).await.unwrap();
+        
+        // Events published after the acknowledgement are delivered
+        let ack = next_json(&mut client).await;
+        assert_eq!(ack["subscribed"].as_array().unwrap().len(), event_types.len());
+        client
+    }
+    
+    async fn next_json(client: &mut Client) -> serde_json::Value {
+        loop {
+            match client.next().await.unwrap().unwrap() {
+                Message::Text(text) => {
+                    assert!(text.ends_with('\n'));
+                    return serde_json::from_str(&text).unwrap();
+                }
+                _ => continue,
+            }
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_bridge_streams_subscribed_events() {
+        let bus = EventBus::new();
+        let addr = start_bridge(WebSocketBridge::new(bus.clone(), "127.0.0.1:0".parse().unwrap())).await;
+        let mut client = connect(addr, &["market_data"]).await;
+        
+        bus.publish(OrderUpdateEvent {
+            order_id: uuid::Uuid::new_v4(),
+            timestamp: 0,
+            status: OrderStatus::Filled,
+            filled_quantity: 1.0,
+            remaining_quantity: 0.0,
+        }).await.unwrap();
+        bus.publish(quote("ES", 6000.0)).await.unwrap();
+        
+        let message = next_json(&mut client).await;
+        assert_eq!(message["event_type"], "market_data");
+        assert_eq!(message["priority"], 5);
+        assert_eq!(message["payload"]["price"], 6000.0);
+    }
+    
+    #[tokio::test]
+    async fn test_bridge_drops_events_for_slow_client() {
+        let bus = EventBus::new();
+        let bridge = WebSocketBridge::new(bus.clone(), "127.0.0.1:0".parse().unwrap())
+            .client_buffer_size(4);
+        let dropped = bridge.dropped_counter();
+        let addr = start_bridge(bridge).await;
+        let mut client = connect(addr, &["market_data"]).await;
+        
+        // Publishing never waits for the client
+        for i in 0..1000 {
+            bus.publish(quote("ES", i as f64)).await.unwrap();
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        assert!(dropped.load(Ordering::Relaxed) > 0);
+        
+        // Events that fit in the buffer still arrive, in order
+        let first = next_json(&mut client).await;
+        let second = next_json(&mut client).await;
+        assert!(first["payload"]["price"].as_f64() < second["payload"]["price"].as_f64());
+    }
+}
@@ -1,221 +1,2621 @@
 //! Core event bus implementation
 
-use crate::events::{Event, EventEnvelope};
-use anyhow::Result;
+use crate::content_cache::ContentAddressedCache;
+use crate::dead_letter::{DeadLetterEntry, DeadLetterQueue, DeadLetterReason};
+use crate::events::{
+    Event, EventEnvelope, FeatureEvent, FillEvent, MarketDataEvent, OrderEvent, SignalEvent, StaticEventType,
+    TransactionRolledBackEvent,
+};
+use crate::metrics::MetricEventBus;
+use crate::middleware::{Middleware, MiddlewareResult};
+use crate::pattern::PatternKind;
+use crate::pool::PooledEnvelope;
+use crate::priority_bus::PriorityEventBus;
+use crate::size_budget::{SizeBudget, SizeBudgetPolicy};
+use crate::replay::EventRecorder;
+use crate::replay_mode::{EventReplay, EventReplayBuilder, ReplaySpeed, ReplayStats, SharedClock};
+use anyhow::{anyhow, bail, Result};
+use dashmap::mapref::entry::Entry;
 use dashmap::DashMap;
-use std::sync::Arc;
-use tokio::sync::broadcast;
+use std::any::{Any, TypeId};
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::sync::atomic::{AtomicI64, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock, Weak};
+use std::time::Duration;
+use futures_util::StreamExt;
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+use tokio::sync::{broadcast, mpsc, oneshot};
 use tracing::{debug, warn};
+use uuid::Uuid;
 
 /// Channel capacity for each event type
-const CHANNEL_CAPACITY: usize = 10000;
+pub(crate) const CHANNEL_CAPACITY: usize = 10000;
+
+/// Events over which `SampledReceiver::effective_rate` is measured
+pub const SAMPLING_WINDOW: usize = 10_000;
 
 /// High-performance event bus for multi-threaded pub/sub
+///
+/// Cloning is cheap and yields a handle to the same channels.
+#[derive(Clone)]
 pub struct EventBus {
     /// Broadcast channels for each event type
-    channels: Arc<DashMap<String, broadcast::Sender<EventEnvelope>>>,
+    channels: Arc<DashMap<String, Channel>>,
+    
+    /// Wildcard/prefix subscriptions, checked after the direct lookup
+    patterns: Arc<RwLock<Vec<(PatternKind, broadcast::Sender<EventEnvelope>)>>>,
+    
+    /// Number of registered patterns (lets publish skip the lock when zero)
+    pattern_count: Arc<AtomicUsize>,
     
     /// Event recorder for replay (optional)
     recorder: Option<Arc<crate::replay::EventRecorder>>,
     
     /// Statistics
     stats: Arc<DashMap<String, EventStats>>,
+    
+    /// Content-addressed deduplication (optional)
+    content_cache: Option<Arc<ContentAddressedCache>>,
+    
+    /// Multicast groups indexed by group name
+    groups: Arc<DashMap<String, MulticastGroup>>,
+    
+    /// Event types each type group fans out to, indexed by group name
+    type_groups: Arc<DashMap<String, Vec<String>>>,
+    
+    /// Sequence number of the last snapshot taken or restored
+    snapshot_sequence: Arc<AtomicU64>,
+    
+    /// Secondary per-type queues for synchronous `try_drain`
+    drains: Arc<DashMap<String, DrainQueue>>,
+    
+    /// Typed channels for `subscribe_typed`, indexed by Rust type
+    typed: Arc<DashMap<TypeId, TypedChannel>>,
+    
+    /// Number of typed channels (lets publish skip the lookup when zero)
+    typed_count: Arc<AtomicUsize>,
+    
+    /// Content-routed subscriptions from `subscribe_where`
+    predicates: Arc<RwLock<Vec<(TypeId, Arc<dyn Predicate>, broadcast::Sender<EventEnvelope>)>>>,
+    
+    /// Number of predicate subscriptions (lets publish skip the lock when zero)
+    predicate_count: Arc<AtomicUsize>,
+    
+    /// Per-correlation channels for `subscribe_correlation`
+    correlations: Arc<DashMap<Uuid, broadcast::Sender<EventEnvelope>>>,
+    
+    /// Publish-time middleware, run in insertion order
+    middleware: Arc<RwLock<Vec<Arc<dyn Middleware + Send + Sync>>>>,
+    
+    /// Number of middleware steps (lets publish skip the lock when zero)
+    middleware_count: Arc<AtomicUsize>,
+    
+    /// Serialized size limit for published envelopes (optional)
+    size_budget: Option<Arc<SizeBudget>>,
+    
+    /// Captures events published with no subscribers (optional)
+    dead_letters: Option<DeadLetterQueue>,
+    
+    /// Virtual clock used to stamp new envelopes instead of wall-clock time (optional)
+    clock: Option<SharedClock>,
+    
+    /// Serializes `publish_transaction` calls
+    transaction_lock: Arc<tokio::sync::Mutex<()>>,
 }
 
-#[derive(Debug, Clone, Default)]
-pub struct EventStats {
-    pub published: u64,
-    pub received: u64,
-    pub dropped: u64,
+/// Broadcast sender for one event type plus its diagnostics
+#[derive(Clone)]
+struct Channel {
+    /// Shared so receipts can watch the queue through a `Weak` without
+    /// keeping the channel open
+    sender: Arc<broadcast::Sender<EventEnvelope>>,
+    /// Timestamp of the most recent envelope published to the channel
+    last_publish_ns: Arc<AtomicI64>,
+    /// Sends started on `sender`, incremented before each send so a receipt
+    /// can count envelopes known to be queued behind its own
+    sends: Arc<AtomicU64>,
+    /// Next sequence number, set by `EventBus::enable_sequencing`
+    ///
+    /// The lock is held from numbering an envelope until it is sent, so
+    /// receivers see numbers in order even with concurrent publishers.
+    sequence: Option<Arc<tokio::sync::Mutex<u64>>>,
 }
 
-impl EventBus {
-    /// Create a new event bus
-    pub fn new() -> Self {
+impl Channel {
+    fn new() -> Self {
         Self {
-            channels: Arc::new(DashMap::new()),
-            recorder: None,
-            stats: Arc::new(DashMap::new()),
+            sender: Arc::new(broadcast::channel(CHANNEL_CAPACITY).0),
+            last_publish_ns: Arc::new(AtomicI64::new(0)),
+            sends: Arc::new(AtomicU64::new(0)),
+            sequence: None,
         }
     }
     
-    /// Create event bus with recording enabled
-    pub fn with_recording(capacity: usize) -> Self {
-        Self {
-            channels: Arc::new(DashMap::new()),
-            recorder: Some(Arc::new(crate::replay::EventRecorder::new(capacity))),
-            stats: Arc::new(DashMap::new()),
+    /// Give `envelope` the channel's next sequence number
+    ///
+    /// Returns the sequence lock, which the caller holds until the envelope
+    /// is sent. On an unsequenced channel, or with `sequenced` false, the
+    /// envelope gets `sequence_number: None`.
+    async fn number(
+        &self,
+        envelope: &mut EventEnvelope,
+        sequenced: bool,
+    ) -> Option<tokio::sync::MutexGuard<'_, u64>> {
+        match &self.sequence {
+            Some(sequence) if sequenced => {
+                let mut next = sequence.lock().await;
+                envelope.sequence_number = Some(*next);
+                *next += 1;
+                Some(next)
+            }
+            _ => {
+                envelope.sequence_number = None;
+                None
+            }
         }
     }
     
-    /// Publish an event to all subscribers
-    pub async fn publish<T: Event + Send + 'static>(&self, event: T) -> Result<()> {
-        self.publish_with_priority(event, 5).await
+    /// Send an envelope already passed through `number`
+    fn send(&self, envelope: EventEnvelope) -> Result<usize, broadcast::error::SendError<EventEnvelope>> {
+        self.last_publish_ns.store(envelope.timestamp_ns, Ordering::Relaxed);
+        self.sends.fetch_add(1, Ordering::AcqRel);
+        self.sender.send(envelope)
     }
     
-    /// Publish event with specific priority (0 = highest)
-    pub async fn publish_with_priority<T: Event + Send + 'static>(&self, event: T, priority: u8) -> Result<()> {
-        let event_type = Self::event_type_name(&event);
-        let envelope = EventEnvelope::new(event, priority);
-        
-        // Record event if recording is enabled
-        if let Some(recorder) = &self.recorder {
-            recorder.record(envelope.clone()).await;
-        }
-        
-        // Get or create channel for this event type
-        let sender = self.channels.entry(event_type.to_string())
-            .or_insert_with(|| {
-                debug!("Creating new channel for event type: {}", event_type);
-                broadcast::channel(CHANNEL_CAPACITY).0
-            })
-            .clone();
-        
-        // Publish to channel
-        match sender.send(envelope) {
-            Ok(_subscriber_count) => {
-                self.increment_stat(event_type, |s| s.published += 1);
-                Ok(())
-            }
-            Err(_) => {
-                self.increment_stat(event_type, |s| s.dropped += 1);
-                Ok(()) // Not an error if no subscribers
-            }
+    /// Delivery tracking for the envelope just sent
+    fn delivery(&self) -> Delivery {
+        Delivery {
+            sender: Arc::downgrade(&self.sender),
+            sends: self.sends.clone(),
+            mark: self.sends.load(Ordering::Acquire),
         }
     }
+}
+
+/// Channel state `PublishReceipt::is_delivered` watches
+///
+/// Holds the sender weakly so an outstanding receipt does not stop
+/// `drain_channel` or `drop_channel` from closing the channel.
+#[derive(Clone)]
+struct Delivery {
+    sender: Weak<broadcast::Sender<EventEnvelope>>,
+    sends: Arc<AtomicU64>,
+    /// Send count just after the envelope was queued
+    mark: u64,
+}
+
+/// Result of `EventBus::publish`
+///
+/// `subscriber_count` is the number of channel receivers the envelope was
+/// queued for; pattern, predicate and drain subscribers are not counted.
+/// A content-cache hit delivers nothing; its receipt has a `subscriber_count`
+/// of 0 and the `event_id` and `timestamp_ns` of the envelope first published.
+#[derive(Clone)]
+pub struct PublishReceipt {
+    pub subscriber_count: usize,
+    pub event_id: Uuid,
+    pub timestamp_ns: i64,
+    delivery: Option<Delivery>,
+}
+
+/// Outcome of a conditional publish
+#[derive(Clone)]
+pub enum PublishDecision {
+    Published(PublishReceipt),
+    /// The condition did not hold; nothing was published
+    Suppressed,
+}
+
+impl PublishDecision {
+    pub fn is_published(&self) -> bool {
+        matches!(self, PublishDecision::Published(_))
+    }
     
-    /// Publish an EventEnvelope directly (used for replay)
-    pub async fn publish_envelope(&self, envelope: EventEnvelope) -> Result<()> {
-        let event_type = envelope.event.event_type();
-        
-        // Record event if recording is enabled
-        if let Some(recorder) = &self.recorder {
-            recorder.record(envelope.clone()).await;
+    /// Receipt of the publish (`None` if suppressed)
+    pub fn receipt(&self) -> Option<&PublishReceipt> {
+        match self {
+            PublishDecision::Published(receipt) => Some(receipt),
+            PublishDecision::Suppressed => None,
         }
-        
-        // Get or create channel for this event type
-        let sender = self.channels.entry(event_type.to_string())
-            .or_insert_with(|| {
-                debug!("Creating new channel for event type: {}", event_type);
-                broadcast::channel(CHANNEL_CAPACITY).0
-            })
-            .clone();
-        
-        // Publish to channel
-        match sender.send(envelope) {
-            Ok(_subscriber_count) => {
-                self.increment_stat(event_type, |s| s.published += 1);
-                Ok(())
+    }
+}
+
+impl PublishReceipt {
+    fn empty(event_id: Uuid, timestamp_ns: i64) -> Self {
+        Self::untracked(0, event_id, timestamp_ns)
+    }
+    
+    /// Receipt whose delivery is not tracked (`is_delivered` is always true)
+    pub(crate) fn untracked(subscriber_count: usize, event_id: Uuid, timestamp_ns: i64) -> Self {
+        Self { subscriber_count, event_id, timestamp_ns, delivery: None }
+    }
+    
+    /// Check if every receiver alive at publish time has consumed the event
+    ///
+    /// Receivers that lagged past the event count as done, as do receivers
+    /// of a channel that has since been drained or dropped. The broadcast
+    /// queue is FIFO, so the event is consumed once the queue holds no more
+    /// than the envelopes started after its send returned.
+    pub fn is_delivered(&self) -> bool {
+        let Some(delivery) = &self.delivery else {
+            return true;
+        };
+        match delivery.sender.upgrade() {
+            Some(sender) => {
+                let sent_after = delivery.sends.load(Ordering::Acquire).saturating_sub(delivery.mark);
+                sender.len() as u64 <= sent_after
             }
-            Err(_) => {
-                self.increment_stat(event_type, |s| s.dropped += 1);
-                Ok(()) // Not an error if no subscribers
+            None => true,
+        }
+    }
+}
+
+impl std::fmt::Debug for PublishReceipt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PublishReceipt")
+            .field("subscriber_count", &self.subscriber_count)
+            .field("event_id", &self.event_id)
+            .field("timestamp_ns", &self.timestamp_ns)
+            .finish()
+    }
+}
+
+/// One event of an `EventBus::publish_transaction` call
+#[derive(Debug)]
+pub struct TransactionEvent {
+    pub event: Box<dyn Event>,
+    pub priority: u8,
+}
+
+impl TransactionEvent {
+    pub fn new<T: Event + 'static>(event: T, priority: u8) -> Self {
+        Self { event: Box::new(event), priority }
+    }
+}
+
+/// Result of a committed `EventBus::publish_transaction`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransactionReceipt {
+    /// `EventEnvelope::transaction_id` of every envelope in the transaction
+    pub transaction_id: Uuid,
+    pub events_published: usize,
+}
+
+/// Type-erased `broadcast::Sender<T>` plus the function that clones a `T` into it
+struct TypedChannel {
+    sender: Box<dyn Any + Send + Sync>,
+    forward: fn(&(dyn Any + Send + Sync), &dyn Any),
+}
+
+/// Clone `event` into `sender` (both erased; `T` is fixed when the channel is created)
+fn forward_typed_event<T: Clone + Send + 'static>(sender: &(dyn Any + Send + Sync), event: &dyn Any) {
+    if let (Some(sender), Some(event)) = (
+        sender.downcast_ref::<broadcast::Sender<T>>(),
+        event.downcast_ref::<T>(),
+    ) {
+        // No receivers is not an error
+        let _ = sender.send(event.clone());
+    }
+}
+
+/// Type-erased filter for `subscribe_where`
+trait Predicate: Send + Sync {
+    fn matches(&self, event: &dyn Any) -> bool;
+}
+
+impl<F: Fn(&dyn Any) -> bool + Send + Sync> Predicate for F {
+    fn matches(&self, event: &dyn Any) -> bool {
+        self(event)
+    }
+}
+
+/// Receiver for one concrete event type, without envelopes or dyn dispatch
+pub struct TypedReceiver<T> {
+    receiver: broadcast::Receiver<T>,
+}
+
+impl<T: Clone> TypedReceiver<T> {
+    /// Receive next event (errors if the receiver lagged or the bus is gone)
+    pub async fn recv(&mut self) -> Result<T> {
+        match self.receiver.recv().await {
+            Ok(event) => Ok(event),
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                bail!("Typed receiver lagged, skipped {} events", skipped)
             }
+            Err(broadcast::error::RecvError::Closed) => bail!("Typed channel closed"),
         }
     }
     
-    /// Subscribe to a specific event type
-    pub async fn subscribe(&self, event_type: &str) -> broadcast::Receiver<EventEnvelope> {
-        let sender = self.channels.entry(event_type.to_string())
-            .or_insert_with(|| {
-                debug!("Creating new channel for subscription: {}", event_type);
-                broadcast::channel(CHANNEL_CAPACITY).0
-            })
-            .clone();
-        
-        sender.subscribe()
+    /// Try to receive without blocking
+    pub fn try_recv(&mut self) -> Option<T> {
+        self.receiver.try_recv().ok()
+    }
+}
+
+/// Receiver merging several event-type channels in arrival order
+///
+/// Fed by a background task that exits when every source channel closes or
+/// this receiver is dropped.
+pub struct MergedReceiver {
+    receiver: flume::Receiver<EventEnvelope>,
+    /// Dropping this stops the forwarding task
+    _shutdown: oneshot::Sender<()>,
+}
+
+impl MergedReceiver {
+    /// Receive next event (`None` once all source channels close)
+    pub async fn recv(&mut self) -> Option<EventEnvelope> {
+        self.receiver.recv_async().await.ok()
     }
     
-    /// Subscribe to market data events
-    pub async fn subscribe_market_data(&self) -> broadcast::Receiver<EventEnvelope> {
-        self.subscribe("MarketData").await
+    /// Try to receive without blocking
+    pub fn try_recv(&mut self) -> Option<EventEnvelope> {
+        self.receiver.try_recv().ok()
     }
+}
+
+/// Forward envelopes from every source into `tx` until all close or `shutdown` fires
+async fn merge_channels(
+    sources: Vec<broadcast::Receiver<EventEnvelope>>,
+    tx: flume::Sender<EventEnvelope>,
+    mut shutdown: oneshot::Receiver<()>,
+) {
+    let streams = sources.into_iter().map(|receiver| {
+        Box::pin(futures_util::stream::unfold(receiver, |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(envelope) => return Some((envelope, receiver)),
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!("Merged receiver lagged, skipped {} events", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                }
+            }
+        }))
+    });
+    let mut merged = futures_util::stream::select_all(streams);
     
-    /// Subscribe to signal events
-    pub async fn subscribe_signals(&self) -> broadcast::Receiver<EventEnvelope> {
-        self.subscribe("Signal").await
+    loop {
+        tokio::select! {
+            envelope = merged.next() => match envelope {
+                Some(envelope) => {
+                    if tx.send_async(envelope).await.is_err() {
+                        return;
+                    }
+                }
+                None => return,
+            },
+            _ = &mut shutdown => return,
+        }
+    }
+}
+
+/// Receiver for a sample of one event type's envelopes
+///
+/// Fed by a background task that exits when the source channel closes or
+/// this receiver is dropped.
+pub struct SampledReceiver {
+    receiver: flume::Receiver<EventEnvelope>,
+    window: Arc<SamplingWindow>,
+    /// Dropping this stops the sampling task
+    _shutdown: oneshot::Sender<()>,
+}
+
+impl SampledReceiver {
+    /// Receive next sampled event (`None` once the source channel closes)
+    pub async fn recv(&mut self) -> Option<EventEnvelope> {
+        self.receiver.recv_async().await.ok()
     }
     
-    /// Subscribe to fill events
-    pub async fn subscribe_fills(&self) -> broadcast::Receiver<EventEnvelope> {
-        self.subscribe("Fill").await
+    /// Try to receive without blocking
+    pub fn try_recv(&mut self) -> Option<EventEnvelope> {
+        self.receiver.try_recv().ok()
     }
     
-    /// Subscribe to order events
-    pub async fn subscribe_orders(&self) -> broadcast::Receiver<EventEnvelope> {
-        self.subscribe("Order").await
+    /// Fraction of the last `SAMPLING_WINDOW` events that were forwarded
+    /// (0.0 before any event arrives)
+    pub fn effective_rate(&self) -> f64 {
+        let decisions = self.window.decisions.lock().unwrap();
+        if decisions.recent.is_empty() {
+            return 0.0;
+        }
+        decisions.forwarded as f64 / decisions.recent.len() as f64
     }
     
-    /// Subscribe to feature events
-    pub async fn subscribe_features(&self) -> broadcast::Receiver<EventEnvelope> {
-        self.subscribe("Feature").await
+    /// Events the sampler has considered so far
+    pub fn seen(&self) -> u64 {
+        self.window.seen.load(Ordering::Relaxed)
+    }
+}
+
+/// How `sample_channel` picks envelopes
+enum Sampling {
+    Probability(f64, SmallRng),
+    EveryNth(u64),
+}
+
+/// Sampling decisions shared between the task and its `SampledReceiver`
+#[derive(Default)]
+struct SamplingWindow {
+    seen: AtomicU64,
+    decisions: Mutex<SamplingDecisions>,
+}
+
+#[derive(Default)]
+struct SamplingDecisions {
+    /// Last `SAMPLING_WINDOW` decisions, oldest first
+    recent: VecDeque<bool>,
+    /// Forwarded events in `recent`
+    forwarded: usize,
+}
+
+impl SamplingWindow {
+    fn record(&self, forwarded: bool) {
+        let mut decisions = self.decisions.lock().unwrap();
+        if decisions.recent.len() == SAMPLING_WINDOW && decisions.recent.pop_front() == Some(true) {
+            decisions.forwarded -= 1;
+        }
+        decisions.recent.push_back(forwarded);
+        decisions.forwarded += forwarded as usize;
+    }
+}
+
+/// Forward a sample of `source` into `tx` until it closes or `shutdown` fires
+async fn sample_channel(
+    mut source: broadcast::Receiver<EventEnvelope>,
+    tx: flume::Sender<EventEnvelope>,
+    window: Arc<SamplingWindow>,
+    mut sampling: Sampling,
+    mut shutdown: oneshot::Receiver<()>,
+) {
+    loop {
+        let envelope = tokio::select! {
+            received = source.recv() => match received {
+                Ok(envelope) => envelope,
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!("Sampled receiver lagged, skipped {} events", skipped);
+                    continue;
+                }
+                Err(broadcast::error::RecvError::Closed) => return,
+            },
+            _ = &mut shutdown => return,
+        };
+        
+        let index = window.seen.fetch_add(1, Ordering::Relaxed);
+        let forward = match &mut sampling {
+            Sampling::Probability(rate, rng) => rng.gen_bool(*rate),
+            Sampling::EveryNth(n) => index % *n == 0,
+        };
+        window.record(forward);
+        if forward && tx.send_async(envelope).await.is_err() {
+            return;
+        }
+    }
+}
+
+/// Receiver yielding windows of consecutive envelopes of one event type
+///
+/// A lag on the underlying channel clears the buffer, so no window spans
+/// skipped events.
+pub struct WindowedReceiver {
+    inner: broadcast::Receiver<EventEnvelope>,
+    buffer: VecDeque<EventEnvelope>,
+    window_size: usize,
+}
+
+impl WindowedReceiver {
+    /// Receive until the buffer holds `window_size` events
+    ///
+    /// Returns false once the channel closes.
+    async fn fill(&mut self) -> bool {
+        while self.buffer.len() < self.window_size {
+            match self.inner.recv().await {
+                Ok(envelope) => self.buffer.push_back(envelope),
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!("Windowed receiver lagged, skipped {} events", skipped);
+                    self.buffer.clear();
+                }
+                Err(broadcast::error::RecvError::Closed) => return false,
+            }
+        }
+        true
     }
     
-    /// Get event statistics
-    pub fn get_stats(&self) -> Vec<(String, EventStats)> {
-        self.stats.iter()
-            .map(|entry| (entry.key().clone(), entry.value().clone()))
-            .collect()
+    /// Next sliding window, oldest event first
+    ///
+    /// The first call waits for `window_size` events; each later call
+    /// advances the window by one event. `None` once the channel closes.
+    pub async fn recv_window(&mut self) -> Option<&[EventEnvelope]> {
+        if self.buffer.len() == self.window_size {
+            self.buffer.pop_front();
+        }
+        if !self.fill().await {
+            return None;
+        }
+        Some(&*self.buffer.make_contiguous())
     }
     
-    /// Get event recorder for replay
-    pub fn recorder(&self) -> Option<Arc<crate::replay::EventRecorder>> {
-        self.recorder.clone()
+    /// Next non-overlapping window of `window_size` events
+    ///
+    /// `None` once the channel closes; a partial window is discarded.
+    pub async fn recv_tumbling_window(&mut self) -> Option<Vec<EventEnvelope>> {
+        if !self.fill().await {
+            return None;
+        }
+        Some(self.buffer.drain(..).collect())
     }
     
-    /// Helper to get event type name (zero-alloc)
-    fn event_type_name(event: &dyn Event) -> &'static str {
-        event.event_type()
+    /// Events per window
+    pub fn window_size(&self) -> usize {
+        self.window_size
+    }
+}
+
+/// Receiver pairing envelopes of two event types by timestamp
+///
+/// Each envelope joins at most one envelope of the other type: the buffered
+/// one closest in time, if within `tolerance_ns`. Both channels are assumed
+/// to deliver in timestamp order, so a buffered envelope is dropped as
+/// unmatched once the other type moves more than `tolerance_ns` past it, or
+/// when its buffer reaches `CHANNEL_CAPACITY`.
+///
+/// The two channels are merged by timestamp, the first type winning ties,
+/// so pairing does not depend on task scheduling. This means waiting for an
+/// envelope of each open type before handling either; a type that stops
+/// publishing holds back the other until its channel closes. Envelopes
+/// still buffered when both channels close count as unmatched.
+pub struct JoinReceiver {
+    a: broadcast::Receiver<EventEnvelope>,
+    b: broadcast::Receiver<EventEnvelope>,
+    /// Next envelope of each type, received but not yet handled
+    head_a: Option<EventEnvelope>,
+    head_b: Option<EventEnvelope>,
+    a_open: bool,
+    b_open: bool,
+    /// Envelopes still waiting for a partner, oldest first
+    pending_a: VecDeque<EventEnvelope>,
+    pending_b: VecDeque<EventEnvelope>,
+    tolerance_ns: i64,
+    unmatched_a: usize,
+    unmatched_b: usize,
+}
+
+impl JoinReceiver {
+    /// Next `(a, b)` pair
+    ///
+    /// `None` once both channels have closed. Cancel safe: an envelope
+    /// received before cancellation is kept for the next call.
+    pub async fn recv(&mut self) -> Option<(EventEnvelope, EventEnvelope)> {
+        loop {
+            if self.head_a.is_none() && self.a_open {
+                self.head_a = next_joined(&mut self.a, &mut self.a_open).await;
+            }
+            if self.head_b.is_none() && self.b_open {
+                self.head_b = next_joined(&mut self.b, &mut self.b_open).await;
+            }
+            
+            let from_a = match (&self.head_a, &self.head_b) {
+                (Some(a), Some(b)) => a.timestamp_ns <= b.timestamp_ns,
+                (Some(_), None) => true,
+                (None, Some(_)) => false,
+                (None, None) => {
+                    // Nothing left to pair the buffered envelopes with
+                    self.unmatched_a += self.pending_a.len();
+                    self.unmatched_b += self.pending_b.len();
+                    self.pending_a.clear();
+                    self.pending_b.clear();
+                    return None;
+                }
+            };
+            
+            if from_a {
+                let envelope = self.head_a.take()?;
+                let timestamp_ns = envelope.timestamp_ns;
+                match take_partner(&mut self.pending_b, &mut self.unmatched_b, timestamp_ns, self.tolerance_ns) {
+                    Some(b) => return Some((envelope, b)),
+                    None => buffer_unmatched(&mut self.pending_a, &mut self.unmatched_a, envelope),
+                }
+            } else {
+                let envelope = self.head_b.take()?;
+                let timestamp_ns = envelope.timestamp_ns;
+                match take_partner(&mut self.pending_a, &mut self.unmatched_a, timestamp_ns, self.tolerance_ns) {
+                    Some(a) => return Some((a, envelope)),
+                    None => buffer_unmatched(&mut self.pending_b, &mut self.unmatched_b, envelope),
+                }
+            }
+        }
     }
     
-    /// Increment statistics
-    fn increment_stat<F>(&self, event_type: &str, f: F)
-    where
-        F: FnOnce(&mut EventStats),
-    {
-        self.stats.entry(event_type.to_string())
-            .or_insert_with(EventStats::default)
-            .value_mut()
-            .apply(f);
+    /// Envelopes of the first type dropped without a partner
+    pub fn unmatched_a(&self) -> usize {
+        self.unmatched_a
+    }
+    
+    /// Envelopes of the second type dropped without a partner
+    pub fn unmatched_b(&self) -> usize {
+        self.unmatched_b
+    }
+    
+    /// Maximum timestamp difference between paired envelopes
+    pub fn tolerance_ns(&self) -> i64 {
+        self.tolerance_ns
     }
 }
 
-impl Default for EventBus {
-    fn default() -> Self {
-        Self::new()
+/// Next envelope from one side of a join (`None` once its channel closes)
+async fn next_joined(rx: &mut broadcast::Receiver<EventEnvelope>, open: &mut bool) -> Option<EventEnvelope> {
+    loop {
+        match rx.recv().await {
+            Ok(envelope) => return Some(envelope),
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                warn!("Join receiver lagged, skipped {} events", skipped);
+            }
+            Err(broadcast::error::RecvError::Closed) => {
+                *open = false;
+                return None;
+            }
+        }
     }
 }
 
-trait Apply {
-    fn apply<F>(&mut self, f: F)
-    where
-        F: FnOnce(&mut Self);
+/// Remove the pending envelope closest to `timestamp_ns` within `tolerance_ns`
+///
+/// Pending envelopes too old to match `timestamp_ns` (or anything after it)
+/// are dropped first and counted in `unmatched`.
+fn take_partner(
+    pending: &mut VecDeque<EventEnvelope>,
+    unmatched: &mut usize,
+    timestamp_ns: i64,
+    tolerance_ns: i64,
+) -> Option<EventEnvelope> {
+    let oldest_match_ns = timestamp_ns.saturating_sub(tolerance_ns);
+    while pending.front().is_some_and(|e| e.timestamp_ns < oldest_match_ns) {
+        pending.pop_front();
+        *unmatched += 1;
+    }
+    let index = pending.iter()
+        .enumerate()
+        .map(|(index, e)| (index, e.timestamp_ns.abs_diff(timestamp_ns)))
+        .filter(|&(_, distance)| distance <= tolerance_ns as u64)
+        .min_by_key(|&(_, distance)| distance)
+        .map(|(index, _)| index)?;
+    pending.remove(index)
 }
 
-impl Apply for EventStats {
-    fn apply<F>(&mut self, f: F)
-    where
-        F: FnOnce(&mut Self),
-    {
-        f(self);
+/// Buffer an envelope awaiting a partner, dropping the oldest when full
+fn buffer_unmatched(pending: &mut VecDeque<EventEnvelope>, unmatched: &mut usize, envelope: EventEnvelope) {
+    if pending.len() == CHANNEL_CAPACITY {
+        pending.pop_front();
+        *unmatched += 1;
     }
+    pending.push_back(envelope);
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::events::MarketDataEvent;
+/// Forwards selected event types from a parent bus into a child from `EventBus::fork`
+///
+/// Forwarded envelopes keep their ID, timestamp and correlation. Dropping
+/// the handle stops all forwarding.
+pub struct BridgeHandle {
+    parent: EventBus,
+    child: EventBus,
+    /// Shutdown signal of each forwarding task, by event type
+    forwards: DashMap<String, oneshot::Sender<()>>,
+}
+
+impl BridgeHandle {
+    /// Start forwarding `event_type` from the parent to the child
+    ///
+    /// Parent events published after this returns are forwarded. Must be
+    /// called within a Tokio runtime. Returns false if already forwarded.
+    pub fn forward(&self, event_type: &str) -> bool {
+        match self.forwards.entry(event_type.to_string()) {
+            Entry::Occupied(_) => false,
+            Entry::Vacant(slot) => {
+                let source = self.parent.channel_receiver(event_type);
+                let (shutdown, shutdown_rx) = oneshot::channel();
+                tokio::spawn(forward_to_bus(source, self.child.clone(), shutdown_rx));
+                slot.insert(shutdown);
+                true
+            }
+        }
+    }
     
-    #[tokio::test]
-    async fn test_publish_subscribe() {
-        let bus = EventBus::new();
-        
-        // Subscribe before publishing
-        let mut rx = bus.subscribe_market_data().await;
+    /// Stop forwarding `event_type`; returns false if it was not forwarded
+    pub fn stop_forwarding(&self, event_type: &str) -> bool {
+        self.forwards.remove(event_type).is_some()
+    }
+    
+    /// Event types currently forwarded
+    pub fn forwarded_types(&self) -> Vec<String> {
+        self.forwards.iter().map(|entry| entry.key().clone()).collect()
+    }
+}
+
+/// Republish envelopes from `source` on `target` until the source closes or `shutdown` fires
+async fn forward_to_bus(
+    mut source: broadcast::Receiver<EventEnvelope>,
+    target: EventBus,
+    mut shutdown: oneshot::Receiver<()>,
+) {
+    loop {
+        tokio::select! {
+            received = source.recv() => match received {
+                Ok(envelope) => {
+                    if let Err(e) = target.publish_envelope(envelope).await {
+                        warn!("Failed to forward event to forked bus: {}", e);
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!("Bus bridge lagged, skipped {} events", skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => return,
+            },
+            _ = &mut shutdown => return,
+        }
+    }
+}
+
+/// Hands each envelope from `receiver` to the worker chosen by `pick`
+///
+/// If the chosen worker's receiver was dropped, the next live worker takes
+/// the envelope. Exits when the source channel closes or every worker is gone.
+async fn dispatch_to_workers<F>(
+    mut receiver: broadcast::Receiver<EventEnvelope>,
+    workers: Vec<flume::Sender<EventEnvelope>>,
+    mut pick: F,
+) where
+    F: FnMut(&EventEnvelope) -> usize + Send,
+{
+    loop {
+        let mut envelope = match receiver.recv().await {
+            Ok(envelope) => envelope,
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                warn!("Worker dispatcher lagged, skipped {} events", skipped);
+                continue;
+            }
+            Err(broadcast::error::RecvError::Closed) => return,
+        };
         
-        // Publish event
-        let event = Event::MarketData(MarketDataEvent {
-            timestamp: 1234567890,
+        let first = pick(&envelope) % workers.len();
+        let mut delivered = false;
+        for offset in 0..workers.len() {
+            match workers[(first + offset) % workers.len()].send_async(envelope).await {
+                Ok(()) => {
+                    delivered = true;
+                    break;
+                }
+                Err(flume::SendError(returned)) => envelope = returned,
+            }
+        }
+        if !delivered {
+            return;
+        }
+    }
+}
+
+/// Bounded queue fed alongside a broadcast channel (oldest events evicted when full)
+#[derive(Clone)]
+struct DrainQueue {
+    tx: flume::Sender<EventEnvelope>,
+    rx: flume::Receiver<EventEnvelope>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct EventStats {
+    pub published: u64,
+    pub received: u64,
+    pub dropped: u64,
+    pub deduplicated: u64,
+    /// Dropped by middleware
+    pub filtered: u64,
+}
+
+/// Per-channel diagnostics returned by `EventBus::channel_stats_snapshot`
+#[derive(Debug, Clone, Default)]
+pub struct ChannelSnapshot {
+    pub published: u64,
+    pub dropped: u64,
+    /// Live receivers on the channel
+    pub subscriber_count: usize,
+    /// Fraction of the channel buffer in use, `queued / CHANNEL_CAPACITY`
+    ///
+    /// An envelope stays queued until every receiver has seen it, so this
+    /// tracks the slowest subscriber.
+    pub buffer_utilization: f64,
+    /// Timestamp of the most recent envelope published (0 if none)
+    pub last_publish_ns: i64,
+}
+
+/// Copy of bus state for hot-standby failover (see `EventBus::take_snapshot`)
+#[derive(Debug, Clone)]
+pub struct BusSnapshot {
+    /// Recorded events grouped by event type (oldest first)
+    pub channels: HashMap<String, Vec<EventEnvelope>>,
+    
+    /// Per-type statistics
+    pub stats: HashMap<String, EventStats>,
+    
+    /// Monotonic snapshot sequence number
+    pub sequence: u64,
+    
+    /// Wall-clock time the snapshot was taken (nanoseconds)
+    pub taken_at: i64,
+}
+
+impl BusSnapshot {
+    /// Nanoseconds elapsed since the snapshot was taken
+    pub fn age_ns(&self) -> i64 {
+        chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0) - self.taken_at
+    }
+    
+    /// Total number of events captured
+    pub fn event_count(&self) -> usize {
+        self.channels.values().map(|events| events.len()).sum()
+    }
+}
+
+/// Configuration carried over to a forked bus
+#[derive(Debug, Clone, Default)]
+pub struct ForkConfig {
+    /// Recorder capacity for the forked bus (`None` = no recording)
+    pub recording_capacity: Option<usize>,
+}
+
+/// Named fan-out group for a single event type
+///
+/// Each member owns a dedicated broadcast channel, so a group can be
+/// published to independently of the bus-wide per-type channel.
+#[derive(Debug)]
+pub struct MulticastGroup {
+    pub name: String,
+    pub event_type: String,
+    pub members: Vec<broadcast::Sender<EventEnvelope>>,
+}
+
+/// Outcome of `publish_to_type_group`
+///
+/// `failed` lists member types whose channel had no subscribers.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GroupPublishResult {
+    pub successful: Vec<String>,
+    pub failed: Vec<String>,
+}
+
+/// Envelope that passed the publish pipeline, or the receipt for one that stopped
+enum Prepared {
+    Deliver(EventEnvelope),
+    Done(PublishReceipt),
+}
+
+/// Handle for joining a multicast group
+#[derive(Clone)]
+pub struct GroupHandle {
+    name: String,
+    groups: Arc<DashMap<String, MulticastGroup>>,
+}
+
+impl GroupHandle {
+    /// Group name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    
+    /// Join the group, returning a receiver for events published to it
+    ///
+    /// If the group has been disbanded the returned receiver is already closed.
+    pub fn join(&self) -> broadcast::Receiver<EventEnvelope> {
+        let (tx, rx) = broadcast::channel(CHANNEL_CAPACITY);
+        if let Some(mut group) = self.groups.get_mut(&self.name) {
+            group.members.push(tx);
+        }
+        rx
+    }
+}
+
+impl EventBus {
+    /// Create a new event bus
+    pub fn new() -> Self {
+        Self {
+            channels: Arc::new(DashMap::new()),
+            patterns: Arc::new(RwLock::new(Vec::new())),
+            pattern_count: Arc::new(AtomicUsize::new(0)),
+            recorder: None,
+            stats: Arc::new(DashMap::new()),
+            content_cache: None,
+            groups: Arc::new(DashMap::new()),
+            type_groups: Arc::new(DashMap::new()),
+            snapshot_sequence: Arc::new(AtomicU64::new(0)),
+            drains: Arc::new(DashMap::new()),
+            typed: Arc::new(DashMap::new()),
+            typed_count: Arc::new(AtomicUsize::new(0)),
+            predicates: Arc::new(RwLock::new(Vec::new())),
+            predicate_count: Arc::new(AtomicUsize::new(0)),
+            correlations: Arc::new(DashMap::new()),
+            middleware: Arc::new(RwLock::new(Vec::new())),
+            middleware_count: Arc::new(AtomicUsize::new(0)),
+            size_budget: None,
+            dead_letters: None,
+            clock: None,
+            transaction_lock: Arc::new(tokio::sync::Mutex::new(())),
+        }
+    }
+    
+    /// Create event bus with recording enabled
+    pub fn with_recording(capacity: usize) -> Self {
+        Self {
+            recorder: Some(Arc::new(crate::replay::EventRecorder::new(capacity))),
+            ..Self::new()
+        }
+    }
+    
+    /// Create event bus that captures undeliverable events in a dead-letter queue
+    ///
+    /// The queue holds at most `capacity` entries; read them through
+    /// `dead_letter_receiver`.
+    pub fn with_dead_letter_queue(capacity: usize) -> Self {
+        Self {
+            dead_letters: Some(DeadLetterQueue::new(capacity)),
+            ..Self::new()
+        }
+    }
+    
+    /// Create event bus that stamps published envelopes with `clock` time
+    ///
+    /// Used during replay so events strategies publish in response carry
+    /// virtual time. `publish_envelope` keeps the envelope's own timestamp.
+    pub fn with_shared_clock(clock: SharedClock) -> Self {
+        Self {
+            clock: Some(clock),
+            ..Self::new()
+        }
+    }
+    
+    /// Create a bus that delivers queued events in priority order
+    ///
+    /// Must be called inside a Tokio runtime.
+    pub fn new_priority_ordered() -> PriorityEventBus {
+        PriorityEventBus::new()
+    }
+    
+    /// Create a bus that measures publish-to-receive latency per event type
+    pub fn new_with_metrics() -> MetricEventBus {
+        MetricEventBus::new(Self::new())
+    }
+    
+    /// Create event bus that drops events with content seen within `ttl_ns`
+    pub fn with_content_cache(ttl_ns: u64) -> Self {
+        Self {
+            content_cache: Some(Arc::new(ContentAddressedCache::new(ttl_ns))),
+            ..Self::new()
+        }
+    }
+    
+    /// Create event bus that rejects envelopes over `max_bytes` of serialized JSON
+    ///
+    /// `publish` fails with `EventTooLargeError`; an envelope of exactly
+    /// `max_bytes` is accepted.
+    pub fn with_max_event_size(max_bytes: usize) -> Self {
+        Self::with_size_budget_policy(max_bytes, SizeBudgetPolicy::Reject)
+    }
+    
+    /// Create event bus that applies `policy` to envelopes over `max_bytes`
+    pub fn with_size_budget_policy(max_bytes: usize, policy: SizeBudgetPolicy) -> Self {
+        Self {
+            size_budget: Some(Arc::new(SizeBudget::new(max_bytes, policy))),
+            ..Self::new()
+        }
+    }
+    
+    /// Mean serialized size of published `event_type` envelopes
+    ///
+    /// Only measured on buses with a size budget; 0 otherwise or before the
+    /// first measured publish.
+    pub fn average_event_size_bytes(&self, event_type: &str) -> f64 {
+        self.size_budget.as_ref().map_or(0.0, |budget| budget.average(event_type))
+    }
+    
+    /// Append `middleware` to the publish pipeline
+    ///
+    /// Runs for `publish` and its variants, including `publish_batch`, after
+    /// the envelope is stamped and before it is recorded or delivered.
+    /// `publish_envelope` (replay) and multicast `publish_to_group` bypass the
+    /// chain.
+    pub fn add_middleware(&self, middleware: impl Middleware + Send + Sync + 'static) {
+        let mut chain = self.middleware.write().unwrap();
+        chain.push(Arc::new(middleware));
+        self.middleware_count.store(chain.len(), Ordering::Release);
+    }
+    
+    /// Remove all middleware
+    pub fn clear_middleware(&self) {
+        let mut chain = self.middleware.write().unwrap();
+        chain.clear();
+        self.middleware_count.store(0, Ordering::Release);
+    }
+    
+    /// Publish an event to all subscribers
+    pub async fn publish<T: Event + Send + 'static>(&self, event: T) -> Result<PublishReceipt> {
+        self.publish_linked(event, 5, None).await
+    }
+    
+    /// Publish only if `condition` holds at publish time
+    ///
+    /// The condition is evaluated once, immediately before publishing.
+    pub async fn publish_if<T: Event + Send + 'static>(
+        &self,
+        event: T,
+        condition: impl Fn() -> bool + Send + 'static,
+    ) -> Result<PublishDecision> {
+        if !condition() {
+            return Ok(PublishDecision::Suppressed);
+        }
+        self.publish(event).await.map(PublishDecision::Published)
+    }
+    
+    /// Publish event with default priority, discarding the receipt
+    pub async fn publish_fire_and_forget<T: Event + Send + 'static>(&self, event: T) -> Result<()> {
+        self.publish_linked(event, 5, None).await.map(|_| ())
+    }
+    
+    /// Publish without a sequence number, even on a sequenced channel
+    ///
+    /// For events whose order does not matter (heartbeats, telemetry);
+    /// `ContiguousSubscriber` passes them through unchecked.
+    pub async fn publish_unsequenced<T: Event + Send + 'static>(&self, event: T) -> Result<()> {
+        self.publish_inner(event, 5, None, false).await.map(|_| ())
+    }
+    
+    /// Number envelopes published to `event_type` from now on
+    ///
+    /// Each channel counts from 0. Sequence numbers are assigned and sent
+    /// atomically, so every receiver sees them in increasing order and a
+    /// gap means the receiver missed events. Publishes to a sequenced
+    /// channel are serialized. Returns false if already enabled.
+    pub fn enable_sequencing(&self, event_type: &str) -> bool {
+        let mut channel = self.channels.entry(event_type.to_string())
+            .or_insert_with(|| {
+                debug!("Creating new channel for sequencing: {}", event_type);
+                Channel::new()
+            });
+        if channel.sequence.is_some() {
+            return false;
+        }
+        channel.sequence = Some(Arc::new(tokio::sync::Mutex::new(0)));
+        true
+    }
+    
+    /// Publish event with specific priority (0 = highest)
+    pub async fn publish_with_priority<T: Event + Send + 'static>(&self, event: T, priority: u8) -> Result<()> {
+        self.publish_linked(event, priority, None).await.map(|_| ())
+    }
+    
+    /// Publish an event held in a recycled box from an `EnvelopePool`
+    ///
+    /// The box goes back to its pool once every subscriber (and the
+    /// `try_drain` queue) has dropped the envelope. Subscribers downcast to
+    /// `T` as usual. The envelope still allocates its shared handle, a small
+    /// fixed-size `Arc` around the pooled box, so this saves the
+    /// payload-sized allocation rather than every allocation.
+    pub async fn publish_pooled<T: Event + 'static>(&self, pooled: PooledEnvelope<T>) -> Result<()> {
+        self.publish_with_priority(pooled, 5).await
+    }
+    
+    /// Publish an event caused by `parent`, continuing its correlation chain
+    pub async fn publish_caused_by<T: Event + Send + 'static>(&self, event: T, parent: &EventEnvelope) -> Result<()> {
+        self.publish_linked(event, 5, Some(parent)).await.map(|_| ())
+    }
+    
+    /// Publish with an optional causal parent
+    async fn publish_linked<T: Event + Send + 'static>(
+        &self,
+        event: T,
+        priority: u8,
+        parent: Option<&EventEnvelope>,
+    ) -> Result<PublishReceipt> {
+        self.publish_inner(event, priority, parent, true).await
+    }
+    
+    /// Publish with an optional causal parent, opting out of sequencing if
+    /// `sequenced` is false
+    async fn publish_inner<T: Event + Send + 'static>(
+        &self,
+        event: T,
+        priority: u8,
+        parent: Option<&EventEnvelope>,
+        sequenced: bool,
+    ) -> Result<PublishReceipt> {
+        let mut envelope = match self.prepare(event, priority, parent)? {
+            Prepared::Deliver(envelope) => envelope,
+            Prepared::Done(receipt) => return Ok(receipt),
+        };
+        // Middleware or truncation may have replaced the event
+        let event_type = envelope.event.event_type();
+        self.forward_typed(envelope.event.as_ref());
+        
+        // Get or create channel for this event type
+        let channel = self.channels.entry(event_type.to_string())
+            .or_insert_with(|| {
+                debug!("Creating new channel for event type: {}", event_type);
+                Channel::new()
+            })
+            .clone();
+        
+        // Number first so the recording and every route carry the same
+        // sequence number as the channel
+        let sequence = channel.number(&mut envelope, sequenced).await;
+        
+        // Record event if recording is enabled
+        if let Some(recorder) = &self.recorder {
+            recorder.record(envelope.clone()).await;
+        }
+        
+        // Route to wildcard/prefix subscribers
+        self.route_patterns(event_type, &envelope);
+        self.route_predicates(&envelope);
+        self.route_correlation(&envelope);
+        self.feed_drain(event_type, &envelope);
+        
+        // Publish to channel
+        let (event_id, timestamp_ns) = (envelope.id, envelope.timestamp_ns);
+        let sent = channel.send(envelope);
+        drop(sequence);
+        match sent {
+            Ok(subscriber_count) => {
+                self.increment_stat(event_type, |s| s.published += 1);
+                Ok(PublishReceipt {
+                    subscriber_count,
+                    event_id,
+                    timestamp_ns,
+                    delivery: Some(channel.delivery()),
+                })
+            }
+            Err(broadcast::error::SendError(envelope)) => {
+                self.increment_stat(event_type, |s| s.dropped += 1);
+                self.dead_letter(DeadLetterReason::NoSubscribers, envelope);
+                // Not an error if no subscribers
+                Ok(PublishReceipt::empty(event_id, timestamp_ns))
+            }
+        }
+    }
+    
+    /// Stamp `event` and run the content cache, middleware and size budget
+    ///
+    /// Shared by every publish path that honours the pipeline; `Done` carries
+    /// the receipt for an event that was deduplicated or filtered.
+    fn prepare<T: Event + Send + 'static>(
+        &self,
+        event: T,
+        priority: u8,
+        parent: Option<&EventEnvelope>,
+    ) -> Result<Prepared> {
+        let event_type = Self::event_type_name(&event);
+        let mut envelope = self.stamp(EventEnvelope::new(event, priority));
+        
+        // Skip content already published within the cache TTL; the receipt
+        // names the envelope that was published first
+        if let Some(cache) = &self.content_cache {
+            if let Some(hash) = ContentAddressedCache::content_hash(envelope.event.as_ref()) {
+                if let Some((first_id, first_ns)) = cache.check_and_insert(hash, envelope.id, envelope.timestamp_ns) {
+                    self.increment_stat(event_type, |s| s.deduplicated += 1);
+                    return Ok(Prepared::Done(PublishReceipt::empty(first_id, first_ns)));
+                }
+            }
+        }
+        
+        if let Some(parent) = parent {
+            envelope = envelope.caused_by(parent);
+        }
+        
+        let (event_id, timestamp_ns) = (envelope.id, envelope.timestamp_ns);
+        let Some(envelope) = self.run_middleware(envelope) else {
+            self.increment_stat(event_type, |s| s.filtered += 1);
+            return Ok(Prepared::Done(PublishReceipt::empty(event_id, timestamp_ns)));
+        };
+        let envelope = match &self.size_budget {
+            Some(budget) => budget.enforce(envelope)?,
+            None => envelope,
+        };
+        Ok(Prepared::Deliver(envelope))
+    }
+    
+    /// Publish a slice of events with default priority
+    ///
+    /// The channel sender is looked up once per run of same-typed events
+    /// rather than once per event. Returns the number of events delivered to
+    /// at least one subscriber.
+    pub async fn publish_batch<T: Event + Send + Clone + 'static>(&self, events: &[T]) -> Result<usize> {
+        // Middleware and size budgets may drop or replace events, so take the
+        // per-event path
+        if self.middleware_count.load(Ordering::Acquire) > 0 || self.size_budget.is_some() {
+            let mut delivered = 0;
+            for event in events {
+                if self.publish_linked(event.clone(), 5, None).await?.subscriber_count > 0 {
+                    delivered += 1;
+                }
+            }
+            return Ok(delivered);
+        }
+        
+        let mut delivered = 0;
+        let mut current: Option<(&'static str, Channel)> = None;
+        let mut published = 0u64;
+        let mut dropped = 0u64;
+        
+        for event in events {
+            let event_type = Self::event_type_name(event);
+            let mut envelope = self.stamp(EventEnvelope::new(event.clone(), 5));
+            
+            // Skip content already published within the cache TTL
+            if let Some(cache) = &self.content_cache {
+                if let Some(hash) = ContentAddressedCache::content_hash(event) {
+                    if cache.check_and_insert(hash, envelope.id, envelope.timestamp_ns).is_some() {
+                        self.increment_stat(event_type, |s| s.deduplicated += 1);
+                        continue;
+                    }
+                }
+            }
+            
+            // Flush counters and look up the sender when the type changes
+            if !matches!(&current, Some((t, _)) if *t == event_type) {
+                if let Some((previous, _)) = current.take() {
+                    self.increment_stat(previous, |s| {
+                        s.published += published;
+                        s.dropped += dropped;
+                    });
+                    published = 0;
+                    dropped = 0;
+                }
+                let channel = self.channels.entry(event_type.to_string())
+                    .or_insert_with(|| {
+                        debug!("Creating new channel for event type: {}", event_type);
+                        Channel::new()
+                    })
+                    .clone();
+                current = Some((event_type, channel));
+            }
+            
+            let Some((_, channel)) = &current else {
+                continue;
+            };
+            
+            self.forward_typed(event);
+            let sequence = channel.number(&mut envelope, true).await;
+            
+            if let Some(recorder) = &self.recorder {
+                recorder.record(envelope.clone()).await;
+            }
+            
+            self.route_patterns(event_type, &envelope);
+            self.route_predicates(&envelope);
+            self.route_correlation(&envelope);
+            self.feed_drain(event_type, &envelope);
+            
+            let sent = channel.send(envelope);
+            drop(sequence);
+            match sent {
+                Ok(_) => {
+                    delivered += 1;
+                    published += 1;
+                }
+                Err(broadcast::error::SendError(envelope)) => {
+                    dropped += 1;
+                    self.dead_letter(DeadLetterReason::NoSubscribers, envelope);
+                }
+            }
+        }
+        
+        if let Some((event_type, _)) = current {
+            self.increment_stat(event_type, |s| {
+                s.published += published;
+                s.dropped += dropped;
+            });
+        }
+        
+        Ok(delivered)
+    }
+    
+    /// Publish an EventEnvelope directly (used for replay)
+    pub async fn publish_envelope(&self, mut envelope: EventEnvelope) -> Result<()> {
+        let event_type = envelope.event.event_type();
+        
+        // Get or create channel for this event type
+        let channel = self.channels.entry(event_type.to_string())
+            .or_insert_with(|| {
+                debug!("Creating new channel for event type: {}", event_type);
+                Channel::new()
+            })
+            .clone();
+        let sequence = channel.number(&mut envelope, true).await;
+        
+        // Record event if recording is enabled
+        if let Some(recorder) = &self.recorder {
+            recorder.record(envelope.clone()).await;
+        }
+        
+        // Route to wildcard/prefix subscribers
+        self.route_patterns(event_type, &envelope);
+        self.route_predicates(&envelope);
+        self.route_correlation(&envelope);
+        self.feed_drain(event_type, &envelope);
+        
+        // Publish to channel
+        let sent = channel.send(envelope);
+        drop(sequence);
+        match sent {
+            Ok(_subscriber_count) => {
+                self.increment_stat(event_type, |s| s.published += 1);
+                Ok(())
+            }
+            Err(broadcast::error::SendError(envelope)) => {
+                self.increment_stat(event_type, |s| s.dropped += 1);
+                self.dead_letter(DeadLetterReason::NoSubscribers, envelope);
+                Ok(()) // Not an error if no subscribers
+            }
+        }
+    }
+    
+    /// Publish `events` all or nothing
+    ///
+    /// Every event is checked before any is sent: it must pass middleware and
+    /// the size budget, and its channel must have a subscriber and room for
+    /// the transaction's events without evicting unread ones. If any check
+    /// fails nothing is published. Transactions are serialized with each
+    /// other (plain publishes may still interleave), and their envelopes
+    /// carry the transaction ID in `transaction_id`; causation and
+    /// correlation IDs are left as middleware set them. Recording and the
+    /// pattern, predicate, correlation, drain and typed routes only see an
+    /// event once its channel has accepted it.
+    ///
+    /// A channel can still lose its last subscriber mid-transaction; the
+    /// remaining events are then abandoned, a `TransactionRolledBackEvent` is
+    /// published on the `transaction_rolled_back` channel and an error returned.
+    pub async fn publish_transaction(&self, events: Vec<TransactionEvent>) -> Result<TransactionReceipt> {
+        let transaction_id = Uuid::new_v4();
+        let _guard = self.transaction_lock.lock().await;
+        
+        // Build every envelope before sending any
+        let mut envelopes = Vec::with_capacity(events.len());
+        for TransactionEvent { event, priority } in events {
+            let envelope = self.stamp(EventEnvelope::from_boxed(event, priority));
+            let event_type = envelope.event.event_type();
+            let Some(envelope) = self.run_middleware(envelope) else {
+                self.increment_stat(event_type, |s| s.filtered += 1);
+                bail!("Transaction {} rejected: middleware dropped a {} event", transaction_id, event_type);
+            };
+            let mut envelope = match &self.size_budget {
+                Some(budget) => budget.enforce(envelope)?,
+                None => envelope,
+            };
+            envelope.transaction_id = Some(transaction_id);
+            envelopes.push(envelope);
+        }
+        
+        // Check each target channel once for the events it will receive
+        let mut pending: HashMap<&'static str, usize> = HashMap::new();
+        for envelope in &envelopes {
+            *pending.entry(envelope.event.event_type()).or_default() += 1;
+        }
+        let mut channels = HashMap::with_capacity(pending.len());
+        for (event_type, count) in pending {
+            let channel = match self.channels.get(event_type) {
+                Some(channel) if channel.sender.receiver_count() > 0 => channel.clone(),
+                _ => bail!("Transaction {} rejected: no subscribers for {}", transaction_id, event_type),
+            };
+            let queued = channel.sender.len();
+            if queued + count > CHANNEL_CAPACITY {
+                bail!(
+                    "Transaction {} rejected: {} channel has room for {} of {} events",
+                    transaction_id,
+                    event_type,
+                    CHANNEL_CAPACITY.saturating_sub(queued),
+                    count
+                );
+            }
+            channels.insert(event_type, channel);
+        }
+        
+        let events_total = envelopes.len();
+        let mut events_published = 0;
+        for mut envelope in envelopes {
+            let event_type = envelope.event.event_type();
+            let channel = &channels[event_type];
+            let sequence = channel.number(&mut envelope, true).await;
+            
+            // Side routes run only for events the channel accepted, so a
+            // rolled-back event is never recorded or routed elsewhere
+            let routed = envelope.clone();
+            if let Err(broadcast::error::SendError(envelope)) = channel.send(envelope) {
+                drop(sequence);
+                self.increment_stat(event_type, |s| s.dropped += 1);
+                let reason = format!("{} channel lost its subscribers", event_type);
+                let rollback = TransactionRolledBackEvent {
+                    timestamp: envelope.timestamp_ns,
+                    transaction_id,
+                    events_published,
+                    events_total,
+                    reason: reason.clone(),
+                };
+                self.dead_letter(DeadLetterReason::NoSubscribers, envelope);
+                
+                let mut rollback = self.stamp(EventEnvelope::new(rollback, 0));
+                rollback.transaction_id = Some(transaction_id);
+                self.publish_envelope(rollback).await?;
+                bail!(
+                    "Transaction {} rolled back after {} of {} events: {}",
+                    transaction_id,
+                    events_published,
+                    events_total,
+                    reason
+                );
+            }
+            
+            self.forward_typed(routed.event.as_ref());
+            if let Some(recorder) = &self.recorder {
+                recorder.record(routed.clone()).await;
+            }
+            self.route_patterns(event_type, &routed);
+            self.route_predicates(&routed);
+            self.route_correlation(&routed);
+            self.feed_drain(event_type, &routed);
+            drop(sequence);
+            
+            self.increment_stat(event_type, |s| s.published += 1);
+            events_published += 1;
+        }
+        
+        Ok(TransactionReceipt { transaction_id, events_published })
+    }
+    
+    /// Subscribe to events of Rust type `T` without envelopes
+    ///
+    /// Events published as `T` are cloned into a dedicated `broadcast`
+    /// channel, so receivers get the concrete value directly.
+    pub fn subscribe_typed<T: Event + Clone + 'static>(&self) -> TypedReceiver<T> {
+        let channel = self.typed.entry(TypeId::of::<T>())
+            .or_insert_with(|| {
+                self.typed_count.fetch_add(1, Ordering::Release);
+                TypedChannel {
+                    sender: Box::new(broadcast::channel::<T>(CHANNEL_CAPACITY).0),
+                    forward: forward_typed_event::<T>,
+                }
+            });
+        
+        let sender = channel.sender.downcast_ref::<broadcast::Sender<T>>()
+            .expect("Type mismatch in typed channel registry");
+        TypedReceiver { receiver: sender.subscribe() }
+    }
+    
+    /// Send a clone of `event` to its typed channel, if one exists
+    #[inline]
+    fn forward_typed(&self, event: &dyn Event) {
+        if self.typed_count.load(Ordering::Acquire) == 0 {
+            return;
+        }
+        // Look through pool wrappers so pooled events reach `T`'s channel
+        let payload = event.payload_any();
+        if let Some(channel) = self.typed.get(&Any::type_id(payload)) {
+            (channel.forward)(channel.sender.as_ref(), payload);
+        }
+    }
+    
+    /// Subscribe to events of Rust type `T` for which `predicate` holds
+    ///
+    /// Each call gets its own channel that only receives matching events,
+    /// e.g. market data for one symbol. Regular subscribers are unaffected.
+    pub fn subscribe_where<T, F>(&self, predicate: F) -> broadcast::Receiver<EventEnvelope>
+    where
+        T: Event + Clone + 'static,
+        F: Fn(&T) -> bool + Send + Sync + 'static,
+    {
+        let predicate: Arc<dyn Predicate> = Arc::new(move |event: &dyn Any| {
+            event.downcast_ref::<T>().is_some_and(&predicate)
+        });
+        let (sender, receiver) = broadcast::channel(CHANNEL_CAPACITY);
+        
+        let mut predicates = self.predicates.write().unwrap();
+        predicates.push((TypeId::of::<T>(), predicate, sender));
+        self.predicate_count.store(predicates.len(), Ordering::Release);
+        receiver
+    }
+    
+    /// Forward an envelope to every predicate subscription it matches
+    #[inline]
+    fn route_predicates(&self, envelope: &EventEnvelope) {
+        // Fast path: no predicate subscriptions
+        if self.predicate_count.load(Ordering::Acquire) == 0 {
+            return;
+        }
+        
+        let event = envelope.event.payload_any();
+        let type_id = Any::type_id(event);
+        let mut stale = false;
+        for (id, predicate, sender) in self.predicates.read().unwrap().iter() {
+            if *id == type_id && predicate.matches(event) && sender.send(envelope.clone()).is_err() {
+                stale = true;
+            }
+        }
+        
+        // Prune subscriptions whose receivers have been dropped
+        if stale {
+            let mut predicates = self.predicates.write().unwrap();
+            predicates.retain(|(_, _, sender)| sender.receiver_count() > 0);
+            self.predicate_count.store(predicates.len(), Ordering::Release);
+        }
+    }
+    
+    /// Subscribe to every envelope carrying `correlation_id`
+    ///
+    /// Follows one causal chain (e.g. model deployment -> signals -> fills)
+    /// across event types.
+    pub fn subscribe_correlation(&self, correlation_id: Uuid) -> broadcast::Receiver<EventEnvelope> {
+        self.correlations.entry(correlation_id)
+            .or_insert_with(|| broadcast::channel(CHANNEL_CAPACITY).0)
+            .subscribe()
+    }
+    
+    /// Forward an envelope to its correlation channel, if anyone follows it
+    #[inline]
+    fn route_correlation(&self, envelope: &EventEnvelope) {
+        let Some(correlation_id) = envelope.correlation_id else {
+            return;
+        };
+        let closed = match self.correlations.get(&correlation_id) {
+            Some(sender) => sender.send(envelope.clone()).is_err(),
+            None => return,
+        };
+        if closed {
+            self.correlations.remove_if(&correlation_id, |_, sender| sender.receiver_count() == 0);
+        }
+    }
+    
+    /// Start queueing `event_type` for `try_drain`
+    ///
+    /// Types without a queue are not copied on publish. Calling this again
+    /// keeps the existing queue.
+    pub fn enable_drain(&self, event_type: &str) {
+        self.drain_queue(event_type);
+    }
+    
+    /// Synchronously take up to `max` buffered events of one type
+    ///
+    /// Reads from a secondary queue, so it never consumes events from
+    /// broadcast subscribers. The queue is created by `enable_drain` or by the
+    /// first `try_drain` call for the type, and only events published after
+    /// that are queued. It keeps the most recent `CHANNEL_CAPACITY` events;
+    /// each event is drained once.
+    pub fn try_drain(&self, event_type: &str, max: usize) -> Vec<EventEnvelope> {
+        self.drain_queue(event_type).rx.try_iter().take(max).collect()
+    }
+    
+    /// The type's drain queue, creating it if needed
+    fn drain_queue(&self, event_type: &str) -> DrainQueue {
+        if let Some(queue) = self.drains.get(event_type) {
+            return queue.clone();
+        }
+        self.drains.entry(event_type.to_string())
+            .or_insert_with(|| {
+                let (tx, rx) = flume::bounded(CHANNEL_CAPACITY);
+                DrainQueue { tx, rx }
+            })
+            .clone()
+    }
+    
+    /// Copy an envelope into the type's drain queue, if it has one
+    fn feed_drain(&self, event_type: &str, envelope: &EventEnvelope) {
+        let Some(queue) = self.drains.get(event_type) else {
+            return;
+        };
+        
+        if let Err(flume::TrySendError::Full(envelope)) = queue.tx.try_send(envelope.clone()) {
+            // Evict the oldest event to make room
+            let _ = queue.rx.try_recv();
+            let _ = queue.tx.try_send(envelope);
+        }
+    }
+    
+    /// Subscribe to a specific event type
+    pub async fn subscribe(&self, event_type: &str) -> broadcast::Receiver<EventEnvelope> {
+        self.channel_receiver(event_type)
+    }
+    
+    /// Receiver on `event_type`'s channel, creating the channel if needed
+    fn channel_receiver(&self, event_type: &str) -> broadcast::Receiver<EventEnvelope> {
+        let channel = self.channels.entry(event_type.to_string())
+            .or_insert_with(|| {
+                debug!("Creating new channel for subscription: {}", event_type);
+                Channel::new()
+            })
+            .clone();
+        
+        channel.sender.subscribe()
+    }
+    
+    /// Subscribe to several event types through one receiver
+    ///
+    /// Events from all channels are delivered in the order they are
+    /// forwarded; the merged queue holds up to `CHANNEL_CAPACITY` events.
+    pub async fn subscribe_merged(&self, event_types: &[&str]) -> MergedReceiver {
+        let mut sources = Vec::with_capacity(event_types.len());
+        for event_type in event_types {
+            sources.push(self.subscribe(event_type).await);
+        }
+        
+        let (tx, receiver) = flume::bounded(CHANNEL_CAPACITY);
+        let (shutdown, shutdown_rx) = oneshot::channel();
+        tokio::spawn(merge_channels(sources, tx, shutdown_rx));
+        MergedReceiver { receiver, _shutdown: shutdown }
+    }
+    
+    /// Subscribe to a random sample of an event type
+    ///
+    /// Each envelope is forwarded independently with probability
+    /// `sample_rate`; the sample queue holds up to `CHANNEL_CAPACITY` events.
+    ///
+    /// # Panics
+    ///
+    /// If `sample_rate` is not in (0.0, 1.0].
+    pub async fn subscribe_sampled(&self, event_type: &str, sample_rate: f64) -> SampledReceiver {
+        assert!(
+            sample_rate > 0.0 && sample_rate <= 1.0,
+            "sample rate {} is not in (0.0, 1.0]",
+            sample_rate
+        );
+        let rng = SmallRng::from_rng(rand::thread_rng()).expect("thread_rng does not fail");
+        self.subscribe_sampling(event_type, Sampling::Probability(sample_rate, rng)).await
+    }
+    
+    /// Subscribe to every `n`th envelope of an event type, starting with the first
+    ///
+    /// # Panics
+    ///
+    /// If `n` is zero.
+    pub async fn subscribe_every_nth(&self, event_type: &str, n: usize) -> SampledReceiver {
+        assert!(n > 0, "decimation factor must be non-zero");
+        self.subscribe_sampling(event_type, Sampling::EveryNth(n as u64)).await
+    }
+    
+    async fn subscribe_sampling(&self, event_type: &str, sampling: Sampling) -> SampledReceiver {
+        let source = self.subscribe(event_type).await;
+        let (tx, receiver) = flume::bounded(CHANNEL_CAPACITY);
+        let (shutdown, shutdown_rx) = oneshot::channel();
+        let window = Arc::new(SamplingWindow::default());
+        tokio::spawn(sample_channel(source, tx, window.clone(), sampling, shutdown_rx));
+        SampledReceiver { receiver, window, _shutdown: shutdown }
+    }
+    
+    /// Subscribe to windows of `window_size` consecutive envelopes of an event type
+    ///
+    /// # Panics
+    ///
+    /// If `window_size` is zero.
+    pub async fn subscribe_windowed(&self, event_type: &str, window_size: usize) -> WindowedReceiver {
+        assert!(window_size > 0, "window size must be non-zero");
+        WindowedReceiver {
+            inner: self.subscribe(event_type).await,
+            buffer: VecDeque::with_capacity(window_size),
+            window_size,
+        }
+    }
+    
+    /// Subscribe to pairs of `type_a` and `type_b` envelopes at most `tolerance_ns` apart
+    ///
+    /// # Panics
+    ///
+    /// If `tolerance_ns` is negative.
+    pub async fn subscribe_join(&self, type_a: &str, type_b: &str, tolerance_ns: i64) -> JoinReceiver {
+        assert!(tolerance_ns >= 0, "join tolerance must be non-negative");
+        JoinReceiver {
+            a: self.subscribe(type_a).await,
+            b: self.subscribe(type_b).await,
+            head_a: None,
+            head_b: None,
+            a_open: true,
+            b_open: true,
+            pending_a: VecDeque::new(),
+            pending_b: VecDeque::new(),
+            tolerance_ns,
+            unmatched_a: 0,
+            unmatched_b: 0,
+        }
+    }
+    
+    /// Split an event type between `n` workers, one envelope each in turn
+    ///
+    /// Every envelope reaches exactly one receiver. A background task feeds
+    /// the receivers and exits once all of them are dropped.
+    pub async fn subscribe_round_robin(&self, event_type: &str, n: usize) -> Vec<flume::Receiver<EventEnvelope>> {
+        let mut next = 0usize;
+        self.subscribe_workers(event_type, n, move |_| {
+            let index = next;
+            next = next.wrapping_add(1);
+            index
+        }).await
+    }
+    
+    /// Split an event type between `n` workers by `key_fn`
+    ///
+    /// Envelopes with the same key go to the same worker (e.g. hash of the
+    /// symbol), as long as that worker's receiver is alive.
+    pub async fn subscribe_hash_partitioned(
+        &self,
+        event_type: &str,
+        n: usize,
+        key_fn: Arc<dyn Fn(&EventEnvelope) -> u64 + Send + Sync>,
+    ) -> Vec<flume::Receiver<EventEnvelope>> {
+        let n_workers = n.max(1) as u64;
+        self.subscribe_workers(event_type, n, move |envelope| (key_fn(envelope) % n_workers) as usize).await
+    }
+    
+    async fn subscribe_workers<F>(&self, event_type: &str, n: usize, pick: F) -> Vec<flume::Receiver<EventEnvelope>>
+    where
+        F: FnMut(&EventEnvelope) -> usize + Send + 'static,
+    {
+        if n == 0 {
+            return Vec::new();
+        }
+        let (workers, receivers): (Vec<_>, Vec<_>) = (0..n)
+            .map(|_| flume::bounded(CHANNEL_CAPACITY))
+            .unzip();
+        let source = self.subscribe(event_type).await;
+        tokio::spawn(dispatch_to_workers(source, workers, pick));
+        receivers
+    }
+    
+    /// Subscribe to all event types starting with `prefix`
+    pub async fn subscribe_prefix(&self, prefix: &str) -> broadcast::Receiver<EventEnvelope> {
+        self.subscribe_with_pattern(PatternKind::Prefix(prefix.to_string()))
+    }
+    
+    /// Subscribe to all event types matching a glob (`*` and `?` wildcards)
+    pub async fn subscribe_pattern(&self, glob: &str) -> broadcast::Receiver<EventEnvelope> {
+        self.subscribe_with_pattern(PatternKind::Glob(glob.to_string()))
+    }
+    
+    fn subscribe_with_pattern(&self, pattern: PatternKind) -> broadcast::Receiver<EventEnvelope> {
+        let mut patterns = self.patterns.write().unwrap();
+        
+        if let Some((_, sender)) = patterns.iter().find(|(p, _)| *p == pattern) {
+            return sender.subscribe();
+        }
+        
+        debug!("Creating new pattern subscription: {:?}", pattern);
+        let (sender, receiver) = broadcast::channel(CHANNEL_CAPACITY);
+        patterns.push((pattern, sender));
+        self.pattern_count.store(patterns.len(), Ordering::Release);
+        receiver
+    }
+    
+    /// Forward an envelope to every matching pattern subscription
+    #[inline]
+    fn route_patterns(&self, event_type: &str, envelope: &EventEnvelope) {
+        // Fast path: no pattern subscriptions
+        if self.pattern_count.load(Ordering::Acquire) == 0 {
+            return;
+        }
+        
+        let mut stale = false;
+        for (pattern, sender) in self.patterns.read().unwrap().iter() {
+            if pattern.matches(event_type) && sender.send(envelope.clone()).is_err() {
+                stale = true;
+            }
+        }
+        
+        // Prune patterns whose subscribers have all gone away
+        if stale {
+            let mut patterns = self.patterns.write().unwrap();
+            patterns.retain(|(_, sender)| sender.receiver_count() > 0);
+            self.pattern_count.store(patterns.len(), Ordering::Release);
+        }
+    }
+    
+    /// Subscribe to market data events
+    pub async fn subscribe_market_data(&self) -> broadcast::Receiver<EventEnvelope> {
+        self.subscribe(MarketDataEvent::EVENT_TYPE).await
+    }
+    
+    /// Subscribe to signal events
+    pub async fn subscribe_signals(&self) -> broadcast::Receiver<EventEnvelope> {
+        self.subscribe(SignalEvent::EVENT_TYPE).await
+    }
+    
+    /// Subscribe to fill events
+    pub async fn subscribe_fills(&self) -> broadcast::Receiver<EventEnvelope> {
+        self.subscribe(FillEvent::EVENT_TYPE).await
+    }
+    
+    /// Subscribe to order events
+    pub async fn subscribe_orders(&self) -> broadcast::Receiver<EventEnvelope> {
+        self.subscribe(OrderEvent::EVENT_TYPE).await
+    }
+    
+    /// Subscribe to feature events
+    pub async fn subscribe_features(&self) -> broadcast::Receiver<EventEnvelope> {
+        self.subscribe(FeatureEvent::EVENT_TYPE).await
+    }
+    
+    /// Collect the next `n` events of `event_type`, then unsubscribe
+    ///
+    /// Subscribes when called, so events published before the future is
+    /// first polled are included. Events lost to lag are skipped, not
+    /// counted. Returns early with fewer events if the channel closes.
+    pub fn collect_n(&self, event_type: &str, n: usize) -> impl Future<Output = Vec<EventEnvelope>> + Send + 'static {
+        let mut receiver = self.channel_receiver(event_type);
+        async move {
+            let mut events = Vec::with_capacity(n);
+            collect_into(&mut receiver, n, &mut events).await;
+            events
+        }
+    }
+    
+    /// Like `collect_n`, but returns whatever arrived within `timeout`
+    pub fn collect_n_timeout(
+        &self,
+        event_type: &str,
+        n: usize,
+        timeout: Duration,
+    ) -> impl Future<Output = Vec<EventEnvelope>> + Send + 'static {
+        let mut receiver = self.channel_receiver(event_type);
+        async move {
+            let mut events = Vec::with_capacity(n);
+            let _ = tokio::time::timeout(timeout, collect_into(&mut receiver, n, &mut events)).await;
+            events
+        }
+    }
+    
+    /// Replace the channel for `event_type` with a fresh one
+    ///
+    /// Existing receivers get `RecvError::Closed` once they have read any
+    /// buffered events; new subscribers attach to the fresh channel. Returns
+    /// the number of receivers that were closed.
+    pub fn drain_channel(&self, event_type: &str) -> usize {
+        match self.channels.get_mut(event_type) {
+            Some(mut channel) => {
+                let closed = channel.sender.receiver_count();
+                channel.sender = Arc::new(broadcast::channel(CHANNEL_CAPACITY).0);
+                channel.sends = Arc::new(AtomicU64::new(0));
+                debug!("Drained channel {} ({} receivers closed)", event_type, closed);
+                closed
+            }
+            None => 0,
+        }
+    }
+    
+    /// Remove the channel for `event_type`, closing its receivers
+    ///
+    /// Returns false if no such channel existed. The channel is recreated by
+    /// the next publish or subscribe.
+    pub fn drop_channel(&self, event_type: &str) -> bool {
+        self.channels.remove(event_type).is_some()
+    }
+    
+    /// Drain every channel, returning the total number of receivers closed
+    pub fn unsubscribe_all(&self) -> usize {
+        self.active_channels()
+            .iter()
+            .map(|event_type| self.drain_channel(event_type))
+            .sum()
+    }
+    
+    /// Event types that currently have a channel
+    pub fn active_channels(&self) -> Vec<String> {
+        self.channels.iter().map(|entry| entry.key().clone()).collect()
+    }
+    
+    /// Create a multicast group for an event type
+    ///
+    /// Creating a group that already exists returns a handle to the existing group.
+    pub fn create_group(&self, group_name: &str, event_type: &str) -> GroupHandle {
+        self.groups.entry(group_name.to_string())
+            .or_insert_with(|| {
+                debug!("Creating multicast group {} for event type: {}", group_name, event_type);
+                MulticastGroup {
+                    name: group_name.to_string(),
+                    event_type: event_type.to_string(),
+                    members: Vec::new(),
+                }
+            });
+        
+        GroupHandle {
+            name: group_name.to_string(),
+            groups: self.groups.clone(),
+        }
+    }
+    
+    /// Publish an event to every member of a multicast group
+    ///
+    /// Returns the number of members the event was delivered to. Members whose
+    /// receivers have been dropped are pruned from the group.
+    pub async fn publish_to_group(&self, group_name: &str, event: Box<dyn Event + Send>) -> Result<usize> {
+        let event_type = event.event_type();
+        let priority = event.priority();
+        
+        match self.groups.get(group_name) {
+            None => bail!("Unknown multicast group: {}", group_name),
+            Some(group) if group.event_type != event_type => bail!(
+                "Multicast group {} carries {} events, got {}",
+                group_name, group.event_type, event_type
+            ),
+            Some(_) => {}
+        }
+        
+        let envelope = self.stamp(EventEnvelope::from_boxed(event, priority));
+        
+        if let Some(recorder) = &self.recorder {
+            recorder.record(envelope.clone()).await;
+        }
+        
+        let mut delivered = 0;
+        if let Some(mut group) = self.groups.get_mut(group_name) {
+            group.members.retain(|member| {
+                if member.send(envelope.clone()).is_ok() {
+                    delivered += 1;
+                    true
+                } else {
+                    false
+                }
+            });
+        }
+        
+        if delivered > 0 {
+            self.increment_stat(event_type, |s| s.published += 1);
+        } else {
+            self.increment_stat(event_type, |s| s.dropped += 1);
+            self.dead_letter(DeadLetterReason::NoSubscribers, envelope);
+        }
+        
+        Ok(delivered)
+    }
+    
+    /// Disband a multicast group, closing all member receivers
+    pub fn disband_group(&self, group_name: &str) {
+        if self.groups.remove(group_name).is_some() {
+            debug!("Disbanded multicast group: {}", group_name);
+        }
+    }
+    
+    /// Create a group that delivers one event to several event-type channels
+    ///
+    /// This is the `create_group(name, members)` of the type-group request;
+    /// `create_group` already names multicast groups, so the type-group API
+    /// carries a `type_` prefix. Unlike multicast groups, type groups reuse the
+    /// bus-wide channels: a subscriber of any member type receives events
+    /// published to the group. Fails if the group already exists or has no
+    /// members.
+    pub fn create_type_group<S: Into<String>>(&self, group_name: &str, member_types: impl IntoIterator<Item = S>) -> Result<()> {
+        let member_types: Vec<String> = member_types.into_iter().map(Into::into).collect();
+        if member_types.is_empty() {
+            bail!("Type group {} has no member types", group_name);
+        }
+        match self.type_groups.entry(group_name.to_string()) {
+            Entry::Occupied(_) => bail!("Type group already exists: {}", group_name),
+            Entry::Vacant(entry) => {
+                debug!("Creating type group {} for {:?}", group_name, member_types);
+                entry.insert(member_types);
+                Ok(())
+            }
+        }
+    }
+    
+    /// Publish one envelope to every member channel of a type group
+    ///
+    /// The event goes through the content cache, middleware and size budget
+    /// once, like `publish`; if it is dropped there no member receives it and
+    /// the result is empty. All member senders are looked up before the first
+    /// send, so the same envelope (one ID, one timestamp) reaches every
+    /// channel. Pattern and drain subscribers see it once per member type,
+    /// predicate and correlation subscribers once.
+    pub async fn publish_to_type_group<T: Event + Send + 'static>(&self, group_name: &str, event: T) -> Result<GroupPublishResult> {
+        let members = self.type_groups.get(group_name)
+            .map(|members| members.clone())
+            .ok_or_else(|| anyhow!("Unknown type group: {}", group_name))?;
+        let channels: Vec<(String, Channel)> = members.into_iter()
+            .map(|event_type| {
+                let channel = self.channels.entry(event_type.clone())
+                    .or_insert_with(Channel::new)
+                    .clone();
+                (event_type, channel)
+            })
+            .collect();
+        
+        let priority = event.priority();
+        let envelope = match self.prepare(event, priority, None)? {
+            Prepared::Deliver(envelope) => envelope,
+            Prepared::Done(_) => return Ok(GroupPublishResult::default()),
+        };
+        self.forward_typed(envelope.event.as_ref());
+        
+        if let Some(recorder) = &self.recorder {
+            recorder.record(envelope.clone()).await;
+        }
+        self.route_predicates(&envelope);
+        self.route_correlation(&envelope);
+        
+        let mut result = GroupPublishResult::default();
+        for (event_type, channel) in channels {
+            let mut envelope = envelope.clone();
+            let _sequence = channel.number(&mut envelope, true).await;
+            self.route_patterns(&event_type, &envelope);
+            self.feed_drain(&event_type, &envelope);
+            if channel.send(envelope).is_ok() {
+                self.increment_stat(&event_type, |s| s.published += 1);
+                result.successful.push(event_type);
+            } else {
+                self.increment_stat(&event_type, |s| s.dropped += 1);
+                result.failed.push(event_type);
+            }
+        }
+        
+        if result.successful.is_empty() {
+            self.dead_letter(DeadLetterReason::NoSubscribers, envelope);
+        }
+        Ok(result)
+    }
+    
+    /// Remove a type group, returning false if it did not exist
+    pub fn remove_type_group(&self, group_name: &str) -> bool {
+        self.type_groups.remove(group_name).is_some()
+    }
+    
+    /// Names of all type groups
+    pub fn list_type_groups(&self) -> Vec<String> {
+        self.type_groups.iter().map(|entry| entry.key().clone()).collect()
+    }
+    
+    /// Capture recorder contents and statistics for failover
+    ///
+    /// Best-effort: statistics are updated outside the recorder lock, so a
+    /// publish racing the snapshot can be counted in `stats` without its
+    /// envelope appearing in `channels`, or the reverse.
+    pub async fn take_snapshot(&self) -> BusSnapshot {
+        let events = match &self.recorder {
+            Some(recorder) => Some(recorder.read().await),
+            None => None,
+        };
+        
+        let stats: HashMap<String, EventStats> = self.stats.iter()
+            .map(|entry| (entry.key().clone(), entry.value().clone()))
+            .collect();
+        
+        let mut channels: HashMap<String, Vec<EventEnvelope>> = HashMap::new();
+        if let Some(events) = &events {
+            let mut ordered: Vec<&EventEnvelope> = events.iter().collect();
+            ordered.sort_by_key(|e| e.timestamp_ns);
+            for envelope in ordered {
+                channels.entry(envelope.event.event_type().to_string())
+                    .or_default()
+                    .push(envelope.clone());
+            }
+        }
+        drop(events);
+        
+        BusSnapshot {
+            channels,
+            stats,
+            sequence: self.snapshot_sequence.fetch_add(1, Ordering::SeqCst) + 1,
+            taken_at: chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0),
+        }
+    }
+    
+    /// Reload the recorder and statistics from a snapshot
+    ///
+    /// Fails if this bus was not created `with_recording`.
+    pub async fn restore_from_snapshot(&self, snapshot: BusSnapshot) -> Result<()> {
+        let recorder = match &self.recorder {
+            Some(recorder) => recorder,
+            None => bail!("Cannot restore snapshot {}: recording is not enabled", snapshot.sequence),
+        };
+        
+        let mut events: Vec<EventEnvelope> = snapshot.channels.into_values().flatten().collect();
+        events.sort_by_key(|e| e.timestamp_ns);
+        recorder.restore(events).await;
+        
+        self.stats.clear();
+        for (event_type, stats) in snapshot.stats {
+            self.stats.insert(event_type, stats);
+        }
+        
+        self.snapshot_sequence.fetch_max(snapshot.sequence, Ordering::SeqCst);
+        debug!("Restored bus state from snapshot {}", snapshot.sequence);
+        
+        Ok(())
+    }
+    
+    /// Create a bus with this bus's configuration but none of its channels
+    ///
+    /// A plain `clone()` shares channels, so events published through it reach
+    /// this bus's subscribers. The isolated bus starts with no channels,
+    /// subscriptions, statistics or cached content. It keeps the middleware
+    /// chain (the same instances, so e.g. a `RateLimitMiddleware` budget is
+    /// shared), the size budget, the dead-letter capacity and the virtual
+    /// clock, and records into a new in-memory recorder of the same capacity.
+    pub fn clone_with_isolation(&self) -> EventBus {
+        let middleware = self.middleware.read().unwrap().clone();
+        Self {
+            recorder: self.recorder.as_ref()
+                .map(|recorder| Arc::new(crate::replay::EventRecorder::new(recorder.capacity()))),
+            content_cache: self.content_cache.as_ref()
+                .map(|cache| Arc::new(ContentAddressedCache::new(cache.ttl_ns()))),
+            middleware_count: Arc::new(AtomicUsize::new(middleware.len())),
+            middleware: Arc::new(RwLock::new(middleware)),
+            size_budget: self.size_budget.as_ref().map(|budget| Arc::new(budget.fresh())),
+            dead_letters: self.dead_letters.as_ref().map(|queue| DeadLetterQueue::new(queue.capacity())),
+            clock: self.clock.clone(),
+            ..Self::new()
+        }
+    }
+    
+    /// Create an isolated child bus (see `clone_with_isolation`) and a bridge
+    /// that forwards chosen event types into it
+    ///
+    /// Nothing is forwarded until `BridgeHandle::forward` is called.
+    pub fn fork(&self) -> (EventBus, BridgeHandle) {
+        let child = self.clone_with_isolation();
+        let bridge = BridgeHandle {
+            parent: self.clone(),
+            child: child.clone(),
+            forwards: DashMap::new(),
+        };
+        (child, bridge)
+    }
+    
+    /// Virtual clock stamping this bus's envelopes, if any
+    pub(crate) fn clock(&self) -> Option<&SharedClock> {
+        self.clock.as_ref()
+    }
+    
+    /// Current time on the bus's clock (virtual if set, otherwise wall time)
+    pub(crate) fn now_ns(&self) -> i64 {
+        match &self.clock {
+            Some(clock) => clock.now(),
+            None => chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0),
+        }
+    }
+    
+    /// Get the configuration a fork of this bus would inherit
+    pub fn fork_config(&self) -> ForkConfig {
+        ForkConfig {
+            recording_capacity: self.recorder.as_ref().map(|r| r.capacity()),
+        }
+    }
+    
+    /// Create a new bus warmed up with recent history from this bus's recorder
+    ///
+    /// Recorded events newer than `lookback_ns` are replayed through the new
+    /// bus at max speed. The fork only records (and so retains the warm-up
+    /// history) if this bus was created `with_recording`.
+    pub async fn fork_with_history(&self, lookback_ns: i64) -> (EventBus, ReplayStats) {
+        self.fork_with_history_config(self.fork_config(), lookback_ns).await
+    }
+    
+    /// Same as `fork_with_history` with an explicit fork configuration
+    pub async fn fork_with_history_config(&self, config: ForkConfig, lookback_ns: i64) -> (EventBus, ReplayStats) {
+        let forked = match config.recording_capacity {
+            Some(capacity) => EventBus::with_recording(capacity),
+            None => EventBus::new(),
+        };
+        
+        let history = match &self.recorder {
+            Some(recorder) => {
+                let now_ns = chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0);
+                recorder.get_events_in_range(now_ns.saturating_sub(lookback_ns), i64::MAX).await
+            }
+            None => Vec::new(),
+        };
+        
+        debug!("Forking bus with {} events of history", history.len());
+        
+        let mut replay = EventReplay::new(forked.clone(), ReplaySpeed::Max);
+        replay.set_events(history);
+        let stats = replay.run().await;
+        
+        (forked, stats)
+    }
+    
+    /// Build a replayer for everything the recorder holds, targeting this bus
+    ///
+    /// Replayed events are published through this bus, so they are recorded
+    /// again.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bus was not created `with_recording`.
+    pub async fn replay_recorded(&self, speed: ReplaySpeed) -> EventReplay {
+        let recorder = self.recorder.as_ref()
+            .expect("replay_recorded requires a bus created with_recording");
+        let events = recorder.get_events().await;
+        
+        EventReplayBuilder::new(self.clone())
+            .speed(speed)
+            .events(events)
+            .build()
+    }
+    
+    /// Build a replayer for recorded events with `start_ns <= timestamp <= end_ns`
+    ///
+    /// Fails if the bus has no recorder or the range is empty.
+    pub async fn replay_recorded_range(&self, start_ns: i64, end_ns: i64, speed: ReplaySpeed) -> Result<EventReplay> {
+        let recorder = self.recorder.as_ref()
+            .ok_or_else(|| anyhow!("Bus has no recorder attached"))?;
+        if start_ns > end_ns {
+            bail!("Invalid replay range: start {} is after end {}", start_ns, end_ns);
+        }
+        let events = recorder.get_events_in_range(start_ns, end_ns).await;
+        
+        Ok(EventReplayBuilder::new(self.clone())
+            .speed(speed)
+            .events(events)
+            .build())
+    }
+    
+    /// Replay the events of `recorder` that match `predicate` through this bus
+    ///
+    /// Matching events keep their timestamp order (ties in recording order).
+    /// If this bus records, the replayed events are recorded again.
+    pub async fn replay_while<F>(&self, recorder: &EventRecorder, predicate: F, speed: ReplaySpeed) -> ReplayStats
+    where
+        F: Fn(&EventEnvelope) -> bool + Send,
+    {
+        let events: Vec<EventEnvelope> = recorder.get_events_ordered().await
+            .into_iter()
+            .filter(|envelope| predicate(envelope))
+            .collect();
+        debug!("Replaying {} filtered events", events.len());
+        
+        EventReplayBuilder::new(self.clone())
+            .speed(speed)
+            .events(events)
+            .build()
+            .run()
+            .await
+    }
+    
+    /// Replay the recorded market events for `symbol`
+    ///
+    /// Events that are not market events (see `Event::as_market_event`) are skipped.
+    pub async fn replay_symbol(&self, recorder: &EventRecorder, symbol: &str, speed: ReplaySpeed) -> ReplayStats {
+        let for_symbol = |envelope: &EventEnvelope| {
+            envelope.event.as_market_event().and_then(|event| event.symbol()) == Some(symbol)
+        };
+        self.replay_while(recorder, for_symbol, speed).await
+    }
+    
+    /// Replay the recorded events of `event_type`
+    pub async fn replay_type(&self, recorder: &EventRecorder, event_type: &str, speed: ReplaySpeed) -> ReplayStats {
+        self.replay_while(recorder, |envelope| envelope.event.event_type() == event_type, speed).await
+    }
+    
+    /// Get event statistics
+    pub fn get_stats(&self) -> Vec<(String, EventStats)> {
+        self.stats.iter()
+            .map(|entry| (entry.key().clone(), entry.value().clone()))
+            .collect()
+    }
+    
+    /// Diagnostics for every channel, keyed by event type
+    pub fn channel_stats_snapshot(&self) -> HashMap<String, ChannelSnapshot> {
+        self.channels.iter()
+            .map(|entry| {
+                let stats = self.stats.get(entry.key())
+                    .map(|s| s.clone())
+                    .unwrap_or_default();
+                let snapshot = ChannelSnapshot {
+                    published: stats.published,
+                    dropped: stats.dropped,
+                    subscriber_count: entry.sender.receiver_count(),
+                    buffer_utilization: entry.sender.len() as f64 / CHANNEL_CAPACITY as f64,
+                    last_publish_ns: entry.last_publish_ns.load(Ordering::Relaxed),
+                };
+                (entry.key().clone(), snapshot)
+            })
+            .collect()
+    }
+    
+    /// Envelopes queued for the slowest subscriber of the fullest channel
+    /// among `event_types`
+    ///
+    /// An envelope stays queued until every receiver has seen it; at
+    /// `CHANNEL_CAPACITY` lagging subscribers start losing events.
+    pub(crate) fn max_queue_depth<'a>(&self, event_types: impl IntoIterator<Item = &'a str>) -> usize {
+        event_types.into_iter()
+            .filter_map(|event_type| self.channels.get(event_type).map(|entry| entry.sender.len()))
+            .max()
+            .unwrap_or(0)
+    }
+    
+    /// Channel statistics in the Prometheus text exposition format
+    ///
+    /// One sample per event type for each of `hft_eventbus_published_total`,
+    /// `hft_eventbus_dropped_total`, `hft_eventbus_subscriber_count` and
+    /// `hft_eventbus_channel_buffer_utilization`, sorted by event type.
+    pub fn to_prometheus_metrics(&self) -> String {
+        let mut channels: Vec<_> = self.channel_stats_snapshot().into_iter().collect();
+        channels.sort_by(|a, b| a.0.cmp(&b.0));
+        
+        let families: [(&str, &str, &str, fn(&ChannelSnapshot) -> String); 4] = [
+            ("hft_eventbus_published_total", "counter", "Events published per event type",
+                |s| s.published.to_string()),
+            ("hft_eventbus_dropped_total", "counter", "Events published with no live subscriber",
+                |s| s.dropped.to_string()),
+            ("hft_eventbus_subscriber_count", "gauge", "Live receivers per channel",
+                |s| s.subscriber_count.to_string()),
+            ("hft_eventbus_channel_buffer_utilization", "gauge", "Fraction of the channel buffer holding unread events",
+                |s| s.buffer_utilization.to_string()),
+        ];
+        
+        let mut out = String::new();
+        for (name, kind, help, value) in families {
+            out.push_str(&format!("# HELP {} {}\n# TYPE {} {}\n", name, help, name, kind));
+            for (event_type, snapshot) in &channels {
+                out.push_str(&format!(
+                    "{}{{event_type=\"{}\"}} {}\n",
+                    name,
+                    escape_label_value(event_type),
+                    value(snapshot)
+                ));
+            }
+        }
+        out
+    }
+    
+    /// Get content-addressed cache (if enabled)
+    pub fn content_cache(&self) -> Option<Arc<ContentAddressedCache>> {
+        self.content_cache.clone()
+    }
+    
+    /// Get event recorder for replay
+    pub fn recorder(&self) -> Option<Arc<crate::replay::EventRecorder>> {
+        self.recorder.clone()
+    }
+    
+    /// Receiver for dropped events (if a dead-letter queue is enabled)
+    pub fn dead_letter_receiver(&self) -> Option<flume::Receiver<DeadLetterEntry>> {
+        self.dead_letters.as_ref().map(DeadLetterQueue::receiver)
+    }
+    
+    /// Use virtual time for a new envelope if a shared clock is set
+    #[inline]
+    fn stamp(&self, mut envelope: EventEnvelope) -> EventEnvelope {
+        if let Some(clock) = &self.clock {
+            envelope.timestamp_ns = clock.now();
+        }
+        envelope
+    }
+    
+    /// Pass `envelope` through the middleware chain; `None` if a step dropped it
+    fn run_middleware(&self, mut envelope: EventEnvelope) -> Option<EventEnvelope> {
+        if self.middleware_count.load(Ordering::Acquire) == 0 {
+            return Some(envelope);
+        }
+        for middleware in self.middleware.read().unwrap().iter() {
+            match middleware.before_publish(&mut envelope) {
+                MiddlewareResult::Continue => {}
+                MiddlewareResult::Drop => return None,
+                MiddlewareResult::Transform(replacement) => envelope = replacement,
+            }
+        }
+        Some(envelope)
+    }
+    
+    /// Capture a dropped event if a dead-letter queue is enabled
+    #[inline]
+    fn dead_letter(&self, reason: DeadLetterReason, envelope: EventEnvelope) {
+        if let Some(queue) = &self.dead_letters {
+            queue.push(reason, envelope);
+        }
+    }
+    
+    /// Helper to get event type name (zero-alloc)
+    fn event_type_name(event: &dyn Event) -> &'static str {
+        event.event_type()
+    }
+    
+    /// Increment statistics
+    fn increment_stat<F>(&self, event_type: &str, f: F)
+    where
+        F: FnOnce(&mut EventStats),
+    {
+        self.stats.entry(event_type.to_string())
+            .or_insert_with(EventStats::default)
+            .value_mut()
+            .apply(f);
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Three-level priority queue in front of a single broadcast channel
+///
+/// Critical (0), normal (5) and low (9) events go into separate queues. One
+/// dispatcher task drains them with a bias toward critical, then normal, so
+/// queued critical events always go out before queued normal/low events.
+/// Subscribers receive every event type on one broadcast channel.
+#[derive(Clone)]
+pub struct PriorityBus {
+    critical: mpsc::Sender<EventEnvelope>,
+    normal: mpsc::Sender<EventEnvelope>,
+    low: mpsc::Sender<EventEnvelope>,
+    output: broadcast::Sender<EventEnvelope>,
+}
+
+impl PriorityBus {
+    /// Create bus and spawn its dispatcher (must be called inside a Tokio runtime)
+    ///
+    /// The dispatcher exits once every handle has been dropped and the queues are drained.
+    pub fn new() -> Self {
+        let (critical, critical_rx) = mpsc::channel(CHANNEL_CAPACITY);
+        let (normal, normal_rx) = mpsc::channel(CHANNEL_CAPACITY);
+        let (low, low_rx) = mpsc::channel(CHANNEL_CAPACITY);
+        let output = broadcast::channel(CHANNEL_CAPACITY).0;
+        
+        tokio::spawn(Self::dispatch(critical_rx, normal_rx, low_rx, output.clone()));
+        
+        Self { critical, normal, low, output }
+    }
+    
+    async fn dispatch(
+        mut critical: mpsc::Receiver<EventEnvelope>,
+        mut normal: mpsc::Receiver<EventEnvelope>,
+        mut low: mpsc::Receiver<EventEnvelope>,
+        output: broadcast::Sender<EventEnvelope>,
+    ) {
+        loop {
+            let envelope = tokio::select! {
+                biased;
+                Some(envelope) = critical.recv() => envelope,
+                Some(envelope) = normal.recv() => envelope,
+                Some(envelope) = low.recv() => envelope,
+                else => break,
+            };
+            // No subscribers is not an error
+            let _ = output.send(envelope);
+        }
+        debug!("Priority bus dispatcher stopped");
+    }
+    
+    /// Enqueue an event at critical priority (0)
+    pub async fn publish_critical<T: Event + Send + 'static>(&self, event: T) -> Result<()> {
+        Self::enqueue(&self.critical, EventEnvelope::new(event, 0)).await
+    }
+    
+    /// Enqueue an event at normal priority (5)
+    pub async fn publish_normal<T: Event + Send + 'static>(&self, event: T) -> Result<()> {
+        Self::enqueue(&self.normal, EventEnvelope::new(event, 5)).await
+    }
+    
+    /// Enqueue an event at low priority (9)
+    pub async fn publish_low<T: Event + Send + 'static>(&self, event: T) -> Result<()> {
+        Self::enqueue(&self.low, EventEnvelope::new(event, 9)).await
+    }
+    
+    async fn enqueue(queue: &mpsc::Sender<EventEnvelope>, envelope: EventEnvelope) -> Result<()> {
+        queue.send(envelope).await
+            .map_err(|_| anyhow!("Priority bus dispatcher stopped"))
+    }
+    
+    /// Subscribe to all events in dispatch order
+    pub fn subscribe(&self) -> broadcast::Receiver<EventEnvelope> {
+        self.output.subscribe()
+    }
+}
+
+impl Default for PriorityBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+trait Apply {
+    fn apply<F>(&mut self, f: F)
+    where
+        F: FnOnce(&mut Self);
+}
+
+impl Apply for EventStats {
+    fn apply<F>(&mut self, f: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        f(self);
+    }
+}
+
+/// Publish/subscribe operations shared by `EventBus` and `ShardedEventBus`
+///
+/// Lets code (and tests) run against either bus.
+pub trait EventBusTrait: Clone + Send + Sync + 'static {
+    /// Publish event with default priority
+    fn publish<T: Event + Send + 'static>(&self, event: T) -> impl Future<Output = Result<PublishReceipt>> + Send;
+    
+    /// Publish an EventEnvelope directly
+    fn publish_envelope(&self, envelope: EventEnvelope) -> impl Future<Output = Result<()>> + Send;
+    
+    /// Subscribe to a specific event type
+    fn subscribe(&self, event_type: &str) -> impl Future<Output = broadcast::Receiver<EventEnvelope>> + Send;
+}
+
+impl EventBusTrait for EventBus {
+    fn publish<T: Event + Send + 'static>(&self, event: T) -> impl Future<Output = Result<PublishReceipt>> + Send {
+        EventBus::publish(self, event)
+    }
+    
+    fn publish_envelope(&self, envelope: EventEnvelope) -> impl Future<Output = Result<()>> + Send {
+        EventBus::publish_envelope(self, envelope)
+    }
+    
+    fn subscribe(&self, event_type: &str) -> impl Future<Output = broadcast::Receiver<EventEnvelope>> + Send {
+        EventBus::subscribe(self, event_type)
+    }
+}
+
+/// Receive into `events` until it holds `n` events or the channel closes
+///
+/// Cancel-safe: events received so far stay in `events`.
+async fn collect_into(receiver: &mut broadcast::Receiver<EventEnvelope>, n: usize, events: &mut Vec<EventEnvelope>) {
+    while events.len() < n {
+        match receiver.recv().await {
+            Ok(envelope) => events.push(envelope),
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                warn!("Collector lagged, skipped {} events", skipped);
+            }
+            Err(broadcast::error::RecvError::Closed) => break,
+        }
+    }
+}
+
+/// Escape a Prometheus label value (backslash, double quote, newline)
+fn escape_label_value(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{FeatureEvent, FillEvent, HealthEvent, HealthStatus, MarketDataEvent, OrderEvent, OrderSide, OrderStatus, OrderType, OrderUpdateEvent, PerformanceEvent};
+    use crate::test_fixtures::quote;
+    use std::collections::HashSet;
+    use uuid::Uuid;
+    
+    #[tokio::test]
+    async fn test_publish_subscribe() {
+        let bus = EventBus::new();
+        
+        // Subscribe before publishing
+        let mut rx = bus.subscribe_market_data().await;
+        
+        // Publish event
+        let event = MarketDataEvent {
+            timestamp: 1234567890,
+            symbol: "ES".to_string(),
+            price: 6000.0,
+            volume: 10.0,
+            bid_price: 5999.5,
+            bid_size: 5.0,
+            ask_price: 6000.5,
+            ask_size: 5.0,
+        };
+        
+        bus.publish(event).await.unwrap();
+        
+        // Receive event
+        let received = rx.recv().await.unwrap();
+        assert_eq!(received.priority, 5);
+    }
+    
+    #[tokio::test]
+    async fn test_multiple_subscribers() {
+        let bus = EventBus::new();
+        
+        let mut rx1 = bus.subscribe_market_data().await;
+        let mut rx2 = bus.subscribe_market_data().await;
+        
+        let event = MarketDataEvent {
+            timestamp: 1234567890,
             symbol: "ES".to_string(),
             price: 6000.0,
             volume: 10.0,
@@ -223,37 +2623,1255 @@ mod tests {
             bid_size: 5.0,
             ask_price: 6000.5,
             ask_size: 5.0,
+        };
+        
+        bus.publish(event).await.unwrap();
+        
+        // Both should receive
+        assert!(rx1.recv().await.is_ok());
+        assert!(rx2.recv().await.is_ok());
+    }
+    
+    #[tokio::test]
+    async fn test_multicast_groups() {
+        let bus = EventBus::new();
+        
+        let equity = bus.create_group("equity_options", "market_data");
+        let index = bus.create_group("index_options", "market_data");
+        
+        let mut equity_rxs: Vec<_> = (0..3).map(|_| equity.join()).collect();
+        let mut index_rxs: Vec<_> = (0..3).map(|_| index.join()).collect();
+        
+        let delivered = bus.publish_to_group("equity_options", Box::new(quote("AAPL", 190.0))).await.unwrap();
+        assert_eq!(delivered, 3);
+        
+        let delivered = bus.publish_to_group("index_options", Box::new(quote("SPX", 6000.0))).await.unwrap();
+        assert_eq!(delivered, 3);
+        
+        // Each member sees exactly one event from its own group
+        for rx in equity_rxs.iter_mut().chain(index_rxs.iter_mut()) {
+            assert!(rx.recv().await.is_ok());
+            assert!(rx.try_recv().is_err());
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_multicast_group_prunes_dropped_members() {
+        let bus = EventBus::new();
+        let group = bus.create_group("equity_options", "market_data");
+        
+        let _rx1 = group.join();
+        let rx2 = group.join();
+        drop(rx2);
+        
+        let delivered = bus.publish_to_group("equity_options", Box::new(quote("AAPL", 190.0))).await.unwrap();
+        assert_eq!(delivered, 1);
+    }
+    
+    #[tokio::test]
+    async fn test_multicast_group_errors() {
+        let bus = EventBus::new();
+        
+        // Unknown group
+        assert!(bus.publish_to_group("missing", Box::new(quote("ES", 6000.0))).await.is_err());
+        
+        // Wrong event type for the group
+        bus.create_group("fills", "fill");
+        assert!(bus.publish_to_group("fills", Box::new(quote("ES", 6000.0))).await.is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_disband_group() {
+        let bus = EventBus::new();
+        let group = bus.create_group("equity_options", "market_data");
+        let mut rx = group.join();
+        
+        bus.disband_group("equity_options");
+        
+        assert!(matches!(rx.recv().await, Err(broadcast::error::RecvError::Closed)));
+        assert!(bus.publish_to_group("equity_options", Box::new(quote("AAPL", 190.0))).await.is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_fork_with_history() {
+        let bus = EventBus::with_recording(1000);
+        
+        for i in 0..100 {
+            bus.publish(quote("ES", 6000.0 + i as f64)).await.unwrap();
+        }
+        
+        let (forked, stats) = bus.fork_with_history(60_000_000_000).await;
+        assert_eq!(stats.events_replayed, 100);
+        
+        // Warm-up history is retained by the fork's own recorder
+        let recorder = forked.recorder().expect("fork inherits recording");
+        assert_eq!(recorder.len().await, 100);
+        assert_eq!(recorder.capacity(), 1000);
+        
+        // Fork is independent of the parent and accepts live events
+        let mut rx = forked.subscribe_market_data().await;
+        forked.publish(quote("ES", 6100.0)).await.unwrap();
+        assert!(rx.recv().await.is_ok());
+        assert_eq!(bus.recorder().unwrap().len().await, 100);
+    }
+    
+    #[tokio::test]
+    async fn test_fork_with_history_lookback() {
+        let bus = EventBus::with_recording(1000);
+        let now_ns = chrono::Utc::now().timestamp_nanos_opt().unwrap();
+        
+        // 50 stale events from an hour ago, 50 recent ones
+        for i in 0..100 {
+            let mut envelope = EventEnvelope::new(quote("ES", 6000.0), 5);
+            if i < 50 {
+                envelope.timestamp_ns = now_ns - 3_600_000_000_000;
+            }
+            bus.publish_envelope(envelope).await.unwrap();
+        }
+        
+        let (_forked, stats) = bus.fork_with_history(60_000_000_000).await;
+        assert_eq!(stats.events_replayed, 50);
+    }
+    
+    #[tokio::test]
+    async fn test_fork_without_recording() {
+        let bus = EventBus::new();
+        bus.publish(quote("ES", 6000.0)).await.unwrap();
+        
+        assert!(bus.fork_config().recording_capacity.is_none());
+        
+        let (forked, stats) = bus.fork_with_history(60_000_000_000).await;
+        assert_eq!(stats.events_replayed, 0);
+        assert!(forked.recorder().is_none());
+    }
+    
+    #[tokio::test]
+    async fn test_clone_with_isolation() {
+        let bus = EventBus::with_recording(100);
+        bus.add_middleware(|envelope: &mut EventEnvelope| {
+            envelope.priority = 1;
+            MiddlewareResult::Continue
+        });
+        let mut prod_rx = bus.subscribe_market_data().await;
+        
+        let isolated = bus.clone_with_isolation();
+        let mut test_rx = isolated.subscribe_market_data().await;
+        let receipt = isolated.publish(quote("ES", 6000.0)).await.unwrap();
+        assert_eq!(receipt.subscriber_count, 1);
+        
+        // Middleware carried over, channels not
+        assert_eq!(test_rx.recv().await.unwrap().priority, 1);
+        assert!(prod_rx.try_recv().is_err());
+        
+        bus.publish(quote("NQ", 21000.0)).await.unwrap();
+        assert!(prod_rx.try_recv().is_ok());
+        assert!(test_rx.try_recv().is_err());
+        
+        // Separate recorders of the same capacity
+        let recorder = isolated.recorder().expect("isolated bus inherits recording");
+        assert_eq!(recorder.capacity(), 100);
+        assert_eq!(recorder.len().await, 1);
+        assert_eq!(bus.recorder().unwrap().len().await, 1);
+        
+        // A plain clone still shares channels
+        bus.clone().publish(quote("ES", 6001.0)).await.unwrap();
+        assert!(prod_rx.try_recv().is_ok());
+    }
+    
+    #[tokio::test]
+    async fn test_fork_bridges_selected_types() {
+        let bus = EventBus::new();
+        let (child, bridge) = bus.fork();
+        let mut child_market = child.subscribe_market_data().await;
+        let mut child_orders = child.subscribe_orders().await;
+        let mut parent_market = bus.subscribe_market_data().await;
+        
+        // Not forwarded yet
+        bus.publish(quote("ES", 6000.0)).await.unwrap();
+        assert!(parent_market.recv().await.is_ok());
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        assert!(child_market.try_recv().is_err());
+        
+        assert!(bridge.forward("market_data"));
+        assert!(!bridge.forward("market_data"));
+        assert_eq!(bridge.forwarded_types(), vec!["market_data".to_string()]);
+        
+        let receipt = bus.publish(quote("ES", 6001.0)).await.unwrap();
+        let forwarded = child_market.recv().await.unwrap();
+        assert_eq!(forwarded.id, receipt.event_id);
+        
+        // Unbridged types stay on the parent, and child events never go up
+        bus.publish(order_event()).await.unwrap();
+        child.publish(quote("NQ", 21000.0)).await.unwrap();
+        assert!(child_market.recv().await.is_ok());
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        assert!(child_orders.try_recv().is_err());
+        assert!(parent_market.recv().await.is_ok());
+        assert!(parent_market.try_recv().is_err());
+        
+        assert!(bridge.stop_forwarding("market_data"));
+        assert!(!bridge.stop_forwarding("market_data"));
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        bus.publish(quote("ES", 6002.0)).await.unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        assert!(child_market.try_recv().is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_publish_if() {
+        let bus = EventBus::new();
+        let mut rx = bus.subscribe_market_data().await;
+        let risk_ok = Arc::new(std::sync::atomic::AtomicBool::new(true));
+        
+        let flag = risk_ok.clone();
+        let decision = bus.publish_if(quote("ES", 1.0), move || flag.load(Ordering::Relaxed)).await.unwrap();
+        assert_eq!(rx.recv().await.unwrap().id, decision.receipt().unwrap().event_id);
+        
+        risk_ok.store(false, Ordering::Relaxed);
+        let flag = risk_ok.clone();
+        let decision = bus.publish_if(quote("ES", 2.0), move || flag.load(Ordering::Relaxed)).await.unwrap();
+        assert!(matches!(decision, PublishDecision::Suppressed));
+        assert!(rx.try_recv().is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_collect_n() {
+        let bus = EventBus::new();
+        let first_ten = bus.collect_n("market_data", 10);
+        let mut receipts = Vec::new();
+        for i in 0..20 {
+            receipts.push(bus.publish(quote("ES", 6000.0 + i as f64)).await.unwrap());
+        }
+        
+        let events = first_ten.await;
+        let ids: Vec<_> = events.iter().map(|envelope| envelope.id).collect();
+        let expected: Vec<_> = receipts[..10].iter().map(|receipt| receipt.event_id).collect();
+        assert_eq!(ids, expected);
+        // The collector's receiver is gone
+        assert_eq!(bus.publish(quote("ES", 6100.0)).await.unwrap().subscriber_count, 0);
+    }
+    
+    #[tokio::test]
+    async fn test_collect_n_timeout() {
+        let bus = EventBus::new();
+        let collector = bus.collect_n_timeout("market_data", 10, std::time::Duration::from_millis(20));
+        for i in 0..3 {
+            bus.publish(quote("ES", 6000.0 + i as f64)).await.unwrap();
+        }
+        assert_eq!(collector.await.len(), 3);
+        
+        let events = bus.collect_n_timeout("market_data", 0, std::time::Duration::from_secs(10)).await;
+        assert!(events.is_empty());
+    }
+    
+    #[tokio::test]
+    async fn test_snapshot_failover() {
+        let active = EventBus::with_recording(1000);
+        let _rx = active.subscribe_market_data().await;
+        
+        for i in 0..10 {
+            active.publish(quote("ES", 6000.0 + i as f64)).await.unwrap();
+        }
+        for _ in 0..5 {
+            active.publish(PerformanceEvent::builder().cpu_usage(50.0).build()).await.unwrap();
+        }
+        
+        let snapshot = active.take_snapshot().await;
+        assert_eq!(snapshot.sequence, 1);
+        assert_eq!(snapshot.event_count(), 15);
+        assert_eq!(snapshot.channels["market_data"].len(), 10);
+        assert_eq!(snapshot.channels["performance"].len(), 5);
+        assert!(snapshot.age_ns() >= 0);
+        
+        // Passive node takes over
+        let passive = EventBus::with_recording(1000);
+        passive.publish(quote("NQ", 21000.0)).await.unwrap();
+        passive.restore_from_snapshot(snapshot).await.unwrap();
+        
+        assert_eq!(passive.recorder().unwrap().len().await, 15);
+        
+        let stats: HashMap<String, EventStats> = passive.get_stats().into_iter().collect();
+        assert_eq!(stats["market_data"].published, 10);
+        assert_eq!(stats["performance"].dropped, 5);
+        
+        // Snapshots taken after failover continue the sequence
+        assert_eq!(passive.take_snapshot().await.sequence, 2);
+    }
+    
+    #[tokio::test]
+    async fn test_snapshot_requires_recording() {
+        let active = EventBus::new();
+        active.publish(quote("ES", 6000.0)).await.unwrap();
+        
+        let snapshot = active.take_snapshot().await;
+        assert_eq!(snapshot.event_count(), 0);
+        assert_eq!(snapshot.stats.len(), 1);
+        
+        let passive = EventBus::new();
+        assert!(passive.restore_from_snapshot(snapshot).await.is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_content_cache_deduplicates() {
+        let bus = EventBus::with_content_cache(60_000_000_000);
+        let mut rx = bus.subscribe_market_data().await;
+        
+        // Same content from two redundant pipelines
+        let first = bus.publish(quote("ES", 6000.0)).await.unwrap();
+        let duplicate = bus.publish(quote("ES", 6000.0)).await.unwrap();
+        bus.publish(quote("ES", 6001.0)).await.unwrap();
+        
+        // The duplicate's receipt names the envelope that was delivered
+        assert_eq!(duplicate.subscriber_count, 0);
+        assert_eq!(duplicate.event_id, first.event_id);
+        assert_eq!(duplicate.timestamp_ns, first.timestamp_ns);
+        assert_eq!(rx.recv().await.unwrap().id, first.event_id);
+        assert!(rx.recv().await.is_ok());
+        assert!(rx.try_recv().is_err());
+        
+        let stats: HashMap<String, EventStats> = bus.get_stats().into_iter().collect();
+        assert_eq!(stats["market_data"].published, 2);
+        assert_eq!(stats["market_data"].deduplicated, 1);
+        
+        let cache = bus.content_cache().unwrap();
+        assert!((cache.hit_rate() - 1.0 / 3.0).abs() < 1e-12);
+    }
+    
+    #[tokio::test]
+    async fn test_content_cache_ttl_expiry() {
+        let bus = EventBus::with_content_cache(10_000_000); // 10ms
+        let mut rx = bus.subscribe_market_data().await;
+        
+        bus.publish(quote("ES", 6000.0)).await.unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        bus.publish(quote("ES", 6000.0)).await.unwrap();
+        
+        assert!(rx.recv().await.is_ok());
+        assert!(rx.recv().await.is_ok());
+    }
+    
+    fn order_event() -> OrderEvent {
+        OrderEvent {
+            order_id: Uuid::from_u128(1),
+            signal_id: None,
+            timestamp: 1234567890,
+            symbol: "ES".to_string(),
+            side: OrderSide::Buy,
+            order_type: OrderType::Limit,
+            quantity: 1.0,
+            price: Some(6000.0),
+        }
+    }
+    
+    fn order_update_event() -> OrderUpdateEvent {
+        OrderUpdateEvent {
+            order_id: Uuid::from_u128(1),
+            timestamp: 1234567891,
+            status: OrderStatus::Filled,
+            filled_quantity: 1.0,
+            remaining_quantity: 0.0,
+        }
+    }
+    
+    fn fill_event() -> FillEvent {
+        FillEvent {
+            fill_id: Uuid::from_u128(2),
+            order_id: Uuid::from_u128(1),
+            signal_id: None,
+            timestamp: 1234567892,
+            symbol: "ES".to_string(),
+            side: OrderSide::Buy,
+            filled_quantity: 1.0,
+            fill_price: 6000.0,
+            commission: 0.5,
+            slippage_bps: 0.1,
+        }
+    }
+    
+    fn drain_types(rx: &mut broadcast::Receiver<EventEnvelope>) -> Vec<&'static str> {
+        let mut types = Vec::new();
+        while let Ok(envelope) = rx.try_recv() {
+            types.push(envelope.event.event_type());
+        }
+        types
+    }
+    
+    #[tokio::test]
+    async fn test_pattern_subscriptions_overlap() {
+        let bus = EventBus::new();
+        
+        let mut all_orders = bus.subscribe_prefix("order").await;
+        let mut order_children = bus.subscribe_pattern("order_*").await;
+        let mut updates = bus.subscribe_pattern("*update").await;
+        let mut fills = bus.subscribe_pattern("fil?").await;
+        let mut everything = bus.subscribe_pattern("*").await;
+        let mut direct = bus.subscribe("order").await;
+        
+        bus.publish(order_event()).await.unwrap();
+        bus.publish(order_update_event()).await.unwrap();
+        bus.publish(fill_event()).await.unwrap();
+        
+        assert_eq!(drain_types(&mut all_orders), vec!["order", "order_update"]);
+        assert_eq!(drain_types(&mut order_children), vec!["order_update"]);
+        assert_eq!(drain_types(&mut updates), vec!["order_update"]);
+        assert_eq!(drain_types(&mut fills), vec!["fill"]);
+        assert_eq!(drain_types(&mut everything), vec!["order", "order_update", "fill"]);
+        
+        // Direct subscriptions are unaffected
+        assert_eq!(drain_types(&mut direct), vec!["order"]);
+    }
+    
+    #[tokio::test]
+    async fn test_pattern_subscription_shared_and_pruned() {
+        let bus = EventBus::new();
+        
+        let mut rx1 = bus.subscribe_prefix("order").await;
+        let mut rx2 = bus.subscribe_prefix("order").await;
+        assert_eq!(bus.pattern_count.load(Ordering::Acquire), 1);
+        
+        bus.publish(order_event()).await.unwrap();
+        assert_eq!(drain_types(&mut rx1), vec!["order"]);
+        assert_eq!(drain_types(&mut rx2), vec!["order"]);
+        
+        drop(rx1);
+        drop(rx2);
+        bus.publish(order_event()).await.unwrap();
+        assert_eq!(bus.pattern_count.load(Ordering::Acquire), 0);
+    }
+    
+    #[tokio::test]
+    async fn test_publish_batch_preserves_order() {
+        let bus = EventBus::new();
+        let mut rx = bus.subscribe_market_data().await;
+        
+        let events: Vec<MarketDataEvent> = (0..512).map(|i| quote("ES", i as f64)).collect();
+        let delivered = bus.publish_batch(&events).await.unwrap();
+        assert_eq!(delivered, 512);
+        
+        for i in 0..512 {
+            let envelope = rx.recv().await.unwrap();
+            let json = envelope.event.to_json().unwrap();
+            assert_eq!(json["price"].as_f64(), Some(i as f64));
+        }
+        assert!(rx.try_recv().is_err());
+        
+        let stats: HashMap<String, EventStats> = bus.get_stats().into_iter().collect();
+        assert_eq!(stats["market_data"].published, 512);
+    }
+    
+    #[tokio::test]
+    async fn test_publish_batch_without_subscribers() {
+        let bus = EventBus::new();
+        let events = vec![quote("ES", 1.0), quote("NQ", 2.0)];
+        
+        assert_eq!(bus.publish_batch(&events).await.unwrap(), 0);
+        let stats: HashMap<String, EventStats> = bus.get_stats().into_iter().collect();
+        assert_eq!(stats["market_data"].dropped, 2);
+        assert_eq!(bus.publish_batch::<MarketDataEvent>(&[]).await.unwrap(), 0);
+    }
+    
+    #[tokio::test]
+    async fn test_try_drain() {
+        let bus = EventBus::new();
+        let mut rx = bus.subscribe_market_data().await;
+        
+        // Nothing is queued before the first try_drain
+        bus.publish(quote("ES", -1.0)).await.unwrap();
+        assert!(bus.try_drain("market_data", 10).is_empty());
+        
+        for i in 0..500 {
+            bus.publish(quote("ES", i as f64)).await.unwrap();
+        }
+        
+        let first = bus.try_drain("market_data", 200);
+        let second = bus.try_drain("market_data", 200);
+        assert_eq!(first.len(), 200);
+        assert_eq!(second.len(), 200);
+        
+        let ids: std::collections::HashSet<Uuid> = first.iter().chain(&second).map(|e| e.id).collect();
+        assert_eq!(ids.len(), 400);
+        
+        // Broadcast subscribers are unaffected
+        let mut received = 0;
+        while rx.try_recv().is_ok() {
+            received += 1;
+        }
+        assert_eq!(received, 501);
+        assert_eq!(bus.try_drain("market_data", 1000).len(), 100);
+    }
+    
+    #[tokio::test]
+    async fn test_sequence_number_shared_by_routes() {
+        let bus = EventBus::with_recording(100);
+        assert!(bus.enable_sequencing("market_data"));
+        bus.enable_drain("market_data");
+        let mut direct = bus.subscribe_market_data().await;
+        let mut pattern = bus.subscribe_pattern("market_*").await;
+        
+        bus.publish(quote("ES", 1.0)).await.unwrap();
+        bus.publish_batch(&[quote("ES", 2.0)]).await.unwrap();
+        bus.publish_envelope(EventEnvelope::new(quote("ES", 3.0), 5)).await.unwrap();
+        
+        let expected = vec![Some(0), Some(1), Some(2)];
+        let recorded: Vec<Option<u64>> = bus.recorder().unwrap().get_events().await
+            .iter()
+            .map(|e| e.sequence_number)
+            .collect();
+        assert_eq!(recorded, expected);
+        for rx in [&mut direct, &mut pattern] {
+            let received: Vec<Option<u64>> = (0..3).map(|_| rx.try_recv().unwrap().sequence_number).collect();
+            assert_eq!(received, expected);
+        }
+        let drained: Vec<Option<u64>> = bus.try_drain("market_data", 10).iter().map(|e| e.sequence_number).collect();
+        assert_eq!(drained, expected);
+    }
+    
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_priority_bus_critical_first() {
+        const PER_PUBLISHER: u64 = 2_000;
+        
+        let bus = PriorityBus::new();
+        let mut rx = bus.subscribe();
+        
+        // Global publish order, carried in the event payload
+        let sequence = Arc::new(AtomicU64::new(0));
+        let metrics = |seq: u64| crate::events::MetricsEvent {
+            timestamp: 0,
+            strategy_id: None,
+            pnl: 0.0,
+            sharpe_ratio: 0.0,
+            max_drawdown: 0.0,
+            win_rate: 0.0,
+            total_trades: seq,
+        };
+        
+        let mut publishers = Vec::new();
+        for priority in [0u8, 5, 9] {
+            let bus = bus.clone();
+            let sequence = sequence.clone();
+            publishers.push(tokio::spawn(async move {
+                for _ in 0..PER_PUBLISHER {
+                    let event = metrics(sequence.fetch_add(1, Ordering::SeqCst));
+                    match priority {
+                        0 => bus.publish_critical(event).await.unwrap(),
+                        5 => bus.publish_normal(event).await.unwrap(),
+                        _ => bus.publish_low(event).await.unwrap(),
+                    }
+                }
+            }));
+        }
+        for publisher in publishers {
+            publisher.await.unwrap();
+        }
+        
+        let mut received = Vec::new();
+        for _ in 0..3 * PER_PUBLISHER {
+            let envelope = rx.recv().await.unwrap();
+            let seq = envelope.event.to_json().unwrap()["total_trades"].as_u64().unwrap();
+            received.push((envelope.priority, seq));
+        }
+        
+        // Inversion: a normal/low event published after a critical one but
+        // delivered before it
+        let mut inversions = 0;
+        let mut max_non_critical_seq = None;
+        for &(priority, seq) in &received {
+            if priority == 0 {
+                if matches!(max_non_critical_seq, Some(max) if max > seq) {
+                    inversions += 1;
+                }
+            } else {
+                max_non_critical_seq = max_non_critical_seq.max(Some(seq));
+            }
+        }
+        
+        let allowed = (PER_PUBLISHER as f64 * 0.02) as usize;
+        assert!(inversions <= allowed, "{} critical events delivered late", inversions);
+    }
+    
+    #[tokio::test]
+    async fn test_priority_bus_queued_order() {
+        let bus = PriorityBus::new();
+        let mut rx = bus.subscribe();
+        
+        // Current-thread runtime: everything is queued before the dispatcher runs
+        bus.publish_low(quote("ES", 3.0)).await.unwrap();
+        bus.publish_normal(quote("ES", 2.0)).await.unwrap();
+        bus.publish_critical(quote("ES", 1.0)).await.unwrap();
+        
+        let priorities: Vec<u8> = [rx.recv().await, rx.recv().await, rx.recv().await]
+            .into_iter()
+            .map(|envelope| envelope.unwrap().priority)
+            .collect();
+        assert_eq!(priorities, vec![0, 5, 9]);
+    }
+    
+    #[tokio::test]
+    async fn test_subscribe_typed() {
+        let bus = EventBus::new();
+        let mut typed = bus.subscribe_typed::<MarketDataEvent>();
+        let mut second = bus.subscribe_typed::<MarketDataEvent>();
+        let mut envelopes = bus.subscribe_market_data().await;
+        
+        bus.publish(quote("ES", 6000.0)).await.unwrap();
+        bus.publish(fill_event()).await.unwrap();
+        bus.publish_batch(&[quote("NQ", 21000.0)]).await.unwrap();
+        
+        let first = typed.recv().await.unwrap();
+        assert_eq!(first.symbol, "ES");
+        assert_eq!(typed.recv().await.unwrap().symbol, "NQ");
+        assert!(typed.try_recv().is_none());
+        assert_eq!(second.try_recv().unwrap().price, 6000.0);
+        
+        // Envelope subscribers still see everything
+        assert!(envelopes.try_recv().is_ok());
+        assert!(envelopes.try_recv().is_ok());
+    }
+    
+    #[tokio::test]
+    async fn test_subscribe_where() {
+        let bus = EventBus::new();
+        let mut es = bus.subscribe_where(|e: &MarketDataEvent| e.symbol == "ES");
+        let mut expensive = bus.subscribe_where(|e: &MarketDataEvent| e.price > 10000.0);
+        let mut all = bus.subscribe_market_data().await;
+        
+        bus.publish(quote("ES", 6000.0)).await.unwrap();
+        bus.publish(quote("NQ", 21000.0)).await.unwrap();
+        bus.publish(fill_event()).await.unwrap();
+        bus.publish_batch(&[quote("ES", 6001.0)]).await.unwrap();
+        
+        let prices = |rx: &mut broadcast::Receiver<EventEnvelope>| {
+            std::iter::from_fn(|| rx.try_recv().ok())
+                .map(|env| env.event.downcast_ref::<MarketDataEvent>().unwrap().price)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(prices(&mut es), vec![6000.0, 6001.0]);
+        assert_eq!(prices(&mut expensive), vec![21000.0]);
+        assert_eq!(prices(&mut all).len(), 3);
+        
+        // Dropped subscriptions are pruned on the next matching publish
+        drop(es);
+        bus.publish(quote("ES", 6002.0)).await.unwrap();
+        assert_eq!(bus.predicate_count.load(Ordering::Acquire), 1);
+    }
+    
+    #[tokio::test]
+    async fn test_subscribe_correlation() {
+        let bus = EventBus::new();
+        let mut orders = bus.subscribe_orders().await;
+        
+        // Root order starts the chain; its ID is the correlation ID
+        bus.publish(order_event()).await.unwrap();
+        let root = orders.recv().await.unwrap();
+        assert!(root.correlation_id.is_none());
+        
+        let mut chain = bus.subscribe_correlation(root.id);
+        bus.publish_caused_by(order_update_event(), &root).await.unwrap();
+        let update = chain.recv().await.unwrap();
+        assert_eq!(update.causation_id, Some(root.id));
+        assert_eq!(update.correlation_id, Some(root.id));
+        
+        // Second hop keeps the correlation, unrelated events are filtered out
+        bus.publish(fill_event()).await.unwrap();
+        bus.publish_caused_by(fill_event(), &update).await.unwrap();
+        let fill = chain.recv().await.unwrap();
+        assert_eq!(fill.causation_id, Some(update.id));
+        assert_eq!(fill.correlation_id, Some(root.id));
+        assert!(fill.event.downcast_ref::<FillEvent>().is_some());
+        assert!(chain.try_recv().is_err());
+        
+        // Channel is removed once its last receiver is gone
+        drop(chain);
+        bus.publish_caused_by(fill_event(), &update).await.unwrap();
+        assert!(bus.correlations.is_empty());
+    }
+    
+    #[tokio::test]
+    async fn test_publish_pooled() {
+        let bus = EventBus::new();
+        let pool = crate::pool::EnvelopePool::new(8);
+        let mut envelopes = bus.subscribe_market_data().await;
+        let mut typed = bus.subscribe_typed::<MarketDataEvent>();
+        bus.enable_drain(MarketDataEvent::EVENT_TYPE);
+        
+        bus.publish_pooled(pool.acquire(quote("ES", 6000.0))).await.unwrap();
+        let envelope = envelopes.recv().await.unwrap();
+        assert_eq!(envelope.event.downcast_ref::<MarketDataEvent>().unwrap().symbol, "ES");
+        assert_eq!(typed.recv().await.unwrap().price, 6000.0);
+        
+        // Box is back in the pool once the receiver and the drain queue release it
+        drop(envelope);
+        assert_eq!(pool.available(), 0);
+        assert_eq!(bus.try_drain(MarketDataEvent::EVENT_TYPE, usize::MAX).len(), 1);
+        assert_eq!(pool.available(), 1);
+        bus.publish_pooled(pool.acquire(quote("NQ", 21000.0))).await.unwrap();
+        assert_eq!(pool.reused(), 1);
+        assert_eq!(pool.allocated(), 1);
+    }
+    
+    #[tokio::test]
+    async fn test_replay_recorded() {
+        let bus = EventBus::with_recording(4000);
+        for i in 0..1000 {
+            let mut envelope = EventEnvelope::new(quote("ES", 6000.0 + i as f64), 5);
+            envelope.timestamp_ns = i * 1000;
+            bus.publish_envelope(envelope).await.unwrap();
+        }
+        
+        let mut rx = bus.subscribe_market_data().await;
+        let consumer = tokio::spawn(async move {
+            let mut prices = Vec::new();
+            while prices.len() < 1000 {
+                let envelope = rx.recv().await.unwrap();
+                prices.push(envelope.event.downcast_ref::<MarketDataEvent>().unwrap().price);
+            }
+            prices
         });
         
-        bus.publish(event).await.unwrap();
+        let mut replay = bus.replay_recorded(ReplaySpeed::Max).await;
+        assert_eq!(replay.event_count(), 1000);
+        assert_eq!(replay.run().await.events_replayed, 1000);
         
-        // Receive event
-        let received = rx.recv().await.unwrap();
-        assert_eq!(received.priority, 5);
+        let prices = consumer.await.unwrap();
+        assert_eq!(prices.len(), 1000);
+        assert_eq!(prices[0], 6000.0);
+        assert_eq!(prices[999], 6999.0);
+        
+        // Range is inclusive on both ends (the recorder now also holds the replayed copies)
+        let replay = bus.replay_recorded_range(100_000, 199_000, ReplaySpeed::Max).await.unwrap();
+        assert_eq!(replay.event_count(), 200);
+        assert!(bus.replay_recorded_range(10, 0, ReplaySpeed::Max).await.is_err());
+        assert!(EventBus::new().replay_recorded_range(0, 10, ReplaySpeed::Max).await.is_err());
     }
     
     #[tokio::test]
-    async fn test_multiple_subscribers() {
+    async fn test_replay_filtered() {
+        let recorder = EventRecorder::new(100);
+        // Recorded out of timestamp order
+        for (i, symbol) in ["ES", "NQ", "ES", "CL", "NQ", "ES"].iter().enumerate() {
+            let mut envelope = EventEnvelope::new(quote(symbol, i as f64), 5);
+            envelope.timestamp_ns = 1_000 - i as i64;
+            recorder.record(envelope).await;
+        }
+        let mut health = EventEnvelope::new(HealthEvent {
+            timestamp: 0,
+            component: "gateway".to_string(),
+            status: HealthStatus::Healthy,
+            message: String::new(),
+        }, 5);
+        health.timestamp_ns = 500;
+        recorder.record(health).await;
+        
         let bus = EventBus::new();
+        let mut rx = bus.subscribe_market_data().await;
+        let stats = bus.replay_symbol(&recorder, "ES", ReplaySpeed::Max).await;
+        assert_eq!(stats.events_replayed, 3);
+        let mut prices = Vec::new();
+        while let Ok(envelope) = rx.try_recv() {
+            let event = envelope.event.downcast_ref::<MarketDataEvent>().unwrap();
+            assert_eq!(event.symbol, "ES");
+            prices.push(event.price);
+        }
+        // Timestamp order, not recording order
+        assert_eq!(prices, vec![5.0, 2.0, 0.0]);
         
-        let mut rx1 = bus.subscribe_market_data().await;
-        let mut rx2 = bus.subscribe_market_data().await;
+        let mut health_rx = bus.subscribe(HealthEvent::EVENT_TYPE).await;
+        assert_eq!(bus.replay_type(&recorder, HealthEvent::EVENT_TYPE, ReplaySpeed::Max).await.events_replayed, 1);
+        assert!(health_rx.try_recv().is_ok());
+        assert!(rx.try_recv().is_err());
         
-        let event = Event::MarketData(MarketDataEvent {
-            timestamp: 1234567890,
+        let stats = bus.replay_while(&recorder, |envelope| envelope.timestamp_ns > 997, ReplaySpeed::Max).await;
+        assert_eq!(stats.events_replayed, 3);
+        assert_eq!(bus.replay_symbol(&recorder, "GC", ReplaySpeed::Max).await.events_replayed, 0);
+    }
+    
+    /// Publish `count` market data events, draining `sampled` as the sampler keeps up
+    async fn publish_sampled(bus: &EventBus, sampled: &mut SampledReceiver, count: u64) -> Vec<EventEnvelope> {
+        let mut received = Vec::new();
+        for chunk in 0..count / 1000 {
+            for i in 0..1000 {
+                bus.publish(quote("ES", (chunk * 1000 + i) as f64)).await.unwrap();
+            }
+            while sampled.seen() < (chunk + 1) * 1000 {
+                while let Some(envelope) = sampled.try_recv() {
+                    received.push(envelope);
+                }
+                tokio::task::yield_now().await;
+            }
+        }
+        while let Some(envelope) = sampled.try_recv() {
+            received.push(envelope);
+        }
+        received
+    }
+    
+    #[tokio::test]
+    async fn test_subscribe_sampled() {
+        for rate in [0.1, 0.25, 0.5, 1.0] {
+            let bus = EventBus::new();
+            let mut sampled = bus.subscribe_sampled(MarketDataEvent::EVENT_TYPE, rate).await;
+            assert_eq!(sampled.effective_rate(), 0.0);
+            
+            let received = publish_sampled(&bus, &mut sampled, 100_000).await;
+            let actual = received.len() as f64 / 100_000.0;
+            assert!((actual - rate).abs() <= rate * 0.05, "rate {} sampled at {}", rate, actual);
+            if rate >= 0.5 {
+                let effective = sampled.effective_rate();
+                assert!((effective - rate).abs() <= rate * 0.05, "rate {} measured as {}", rate, effective);
+            }
+            
+            // Sampled events keep their order
+            let prices: Vec<f64> = received.iter()
+                .map(|e| e.event.downcast_ref::<MarketDataEvent>().unwrap().price)
+                .collect();
+            assert!(prices.windows(2).all(|w| w[0] < w[1]));
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_subscribe_every_nth() {
+        let bus = EventBus::new();
+        let mut sampled = bus.subscribe_every_nth(MarketDataEvent::EVENT_TYPE, 7).await;
+        let received = publish_sampled(&bus, &mut sampled, 100_000).await;
+        assert_eq!(received.len(), 100_000usize.div_ceil(7));
+        let prices: Vec<f64> = received.iter()
+            .map(|e| e.event.downcast_ref::<MarketDataEvent>().unwrap().price)
+            .collect();
+        assert_eq!(prices[..3], [0.0, 7.0, 14.0]);
+        
+        // Only the last SAMPLING_WINDOW events count towards the rate
+        assert_eq!(sampled.seen(), 100_000);
+        let forwarded_in_window = (90_000..100_000).filter(|i| i % 7 == 0).count();
+        assert_eq!(sampled.effective_rate(), forwarded_in_window as f64 / SAMPLING_WINDOW as f64);
+    }
+    
+    #[tokio::test]
+    #[should_panic(expected = "not in (0.0, 1.0]")]
+    async fn test_subscribe_sampled_rejects_rate() {
+        EventBus::new().subscribe_sampled(MarketDataEvent::EVENT_TYPE, 1.5).await;
+    }
+    
+    #[tokio::test]
+    async fn test_subscribe_windowed() {
+        let bus = EventBus::new();
+        let mut sliding = bus.subscribe_windowed(MarketDataEvent::EVENT_TYPE, 5).await;
+        let mut tumbling = bus.subscribe_windowed(MarketDataEvent::EVENT_TYPE, 5).await;
+        for i in 0..20 {
+            bus.publish(quote("ES", i as f64)).await.unwrap();
+        }
+        bus.drop_channel(MarketDataEvent::EVENT_TYPE);
+        
+        let prices = |window: &[EventEnvelope]| -> Vec<f64> {
+            window.iter()
+                .map(|e| e.event.downcast_ref::<MarketDataEvent>().unwrap().price)
+                .collect()
+        };
+        
+        let mut frames = Vec::new();
+        while let Some(window) = sliding.recv_window().await {
+            frames.push(prices(window));
+        }
+        assert_eq!(frames.len(), 20 - 5 + 1);
+        for (start, frame) in frames.iter().enumerate() {
+            let expected: Vec<f64> = (start..start + 5).map(|p| p as f64).collect();
+            assert_eq!(frame, &expected);
+        }
+        
+        let mut windows = Vec::new();
+        while let Some(window) = tumbling.recv_tumbling_window().await {
+            windows.push(prices(&window));
+        }
+        assert_eq!(windows.len(), 4);
+        assert_eq!(windows[0], vec![0.0, 1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(windows[3], vec![15.0, 16.0, 17.0, 18.0, 19.0]);
+    }
+    
+    #[tokio::test]
+    async fn test_windowed_lag_clears_window() {
+        let bus = EventBus::new();
+        let mut windowed = bus.subscribe_windowed(MarketDataEvent::EVENT_TYPE, 3).await;
+        for i in 0..CHANNEL_CAPACITY + 2 {
+            bus.publish(quote("ES", i as f64)).await.unwrap();
+        }
+        
+        // The oldest events were overwritten; the window restarts after the gap
+        let window = windowed.recv_window().await.unwrap();
+        let first = window[0].event.downcast_ref::<MarketDataEvent>().unwrap().price;
+        assert_eq!(first, 2.0);
+        assert_eq!(window.len(), 3);
+    }
+    
+    /// Publish market data and a feature event stamped `md_ns` and `feature_ns`
+    async fn publish_join_pair(bus: &EventBus, md_ns: i64, feature_ns: i64) {
+        let mut md = EventEnvelope::new(quote("ES", md_ns as f64), 5);
+        md.timestamp_ns = md_ns;
+        bus.publish_envelope(md).await.unwrap();
+        
+        let feature = FeatureEvent {
+            timestamp: feature_ns,
             symbol: "ES".to_string(),
-            price: 6000.0,
-            volume: 10.0,
-            bid_price: 5999.5,
-            bid_size: 5.0,
-            ask_price: 6000.5,
-            ask_size: 5.0,
+            features: HashMap::from([("momentum".to_string(), feature_ns as f64)]),
+        };
+        let mut feature = EventEnvelope::new(feature, 5);
+        feature.timestamp_ns = feature_ns;
+        bus.publish_envelope(feature).await.unwrap();
+    }
+    
+    /// Timestamps of every pair until both channels close
+    async fn collect_joined(join: &mut JoinReceiver) -> Vec<(i64, i64)> {
+        let mut pairs = Vec::new();
+        while let Some((a, b)) = join.recv().await {
+            assert_eq!(a.event.event_type(), MarketDataEvent::EVENT_TYPE);
+            assert_eq!(b.event.event_type(), FeatureEvent::EVENT_TYPE);
+            pairs.push((a.timestamp_ns, b.timestamp_ns));
+        }
+        pairs
+    }
+    
+    #[tokio::test]
+    async fn test_subscribe_join() {
+        let bus = EventBus::new();
+        let mut join = bus.subscribe_join(MarketDataEvent::EVENT_TYPE, FeatureEvent::EVENT_TYPE, 10).await;
+        for i in 0..500 {
+            publish_join_pair(&bus, i * 1_000, i * 1_000).await;
+        }
+        bus.drop_channel(MarketDataEvent::EVENT_TYPE);
+        bus.drop_channel(FeatureEvent::EVENT_TYPE);
+        
+        let pairs = collect_joined(&mut join).await;
+        let expected: Vec<(i64, i64)> = (0..500).map(|i| (i * 1_000, i * 1_000)).collect();
+        assert_eq!(pairs, expected);
+        assert_eq!(join.unmatched_a(), 0);
+        assert_eq!(join.unmatched_b(), 0);
+    }
+    
+    #[tokio::test]
+    async fn test_subscribe_join_tolerance_boundary() {
+        let bus = EventBus::new();
+        let mut join = bus.subscribe_join(MarketDataEvent::EVENT_TYPE, FeatureEvent::EVENT_TYPE, 100).await;
+        // Exactly at the tolerance, one past it, then an exact match
+        publish_join_pair(&bus, 1_000, 1_100).await;
+        publish_join_pair(&bus, 2_000, 2_101).await;
+        publish_join_pair(&bus, 3_000, 3_000).await;
+        bus.drop_channel(MarketDataEvent::EVENT_TYPE);
+        bus.drop_channel(FeatureEvent::EVENT_TYPE);
+        
+        assert_eq!(collect_joined(&mut join).await, vec![(1_000, 1_100), (3_000, 3_000)]);
+        assert_eq!(join.unmatched_a(), 1);
+        assert_eq!(join.unmatched_b(), 1);
+    }
+    
+    #[tokio::test]
+    async fn test_subscribe_join_counts_pending_on_close() {
+        let bus = EventBus::new();
+        let mut join = bus.subscribe_join(MarketDataEvent::EVENT_TYPE, FeatureEvent::EVENT_TYPE, 100).await;
+        // Too far apart to pair, and both still buffered when the channels close
+        publish_join_pair(&bus, 1_000, 5_000).await;
+        bus.drop_channel(MarketDataEvent::EVENT_TYPE);
+        bus.drop_channel(FeatureEvent::EVENT_TYPE);
+        
+        assert!(collect_joined(&mut join).await.is_empty());
+        assert_eq!(join.unmatched_a(), 1);
+        assert_eq!(join.unmatched_b(), 1);
+    }
+    
+    #[tokio::test]
+    async fn test_subscribe_join_picks_closest() {
+        let bus = EventBus::new();
+        let mut join = bus.subscribe_join(MarketDataEvent::EVENT_TYPE, FeatureEvent::EVENT_TYPE, 100).await;
+        for ns in [950, 990, 1_020] {
+            let feature = FeatureEvent { timestamp: ns, symbol: "ES".to_string(), features: HashMap::new() };
+            let mut envelope = EventEnvelope::new(feature, 5);
+            envelope.timestamp_ns = ns;
+            bus.publish_envelope(envelope).await.unwrap();
+        }
+        // Buffer the features before market data arrives
+        assert!(tokio::time::timeout(Duration::from_millis(50), join.recv()).await.is_err());
+        
+        let mut md = EventEnvelope::new(quote("ES", 1.0), 5);
+        md.timestamp_ns = 1_000;
+        bus.publish_envelope(md).await.unwrap();
+        
+        let (a, b) = join.recv().await.unwrap();
+        assert_eq!((a.timestamp_ns, b.timestamp_ns), (1_000, 990));
+    }
+    
+    #[tokio::test]
+    async fn test_subscribe_merged() {
+        let bus = EventBus::new();
+        let mut merged = bus.subscribe_merged(&["market_data", "fill", "order_update"]).await;
+        
+        bus.publish(fill_event()).await.unwrap();
+        let first = merged.recv().await.unwrap();
+        bus.publish(quote("ES", 6000.0)).await.unwrap();
+        let second = merged.recv().await.unwrap();
+        bus.publish(order_update_event()).await.unwrap();
+        let third = merged.recv().await.unwrap();
+        
+        assert_eq!(first.event.event_type(), "fill");
+        assert_eq!(second.event.event_type(), "market_data");
+        assert_eq!(third.event.event_type(), "order_update");
+        
+        // Other types are not merged in
+        bus.publish(order_event()).await.unwrap();
+        tokio::task::yield_now().await;
+        assert!(merged.try_recv().is_none());
+        
+        // Task ends once every source channel is gone
+        drop(bus);
+        assert!(merged.recv().await.is_none());
+    }
+    
+    #[tokio::test]
+    async fn test_subscribe_round_robin() {
+        let bus = EventBus::new();
+        let workers = bus.subscribe_round_robin("market_data", 4).await;
+        
+        let mut published = HashSet::new();
+        for i in 0..1000 {
+            published.insert(i as u64);
+            bus.publish(quote("ES", i as f64)).await.unwrap();
+        }
+        
+        let mut seen = HashSet::new();
+        for worker in &workers {
+            let mut count = 0;
+            while count < 250 {
+                let envelope = worker.recv_async().await.unwrap();
+                let price = envelope.event.downcast_ref::<MarketDataEvent>().unwrap().price;
+                assert!(seen.insert(price as u64), "event delivered twice");
+                count += 1;
+            }
+            assert!(worker.is_empty());
+        }
+        assert_eq!(seen, published);
+        assert!(bus.subscribe_round_robin("market_data", 0).await.is_empty());
+    }
+    
+    #[tokio::test]
+    async fn test_subscribe_hash_partitioned() {
+        let bus = EventBus::new();
+        let key_fn: Arc<dyn Fn(&EventEnvelope) -> u64 + Send + Sync> = Arc::new(|envelope| {
+            use std::hash::{Hash, Hasher};
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            envelope.event.as_market_event().and_then(|e| e.symbol()).hash(&mut hasher);
+            hasher.finish()
         });
+        let workers = bus.subscribe_hash_partitioned("market_data", 3, key_fn).await;
         
-        bus.publish(event).await.unwrap();
+        let symbols = ["ES", "NQ", "CL", "GC", "ZN"];
+        for i in 0..1000 {
+            bus.publish(quote(symbols[i % symbols.len()], i as f64)).await.unwrap();
+        }
         
-        // Both should receive
+        // Wait for the dispatcher to hand out all 1000
+        tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            while workers.iter().map(|w| w.len()).sum::<usize>() < 1000 {
+                tokio::task::yield_now().await;
+            }
+        }).await.unwrap();
+        
+        let mut seen = HashSet::new();
+        let mut owner: HashMap<String, usize> = HashMap::new();
+        for (index, worker) in workers.iter().enumerate() {
+            for envelope in worker.try_iter() {
+                let event = envelope.event.downcast_ref::<MarketDataEvent>().unwrap();
+                assert!(seen.insert(event.price as u64), "event delivered twice");
+                assert_eq!(*owner.entry(event.symbol.clone()).or_insert(index), index);
+            }
+        }
+        assert_eq!(seen.len(), 1000);
+        assert_eq!(owner.len(), symbols.len());
+    }
+    
+    #[tokio::test]
+    async fn test_type_groups() {
+        let bus = EventBus::new();
+        let members = vec!["bar_es", "bar_nq", "bar_ym"];
+        bus.create_type_group("new_bar", members.clone()).unwrap();
+        assert!(bus.create_type_group("new_bar", members).is_err());
+        assert!(bus.create_type_group("empty", Vec::<String>::new()).is_err());
+        assert_eq!(bus.list_type_groups(), vec!["new_bar".to_string()]);
+        
+        let mut es = bus.subscribe("bar_es").await;
+        let mut nq = bus.subscribe("bar_nq").await;
+        
+        let result = bus.publish_to_type_group("new_bar", quote("ES", 6000.0)).await.unwrap();
+        assert_eq!(result.successful, vec!["bar_es".to_string(), "bar_nq".to_string()]);
+        assert_eq!(result.failed, vec!["bar_ym".to_string()]);
+        
+        // Every member receives the same envelope
+        let from_es = es.recv().await.unwrap();
+        let from_nq = nq.recv().await.unwrap();
+        assert_eq!(from_es.id, from_nq.id);
+        assert_eq!(from_es.event.downcast_ref::<MarketDataEvent>().unwrap().price, 6000.0);
+        
+        assert!(bus.publish_to_type_group("missing", quote("ES", 1.0)).await.is_err());
+        
+        // Group publishes run the middleware chain and reach pattern subscribers
+        let mut bars = bus.subscribe_pattern("bar_*").await;
+        bus.add_middleware(|envelope: &mut EventEnvelope| {
+            match envelope.event.downcast_ref::<MarketDataEvent>() {
+                Some(md) if md.price < 0.0 => MiddlewareResult::Drop,
+                _ => MiddlewareResult::Continue,
+            }
+        });
+        let result = bus.publish_to_type_group("new_bar", quote("ES", -1.0)).await.unwrap();
+        assert_eq!(result, GroupPublishResult::default());
+        assert!(es.try_recv().is_err());
+        
+        bus.publish_to_type_group("new_bar", quote("ES", 6001.0)).await.unwrap();
+        assert_eq!(es.recv().await.unwrap().event.downcast_ref::<MarketDataEvent>().unwrap().price, 6001.0);
+        assert!(bars.recv().await.is_ok());
+        
+        assert!(bus.remove_type_group("new_bar"));
+        assert!(!bus.remove_type_group("new_bar"));
+        assert!(bus.publish_to_type_group("new_bar", quote("ES", 1.0)).await.is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_dead_letter_no_subscribers() {
+        let bus = EventBus::with_dead_letter_queue(16);
+        let dead_letters = bus.dead_letter_receiver().unwrap();
+        assert!(EventBus::new().dead_letter_receiver().is_none());
+        
+        bus.publish(fill_event()).await.unwrap();
+        bus.publish_batch(&[order_update_event(), order_update_event()]).await.unwrap();
+        
+        let _rx = bus.subscribe_fills().await;
+        bus.publish(fill_event()).await.unwrap();
+        
+        let entries: Vec<DeadLetterEntry> = dead_letters.try_iter().collect();
+        assert_eq!(entries.len(), 3);
+        assert!(entries.iter().all(|e| e.reason == DeadLetterReason::NoSubscribers));
+        assert!(entries.iter().all(|e| e.dropped_at_ns > 0));
+        assert_eq!(entries[0].envelope.event.event_type(), "fill");
+        assert_eq!(entries[1].envelope.event.event_type(), "order_update");
+    }
+    
+    #[tokio::test]
+    async fn test_drain_channel() {
+        let bus = EventBus::new();
+        let mut rx1 = bus.subscribe_fills().await;
+        let mut rx2 = bus.subscribe_fills().await;
+        let mut orders = bus.subscribe_orders().await;
+        
+        let receipt = bus.publish(fill_event()).await.unwrap();
+        assert_eq!(bus.drain_channel("fill"), 2);
+        assert_eq!(bus.drain_channel("unknown"), 0);
+        
+        // Buffered events are still readable, then the receiver is closed,
+        // even though the publish receipt is still held
         assert!(rx1.recv().await.is_ok());
+        assert!(matches!(rx1.recv().await, Err(broadcast::error::RecvError::Closed)));
         assert!(rx2.recv().await.is_ok());
+        assert!(matches!(rx2.recv().await, Err(broadcast::error::RecvError::Closed)));
+        assert!(receipt.is_delivered());
+        
+        // New subscribers on the same type work
+        let mut fresh = bus.subscribe_fills().await;
+        bus.publish(fill_event()).await.unwrap();
+        assert_eq!(fresh.recv().await.unwrap().event.event_type(), "fill");
+        
+        let mut channels = bus.active_channels();
+        channels.sort();
+        assert_eq!(channels, vec!["fill", "order"]);
+        
+        assert_eq!(bus.unsubscribe_all(), 2);
+        assert!(matches!(fresh.recv().await, Err(broadcast::error::RecvError::Closed)));
+        assert!(matches!(orders.recv().await, Err(broadcast::error::RecvError::Closed)));
+        
+        assert!(bus.drop_channel("order"));
+        assert!(!bus.drop_channel("order"));
+        assert_eq!(bus.active_channels(), vec!["fill"]);
+    }
+    
+    #[tokio::test]
+    async fn test_channel_stats_snapshot() {
+        let bus = EventBus::new();
+        bus.publish(fill_event()).await.unwrap(); // no subscribers yet
+        
+        let rx1 = bus.subscribe_fills().await;
+        let rx2 = bus.subscribe_fills().await;
+        let mut envelope = EventEnvelope::new(fill_event(), 5);
+        envelope.timestamp_ns = 42;
+        bus.publish_envelope(envelope).await.unwrap();
+        
+        let snapshot = &bus.channel_stats_snapshot()["fill"];
+        assert_eq!(snapshot.subscriber_count, 2);
+        assert_eq!(snapshot.published, 1);
+        assert_eq!(snapshot.dropped, 1);
+        // One envelope is queued for both unread receivers
+        assert!((snapshot.buffer_utilization - 1.0 / CHANNEL_CAPACITY as f64).abs() < 1e-12);
+        assert_eq!(snapshot.last_publish_ns, 42);
+        
+        drop(rx1);
+        assert_eq!(bus.channel_stats_snapshot()["fill"].subscriber_count, 1);
+        drop(rx2);
+        assert_eq!(bus.channel_stats_snapshot()["fill"].subscriber_count, 0);
+    }
+    
+    #[tokio::test]
+    async fn test_max_queue_depth_per_type() {
+        let bus = EventBus::new();
+        let _fills = bus.subscribe_fills().await;
+        let _market_data = bus.subscribe_market_data().await;
+        for _ in 0..3 {
+            bus.publish(fill_event()).await.unwrap();
+        }
+        bus.publish(quote("ES", 6000.0)).await.unwrap();
+        
+        assert_eq!(bus.max_queue_depth(["fill", "market_data"]), 3);
+        assert_eq!(bus.max_queue_depth(["market_data"]), 1);
+        assert_eq!(bus.max_queue_depth(["order"]), 0);
+    }
+    
+    #[tokio::test]
+    async fn test_prometheus_metrics() {
+        let bus = EventBus::new();
+        bus.publish(fill_event()).await.unwrap(); // dropped, no subscribers
+        let _fills = bus.subscribe_fills().await;
+        let _orders = bus.subscribe("order").await;
+        bus.publish(fill_event()).await.unwrap();
+        bus.publish(fill_event()).await.unwrap();
+        
+        let text = bus.to_prometheus_metrics();
+        let sample = regex::Regex::new(
+            r#"^(hft_eventbus_[a-z_]+)\{event_type="([^"\\]*)"\} ([0-9]+(?:\.[0-9]+)?(?:e-?[0-9]+)?)$"#,
+        ).unwrap();
+        let comment = regex::Regex::new(r"^# (HELP|TYPE) hft_eventbus_[a-z_]+ .+$").unwrap();
+        
+        let mut samples = HashMap::new();
+        for line in text.lines() {
+            if let Some(caps) = sample.captures(line) {
+                let value: f64 = caps[3].parse().unwrap();
+                samples.insert((caps[1].to_string(), caps[2].to_string()), value);
+            } else {
+                assert!(comment.is_match(line), "malformed line: {:?}", line);
+            }
+        }
+        
+        let get = |name: &str, event_type: &str| samples[&(name.to_string(), event_type.to_string())];
+        assert_eq!(get("hft_eventbus_published_total", "fill"), 3.0);
+        assert_eq!(get("hft_eventbus_dropped_total", "fill"), 1.0);
+        assert_eq!(get("hft_eventbus_subscriber_count", "fill"), 1.0);
+        assert!((get("hft_eventbus_channel_buffer_utilization", "fill") - 2.0 / CHANNEL_CAPACITY as f64).abs() < 1e-12);
+        assert_eq!(get("hft_eventbus_published_total", "order"), 0.0);
+        assert_eq!(get("hft_eventbus_subscriber_count", "order"), 1.0);
+        assert_eq!(samples.len(), 8);
+        
+        assert!(text.contains("# TYPE hft_eventbus_published_total counter\n"));
+        assert!(text.contains("# TYPE hft_eventbus_subscriber_count gauge\n"));
+        
+        // Samples within a family are ordered by event type
+        let fill = text.find(r#"hft_eventbus_published_total{event_type="fill"}"#).unwrap();
+        let order = text.find(r#"hft_eventbus_published_total{event_type="order"}"#).unwrap();
+        assert!(fill < order);
+    }
+    
+    #[test]
+    fn test_escape_label_value() {
+        assert_eq!(escape_label_value("fill"), "fill");
+        assert_eq!(escape_label_value("a\"b\\c\nd"), "a\\\"b\\\\c\\nd");
     }
 }
@@ -0,0 +1,301 @@
+//! Causal chains between envelopes
+//!
+//! Envelopes published with `publish_caused_by` carry the ID of their parent
+//! (`causation_id`) and the ID of their chain (`correlation_id`).
+//! `CausalityGraph` rebuilds the parent/child trees from a recorded set of
+//! envelopes, e.g. model training -> signal -> order -> fill.
+
+use crate::events::EventEnvelope;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// One envelope and everything it caused
+#[derive(Debug, Clone)]
+pub struct CausalityTree {
+    pub envelope: EventEnvelope,
+    pub children: Vec<CausalityTree>,
+}
+
+impl CausalityTree {
+    /// Number of envelopes in the tree
+    pub fn size(&self) -> usize {
+        1 + self.children.iter().map(CausalityTree::size).sum::<usize>()
+    }
+
+    /// Longest root-to-leaf chain, counted in envelopes
+    pub fn depth(&self) -> usize {
+        1 + self.children.iter().map(CausalityTree::depth).max().unwrap_or(0)
+    }
+}
+
+/// Parent/child index over a set of envelopes
+///
+/// Envelopes whose parent is not in the set are treated as roots, so a
+/// partial recording still yields usable trees.
+#[derive(Debug, Default)]
+pub struct CausalityGraph {
+    envelopes: HashMap<Uuid, EventEnvelope>,
+    /// Child IDs per parent, in timestamp order
+    children: HashMap<Uuid, Vec<Uuid>>,
+    /// Root IDs in timestamp order
+    roots: Vec<Uuid>,
+}
+
+impl CausalityGraph {
+    /// Build the graph from envelopes in any order
+    pub fn from_envelopes(mut envelopes: Vec<EventEnvelope>) -> Self {
+        envelopes.sort_by_key(|e| e.timestamp_ns);
+
+        let mut graph = Self {
+            envelopes: envelopes.iter().map(|e| (e.id, e.clone())).collect(),
+            ..Self::default()
+        };
+        for envelope in &envelopes {
+            match envelope.causation_id {
+                Some(parent) if graph.envelopes.contains_key(&parent) => {
+                    graph.children.entry(parent).or_default().push(envelope.id);
+                }
+                _ => graph.roots.push(envelope.id),
+            }
+        }
+        graph
+    }
+
+    /// Number of envelopes in the graph
+    pub fn len(&self) -> usize {
+        self.envelopes.len()
+    }
+
+    /// Check if graph is empty
+    pub fn is_empty(&self) -> bool {
+        self.envelopes.is_empty()
+    }
+
+    /// Envelope with the given ID
+    pub fn get(&self, id: Uuid) -> Option<&EventEnvelope> {
+        self.envelopes.get(&id)
+    }
+
+    /// Envelopes with no known parent
+    pub fn roots(&self) -> Vec<&EventEnvelope> {
+        self.roots.iter().filter_map(|id| self.envelopes.get(id)).collect()
+    }
+
+    /// Envelopes directly caused by `id`
+    pub fn children(&self, id: Uuid) -> Vec<&EventEnvelope> {
+        self.children
+            .get(&id)
+            .map(|ids| ids.iter().filter_map(|id| self.envelopes.get(id)).collect())
+            .unwrap_or_default()
+    }
+
+    /// Chain of causes of `id`, nearest first
+    pub fn ancestors(&self, id: Uuid) -> Vec<&EventEnvelope> {
+        let mut ancestors = Vec::new();
+        let mut current = self.envelopes.get(&id);
+        // Bounded by the graph size in case of a malformed cycle
+        while let Some(parent) = current
+            .and_then(|e| e.causation_id)
+            .and_then(|parent| self.envelopes.get(&parent))
+        {
+            if ancestors.len() == self.envelopes.len() {
+                break;
+            }
+            ancestors.push(parent);
+            current = Some(parent);
+        }
+        ancestors
+    }
+
+    /// Envelopes belonging to one correlation chain, in timestamp order
+    ///
+    /// Includes the chain's root, whose ID is the correlation ID.
+    pub fn correlation(&self, correlation_id: Uuid) -> Vec<&EventEnvelope> {
+        let mut chain: Vec<&EventEnvelope> = self
+            .envelopes
+            .values()
+            .filter(|e| e.correlation_id == Some(correlation_id) || e.id == correlation_id)
+            .collect();
+        chain.sort_by_key(|e| e.timestamp_ns);
+        chain
+    }
+
+    /// Tree rooted at `id`
+    ///
+    /// Each envelope appears at most once, so a malformed cycle is cut where
+    /// it returns to an envelope already in the tree.
+    pub fn tree(&self, id: Uuid) -> Option<CausalityTree> {
+        self.tree_visiting(id, &mut HashSet::new())
+    }
+
+    fn tree_visiting(&self, id: Uuid, visited: &mut HashSet<Uuid>) -> Option<CausalityTree> {
+        if !visited.insert(id) {
+            return None;
+        }
+        let envelope = self.envelopes.get(&id)?.clone();
+        let children = self
+            .children
+            .get(&id)
+            .map(|ids| ids.iter().filter_map(|child| self.tree_visiting(*child, visited)).collect())
+            .unwrap_or_default();
+        Some(CausalityTree { envelope, children })
+    }
+
+    /// One tree per root
+    pub fn trees(&self) -> Vec<CausalityTree> {
+        self.roots.iter().filter_map(|id| self.tree(*id)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{FillEvent, OrderEvent, OrderSide, OrderType, SignalDirection, SignalEvent};
+    use crate::research_topic::{ModelMetrics, ModelTrainingCompletedEvent, ResearchEvent};
+
+    fn at(mut envelope: EventEnvelope, timestamp_ns: i64) -> EventEnvelope {
+        envelope.timestamp_ns = timestamp_ns;
+        envelope
+    }
+
+    fn model_completed() -> EventEnvelope {
+        let event = ResearchEvent::ModelTrainingCompleted(ModelTrainingCompletedEvent {
+            model_id: Uuid::from_u128(7),
+            final_metrics: ModelMetrics {
+                accuracy: Some(0.6),
+                precision: None,
+                recall: None,
+                f1_score: None,
+                mse: None,
+                rmse: None,
+                r_squared: None,
+            },
+            training_time_ms: 1000,
+            model_path: "models/es.onnx".to_string(),
+            completed_at: 0,
+            schema_version: 1,
+        });
+        at(EventEnvelope::new(event, 5), 0)
+    }
+
+    fn signal(strength: f64) -> SignalEvent {
+        SignalEvent {
+            signal_id: Uuid::new_v4(),
+            timestamp: 0,
+            strategy_id: "model".to_string(),
+            symbol: "ES".to_string(),
+            direction: SignalDirection::Long,
+            strength,
+            target_price: None,
+            stop_loss: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    fn order() -> OrderEvent {
+        OrderEvent {
+            order_id: Uuid::new_v4(),
+            signal_id: None,
+            timestamp: 0,
+            symbol: "ES".to_string(),
+            side: OrderSide::Buy,
+            order_type: OrderType::Market,
+            quantity: 1.0,
+            price: None,
+        }
+    }
+
+    fn fill() -> FillEvent {
+        FillEvent {
+            fill_id: Uuid::new_v4(),
+            order_id: Uuid::new_v4(),
+            signal_id: None,
+            timestamp: 0,
+            symbol: "ES".to_string(),
+            side: OrderSide::Buy,
+            filled_quantity: 1.0,
+            fill_price: 6000.0,
+            commission: 0.5,
+            slippage_bps: 0.1,
+        }
+    }
+
+    #[test]
+    fn test_multi_hop_chain() {
+        // model -> 2 signals; first signal -> order -> 2 partial fills
+        let model = model_completed();
+        let signal_a = at(EventEnvelope::new(signal(0.8), 5).caused_by(&model), 10);
+        let signal_b = at(EventEnvelope::new(signal(0.3), 5).caused_by(&model), 20);
+        let order = at(EventEnvelope::new(order(), 5).caused_by(&signal_a), 30);
+        let fill_1 = at(EventEnvelope::new(fill(), 5).caused_by(&order), 40);
+        let fill_2 = at(EventEnvelope::new(fill(), 5).caused_by(&order), 50);
+        let unrelated = at(EventEnvelope::new(fill(), 5), 5);
+
+        // Correlation is inherited across every hop
+        for envelope in [&signal_a, &signal_b, &order, &fill_1, &fill_2] {
+            assert_eq!(envelope.correlation_id, Some(model.id));
+        }
+        assert_eq!(fill_2.causation_id, Some(order.id));
+
+        let graph = CausalityGraph::from_envelopes(vec![
+            fill_2.clone(),
+            order.clone(),
+            unrelated.clone(),
+            signal_b.clone(),
+            model.clone(),
+            fill_1.clone(),
+            signal_a.clone(),
+        ]);
+        assert_eq!(graph.len(), 7);
+
+        let roots: Vec<Uuid> = graph.roots().iter().map(|e| e.id).collect();
+        assert_eq!(roots, vec![model.id, unrelated.id]);
+
+        let children: Vec<Uuid> = graph.children(model.id).iter().map(|e| e.id).collect();
+        assert_eq!(children, vec![signal_a.id, signal_b.id]);
+
+        let ancestors: Vec<Uuid> = graph.ancestors(fill_2.id).iter().map(|e| e.id).collect();
+        assert_eq!(ancestors, vec![order.id, signal_a.id, model.id]);
+        assert!(graph.ancestors(model.id).is_empty());
+
+        let tree = graph.tree(model.id).unwrap();
+        assert_eq!(tree.size(), 6);
+        assert_eq!(tree.depth(), 4);
+        assert_eq!(tree.children[0].children[0].children.len(), 2);
+        assert!(tree.children[1].children.is_empty());
+        assert!(tree.envelope.event.downcast_ref::<ResearchEvent>().is_some());
+
+        assert_eq!(graph.correlation(model.id).len(), 6);
+        assert_eq!(graph.trees().len(), 2);
+    }
+
+    #[test]
+    fn test_missing_parent_becomes_root() {
+        let model = model_completed();
+        let signal = at(EventEnvelope::new(signal(0.5), 5).caused_by(&model), 10);
+        let order = at(EventEnvelope::new(order(), 5).caused_by(&signal), 20);
+
+        // Recording started after the model event
+        let graph = CausalityGraph::from_envelopes(vec![order.clone(), signal.clone()]);
+        let roots: Vec<Uuid> = graph.roots().iter().map(|e| e.id).collect();
+        assert_eq!(roots, vec![signal.id]);
+        assert_eq!(graph.ancestors(order.id).len(), 1);
+        assert_eq!(graph.correlation(model.id).len(), 2);
+    }
+
+    #[test]
+    fn test_cycle_does_not_recurse_forever() {
+        let mut order = at(EventEnvelope::new(order(), 5), 10);
+        let fill = at(EventEnvelope::new(fill(), 5).caused_by(&order), 20);
+        order.causation_id = Some(fill.id);
+
+        let graph = CausalityGraph::from_envelopes(vec![order.clone(), fill.clone()]);
+        assert!(graph.roots().is_empty());
+
+        let tree = graph.tree(order.id).unwrap();
+        assert_eq!(tree.size(), 2);
+        assert_eq!(tree.children[0].envelope.id, fill.id);
+        assert!(tree.children[0].children.is_empty());
+        assert_eq!(graph.ancestors(order.id).len(), 2);
+    }
+}
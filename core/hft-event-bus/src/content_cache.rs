@@ -0,0 +1,167 @@
+//! Content-addressed deduplication for published events
+//!
+//! Redundant pipelines can emit byte-identical events (e.g. the same
+//! `AnalysisCompletedEvent` from two workers). The cache keys events by a
+//! hash of their JSON payload so duplicates within a TTL are dropped.
+
+use crate::events::Event;
+use dashmap::DashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// First publish of a piece of content
+#[derive(Debug, Clone, Copy)]
+struct Origin {
+    seen_at: Instant,
+    event_id: Uuid,
+    timestamp_ns: i64,
+}
+
+/// Cache of recently seen event content hashes
+pub struct ContentAddressedCache {
+    /// Content hash -> first publish within the TTL
+    seen: Arc<DashMap<u64, Origin>>,
+    
+    /// Time-to-live for cache entries (nanoseconds)
+    ttl_ns: u64,
+    
+    /// Lookups that found a live entry
+    hits: AtomicU64,
+    
+    /// Total lookups
+    lookups: AtomicU64,
+}
+
+impl ContentAddressedCache {
+    /// Create cache with given TTL
+    pub fn new(ttl_ns: u64) -> Self {
+        Self {
+            seen: Arc::new(DashMap::new()),
+            ttl_ns,
+            hits: AtomicU64::new(0),
+            lookups: AtomicU64::new(0),
+        }
+    }
+    
+    /// Hash an event's type and JSON payload
+    ///
+    /// Returns `None` for events that don't support JSON serialization.
+    /// `serde_json::Value` keeps object keys sorted, so map-valued fields
+    /// hash deterministically.
+    pub fn content_hash(event: &dyn Event) -> Option<u64> {
+        let payload = serde_json::to_vec(&event.to_json()?).ok()?;
+        let mut hasher = DefaultHasher::new();
+        event.event_type().hash(&mut hasher);
+        payload.hash(&mut hasher);
+        Some(hasher.finish())
+    }
+    
+    /// Record a content hash for the envelope `event_id` stamped at `timestamp_ns`
+    ///
+    /// Returns the id and timestamp of the first publish if the content was
+    /// already seen within the TTL; otherwise this envelope becomes the first.
+    pub fn check_and_insert(&self, hash: u64, event_id: Uuid, timestamp_ns: i64) -> Option<(Uuid, i64)> {
+        self.lookups.fetch_add(1, Ordering::Relaxed);
+        let now = Instant::now();
+        let ttl = self.ttl();
+        let origin = Origin { seen_at: now, event_id, timestamp_ns };
+        
+        let mut duplicate = None;
+        self.seen.entry(hash)
+            .and_modify(|first| {
+                if now.duration_since(first.seen_at) < ttl {
+                    duplicate = Some((first.event_id, first.timestamp_ns));
+                } else {
+                    *first = origin;
+                }
+            })
+            .or_insert(origin);
+        
+        if duplicate.is_some() {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+        }
+        duplicate
+    }
+    
+    /// Fraction of lookups that were duplicates
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.lookups.load(Ordering::Relaxed);
+        if lookups == 0 {
+            return 0.0;
+        }
+        self.hits.load(Ordering::Relaxed) as f64 / lookups as f64
+    }
+    
+    /// Remove entries older than the TTL, returning the number evicted
+    pub fn evict_stale(&self) -> usize {
+        let now = Instant::now();
+        let ttl = self.ttl();
+        let before = self.seen.len();
+        self.seen.retain(|_, first| now.duration_since(first.seen_at) < ttl);
+        before.saturating_sub(self.seen.len())
+    }
+    
+    /// Number of cached hashes
+    pub fn len(&self) -> usize {
+        self.seen.len()
+    }
+    
+    /// Check if cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.seen.is_empty()
+    }
+    
+    /// Time-to-live for cache entries (nanoseconds)
+    pub fn ttl_ns(&self) -> u64 {
+        self.ttl_ns
+    }
+    
+    fn ttl(&self) -> Duration {
+        Duration::from_nanos(self.ttl_ns)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::quote;
+    
+    #[test]
+    fn test_content_hash_stable() {
+        let a = ContentAddressedCache::content_hash(&quote("ES", 6000.0)).unwrap();
+        let b = ContentAddressedCache::content_hash(&quote("ES", 6000.0)).unwrap();
+        let c = ContentAddressedCache::content_hash(&quote("ES", 6001.0)).unwrap();
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+    
+    #[test]
+    fn test_check_and_insert() {
+        let cache = ContentAddressedCache::new(60_000_000_000);
+        let first = Uuid::new_v4();
+        assert_eq!(cache.check_and_insert(42, first, 100), None);
+        assert_eq!(cache.check_and_insert(42, Uuid::new_v4(), 200), Some((first, 100)));
+        assert_eq!(cache.check_and_insert(43, Uuid::new_v4(), 300), None);
+        assert!((cache.hit_rate() - 1.0 / 3.0).abs() < 1e-12);
+    }
+    
+    #[test]
+    fn test_ttl_expiry_and_eviction() {
+        let cache = ContentAddressedCache::new(10_000_000); // 10ms
+        assert!(cache.check_and_insert(1, Uuid::new_v4(), 0).is_none());
+        assert!(cache.check_and_insert(2, Uuid::new_v4(), 0).is_none());
+        
+        std::thread::sleep(Duration::from_millis(20));
+        
+        // Expired entry is treated as new
+        assert!(cache.check_and_insert(1, Uuid::new_v4(), 0).is_none());
+        
+        // Only the untouched entry is stale
+        assert_eq!(cache.evict_stale(), 1);
+        assert_eq!(cache.len(), 1);
+    }
+}
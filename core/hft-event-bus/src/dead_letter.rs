@@ -0,0 +1,122 @@
+//! Dead-letter queue for events that could not be delivered
+//!
+//! `EventBus` publishes to types with no subscribers and `FastChannel::try_send`
+//! on a full channel both drop the event. With a dead-letter queue attached the
+//! dropped event is captured together with the reason, so it can be inspected
+//! or re-published later.
+
+use crate::events::EventEnvelope;
+use flume::{bounded, Receiver, Sender};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Why an event ended up in the dead-letter queue
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeadLetterReason {
+    /// Published to an event type nobody subscribes to
+    NoSubscribers,
+    /// Bounded channel was at capacity
+    ChannelFull,
+}
+
+/// Event captured by a dead-letter queue
+#[derive(Debug, Clone)]
+pub struct DeadLetterEntry<T = EventEnvelope> {
+    pub reason: DeadLetterReason,
+    pub envelope: T,
+    /// Wall-clock time the event was dropped (nanoseconds since epoch)
+    pub dropped_at_ns: i64,
+}
+
+/// Bounded queue of undeliverable events
+///
+/// Cloning yields a handle to the same queue. When the queue itself is full
+/// new entries are discarded and counted in `overflowed`.
+pub struct DeadLetterQueue<T = EventEnvelope> {
+    sender: Sender<DeadLetterEntry<T>>,
+    receiver: Receiver<DeadLetterEntry<T>>,
+    overflowed: Arc<AtomicU64>,
+}
+
+impl<T> DeadLetterQueue<T> {
+    /// Create queue holding at most `capacity` entries
+    pub fn new(capacity: usize) -> Self {
+        let (sender, receiver) = bounded(capacity);
+        Self {
+            sender,
+            receiver,
+            overflowed: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Capture a dropped event (never blocks)
+    pub fn push(&self, reason: DeadLetterReason, envelope: T) {
+        let entry = DeadLetterEntry {
+            reason,
+            envelope,
+            dropped_at_ns: chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0),
+        };
+        if self.sender.try_send(entry).is_err() {
+            self.overflowed.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Receiver for captured entries
+    pub fn receiver(&self) -> Receiver<DeadLetterEntry<T>> {
+        self.receiver.clone()
+    }
+
+    /// Number of entries waiting in the queue
+    pub fn len(&self) -> usize {
+        self.receiver.len()
+    }
+
+    /// Check if queue is empty
+    pub fn is_empty(&self) -> bool {
+        self.receiver.is_empty()
+    }
+
+    /// Entries discarded because the queue was full
+    pub fn overflowed(&self) -> u64 {
+        self.overflowed.load(Ordering::Relaxed)
+    }
+
+    /// Maximum number of entries the queue holds
+    pub fn capacity(&self) -> usize {
+        self.sender.capacity().unwrap_or(usize::MAX)
+    }
+}
+
+impl<T> Clone for DeadLetterQueue<T> {
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+            receiver: self.receiver.clone(),
+            overflowed: self.overflowed.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dead_letter_overflow_counted() {
+        let queue = DeadLetterQueue::<u32>::new(2);
+        queue.push(DeadLetterReason::ChannelFull, 1);
+        queue.push(DeadLetterReason::NoSubscribers, 2);
+        queue.push(DeadLetterReason::ChannelFull, 3);
+
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.overflowed(), 1);
+
+        let rx = queue.receiver();
+        let first = rx.try_recv().unwrap();
+        assert_eq!(first.reason, DeadLetterReason::ChannelFull);
+        assert_eq!(first.envelope, 1);
+        assert!(first.dropped_at_ns > 0);
+        assert_eq!(rx.try_recv().unwrap().reason, DeadLetterReason::NoSubscribers);
+        assert!(queue.is_empty());
+    }
+}
@@ -0,0 +1,335 @@
+//! Suppression of repeated signals
+//!
+//! Feature recalculation can make a strategy emit the same signal twice
+//! within milliseconds. `SignalDeduplicator` treats a signal as a duplicate
+//! if the same `(strategy_id, symbol, direction)` produced a unique signal
+//! less than `window_ns` earlier. Duplicates do not extend the window.
+//!
+//! `BulkPublisher` applies the same rule to batches of any event type from
+//! external feeds, keyed by a caller-supplied function.
+
+use crate::events::{Event, SignalDirection, SignalEvent};
+use crate::publisher::Publisher;
+use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// Cache size above which expired keys are pruned
+const PRUNE_THRESHOLD: usize = 1024;
+
+/// Remembers when each strategy/symbol/direction last produced a unique signal
+#[derive(Debug, Clone)]
+pub struct SignalDeduplicator {
+    pub window_ns: u64,
+    /// Timestamp of the last unique signal per key
+    pub cache: HashMap<(String, String, SignalDirection), i64>,
+}
+
+impl SignalDeduplicator {
+    pub fn new(window_ns: u64) -> Self {
+        Self {
+            window_ns,
+            cache: HashMap::new(),
+        }
+    }
+
+    /// Return `true` and record the signal if it is unique at `now_ns`
+    ///
+    /// A signal exactly `window_ns` after the last unique one is unique again.
+    pub fn check_and_record(&mut self, signal: &SignalEvent, now_ns: i64) -> bool {
+        let key = (signal.strategy_id.clone(), signal.symbol.clone(), signal.direction);
+        match self.cache.get(&key) {
+            Some(&last_ns) if now_ns.saturating_sub(last_ns) < self.window_ns as i64 => false,
+            _ => {
+                self.cache.insert(key, now_ns);
+                true
+            }
+        }
+    }
+
+    /// Forget keys whose window has passed at `now_ns`
+    pub fn prune(&mut self, now_ns: i64) {
+        let window_ns = self.window_ns as i64;
+        self.cache.retain(|_, last_ns| now_ns.saturating_sub(*last_ns) < window_ns);
+    }
+}
+
+/// `Publisher` that drops duplicate signals
+///
+/// Signals are compared by their own `timestamp`, so deduplication behaves
+/// the same in replays as live.
+pub struct DedupPublisher {
+    inner: Publisher,
+    dedup: Mutex<SignalDeduplicator>,
+    suppressed: AtomicU64,
+}
+
+impl DedupPublisher {
+    pub fn new(inner: Publisher, window_ns: u64) -> Self {
+        Self {
+            inner,
+            dedup: Mutex::new(SignalDeduplicator::new(window_ns)),
+            suppressed: AtomicU64::new(0),
+        }
+    }
+
+    /// Publish `signal` unless it duplicates a recent one
+    ///
+    /// Returns `Ok(false)` for a suppressed duplicate.
+    pub async fn publish_signal(&self, signal: SignalEvent) -> Result<bool> {
+        let unique = {
+            let mut dedup = self.dedup.lock().unwrap();
+            if dedup.cache.len() >= PRUNE_THRESHOLD {
+                dedup.prune(signal.timestamp);
+            }
+            dedup.check_and_record(&signal, signal.timestamp)
+        };
+        if !unique {
+            self.suppressed.fetch_add(1, Ordering::Relaxed);
+            return Ok(false);
+        }
+        self.inner.publish_signal(signal).await?;
+        Ok(true)
+    }
+
+    /// Number of signals dropped as duplicates
+    pub fn suppressed_count(&self) -> u64 {
+        self.suppressed.load(Ordering::Relaxed)
+    }
+
+    /// Wrapped publisher, for events other than signals
+    pub fn inner(&self) -> &Publisher {
+        &self.inner
+    }
+}
+
+/// Counters reported by `BulkPublisher::dedup_stats`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DedupStats {
+    /// Events passed to `publish_bulk`
+    pub total_seen: u64,
+    /// Events dropped as duplicates
+    pub total_deduplicated: u64,
+    /// Keys currently remembered
+    pub cache_size: usize,
+}
+
+/// `Publisher` for batches that drops events whose key was seen within
+/// `window_ns`
+///
+/// Times come from the bus clock, so every event in one batch shares a
+/// timestamp and repeats inside a batch are duplicates too.
+pub struct BulkPublisher<K: Hash + Eq> {
+    inner: Publisher,
+    /// Bus time each key was last published
+    dedup_cache: HashMap<K, i64>,
+    window_ns: u64,
+    total_seen: u64,
+    total_deduplicated: u64,
+}
+
+impl<K: Hash + Eq> BulkPublisher<K> {
+    pub fn new(inner: Publisher, window_ns: u64) -> Self {
+        Self {
+            inner,
+            dedup_cache: HashMap::new(),
+            window_ns,
+            total_seen: 0,
+            total_deduplicated: 0,
+        }
+    }
+
+    /// Publish the events whose key is not in the cache, in order
+    ///
+    /// Expired keys are evicted first. Keys and counters are recorded only
+    /// once the batch publishes, so a failed batch can be retried as is.
+    /// Returns `(published, deduplicated)`.
+    pub async fn publish_bulk<T: Event + Send + Clone + 'static>(
+        &mut self,
+        events: Vec<T>,
+        key_fn: impl Fn(&T) -> K,
+    ) -> Result<(usize, usize)> {
+        let now_ns = self.inner.now_ns();
+        let window_ns = self.window_ns as i64;
+        self.dedup_cache.retain(|_, last_ns| now_ns.saturating_sub(*last_ns) < window_ns);
+
+        let total = events.len();
+        let mut fresh = HashSet::new();
+        let unique: Vec<T> = events
+            .into_iter()
+            .filter(|event| {
+                let key = key_fn(event);
+                !self.dedup_cache.contains_key(&key) && fresh.insert(key)
+            })
+            .collect();
+        let deduplicated = total - unique.len();
+
+        self.inner.publish_batch(&unique).await?;
+        self.dedup_cache.extend(fresh.into_iter().map(|key| (key, now_ns)));
+        self.total_seen += total as u64;
+        self.total_deduplicated += deduplicated as u64;
+        Ok((unique.len(), deduplicated))
+    }
+
+    /// Totals since creation and the current cache size
+    pub fn dedup_stats(&self) -> DedupStats {
+        DedupStats {
+            total_seen: self.total_seen,
+            total_deduplicated: self.total_deduplicated,
+            cache_size: self.dedup_cache.len(),
+        }
+    }
+
+    /// Forget every key; counters are kept
+    pub fn clear_cache(&mut self) {
+        self.dedup_cache.clear();
+    }
+
+    /// Wrapped publisher, for events published without deduplication
+    pub fn inner(&self) -> &Publisher {
+        &self.inner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus::EventBus;
+    use crate::events::{FillEvent, OrderSide};
+    use crate::replay_mode::SharedClock;
+    use std::sync::Arc;
+    use uuid::Uuid;
+
+    const WINDOW_NS: u64 = 5_000_000;
+
+    fn signal(strategy_id: &str, symbol: &str, direction: SignalDirection, timestamp: i64) -> SignalEvent {
+        SignalEvent {
+            signal_id: Uuid::new_v4(),
+            timestamp,
+            strategy_id: strategy_id.to_string(),
+            symbol: symbol.to_string(),
+            direction,
+            strength: 0.5,
+            target_price: None,
+            stop_loss: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_window_boundaries() {
+        let mut dedup = SignalDeduplicator::new(WINDOW_NS);
+        let long = |ts| signal("momentum", "ES", SignalDirection::Long, ts);
+
+        assert!(dedup.check_and_record(&long(1_000), 1_000));
+        assert!(!dedup.check_and_record(&long(1_000), 1_000));
+        // One nanosecond inside the window
+        assert!(!dedup.check_and_record(&long(0), 1_000 + WINDOW_NS as i64 - 1));
+        // Exactly at the boundary the window has passed
+        assert!(dedup.check_and_record(&long(0), 1_000 + WINDOW_NS as i64));
+
+        // Duplicates do not extend the window
+        let start = 100_000_000;
+        assert!(dedup.check_and_record(&long(0), start));
+        assert!(!dedup.check_and_record(&long(0), start + 4_000_000));
+        assert!(dedup.check_and_record(&long(0), start + WINDOW_NS as i64));
+    }
+
+    #[test]
+    fn test_key_fields() {
+        let mut dedup = SignalDeduplicator::new(WINDOW_NS);
+        assert!(dedup.check_and_record(&signal("momentum", "ES", SignalDirection::Long, 0), 0));
+        assert!(dedup.check_and_record(&signal("momentum", "ES", SignalDirection::Short, 0), 0));
+        assert!(dedup.check_and_record(&signal("momentum", "NQ", SignalDirection::Long, 0), 0));
+        assert!(dedup.check_and_record(&signal("mean_rev", "ES", SignalDirection::Long, 0), 0));
+        assert!(!dedup.check_and_record(&signal("momentum", "ES", SignalDirection::Long, 0), 0));
+
+        dedup.prune(WINDOW_NS as i64);
+        assert!(dedup.cache.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_dedup_publisher() {
+        let bus = Arc::new(EventBus::new());
+        let publisher = Publisher::new(bus.clone()).with_signal_deduplication(WINDOW_NS);
+        let mut rx = bus.subscribe_signals().await;
+
+        for ts in [0, 1_000_000, 4_999_999, 5_000_000, 9_999_999, 10_000_000] {
+            publisher.publish_signal(signal("momentum", "ES", SignalDirection::Long, ts)).await.unwrap();
+        }
+        assert_eq!(publisher.suppressed_count(), 3);
+
+        let mut published = Vec::new();
+        while let Ok(envelope) = rx.try_recv() {
+            published.push(envelope.event.downcast_ref::<SignalEvent>().unwrap().timestamp);
+        }
+        assert_eq!(published, vec![0, 5_000_000, 10_000_000]);
+    }
+
+    fn fill(id: u128) -> FillEvent {
+        FillEvent {
+            fill_id: Uuid::from_u128(id),
+            order_id: Uuid::from_u128(id),
+            signal_id: None,
+            timestamp: 0,
+            symbol: "ES".to_string(),
+            side: OrderSide::Buy,
+            filled_quantity: 1.0,
+            fill_price: 6000.0,
+            commission: 0.0,
+            slippage_bps: 0.0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bulk_publisher() {
+        let clock = SharedClock::new();
+        let bus = Arc::new(EventBus::with_shared_clock(clock.clone()));
+        let mut bulk = BulkPublisher::new(Publisher::new(bus.clone()), WINDOW_NS);
+        let mut rx = bus.subscribe_fills().await;
+
+        // Each group of ten repeats its first three fills: 700 distinct
+        // fills and 300 duplicates
+        let events: Vec<FillEvent> = (0..1000u128)
+            .map(|i| if i % 10 < 3 { fill(i / 10 * 7 + i % 10) } else { fill(i / 10 * 7 + i % 10 - 3) })
+            .collect();
+        assert_eq!(bulk.publish_bulk(events, |f| f.fill_id).await.unwrap(), (700, 300));
+        assert_eq!(
+            bulk.dedup_stats(),
+            DedupStats { total_seen: 1000, total_deduplicated: 300, cache_size: 700 }
+        );
+
+        let mut received = HashSet::new();
+        while let Ok(envelope) = rx.try_recv() {
+            assert!(received.insert(envelope.event.downcast_ref::<FillEvent>().unwrap().fill_id));
+        }
+        assert_eq!(received.len(), 700);
+
+        // Still inside the window: already-seen keys are dropped
+        clock.advance_to(WINDOW_NS as i64 - 1);
+        assert_eq!(bulk.publish_bulk(vec![fill(0), fill(5_000)], |f| f.fill_id).await.unwrap(), (1, 1));
+
+        // Once the window passes, stale keys are evicted before the batch
+        clock.advance_to(WINDOW_NS as i64);
+        assert_eq!(bulk.publish_bulk(vec![fill(0)], |f| f.fill_id).await.unwrap(), (1, 0));
+        assert_eq!(bulk.dedup_stats().cache_size, 2);
+
+        bulk.clear_cache();
+        assert_eq!(
+            bulk.dedup_stats(),
+            DedupStats { total_seen: 1003, total_deduplicated: 301, cache_size: 0 }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bulk_publisher_failed_batch_not_recorded() {
+        // Every fill exceeds the size budget, so the batch fails
+        let bus = Arc::new(EventBus::with_max_event_size(1));
+        let mut bulk = BulkPublisher::new(Publisher::new(bus), WINDOW_NS);
+
+        assert!(bulk.publish_bulk(vec![fill(0), fill(0), fill(1)], |f| f.fill_id).await.is_err());
+        assert_eq!(bulk.dedup_stats(), DedupStats::default());
+    }
+}
@@ -0,0 +1,214 @@
+//! Append-only, memory-mapped event log backing `EventRecorder::with_file`
+//!
+//! ## Record format
+//!
+//! Each record is a 16-byte header followed by a bincode-encoded body:
+//!
+//! | bytes  | field                         |
+//! |--------|-------------------------------|
+//! | 0..8   | body length (u64, LE)         |
+//! | 8..12  | CRC32 of body (u32, LE)       |
+//! | 12..16 | record magic (u32, LE)        |
+//!
+//! The file is pre-allocated and grown in chunks, so the tail is zero-filled.
+//! A zero header marks the end of the log.
+
+use crate::events::{EventEnvelope, RawEvent};
+use anyhow::{Context, Result};
+use memmap2::{Mmap, MmapMut};
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::warn;
+use uuid::Uuid;
+
+/// Size of the per-record header
+pub const HEADER_LEN: usize = 16;
+
+/// Marks the start of a valid record header ("HFTE")
+const RECORD_MAGIC: u32 = 0x4846_5445;
+
+/// Initial file size and minimum growth step
+const GROWTH_BYTES: u64 = 1 << 20;
+
+/// On-disk representation of an envelope
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredEnvelope {
+    id: u128,
+    timestamp_ns: i64,
+    priority: u8,
+    event_type: String,
+    /// JSON-encoded event payload
+    payload: Vec<u8>,
+}
+
+impl StoredEnvelope {
+    fn from_envelope(envelope: &EventEnvelope) -> Self {
+        let payload = envelope.event.to_json().unwrap_or(serde_json::Value::Null);
+        Self {
+            id: envelope.id.as_u128(),
+            timestamp_ns: envelope.timestamp_ns,
+            priority: envelope.priority,
+            event_type: envelope.event_type_name().to_string(),
+            payload: serde_json::to_vec(&payload).unwrap_or_default(),
+        }
+    }
+    
+    fn into_envelope(self) -> Result<EventEnvelope> {
+        let payload = serde_json::from_slice(&self.payload)?;
+        Ok(EventEnvelope {
+            id: Uuid::from_u128(self.id),
+            timestamp_ns: self.timestamp_ns,
+            priority: self.priority,
+            causation_id: None,
+            correlation_id: None,
+            transaction_id: None,
+            sequence_number: None,
+            event: Arc::new(RawEvent::new(&self.event_type, self.priority, payload)),
+        })
+    }
+}
+
+/// Memory-mapped append-only event log
+pub struct EventLog {
+    path: PathBuf,
+    file: File,
+    mmap: MmapMut,
+    /// Byte offset where the next record is written
+    offset: usize,
+}
+
+impl EventLog {
+    /// Open (or create) a log, returning it with the valid records it already holds
+    pub fn open(path: impl AsRef<Path>) -> Result<(Self, Vec<EventEnvelope>)> {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .with_context(|| format!("failed to open event log {}", path.display()))?;
+        
+        if file.metadata()?.len() < GROWTH_BYTES {
+            file.set_len(GROWTH_BYTES)?;
+        }
+        
+        // SAFETY: the file is owned by this log for its lifetime; external
+        // modification while mapped is not supported
+        let mmap = unsafe { MmapMut::map_mut(&file)? };
+        let (events, offset) = scan(&mmap, &path);
+        
+        Ok((Self { path, file, mmap, offset }, events))
+    }
+    
+    /// Read and validate all records in a log file
+    ///
+    /// Records with a bad checksum or body are skipped with a warning; a
+    /// truncated trailing record ends the scan.
+    pub fn load(path: impl AsRef<Path>) -> Result<Vec<EventEnvelope>> {
+        let path = path.as_ref();
+        let file = File::open(path)
+            .with_context(|| format!("failed to open event log {}", path.display()))?;
+        if file.metadata()?.len() == 0 {
+            return Ok(Vec::new());
+        }
+        
+        // SAFETY: read-only mapping; see `open`
+        let mmap = unsafe { Mmap::map(&file)? };
+        Ok(scan(&mmap, path).0)
+    }
+    
+    /// Append an envelope at the current offset
+    pub fn append(&mut self, envelope: &EventEnvelope) -> Result<()> {
+        let body = bincode::serialize(&StoredEnvelope::from_envelope(envelope))?;
+        let record_len = HEADER_LEN + body.len();
+        
+        if self.offset + record_len > self.mmap.len() {
+            self.grow(self.offset + record_len)?;
+        }
+        
+        let record = &mut self.mmap[self.offset..self.offset + record_len];
+        record[0..8].copy_from_slice(&(body.len() as u64).to_le_bytes());
+        record[8..12].copy_from_slice(&crc32fast::hash(&body).to_le_bytes());
+        record[12..16].copy_from_slice(&RECORD_MAGIC.to_le_bytes());
+        record[HEADER_LEN..].copy_from_slice(&body);
+        
+        self.offset += record_len;
+        Ok(())
+    }
+    
+    /// Flush written records to disk (msync)
+    pub fn flush(&self) -> Result<()> {
+        self.mmap.flush()?;
+        Ok(())
+    }
+    
+    /// Bytes of valid records in the log
+    pub fn len_bytes(&self) -> usize {
+        self.offset
+    }
+    
+    /// Log file path
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+    
+    /// Grow the file and remap so at least `min_len` bytes are available
+    fn grow(&mut self, min_len: usize) -> Result<()> {
+        self.mmap.flush()?;
+        let new_len = (self.mmap.len() as u64 * 2).max(min_len as u64 + GROWTH_BYTES);
+        self.file.set_len(new_len)?;
+        
+        // SAFETY: see `open`
+        self.mmap = unsafe { MmapMut::map_mut(&self.file)? };
+        Ok(())
+    }
+}
+
+/// Decode records from the start of `bytes`, returning them and the end offset
+fn scan(bytes: &[u8], path: &Path) -> (Vec<EventEnvelope>, usize) {
+    let mut events = Vec::new();
+    let mut offset = 0;
+    
+    while offset + HEADER_LEN <= bytes.len() {
+        let header = &bytes[offset..offset + HEADER_LEN];
+        let body_len = u64::from_le_bytes(header[0..8].try_into().unwrap()) as usize;
+        let crc = u32::from_le_bytes(header[8..12].try_into().unwrap());
+        let magic = u32::from_le_bytes(header[12..16].try_into().unwrap());
+        
+        if magic != RECORD_MAGIC {
+            if header.iter().any(|&b| b != 0) {
+                warn!("Invalid record header at offset {} in {}, stopping", offset, path.display());
+            }
+            break;
+        }
+        
+        let body_start = offset + HEADER_LEN;
+        if body_len > bytes.len() - body_start {
+            warn!("Truncated record at offset {} in {}, skipping", offset, path.display());
+            break;
+        }
+        
+        let body = &bytes[body_start..body_start + body_len];
+        let next = body_start + body_len;
+        
+        if crc32fast::hash(body) != crc {
+            warn!("Checksum mismatch at offset {} in {}, skipping record", offset, path.display());
+            offset = next;
+            continue;
+        }
+        
+        let decoded = bincode::deserialize::<StoredEnvelope>(body)
+            .map_err(anyhow::Error::from)
+            .and_then(StoredEnvelope::into_envelope);
+        match decoded {
+            Ok(envelope) => events.push(envelope),
+            Err(e) => warn!("Corrupt record at offset {} in {}: {}", offset, path.display(), e),
+        }
+        offset = next;
+    }
+    
+    (events, offset)
+}
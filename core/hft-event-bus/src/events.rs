@@ -1,10 +1,18 @@
 //! Event type definitions for the HFT system
 
 use serde::{Deserialize, Serialize};
+use std::any::Any;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use tracing::warn;
 use uuid::Uuid;
 
 /// Base event wrapper with metadata
-#[derive(Debug)]
+///
+/// Cloning is cheap: the payload is shared between clones, so every
+/// subscriber of a broadcast channel sees the original event.
+#[derive(Debug, Clone)]
 pub struct EventEnvelope {
     /// Unique event ID
     pub id: Uuid,
@@ -15,48 +23,123 @@ pub struct EventEnvelope {
     /// Event priority (0 = highest)
     pub priority: u8,
     
+    /// ID of the envelope that directly caused this one
+    pub causation_id: Option<Uuid>,
+    
+    /// ID shared by every envelope in one causal chain
+    pub correlation_id: Option<Uuid>,
+    
+    /// ID of the `EventBus::publish_transaction` call that published this
+    /// envelope
+    pub transaction_id: Option<Uuid>,
+    
+    /// Position in its channel, set when the channel is sequenced
+    /// (`EventBus::enable_sequencing`)
+    pub sequence_number: Option<u64>,
+    
     /// Event payload
-    pub event: Box<dyn Event>,
+    pub event: Arc<dyn Event>,
+}
+
+/// Event reconstructed from a serialized payload
+///
+/// Used when an envelope is loaded from storage and the concrete event type
+/// is not available. The JSON payload is kept as-is.
+///
+/// Once the intern table is full, new names are routed as
+/// [`OVERFLOW_EVENT_TYPE`]; [`name`](Self::name) still returns the original.
+#[derive(Debug, Clone)]
+pub struct RawEvent {
+    event_type: &'static str,
+    /// Original name when it could not be interned
+    overflow_name: Option<Box<str>>,
+    priority: u8,
+    payload: serde_json::Value,
 }
 
-impl Clone for EventEnvelope {
-    fn clone(&self) -> Self {
+impl RawEvent {
+    pub fn new(event_type: &str, priority: u8, payload: serde_json::Value) -> Self {
+        let (interned, overflow_name) = match intern_event_type(event_type) {
+            Some(interned) => (interned, None),
+            None => (OVERFLOW_EVENT_TYPE, Some(event_type.into())),
+        };
         Self {
-            id: self.id,
-            timestamp_ns: self.timestamp_ns,
-            priority: self.priority,
-            // For replay purposes, we create a new envelope with the same event data
-            // Note: This is a shallow clone - the actual event data is shared via reference
-            event: Box::new(ReplayEventWrapper {
-                event_type: self.event.event_type().to_string(),
-                priority: self.event.priority(),
-            }),
+            event_type: interned,
+            overflow_name,
+            priority,
+            payload,
         }
     }
+    
+    /// Event type name as given to `new`
+    pub fn name(&self) -> &str {
+        self.overflow_name.as_deref().unwrap_or(self.event_type)
+    }
+    
+    /// Serialized event payload
+    pub fn payload(&self) -> &serde_json::Value {
+        &self.payload
+    }
 }
 
-// Wrapper for replayed events that stores just the essential metadata
-#[derive(Debug, Clone)]
-struct ReplayEventWrapper {
-    event_type: String,
-    priority: u8,
-}
-
-impl Event for ReplayEventWrapper {
+impl Event for RawEvent {
     fn event_type(&self) -> &'static str {
-        // Use string interning or leak the string for static lifetime
-        // This is safe for replay purposes where the event type strings are constant
-        Box::leak(self.event_type.clone().into_boxed_str())
+        self.event_type
     }
     
     fn priority(&self) -> u8 {
         self.priority
     }
+    
+    fn to_json(&self) -> Option<serde_json::Value> {
+        Some(self.payload.clone())
+    }
+}
+
+/// Most distinct names `intern_event_type` will leak
+pub const MAX_INTERNED_EVENT_TYPES: usize = 1024;
+
+/// Event type of a `RawEvent` whose name did not fit in the intern table
+pub const OVERFLOW_EVENT_TYPE: &str = "raw_overflow";
+
+/// Get a `'static` copy of an event type name
+///
+/// Each distinct name is leaked once. Names arrive from bridges and stored
+/// logs, so the table is capped at [`MAX_INTERNED_EVENT_TYPES`]; once it is
+/// full, names not already in it return `None`.
+pub fn intern_event_type(name: &str) -> Option<&'static str> {
+    static NAMES: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+    static WARNED: AtomicBool = AtomicBool::new(false);
+    
+    let mut names = NAMES.get_or_init(|| Mutex::new(HashSet::new())).lock().unwrap();
+    if let Some(interned) = names.get(name) {
+        return Some(interned);
+    }
+    if names.len() >= MAX_INTERNED_EVENT_TYPES {
+        if !WARNED.swap(true, Ordering::Relaxed) {
+            warn!("Event type table full ({} names); routing {} and later new names as {}",
+                  MAX_INTERNED_EVENT_TYPES, name, OVERFLOW_EVENT_TYPE);
+        }
+        return None;
+    }
+    let interned: &'static str = Box::leak(name.to_string().into_boxed_str());
+    names.insert(interned);
+    Some(interned)
 }
 
 impl EventEnvelope {
     pub fn new<T: Event + 'static>(event: T, priority: u8) -> Self {
-        use std::sync::atomic::{AtomicU64, Ordering};
+        // Allocate the shared payload directly instead of boxing then copying
+        Self::from_arc(Arc::new(event), priority)
+    }
+    
+    /// Wrap an already boxed event (used when the concrete type is erased)
+    pub fn from_boxed(event: Box<dyn Event>, priority: u8) -> Self {
+        Self::from_arc(Arc::from(event), priority)
+    }
+    
+    fn from_arc(event: Arc<dyn Event>, priority: u8) -> Self {
+        use std::sync::atomic::AtomicU64;
         static COUNTER: AtomicU64 = AtomicU64::new(1);
         
         // Use monotonic counter instead of Uuid::new_v4() (avoids OS RNG syscall)
@@ -68,9 +151,34 @@ impl EventEnvelope {
             id,
             timestamp_ns: chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0),
             priority,
-            event: Box::new(event),
+            causation_id: None,
+            correlation_id: None,
+            transaction_id: None,
+            sequence_number: None,
+            event,
         }
     }
+    
+    /// Name of the event's type for serialization
+    ///
+    /// Same as `event.event_type()`, except for a `RawEvent` routed as
+    /// [`OVERFLOW_EVENT_TYPE`], which keeps its original name.
+    pub fn event_type_name(&self) -> &str {
+        match self.event.downcast_ref::<RawEvent>() {
+            Some(raw) => raw.name(),
+            None => self.event.event_type(),
+        }
+    }
+    
+    /// Mark this envelope as caused by `parent`
+    ///
+    /// Inherits the parent's correlation ID; a parent without one starts a
+    /// new chain keyed by its own ID.
+    pub fn caused_by(mut self, parent: &EventEnvelope) -> Self {
+        self.causation_id = Some(parent.id);
+        self.correlation_id = Some(parent.correlation_id.unwrap_or(parent.id));
+        self
+    }
 }
 
 // ============================================================================
@@ -104,6 +212,176 @@ pub struct AggregatedDataEvent {
     pub vwap: f64,
 }
 
+impl MarketDataEvent {
+    /// Midpoint of the best bid and ask
+    ///
+    /// ```
+    /// # use hft_event_bus::MarketDataEvent;
+    /// # let quote = MarketDataEvent {
+    /// #     timestamp: 0, symbol: "ES".to_string(), price: 100.0, volume: 1.0,
+    /// #     bid_price: 99.0, bid_size: 30.0, ask_price: 101.0, ask_size: 10.0,
+    /// # };
+    /// assert_eq!(quote.mid_price(), 100.0);
+    /// ```
+    #[inline(always)]
+    pub fn mid_price(&self) -> f64 {
+        (self.bid_price + self.ask_price) / 2.0
+    }
+    
+    /// Ask minus bid (negative when the quote is crossed)
+    ///
+    /// ```
+    /// # use hft_event_bus::MarketDataEvent;
+    /// # let quote = MarketDataEvent {
+    /// #     timestamp: 0, symbol: "ES".to_string(), price: 100.0, volume: 1.0,
+    /// #     bid_price: 99.0, bid_size: 30.0, ask_price: 101.0, ask_size: 10.0,
+    /// # };
+    /// assert_eq!(quote.spread(), 2.0);
+    /// ```
+    #[inline(always)]
+    pub fn spread(&self) -> f64 {
+        self.ask_price - self.bid_price
+    }
+    
+    /// Top-of-book size imbalance in [-1, 1], positive when bids outweigh asks
+    ///
+    /// 0.0 when both sizes are zero.
+    ///
+    /// ```
+    /// # use hft_event_bus::MarketDataEvent;
+    /// # let quote = MarketDataEvent {
+    /// #     timestamp: 0, symbol: "ES".to_string(), price: 100.0, volume: 1.0,
+    /// #     bid_price: 99.0, bid_size: 30.0, ask_price: 101.0, ask_size: 10.0,
+    /// # };
+    /// assert_eq!(quote.bid_ask_imbalance(), 0.5);
+    /// ```
+    #[inline(always)]
+    pub fn bid_ask_imbalance(&self) -> f64 {
+        let total = self.bid_size + self.ask_size;
+        if total == 0.0 {
+            return 0.0;
+        }
+        (self.bid_size - self.ask_size) / total
+    }
+    
+    /// Bid at or above the ask
+    ///
+    /// ```
+    /// # use hft_event_bus::MarketDataEvent;
+    /// # let quote = MarketDataEvent {
+    /// #     timestamp: 0, symbol: "ES".to_string(), price: 100.0, volume: 1.0,
+    /// #     bid_price: 99.0, bid_size: 30.0, ask_price: 101.0, ask_size: 10.0,
+    /// # };
+    /// assert!(!quote.is_crossed());
+    ///
+    /// let locked = MarketDataEvent { bid_price: 101.0, ..quote };
+    /// assert!(locked.is_crossed());
+    /// ```
+    #[inline(always)]
+    pub fn is_crossed(&self) -> bool {
+        self.bid_price >= self.ask_price
+    }
+    
+    /// Value resting at the best bid
+    ///
+    /// ```
+    /// # use hft_event_bus::MarketDataEvent;
+    /// # let quote = MarketDataEvent {
+    /// #     timestamp: 0, symbol: "ES".to_string(), price: 100.0, volume: 1.0,
+    /// #     bid_price: 99.0, bid_size: 30.0, ask_price: 101.0, ask_size: 10.0,
+    /// # };
+    /// assert_eq!(quote.notional_bid(), 2970.0);
+    /// ```
+    #[inline(always)]
+    pub fn notional_bid(&self) -> f64 {
+        self.bid_price * self.bid_size
+    }
+    
+    /// Value resting at the best ask
+    ///
+    /// ```
+    /// # use hft_event_bus::MarketDataEvent;
+    /// # let quote = MarketDataEvent {
+    /// #     timestamp: 0, symbol: "ES".to_string(), price: 100.0, volume: 1.0,
+    /// #     bid_price: 99.0, bid_size: 30.0, ask_price: 101.0, ask_size: 10.0,
+    /// # };
+    /// assert_eq!(quote.notional_ask(), 1010.0);
+    /// ```
+    #[inline(always)]
+    pub fn notional_ask(&self) -> f64 {
+        self.ask_price * self.ask_size
+    }
+    
+    /// Mid shifted toward the thinner side by `beta` times the size imbalance
+    ///
+    /// `mid + beta * imbalance * spread / 2`: `beta = 0` is the plain mid
+    /// and `beta = 1` the size-weighted micro-price,
+    /// `(bid * ask_size + ask * bid_size) / (bid_size + ask_size)`.
+    ///
+    /// ```
+    /// # use hft_event_bus::MarketDataEvent;
+    /// # let quote = MarketDataEvent {
+    /// #     timestamp: 0, symbol: "ES".to_string(), price: 100.0, volume: 1.0,
+    /// #     bid_price: 99.0, bid_size: 30.0, ask_price: 101.0, ask_size: 10.0,
+    /// # };
+    /// assert_eq!(quote.weighted_mid(0.0), 100.0);
+    /// assert_eq!(quote.weighted_mid(1.0), (99.0 * 10.0 + 101.0 * 30.0) / 40.0);
+    /// ```
+    #[inline(always)]
+    pub fn weighted_mid(&self, beta: f64) -> f64 {
+        self.mid_price() + beta * self.bid_ask_imbalance() * self.spread() / 2.0
+    }
+}
+
+impl AggregatedDataEvent {
+    /// High minus low
+    ///
+    /// ```
+    /// # use hft_event_bus::AggregatedDataEvent;
+    /// # let bar = AggregatedDataEvent {
+    /// #     timestamp: 0, symbol: "ES".to_string(), timeframe: "1m".to_string(),
+    /// #     open: 100.0, high: 104.0, low: 98.0, close: 103.0, volume: 50.0, vwap: 101.5,
+    /// # };
+    /// assert_eq!(bar.range(), 6.0);
+    /// ```
+    #[inline(always)]
+    pub fn range(&self) -> f64 {
+        self.high - self.low
+    }
+    
+    /// Closed above the open
+    ///
+    /// ```
+    /// # use hft_event_bus::AggregatedDataEvent;
+    /// # let bar = AggregatedDataEvent {
+    /// #     timestamp: 0, symbol: "ES".to_string(), timeframe: "1m".to_string(),
+    /// #     open: 100.0, high: 104.0, low: 98.0, close: 103.0, volume: 50.0, vwap: 101.5,
+    /// # };
+    /// assert!(bar.is_bullish());
+    /// assert!(!AggregatedDataEvent { close: 100.0, ..bar }.is_bullish());
+    /// ```
+    #[inline(always)]
+    pub fn is_bullish(&self) -> bool {
+        self.close > self.open
+    }
+    
+    /// Distance between open and close, regardless of direction
+    ///
+    /// ```
+    /// # use hft_event_bus::AggregatedDataEvent;
+    /// # let bar = AggregatedDataEvent {
+    /// #     timestamp: 0, symbol: "ES".to_string(), timeframe: "1m".to_string(),
+    /// #     open: 100.0, high: 104.0, low: 98.0, close: 103.0, volume: 50.0, vwap: 101.5,
+    /// # };
+    /// assert_eq!(bar.body_size(), 3.0);
+    /// assert_eq!(AggregatedDataEvent { close: 97.0, ..bar }.body_size(), 3.0);
+    /// ```
+    #[inline(always)]
+    pub fn body_size(&self) -> f64 {
+        (self.close - self.open).abs()
+    }
+}
+
 /// Order book snapshot or update
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OrderBookEvent {
@@ -113,6 +391,22 @@ pub struct OrderBookEvent {
     pub asks: Vec<(f64, f64)>,
 }
 
+/// One changed order book level
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PriceLevel {
+    pub price: f64,
+    pub new_size: f64,  // 0.0 removes the level
+}
+
+/// Incremental order book update (only the levels that changed)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderBookDeltaEvent {
+    pub timestamp: i64,
+    pub symbol: String,
+    pub bid_changes: Vec<PriceLevel>,
+    pub ask_changes: Vec<PriceLevel>,
+}
+
 /// Computed features for strategies
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FeatureEvent {
@@ -159,6 +453,204 @@ pub enum MarketRegime {
     RangeBound,
 }
 
+/// Field of a `QuantumFeatureEvent` that breaks an invariant
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{field}: {message}")]
+pub struct FieldValidationError {
+    pub field: &'static str,
+    pub message: String,
+}
+
+impl FieldValidationError {
+    fn new(field: &'static str, message: impl Into<String>) -> Self {
+        Self { field, message: message.into() }
+    }
+}
+
+impl QuantumFeatureEvent {
+    pub fn builder(symbol: impl Into<String>, timestamp: i64) -> QuantumFeatureEventBuilder {
+        QuantumFeatureEventBuilder::new(symbol, timestamp)
+    }
+    
+    /// Check probability and strength invariants, reporting every violation
+    ///
+    /// - `regime_confidence` and `chi_squared_p_value` lie in [0, 1]
+    /// - `wall_strengths` has one non-negative entry per liquidity wall
+    /// - `kl_divergence` is non-negative
+    ///
+    /// NaN fails every check.
+    pub fn validate(&self) -> Result<(), Vec<FieldValidationError>> {
+        let mut errors = Vec::new();
+        if !(0.0..=1.0).contains(&self.regime_confidence) {
+            errors.push(FieldValidationError::new(
+                "regime_confidence",
+                format!("{} is outside [0, 1]", self.regime_confidence),
+            ));
+        }
+        if self.liquidity_walls.len() != self.wall_strengths.len() {
+            errors.push(FieldValidationError::new(
+                "wall_strengths",
+                format!("{} strengths for {} walls", self.wall_strengths.len(), self.liquidity_walls.len()),
+            ));
+        }
+        for (i, strength) in self.wall_strengths.iter().enumerate() {
+            if strength.is_nan() || *strength < 0.0 {
+                errors.push(FieldValidationError::new(
+                    "wall_strengths",
+                    format!("strength {} at index {} is not >= 0", strength, i),
+                ));
+            }
+        }
+        if let Some(p_value) = self.chi_squared_p_value {
+            if !(0.0..=1.0).contains(&p_value) {
+                errors.push(FieldValidationError::new(
+                    "chi_squared_p_value",
+                    format!("{} is outside [0, 1]", p_value),
+                ));
+            }
+        }
+        if let Some(divergence) = self.kl_divergence {
+            if divergence.is_nan() || divergence < 0.0 {
+                errors.push(FieldValidationError::new("kl_divergence", format!("{} is not >= 0", divergence)));
+            }
+        }
+        
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Builds a `QuantumFeatureEvent`, validating it on `build`
+///
+/// Metrics default to 0 and validation metrics to `None`; the regime must
+/// be set.
+#[derive(Debug, Clone)]
+pub struct QuantumFeatureEventBuilder {
+    timestamp: i64,
+    symbol: String,
+    momentum: f64,
+    variance: f64,
+    skewness: f64,
+    kurtosis: f64,
+    regime: Option<(MarketRegime, f64)>,
+    liquidity_walls: Vec<f64>,
+    wall_strengths: Vec<f64>,
+    momentum_uncertainty: f64,
+    price_std_dev: f64,
+    validation_passed: Option<bool>,
+    chi_squared_p_value: Option<f64>,
+    kl_divergence: Option<f64>,
+}
+
+impl QuantumFeatureEventBuilder {
+    pub fn new(symbol: impl Into<String>, timestamp: i64) -> Self {
+        Self {
+            timestamp,
+            symbol: symbol.into(),
+            momentum: 0.0,
+            variance: 0.0,
+            skewness: 0.0,
+            kurtosis: 0.0,
+            regime: None,
+            liquidity_walls: Vec::new(),
+            wall_strengths: Vec::new(),
+            momentum_uncertainty: 0.0,
+            price_std_dev: 0.0,
+            validation_passed: None,
+            chi_squared_p_value: None,
+            kl_divergence: None,
+        }
+    }
+    
+    /// Set momentum, variance, skewness and kurtosis
+    pub fn moments(mut self, momentum: f64, variance: f64, skewness: f64, kurtosis: f64) -> Self {
+        self.momentum = momentum;
+        self.variance = variance;
+        self.skewness = skewness;
+        self.kurtosis = kurtosis;
+        self
+    }
+    
+    pub fn regime(mut self, regime: MarketRegime, confidence: f64) -> Self {
+        self.regime = Some((regime, confidence));
+        self
+    }
+    
+    /// Add a liquidity wall at `price` with curvature `strength`
+    pub fn liquidity_wall(mut self, price: f64, strength: f64) -> Self {
+        self.liquidity_walls.push(price);
+        self.wall_strengths.push(strength);
+        self
+    }
+    
+    /// Replace all walls; `prices` and `strengths` must have equal lengths
+    pub fn liquidity_walls(mut self, prices: Vec<f64>, strengths: Vec<f64>) -> Self {
+        self.liquidity_walls = prices;
+        self.wall_strengths = strengths;
+        self
+    }
+    
+    pub fn risk(mut self, momentum_uncertainty: f64, price_std_dev: f64) -> Self {
+        self.momentum_uncertainty = momentum_uncertainty;
+        self.price_std_dev = price_std_dev;
+        self
+    }
+    
+    pub fn validation_passed(mut self, passed: bool) -> Self {
+        self.validation_passed = Some(passed);
+        self
+    }
+    
+    pub fn chi_squared_p_value(mut self, p_value: f64) -> Self {
+        self.chi_squared_p_value = Some(p_value);
+        self
+    }
+    
+    pub fn kl_divergence(mut self, divergence: f64) -> Self {
+        self.kl_divergence = Some(divergence);
+        self
+    }
+    
+    /// Build the event, or return every violated invariant
+    pub fn build(self) -> Result<QuantumFeatureEvent, Vec<FieldValidationError>> {
+        let (regime, regime_confidence) = match self.regime {
+            Some(regime) => regime,
+            // Placeholder so the remaining fields are still checked
+            None => (MarketRegime::Consolidation, 0.0),
+        };
+        let event = QuantumFeatureEvent {
+            timestamp: self.timestamp,
+            symbol: self.symbol,
+            momentum: self.momentum,
+            variance: self.variance,
+            skewness: self.skewness,
+            kurtosis: self.kurtosis,
+            regime,
+            regime_confidence,
+            liquidity_walls: self.liquidity_walls,
+            wall_strengths: self.wall_strengths,
+            momentum_uncertainty: self.momentum_uncertainty,
+            price_std_dev: self.price_std_dev,
+            validation_passed: self.validation_passed,
+            chi_squared_p_value: self.chi_squared_p_value,
+            kl_divergence: self.kl_divergence,
+        };
+        
+        let mut errors = event.validate().err().unwrap_or_default();
+        if self.regime.is_none() {
+            errors.insert(0, FieldValidationError::new("regime", "not set"));
+        }
+        if errors.is_empty() {
+            Ok(event)
+        } else {
+            Err(errors)
+        }
+    }
+}
+
 // ============================================================================
 // Strategy & Signal Events
 // ============================================================================
@@ -177,7 +669,7 @@ pub struct SignalEvent {
     pub metadata: std::collections::HashMap<String, String>,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum SignalDirection {
     Long,
     Short,
@@ -304,6 +796,94 @@ pub struct PerformanceEvent {
     pub events_per_second: f64,
 }
 
+impl PerformanceEvent {
+    /// Default latency target used by `saturation_score` (microseconds)
+    pub const DEFAULT_TARGET_LATENCY_US: f64 = 10.0;
+    
+    /// Create a builder for performance events
+    pub fn builder() -> PerformanceEventBuilder {
+        PerformanceEventBuilder::new()
+    }
+    
+    /// Combined load score against the default latency target
+    ///
+    /// 0.0 = idle, 1.0 = fully saturated. Values above 1.0 are possible when
+    /// latency exceeds the target.
+    pub fn saturation_score(&self) -> f64 {
+        self.saturation_score_with_target(Self::DEFAULT_TARGET_LATENCY_US)
+    }
+    
+    /// Combined load score against a custom latency target (microseconds)
+    pub fn saturation_score_with_target(&self, target_latency_us: f64) -> f64 {
+        0.4 * (self.cpu_usage / 100.0)
+            + 0.3 * (self.memory_usage / 100.0)
+            + 0.3 * (self.event_latency_us / target_latency_us)
+    }
+    
+    /// Check if the saturation score is at or above `threshold`
+    pub fn is_saturated(&self, threshold: f64) -> bool {
+        self.saturation_score() >= threshold
+    }
+    
+    /// Fraction of nominal throughput a rate limiter should allow [0.0, 1.0]
+    pub fn throttle_factor(&self) -> f64 {
+        (1.0 - self.saturation_score()).max(0.0)
+    }
+}
+
+/// Builder for `PerformanceEvent`
+#[derive(Debug, Clone, Default)]
+pub struct PerformanceEventBuilder {
+    timestamp: Option<i64>,
+    cpu_usage: f64,
+    memory_usage: f64,
+    event_latency_us: f64,
+    events_per_second: f64,
+}
+
+impl PerformanceEventBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    pub fn timestamp(mut self, timestamp: i64) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+    
+    pub fn cpu_usage(mut self, cpu_usage: f64) -> Self {
+        self.cpu_usage = cpu_usage;
+        self
+    }
+    
+    pub fn memory_usage(mut self, memory_usage: f64) -> Self {
+        self.memory_usage = memory_usage;
+        self
+    }
+    
+    pub fn event_latency_us(mut self, event_latency_us: f64) -> Self {
+        self.event_latency_us = event_latency_us;
+        self
+    }
+    
+    pub fn events_per_second(mut self, events_per_second: f64) -> Self {
+        self.events_per_second = events_per_second;
+        self
+    }
+    
+    /// Build the event (timestamp defaults to now)
+    pub fn build(self) -> PerformanceEvent {
+        PerformanceEvent {
+            timestamp: self.timestamp
+                .unwrap_or_else(|| chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0)),
+            cpu_usage: self.cpu_usage,
+            memory_usage: self.memory_usage,
+            event_latency_us: self.event_latency_us,
+            events_per_second: self.events_per_second,
+        }
+    }
+}
+
 // ============================================================================
 // System Events
 // ============================================================================
@@ -343,6 +923,63 @@ pub struct ErrorEvent {
     pub context: std::collections::HashMap<String, String>,
 }
 
+/// Transaction that failed after some of its events were delivered
+///
+/// Events of a transaction carry its ID in `EventEnvelope::transaction_id`,
+/// so consumers can discard the partial delivery.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionRolledBackEvent {
+    pub timestamp: i64,
+    pub transaction_id: Uuid,
+    pub events_published: usize,
+    pub events_total: usize,
+    pub reason: String,
+}
+
+/// Retry of a failed operation, published by `RetryOrchestrator` once the
+/// backoff has elapsed
+///
+/// The envelope is caused by the `ErrorEvent` being retried. A component
+/// whose retry fails again should publish its `ErrorEvent` caused by this
+/// envelope so the attempt counts against the original error.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetryAttemptEvent {
+    /// When the retry was scheduled
+    pub timestamp: i64,
+    /// Envelope ID of the first `ErrorEvent` in the chain
+    pub original_error_id: Uuid,
+    pub component: String,
+    pub error_type: String,
+    /// 1 for the first retry
+    pub attempt: u32,
+    /// When the retry is due
+    pub next_attempt_at_ns: i64,
+}
+
+/// Failed operation that `RetryOrchestrator` stopped retrying
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GaveUpEvent {
+    pub timestamp: i64,
+    /// Envelope ID of the first `ErrorEvent` in the chain
+    pub original_error_id: Uuid,
+    pub component: String,
+    pub error_type: String,
+    /// Retries made before giving up
+    pub attempts: u32,
+    /// Message of the last error
+    pub message: String,
+}
+
+/// P&L of one fill attributed to the signal (and strategy) behind it,
+/// published by `PnlAttributor`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttributedPnlEvent {
+    pub timestamp: i64,
+    pub fill_id: Uuid,
+    pub symbol: String,
+    pub attribution: crate::pnl::AttributedPnl,
+}
+
 /// Research analysis event
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ResearchEvent {
@@ -356,13 +993,59 @@ pub struct ResearchEvent {
 // Trait Definitions for Event System
 // ============================================================================
 
+/// Upcast to `Any` for downcasting type-erased events
+pub trait AsAny {
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<T: Any> AsAny for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
 /// Base trait for all events in system
-pub trait Event: Send + Sync + std::fmt::Debug {
+pub trait Event: AsAny + Send + Sync + std::fmt::Debug {
     /// Get event type identifier
     fn event_type(&self) -> &'static str;
     
     /// Get event priority (0 = highest)
     fn priority(&self) -> u8 { 5 }
+    
+    /// Serialize the event payload to JSON (`None` if unsupported)
+    fn to_json(&self) -> Option<serde_json::Value> { None }
+    
+    /// View as a market event (`None` if not market-related)
+    fn as_market_event(&self) -> Option<&dyn MarketEvent> { None }
+    
+    /// Wrapped payload for events that only hold another event (`PooledEnvelope`)
+    #[doc(hidden)]
+    fn wrapped_payload(&self) -> Option<&dyn Any> { None }
+}
+
+impl dyn Event {
+    /// Concrete payload as `Any`, looking through pool wrappers
+    pub fn payload_any(&self) -> &dyn Any {
+        self.wrapped_payload().unwrap_or_else(|| self.as_any())
+    }
+    
+    /// Downcast to a concrete event type
+    pub fn downcast_ref<T: Event + 'static>(&self) -> Option<&T> {
+        self.payload_any().downcast_ref::<T>()
+    }
+}
+
+/// Event type name known at compile time
+pub trait StaticEventType {
+    const EVENT_TYPE: &'static str;
+}
+
+/// Envelope did not contain the requested event type
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("expected {expected} event, found {found}")]
+pub struct EventTypeMismatch {
+    pub expected: &'static str,
+    pub found: &'static str,
 }
 
 /// Trait for market-related events
@@ -395,53 +1078,425 @@ pub enum EventType {
 // ============================================================================
 
 impl Event for MarketDataEvent {
-    fn event_type(&self) -> &'static str { "market_data" }
+    fn event_type(&self) -> &'static str { Self::EVENT_TYPE }
+    fn to_json(&self) -> Option<serde_json::Value> { serde_json::to_value(self).ok() }
+    fn as_market_event(&self) -> Option<&dyn MarketEvent> { Some(self) }
 }
 
 impl Event for AggregatedDataEvent {
-    fn event_type(&self) -> &'static str { "aggregated_data" }
+    fn event_type(&self) -> &'static str { Self::EVENT_TYPE }
+    fn to_json(&self) -> Option<serde_json::Value> { serde_json::to_value(self).ok() }
+    fn as_market_event(&self) -> Option<&dyn MarketEvent> { Some(self) }
 }
 
 impl Event for FeatureEvent {
-    fn event_type(&self) -> &'static str { "feature" }
+    fn event_type(&self) -> &'static str { Self::EVENT_TYPE }
+    fn to_json(&self) -> Option<serde_json::Value> { serde_json::to_value(self).ok() }
+    fn as_market_event(&self) -> Option<&dyn MarketEvent> { Some(self) }
 }
 
 impl Event for OrderBookEvent {
-    fn event_type(&self) -> &'static str { "order_book" }
+    fn event_type(&self) -> &'static str { Self::EVENT_TYPE }
+    fn to_json(&self) -> Option<serde_json::Value> { serde_json::to_value(self).ok() }
+    fn as_market_event(&self) -> Option<&dyn MarketEvent> { Some(self) }
+}
+
+impl Event for OrderBookDeltaEvent {
+    fn event_type(&self) -> &'static str { Self::EVENT_TYPE }
+    fn to_json(&self) -> Option<serde_json::Value> { serde_json::to_value(self).ok() }
+    fn as_market_event(&self) -> Option<&dyn MarketEvent> { Some(self) }
 }
 
 impl Event for QuantumFeatureEvent {
-    fn event_type(&self) -> &'static str { "quantum" }
+    fn event_type(&self) -> &'static str { Self::EVENT_TYPE }
+    fn to_json(&self) -> Option<serde_json::Value> { serde_json::to_value(self).ok() }
 }
 
 impl Event for SignalEvent {
-    fn event_type(&self) -> &'static str { "signal" }
+    fn event_type(&self) -> &'static str { Self::EVENT_TYPE }
+    fn to_json(&self) -> Option<serde_json::Value> { serde_json::to_value(self).ok() }
 }
 
 impl Event for OrderEvent {
-    fn event_type(&self) -> &'static str { "order" }
+    fn event_type(&self) -> &'static str { Self::EVENT_TYPE }
+    fn to_json(&self) -> Option<serde_json::Value> { serde_json::to_value(self).ok() }
 }
 
 impl Event for FillEvent {
-    fn event_type(&self) -> &'static str { "fill" }
+    fn event_type(&self) -> &'static str { Self::EVENT_TYPE }
+    fn to_json(&self) -> Option<serde_json::Value> { serde_json::to_value(self).ok() }
 }
 
 impl Event for OrderUpdateEvent {
-    fn event_type(&self) -> &'static str { "order_update" }
+    fn event_type(&self) -> &'static str { Self::EVENT_TYPE }
+    fn to_json(&self) -> Option<serde_json::Value> { serde_json::to_value(self).ok() }
 }
 
 impl Event for MetricsEvent {
-    fn event_type(&self) -> &'static str { "metrics" }
+    fn event_type(&self) -> &'static str { Self::EVENT_TYPE }
+    fn to_json(&self) -> Option<serde_json::Value> { serde_json::to_value(self).ok() }
 }
 
 impl Event for PerformanceEvent {
-    fn event_type(&self) -> &'static str { "performance" }
+    fn event_type(&self) -> &'static str { Self::EVENT_TYPE }
+    fn to_json(&self) -> Option<serde_json::Value> { serde_json::to_value(self).ok() }
 }
 
 impl Event for HealthEvent {
-    fn event_type(&self) -> &'static str { "health" }
+    fn event_type(&self) -> &'static str { Self::EVENT_TYPE }
+    fn to_json(&self) -> Option<serde_json::Value> { serde_json::to_value(self).ok() }
 }
 
 impl Event for ErrorEvent {
-    fn event_type(&self) -> &'static str { "error" }
+    fn event_type(&self) -> &'static str { Self::EVENT_TYPE }
+    fn to_json(&self) -> Option<serde_json::Value> { serde_json::to_value(self).ok() }
+}
+
+impl Event for RetryAttemptEvent {
+    fn event_type(&self) -> &'static str { Self::EVENT_TYPE }
+    fn to_json(&self) -> Option<serde_json::Value> { serde_json::to_value(self).ok() }
+}
+
+impl Event for GaveUpEvent {
+    fn event_type(&self) -> &'static str { Self::EVENT_TYPE }
+    fn to_json(&self) -> Option<serde_json::Value> { serde_json::to_value(self).ok() }
+}
+
+impl Event for AttributedPnlEvent {
+    fn event_type(&self) -> &'static str { Self::EVENT_TYPE }
+    fn to_json(&self) -> Option<serde_json::Value> { serde_json::to_value(self).ok() }
+}
+
+impl Event for TransactionRolledBackEvent {
+    fn event_type(&self) -> &'static str { Self::EVENT_TYPE }
+    fn priority(&self) -> u8 { 0 }
+    fn to_json(&self) -> Option<serde_json::Value> { serde_json::to_value(self).ok() }
+}
+
+// ============================================================================
+// Envelope Conversions
+// ============================================================================
+
+/// Implements `StaticEventType`, `TryFrom<EventEnvelope>` and
+/// `From<T> for EventEnvelope`. The name given here is the one returned by
+/// `Event::event_type`, so the type check and the downcast cannot disagree.
+macro_rules! envelope_conversions {
+    ($($ty:ty => $name:literal),* $(,)?) => {$(
+        impl StaticEventType for $ty {
+            const EVENT_TYPE: &'static str = $name;
+        }
+        
+        impl TryFrom<EventEnvelope> for $ty {
+            type Error = EventTypeMismatch;
+            
+            fn try_from(envelope: EventEnvelope) -> Result<Self, Self::Error> {
+                let found = envelope.event.event_type();
+                let mismatch = EventTypeMismatch { expected: $name, found };
+                if found != $name {
+                    return Err(mismatch);
+                }
+                
+                // Events loaded from storage arrive as RawEvent
+                match envelope.event.downcast_ref::<$ty>() {
+                    Some(event) => Ok(event.clone()),
+                    None => envelope.event.downcast_ref::<RawEvent>()
+                        .and_then(|raw| serde_json::from_value(raw.payload.clone()).ok())
+                        .ok_or(mismatch),
+                }
+            }
+        }
+        
+        impl From<$ty> for EventEnvelope {
+            fn from(event: $ty) -> Self {
+                EventEnvelope::new(event, 5)
+            }
+        }
+    )*};
+}
+
+envelope_conversions! {
+    MarketDataEvent => "market_data",
+    AggregatedDataEvent => "aggregated_data",
+    FeatureEvent => "feature",
+    OrderBookEvent => "order_book",
+    OrderBookDeltaEvent => "order_book_delta",
+    QuantumFeatureEvent => "quantum",
+    SignalEvent => "signal",
+    OrderEvent => "order",
+    FillEvent => "fill",
+    OrderUpdateEvent => "order_update",
+    MetricsEvent => "metrics",
+    PerformanceEvent => "performance",
+    HealthEvent => "health",
+    ErrorEvent => "error",
+    TransactionRolledBackEvent => "transaction_rolled_back",
+    RetryAttemptEvent => "retry_attempt",
+    GaveUpEvent => "gave_up",
+    AttributedPnlEvent => "attributed_pnl",
+}
+
+impl MarketEvent for MarketDataEvent {
+    fn timestamp(&self) -> i64 { self.timestamp }
+    fn symbol(&self) -> Option<&str> { Some(&self.symbol) }
+    fn event_type(&self) -> EventType { EventType::Trade }
+}
+
+impl MarketEvent for AggregatedDataEvent {
+    fn timestamp(&self) -> i64 { self.timestamp }
+    fn symbol(&self) -> Option<&str> { Some(&self.symbol) }
+}
+
+impl MarketEvent for FeatureEvent {
+    fn timestamp(&self) -> i64 { self.timestamp }
+    fn symbol(&self) -> Option<&str> { Some(&self.symbol) }
+    fn event_type(&self) -> EventType { EventType::Feature }
+}
+
+impl MarketEvent for OrderBookEvent {
+    fn timestamp(&self) -> i64 { self.timestamp }
+    fn symbol(&self) -> Option<&str> { Some(&self.symbol) }
+    fn event_type(&self) -> EventType { EventType::OrderBook }
+}
+
+impl MarketEvent for OrderBookDeltaEvent {
+    fn timestamp(&self) -> i64 { self.timestamp }
+    fn symbol(&self) -> Option<&str> { Some(&self.symbol) }
+    fn event_type(&self) -> EventType { EventType::OrderBook }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn perf(cpu: f64, mem: f64, latency_us: f64) -> PerformanceEvent {
+        PerformanceEvent::builder()
+            .timestamp(0)
+            .cpu_usage(cpu)
+            .memory_usage(mem)
+            .event_latency_us(latency_us)
+            .build()
+    }
+    
+    #[test]
+    fn test_saturation_score_idle() {
+        let event = perf(0.0, 0.0, 0.0);
+        assert!(event.saturation_score().abs() < 1e-12);
+        assert!((event.throttle_factor() - 1.0).abs() < 1e-12);
+        assert!(!event.is_saturated(0.5));
+    }
+    
+    #[test]
+    fn test_saturation_score_full() {
+        // 100% CPU, 100% memory, latency exactly on target
+        let event = perf(100.0, 100.0, PerformanceEvent::DEFAULT_TARGET_LATENCY_US);
+        assert!((event.saturation_score() - 1.0).abs() < 1e-12);
+        assert!(event.throttle_factor().abs() < 1e-12);
+        assert!(event.is_saturated(1.0));
+    }
+    
+    #[test]
+    fn test_saturation_score_weights() {
+        assert!((perf(100.0, 0.0, 0.0).saturation_score() - 0.4).abs() < 1e-12);
+        assert!((perf(0.0, 100.0, 0.0).saturation_score() - 0.3).abs() < 1e-12);
+        assert!((perf(0.0, 0.0, 10.0).saturation_score() - 0.3).abs() < 1e-12);
+        assert!((perf(0.0, 0.0, 10.0).saturation_score_with_target(20.0) - 0.15).abs() < 1e-12);
+    }
+    
+    #[test]
+    fn test_throttle_factor_clamped() {
+        // Latency 10x over target pushes the score well above 1.0
+        let event = perf(100.0, 100.0, 100.0);
+        assert!(event.saturation_score() > 1.0);
+        assert_eq!(event.throttle_factor(), 0.0);
+    }
+    
+    #[test]
+    fn test_is_saturated_threshold_boundary() {
+        let event = perf(50.0, 50.0, 5.0); // 0.2 + 0.15 + 0.15 = 0.5
+        assert!(event.is_saturated(0.5 - 1e-9));
+        assert!(!event.is_saturated(0.5 + 1e-9));
+    }
+    
+    fn roundtrip<T>(event: T)
+    where
+        T: Event + StaticEventType + Clone + TryFrom<EventEnvelope, Error = EventTypeMismatch>,
+        EventEnvelope: From<T>,
+    {
+        assert_eq!(event.event_type(), T::EVENT_TYPE);
+        
+        let envelope = EventEnvelope::from(event.clone());
+        assert_eq!(envelope.priority, 5);
+        let back = T::try_from(envelope).unwrap();
+        assert_eq!(back.to_json(), event.to_json());
+        
+        // Same payload reloaded from storage
+        let raw = EventEnvelope::new(RawEvent::new(T::EVENT_TYPE, 5, event.to_json().unwrap()), 5);
+        let back = T::try_from(raw).unwrap();
+        assert_eq!(back.to_json(), event.to_json());
+        
+        // Any other type is rejected
+        let other = EventEnvelope::new(RawEvent::new("other", 5, serde_json::Value::Null), 5);
+        assert_eq!(
+            T::try_from(other).unwrap_err(),
+            EventTypeMismatch { expected: T::EVENT_TYPE, found: "other" }
+        );
+    }
+    
+    #[test]
+    fn test_envelope_roundtrip_all_types() {
+        let id = Uuid::from_u128(7);
+        let mut map = std::collections::HashMap::new();
+        map.insert("k".to_string(), "v".to_string());
+        let mut features = std::collections::HashMap::new();
+        features.insert("spread".to_string(), 0.25);
+        
+        roundtrip(MarketDataEvent {
+            timestamp: 1, symbol: "ES".to_string(), price: 6000.0, volume: 1.0,
+            bid_price: 5999.75, bid_size: 3.0, ask_price: 6000.25, ask_size: 4.0,
+        });
+        roundtrip(AggregatedDataEvent {
+            timestamp: 1, symbol: "ES".to_string(), timeframe: "1m".to_string(),
+            open: 1.0, high: 2.0, low: 0.5, close: 1.5, volume: 10.0, vwap: 1.2,
+        });
+        roundtrip(FeatureEvent { timestamp: 1, symbol: "ES".to_string(), features });
+        roundtrip(OrderBookEvent {
+            timestamp: 1, symbol: "ES".to_string(),
+            bids: vec![(5999.75, 3.0)], asks: vec![(6000.25, 4.0)],
+        });
+        roundtrip(OrderBookDeltaEvent {
+            timestamp: 1, symbol: "ES".to_string(),
+            bid_changes: vec![PriceLevel { price: 5999.75, new_size: 0.0 }],
+            ask_changes: vec![PriceLevel { price: 6000.25, new_size: 5.0 }],
+        });
+        roundtrip(QuantumFeatureEvent {
+            timestamp: 1, symbol: "ES".to_string(), momentum: 0.1, variance: 0.2,
+            skewness: 0.3, kurtosis: 3.0, regime: MarketRegime::Trending, regime_confidence: 0.9,
+            liquidity_walls: vec![6000.0], wall_strengths: vec![1.0], momentum_uncertainty: 0.05,
+            price_std_dev: 1.5, validation_passed: Some(true), chi_squared_p_value: None,
+            kl_divergence: Some(0.01),
+        });
+        roundtrip(SignalEvent {
+            signal_id: id, timestamp: 1, strategy_id: "s".to_string(), symbol: "ES".to_string(),
+            direction: SignalDirection::Long, strength: 0.8, target_price: Some(6010.0),
+            stop_loss: None, metadata: map.clone(),
+        });
+        roundtrip(OrderEvent {
+            order_id: id, signal_id: None, timestamp: 1, symbol: "ES".to_string(),
+            side: OrderSide::Buy, order_type: OrderType::Limit, quantity: 1.0, price: Some(6000.0),
+        });
+        roundtrip(FillEvent {
+            fill_id: id, order_id: id, signal_id: Some(id), timestamp: 1, symbol: "ES".to_string(),
+            side: OrderSide::Sell, filled_quantity: 1.0, fill_price: 6000.0, commission: 0.5,
+            slippage_bps: 0.1,
+        });
+        roundtrip(OrderUpdateEvent {
+            order_id: id, timestamp: 1, status: OrderStatus::PartiallyFilled,
+            filled_quantity: 1.0, remaining_quantity: 2.0,
+        });
+        roundtrip(MetricsEvent {
+            timestamp: 1, strategy_id: Some("s".to_string()), pnl: 10.0, sharpe_ratio: 1.5,
+            max_drawdown: 0.1, win_rate: 0.6, total_trades: 42,
+        });
+        roundtrip(perf(10.0, 20.0, 5.0));
+        roundtrip(HealthEvent {
+            timestamp: 1, component: "bus".to_string(), status: HealthStatus::Degraded,
+            message: "slow".to_string(),
+        });
+        roundtrip(ErrorEvent {
+            timestamp: 1, component: "bus".to_string(), error_type: "io".to_string(),
+            message: "failed".to_string(), context: map,
+        });
+    }
+    
+    #[test]
+    fn test_try_from_wrong_type() {
+        let envelope = EventEnvelope::from(perf(0.0, 0.0, 0.0));
+        let err = MarketDataEvent::try_from(envelope).unwrap_err();
+        assert_eq!(err.expected, "market_data");
+        assert_eq!(err.found, "performance");
+        assert_eq!(err.to_string(), "expected market_data event, found performance");
+    }
+    
+    fn quantum_builder() -> QuantumFeatureEventBuilder {
+        QuantumFeatureEvent::builder("ES", 1)
+            .moments(0.1, 0.2, 0.3, 3.0)
+            .regime(MarketRegime::Trending, 0.9)
+            .liquidity_wall(6000.0, 1.0)
+    }
+    
+    #[test]
+    fn test_quantum_builder_collects_all_errors() {
+        let event = quantum_builder().chi_squared_p_value(0.05).kl_divergence(0.0).build().unwrap();
+        assert_eq!(event.regime_confidence, 0.9);
+        assert_eq!(event.liquidity_walls, vec![6000.0]);
+        assert!(event.validate().is_ok());
+        
+        let errors = QuantumFeatureEvent::builder("ES", 1)
+            .liquidity_walls(vec![6000.0, 6010.0], vec![-1.0])
+            .chi_squared_p_value(1.5)
+            .kl_divergence(f64::NAN)
+            .build()
+            .unwrap_err();
+        let fields: Vec<_> = errors.iter().map(|e| e.field).collect();
+        assert_eq!(
+            fields,
+            vec!["regime", "wall_strengths", "wall_strengths", "chi_squared_p_value", "kl_divergence"]
+        );
+        
+        let mut event = event;
+        event.regime_confidence = 1.01;
+        assert_eq!(event.validate().unwrap_err()[0].to_string(), "regime_confidence: 1.01 is outside [0, 1]");
+    }
+    
+    mod quantum_props {
+        use super::*;
+        use proptest::prelude::*;
+        
+        fn probability() -> impl Strategy<Value = f64> {
+            0.0f64..=1.0
+        }
+        
+        proptest! {
+            #[test]
+            fn valid_events_pass(
+                confidence in probability(),
+                walls in prop::collection::vec((5000.0f64..7000.0, 0.0f64..1e3), 0..8),
+                p_value in prop::option::of(probability()),
+                divergence in prop::option::of(0.0f64..1e3),
+            ) {
+                let (prices, strengths) = walls.into_iter().unzip();
+                let mut builder = QuantumFeatureEvent::builder("ES", 1)
+                    .regime(MarketRegime::Volatile, confidence)
+                    .liquidity_walls(prices, strengths);
+                if let Some(p_value) = p_value {
+                    builder = builder.chi_squared_p_value(p_value);
+                }
+                if let Some(divergence) = divergence {
+                    builder = builder.kl_divergence(divergence);
+                }
+                let event = builder.build().unwrap();
+                prop_assert!(event.validate().is_ok());
+            }
+            
+            #[test]
+            fn invalid_events_fail(
+                confidence in prop_oneof![-1e3f64..-1e-9, 1.0f64 + 1e-9..1e3],
+                negative_strength in -1e3f64..-1e-9,
+                p_value in prop_oneof![-1e3f64..-1e-9, 1.0f64 + 1e-9..1e3],
+                divergence in -1e3f64..-1e-9,
+            ) {
+                let mut event = quantum_builder().build().unwrap();
+                event.regime_confidence = confidence;
+                prop_assert_eq!(event.validate().unwrap_err().len(), 1);
+                
+                let mut event = quantum_builder().build().unwrap();
+                event.wall_strengths = vec![negative_strength];
+                event.liquidity_walls.clear();
+                event.chi_squared_p_value = Some(p_value);
+                event.kl_divergence = Some(divergence);
+                // Length mismatch, negative strength, p-value, divergence
+                prop_assert_eq!(event.validate().unwrap_err().len(), 4);
+            }
+        }
+    }
 }
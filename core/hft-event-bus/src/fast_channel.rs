@@ -2,10 +2,29 @@
 //!
 //! This module provides high-performance channels optimized for
 //! zero-copy event transmission with <1μs latency.
+//!
+//! # Choosing a transport
+//!
+//! - [`FastChannel::bounded`]: flume MPMC queue. Any number of senders and
+//!   receivers, async support, blocking waits park the thread. Each event
+//!   goes to one receiver. Contended under the highest throughput.
+//! - [`FastChannel::spsc`]: lock-free ring for one sender and one receiver.
+//! - [`DisruptorChannel`]: LMAX Disruptor-style ring for one producer and
+//!   up to [`MAX_CONSUMERS`] consumers that each see every event. No locks
+//!   and no allocation after construction, but:
+//!   - waiting spins (then yields), burning a core per waiting thread;
+//!   - the slowest consumer holds back the producer once a ring behind;
+//!   - events are cloned out, so `E` should be cheap to clone;
+//!   - the ring size is fixed at compile time and memory stays allocated.
 
+use crate::dead_letter::{DeadLetterQueue, DeadLetterReason};
 use market_data_engine::types::{MarketEvent, TradeV2, QuoteV2};
 use flume::{Sender, Receiver, bounded, unbounded};
+use std::cell::UnsafeCell;
+use std::mem::MaybeUninit;
+use std::sync::atomic::{fence, AtomicBool, AtomicI64, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::Instant;
 
 /// Fast channel for MarketEvent types
 ///
@@ -25,19 +44,42 @@ use std::sync::Arc;
 pub struct FastChannel<E: MarketEvent> {
     sender: Sender<E>,
     receiver: Receiver<E>,
+    /// Captures copies of events rejected by `try_send` on a full channel (optional)
+    dead_letters: Option<(DeadLetterQueue<E>, fn(&E) -> E)>,
 }
 
 impl<E: MarketEvent> FastChannel<E> {
     /// Create bounded channel (recommended for backpressure)
     pub fn bounded(capacity: usize) -> Self {
         let (sender, receiver) = bounded(capacity);
-        Self { sender, receiver }
+        Self { sender, receiver, dead_letters: None }
+    }
+    
+    /// Create single-producer single-consumer ring buffer channel
+    ///
+    /// Avoids flume's MPMC overhead for one-to-one hot paths. `capacity`
+    /// must be a non-zero power of two.
+    pub fn spsc(capacity: usize) -> Result<(SpscSender<E>, SpscReceiver<E>), CapacityError> {
+        let ring = Arc::new(SpscRing::new(capacity)?);
+        Ok((
+            SpscSender { ring: ring.clone() },
+            SpscReceiver { ring },
+        ))
     }
     
     /// Create unbounded channel (use with caution)
     pub fn unbounded() -> Self {
         let (sender, receiver) = unbounded();
-        Self { sender, receiver }
+        Self { sender, receiver, dead_letters: None }
+    }
+    
+    /// Capture events that `try_send` rejects because the channel is full
+    pub fn with_dead_letter_queue(mut self, queue: DeadLetterQueue<E>) -> Self
+    where
+        E: Clone,
+    {
+        self.dead_letters = Some((queue, E::clone));
+        self
     }
     
     /// Send event (zero-copy)
@@ -47,10 +89,18 @@ impl<E: MarketEvent> FastChannel<E> {
     }
     
     /// Try to send without blocking
+    ///
+    /// A full channel still returns `Full`; with a dead-letter queue attached
+    /// a copy of the event is captured there as well.
     #[inline(always)]
     pub fn try_send(&self, event: E) -> Result<(), TrySendError<E>> {
         self.sender.try_send(event).map_err(|e| match e {
-            flume::TrySendError::Full(ev) => TrySendError::Full(ev),
+            flume::TrySendError::Full(ev) => {
+                if let Some((queue, copy)) = &self.dead_letters {
+                    queue.push(DeadLetterReason::ChannelFull, copy(&ev));
+                }
+                TrySendError::Full(ev)
+            }
             flume::TrySendError::Disconnected(ev) => TrySendError::Disconnected(ev),
         })
     }
@@ -70,6 +120,24 @@ impl<E: MarketEvent> FastChannel<E> {
         })
     }
     
+    /// Sender for use inside async tasks (waits without blocking the thread)
+    pub fn async_sender(&self) -> AsyncSender<E> {
+        AsyncSender { sender: self.sender.clone() }
+    }
+    
+    /// Receiver for use inside async tasks (waits without blocking the thread)
+    pub fn async_receiver(&self) -> AsyncReceiver<E> {
+        AsyncReceiver { receiver: self.receiver.clone() }
+    }
+    
+    /// Split into async halves
+    pub fn into_async(self) -> (AsyncSender<E>, AsyncReceiver<E>) {
+        (
+            AsyncSender { sender: self.sender },
+            AsyncReceiver { receiver: self.receiver },
+        )
+    }
+    
     /// Get sender clone
     pub fn sender(&self) -> Sender<E> {
         self.sender.clone()
@@ -86,11 +154,28 @@ impl<E: MarketEvent> FastChannel<E> {
         self.receiver.is_empty()
     }
     
+    /// Number of receivers other than the channel's own
+    #[inline(always)]
+    pub fn receiver_count(&self) -> usize {
+        self.receiver.receiver_count().saturating_sub(1)
+    }
+    
     /// Get number of messages in channel
     #[inline(always)]
     pub fn len(&self) -> usize {
         self.receiver.len()
     }
+    
+    /// Limit sends to `max_per_sec` events per second (at least 1)
+    ///
+    /// Bursts of up to 1 ms worth of events are allowed.
+    pub fn with_rate_limit(self, max_per_sec: u64) -> RateLimitedChannel<E> {
+        let max_per_sec = max_per_sec.max(1);
+        RateLimitedChannel {
+            channel: self,
+            bucket: TokenBucket::new(max_per_sec / 1000, max_per_sec),
+        }
+    }
 }
 
 impl<E: MarketEvent> Clone for FastChannel<E> {
@@ -98,7 +183,216 @@ impl<E: MarketEvent> Clone for FastChannel<E> {
         Self {
             sender: self.sender.clone(),
             receiver: self.receiver.clone(),
+            dead_letters: self.dead_letters.clone(),
+        }
+    }
+}
+
+/// Lock-free token bucket
+///
+/// Holds up to `capacity` tokens, refilled at `refill_rate` tokens per
+/// second. Refills are computed from elapsed nanoseconds and only advance
+/// `last_refill_ns` by the time the added tokens account for, so no
+/// fractional tokens are lost at high rates.
+pub struct TokenBucket {
+    tokens: AtomicU64,
+    /// Nanoseconds since `origin` up to which tokens have been added
+    last_refill_ns: AtomicI64,
+    capacity: u64,
+    refill_rate: u64,
+    origin: Instant,
+}
+
+impl TokenBucket {
+    /// Create full bucket (`capacity` and `refill_rate` at least 1)
+    pub fn new(capacity: u64, refill_rate: u64) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            tokens: AtomicU64::new(capacity),
+            last_refill_ns: AtomicI64::new(0),
+            capacity,
+            refill_rate: refill_rate.max(1),
+            origin: Instant::now(),
+        }
+    }
+    
+    /// Take one token if available
+    #[inline]
+    pub fn try_acquire(&self) -> bool {
+        self.refill();
+        self.tokens
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |tokens| tokens.checked_sub(1))
+            .is_ok()
+    }
+    
+    /// Tokens currently available
+    pub fn available(&self) -> u64 {
+        self.refill();
+        self.tokens.load(Ordering::Acquire)
+    }
+    
+    pub fn capacity(&self) -> u64 {
+        self.capacity
+    }
+    
+    pub fn refill_rate(&self) -> u64 {
+        self.refill_rate
+    }
+    
+    fn refill(&self) {
+        let now = self.origin.elapsed().as_nanos() as i64;
+        let last = self.last_refill_ns.load(Ordering::Acquire);
+        let elapsed = now - last;
+        if elapsed <= 0 {
+            return;
+        }
+        
+        let added = elapsed as u128 * self.refill_rate as u128 / 1_000_000_000;
+        if added == 0 {
+            return;
+        }
+        // Time the added tokens account for; the remainder carries over
+        let covered = (added * 1_000_000_000 / self.refill_rate as u128) as i64;
+        let added = added.min(self.capacity as u128) as u64;
+        // Whoever advances the refill time adds the tokens
+        if self.last_refill_ns
+            .compare_exchange(last, last + covered, Ordering::AcqRel, Ordering::Relaxed)
+            .is_ok()
+        {
+            let capacity = self.capacity;
+            let _ = self.tokens.fetch_update(Ordering::AcqRel, Ordering::Acquire, |tokens| {
+                Some(tokens.saturating_add(added).min(capacity))
+            });
+        }
+    }
+}
+
+/// [`FastChannel`] whose sends are limited by a [`TokenBucket`]
+///
+/// Created with [`FastChannel::with_rate_limit`]. Receiving is not limited.
+pub struct RateLimitedChannel<E: MarketEvent> {
+    channel: FastChannel<E>,
+    bucket: TokenBucket,
+}
+
+impl<E: MarketEvent> RateLimitedChannel<E> {
+    /// Send if a token is available, otherwise return the event
+    ///
+    /// Blocks like [`FastChannel::send`] if a bounded channel is full.
+    #[inline]
+    pub fn send(&self, event: E) -> Result<(), ThrottleError<E>> {
+        if !self.bucket.try_acquire() {
+            return Err(ThrottleError::RateLimited(event));
+        }
+        self.channel.send(event).map_err(|e| ThrottleError::Disconnected(e.0))
+    }
+    
+    /// Send, spinning up to `spin_limit` nanoseconds for a token
+    pub fn send_blocking(&self, event: E, spin_limit: u64) -> Result<(), ThrottleError<E>> {
+        if !self.bucket.try_acquire() {
+            let start = Instant::now();
+            loop {
+                if start.elapsed().as_nanos() as u64 >= spin_limit {
+                    return Err(ThrottleError::RateLimited(event));
+                }
+                std::hint::spin_loop();
+                if self.bucket.try_acquire() {
+                    break;
+                }
+            }
         }
+        self.channel.send(event).map_err(|e| ThrottleError::Disconnected(e.0))
+    }
+    
+    /// Receive event (blocking)
+    #[inline(always)]
+    pub fn recv(&self) -> Result<E, RecvError> {
+        self.channel.recv()
+    }
+    
+    /// Try to receive without blocking
+    #[inline(always)]
+    pub fn try_recv(&self) -> Result<E, TryRecvError> {
+        self.channel.try_recv()
+    }
+    
+    /// Underlying channel (sending through it bypasses the limit)
+    pub fn channel(&self) -> &FastChannel<E> {
+        &self.channel
+    }
+    
+    pub fn bucket(&self) -> &TokenBucket {
+        &self.bucket
+    }
+}
+
+/// Async producer half of a [`FastChannel`]
+///
+/// Cloning yields another sender for the same channel.
+pub struct AsyncSender<E> {
+    sender: Sender<E>,
+}
+
+impl<E> AsyncSender<E> {
+    /// Send event, yielding to the runtime while the channel is full
+    ///
+    /// If the future is dropped before it completes the event is not sent.
+    #[inline]
+    pub async fn send(&self, event: E) -> Result<(), SendError<E>> {
+        self.sender.send_async(event).await.map_err(|e| SendError(e.0))
+    }
+    
+    /// Check if all receivers have been dropped
+    pub fn is_disconnected(&self) -> bool {
+        self.sender.is_disconnected()
+    }
+}
+
+impl<E> Clone for AsyncSender<E> {
+    fn clone(&self) -> Self {
+        Self { sender: self.sender.clone() }
+    }
+}
+
+/// Async consumer half of a [`FastChannel`]
+///
+/// Cloned receivers compete for events, like `FastChannel::receiver`.
+pub struct AsyncReceiver<E> {
+    receiver: Receiver<E>,
+}
+
+impl<E> AsyncReceiver<E> {
+    /// Receive event, yielding to the runtime while the channel is empty
+    ///
+    /// Cancel-safe: dropping the future never loses an event.
+    #[inline]
+    pub async fn recv(&self) -> Result<E, RecvError> {
+        self.receiver.recv_async().await.map_err(|_| RecvError)
+    }
+    
+    /// Try to receive without waiting
+    #[inline]
+    pub fn try_recv(&self) -> Result<E, TryRecvError> {
+        self.receiver.try_recv().map_err(|e| match e {
+            flume::TryRecvError::Empty => TryRecvError::Empty,
+            flume::TryRecvError::Disconnected => TryRecvError::Disconnected,
+        })
+    }
+    
+    /// Get number of messages in channel
+    pub fn len(&self) -> usize {
+        self.receiver.len()
+    }
+    
+    /// Check if channel is empty
+    pub fn is_empty(&self) -> bool {
+        self.receiver.is_empty()
+    }
+}
+
+impl<E> Clone for AsyncReceiver<E> {
+    fn clone(&self) -> Self {
+        Self { receiver: self.receiver.clone() }
     }
 }
 
@@ -132,6 +426,24 @@ impl<E> std::fmt::Display for TrySendError<E> {
 
 impl<E: std::fmt::Debug> std::error::Error for TrySendError<E> {}
 
+/// Rate-limited send error
+#[derive(Debug)]
+pub enum ThrottleError<E> {
+    RateLimited(E),
+    Disconnected(E),
+}
+
+impl<E> std::fmt::Display for ThrottleError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::RateLimited(_) => write!(f, "rate limited"),
+            Self::Disconnected(_) => write!(f, "channel disconnected"),
+        }
+    }
+}
+
+impl<E: std::fmt::Debug> std::error::Error for ThrottleError<E> {}
+
 /// Receive error
 #[derive(Debug)]
 pub struct RecvError;
@@ -162,6 +474,490 @@ impl std::fmt::Display for TryRecvError {
 
 impl std::error::Error for TryRecvError {}
 
+/// Invalid ring buffer capacity
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CapacityError {
+    pub capacity: usize,
+}
+
+impl std::fmt::Display for CapacityError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "capacity {} is not a non-zero power of two", self.capacity)
+    }
+}
+
+impl std::error::Error for CapacityError {}
+
+/// Pads a value to its own 64-byte cache line
+#[repr(align(64))]
+struct CachePadded<T>(T);
+
+/// Lock-free single-producer single-consumer ring buffer
+///
+/// `head` and `tail` are free-running counters; the slot index is
+/// `counter & mask`. Slots in `[head, tail)` are initialized.
+struct SpscRing<E> {
+    buffer: Box<[UnsafeCell<MaybeUninit<E>>]>,
+    mask: usize,
+    /// Next slot to read (written by consumer only)
+    head: CachePadded<AtomicUsize>,
+    /// Next slot to write (written by producer only)
+    tail: CachePadded<AtomicUsize>,
+    sender_alive: AtomicBool,
+    receiver_alive: AtomicBool,
+}
+
+// SAFETY: slots are only accessed by the single producer (in `[tail, head + cap)`)
+// or the single consumer (in `[head, tail)`), with ownership handed over via
+// the release/acquire pairs on `head` and `tail`.
+unsafe impl<E: Send> Sync for SpscRing<E> {}
+
+impl<E> SpscRing<E> {
+    fn new(capacity: usize) -> Result<Self, CapacityError> {
+        if !capacity.is_power_of_two() {
+            return Err(CapacityError { capacity });
+        }
+        
+        let buffer = (0..capacity)
+            .map(|_| UnsafeCell::new(MaybeUninit::uninit()))
+            .collect();
+        
+        Ok(Self {
+            buffer,
+            mask: capacity - 1,
+            head: CachePadded(AtomicUsize::new(0)),
+            tail: CachePadded(AtomicUsize::new(0)),
+            sender_alive: AtomicBool::new(true),
+            receiver_alive: AtomicBool::new(true),
+        })
+    }
+    
+    #[inline(always)]
+    fn capacity(&self) -> usize {
+        self.mask + 1
+    }
+}
+
+impl<E> Drop for SpscRing<E> {
+    fn drop(&mut self) {
+        let head = *self.head.0.get_mut();
+        let tail = *self.tail.0.get_mut();
+        for i in head..tail {
+            // SAFETY: slots in [head, tail) were written and never read
+            unsafe { (*self.buffer[i & self.mask].get()).assume_init_drop() };
+        }
+    }
+}
+
+/// Producer half of an SPSC channel (see [`FastChannel::spsc`])
+pub struct SpscSender<E> {
+    ring: Arc<SpscRing<E>>,
+}
+
+impl<E> SpscSender<E> {
+    /// Try to send without blocking
+    #[inline(always)]
+    pub fn try_send(&mut self, event: E) -> Result<(), TrySendError<E>> {
+        if !self.ring.receiver_alive.load(Ordering::Relaxed) {
+            return Err(TrySendError::Disconnected(event));
+        }
+        
+        let tail = self.ring.tail.0.load(Ordering::Relaxed);
+        let head = self.ring.head.0.load(Ordering::Acquire);
+        if tail.wrapping_sub(head) == self.ring.capacity() {
+            return Err(TrySendError::Full(event));
+        }
+        
+        // SAFETY: slot `tail` is outside [head, tail), so the consumer will not
+        // touch it until the release store below publishes it
+        unsafe { (*self.ring.buffer[tail & self.ring.mask].get()).write(event) };
+        self.ring.tail.0.store(tail.wrapping_add(1), Ordering::Release);
+        Ok(())
+    }
+    
+    /// Send event, spinning while the buffer is full
+    #[inline(always)]
+    pub fn send(&mut self, mut event: E) -> Result<(), SendError<E>> {
+        loop {
+            match self.try_send(event) {
+                Ok(()) => return Ok(()),
+                Err(TrySendError::Full(ev)) => {
+                    event = ev;
+                    std::hint::spin_loop();
+                }
+                Err(TrySendError::Disconnected(ev)) => return Err(SendError(ev)),
+            }
+        }
+    }
+    
+    /// Capacity of the ring buffer
+    pub fn capacity(&self) -> usize {
+        self.ring.capacity()
+    }
+}
+
+impl<E> Drop for SpscSender<E> {
+    fn drop(&mut self) {
+        self.ring.sender_alive.store(false, Ordering::Release);
+    }
+}
+
+/// Consumer half of an SPSC channel (see [`FastChannel::spsc`])
+pub struct SpscReceiver<E> {
+    ring: Arc<SpscRing<E>>,
+}
+
+impl<E> SpscReceiver<E> {
+    /// Try to receive without blocking
+    #[inline(always)]
+    pub fn try_recv(&mut self) -> Result<E, TryRecvError> {
+        let head = self.ring.head.0.load(Ordering::Relaxed);
+        let tail = self.ring.tail.0.load(Ordering::Acquire);
+        if head == tail {
+            // Re-check after observing the sender gone: it may have pushed
+            // a final event before dropping
+            if !self.ring.sender_alive.load(Ordering::Acquire)
+                && self.ring.tail.0.load(Ordering::Acquire) == head
+            {
+                return Err(TryRecvError::Disconnected);
+            }
+            return Err(TryRecvError::Empty);
+        }
+        
+        // SAFETY: slot `head` is in [head, tail) so it was initialized by the
+        // producer, and the producer won't reuse it until `head` advances
+        let event = unsafe { (*self.ring.buffer[head & self.ring.mask].get()).assume_init_read() };
+        self.ring.head.0.store(head.wrapping_add(1), Ordering::Release);
+        Ok(event)
+    }
+    
+    /// Receive event, spinning while the buffer is empty
+    #[inline(always)]
+    pub fn recv(&mut self) -> Result<E, RecvError> {
+        loop {
+            match self.try_recv() {
+                Ok(event) => return Ok(event),
+                Err(TryRecvError::Empty) => std::hint::spin_loop(),
+                Err(TryRecvError::Disconnected) => return Err(RecvError),
+            }
+        }
+    }
+    
+    /// Number of events waiting in the buffer
+    pub fn len(&self) -> usize {
+        let tail = self.ring.tail.0.load(Ordering::Acquire);
+        tail.wrapping_sub(self.ring.head.0.load(Ordering::Relaxed))
+    }
+    
+    /// Check if buffer is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<E> Drop for SpscReceiver<E> {
+    fn drop(&mut self) {
+        self.ring.receiver_alive.store(false, Ordering::Release);
+    }
+}
+
+/// Maximum number of consumers of one [`DisruptorChannel`]
+pub const MAX_CONSUMERS: usize = 16;
+
+/// Consumer cursor value marking an unused consumer slot
+const INACTIVE: u64 = u64::MAX;
+
+/// Spin-then-yield wait used by the blocking disruptor calls
+struct Backoff {
+    step: u32,
+}
+
+impl Backoff {
+    /// Spins doubling up to 64 iterations, then yields the thread
+    const SPIN_LIMIT: u32 = 6;
+    
+    fn new() -> Self {
+        Self { step: 0 }
+    }
+    
+    #[inline]
+    fn snooze(&mut self) {
+        if self.step <= Self::SPIN_LIMIT {
+            for _ in 0..1 << self.step {
+                std::hint::spin_loop();
+            }
+            self.step += 1;
+        } else {
+            std::thread::yield_now();
+        }
+    }
+}
+
+/// Shared state of a [`DisruptorChannel`]
+///
+/// Sequences are free-running; sequence `s` lives in slot `s % N`. Slots
+/// below `min(cursor, N)` are initialized and stay so until overwritten or
+/// the ring is dropped, since consumers read by cloning.
+struct DisruptorRing<E, const N: usize> {
+    buffer: Box<[UnsafeCell<MaybeUninit<E>>; N]>,
+    /// Number of events published (written by the producer only)
+    cursor: CachePadded<AtomicU64>,
+    /// Next sequence each consumer will read, or `INACTIVE`
+    consumers: [CachePadded<AtomicU64>; MAX_CONSUMERS],
+    producer_claimed: AtomicBool,
+}
+
+// SAFETY: the producer only writes slot `s % N` once every active consumer
+// cursor is past `s - N`, and consumers only read sequences below `cursor`.
+// Both hand-offs are release/acquire pairs on the cursors. Consumers share
+// `&E` across threads while cloning, hence `E: Sync`.
+unsafe impl<E: Send + Sync, const N: usize> Sync for DisruptorRing<E, N> {}
+
+impl<E, const N: usize> DisruptorRing<E, N> {
+    const MASK: u64 = {
+        assert!(N.is_power_of_two(), "DisruptorChannel size must be a non-zero power of two");
+        N as u64 - 1
+    };
+    
+    fn slot(&self, sequence: u64) -> *mut MaybeUninit<E> {
+        self.buffer[(sequence & Self::MASK) as usize].get()
+    }
+    
+    /// Lowest active consumer cursor (`None` without consumers)
+    fn gating_sequence(&self) -> Option<u64> {
+        // Pairs with the fence in `add_consumer`: either a new consumer's
+        // cursor is seen here, or it starts at or after our last publish
+        fence(Ordering::SeqCst);
+        self.consumers.iter()
+            .map(|cursor| cursor.0.load(Ordering::Acquire))
+            .filter(|&sequence| sequence != INACTIVE)
+            .min()
+    }
+}
+
+impl<E, const N: usize> Drop for DisruptorRing<E, N> {
+    fn drop(&mut self) {
+        let published = (*self.cursor.0.get_mut()).min(N as u64);
+        for sequence in 0..published {
+            // SAFETY: the first min(cursor, N) slots were written and are only
+            // ever overwritten in place, never moved out
+            unsafe { (*self.slot(sequence)).assume_init_drop() };
+        }
+    }
+}
+
+/// LMAX Disruptor-style broadcast ring for the lowest-latency paths
+///
+/// One producer, up to [`MAX_CONSUMERS`] consumers, each of which sees every
+/// event (cloned out of the ring). `N` must be a power of two; other sizes
+/// fail to compile. Cloning yields a handle to the same ring.
+pub struct DisruptorChannel<E, const N: usize> {
+    ring: Arc<DisruptorRing<E, N>>,
+}
+
+impl<E, const N: usize> DisruptorChannel<E, N> {
+    /// Allocate the ring (on the heap, `N` slots)
+    pub fn new() -> Self {
+        let _ = DisruptorRing::<E, N>::MASK;
+        let slots: Box<[UnsafeCell<MaybeUninit<E>>]> = (0..N)
+            .map(|_| UnsafeCell::new(MaybeUninit::uninit()))
+            .collect();
+        let buffer = slots.try_into().unwrap_or_else(|_| unreachable!("collected exactly N slots"));
+        
+        Self {
+            ring: Arc::new(DisruptorRing {
+                buffer,
+                cursor: CachePadded(AtomicU64::new(0)),
+                consumers: std::array::from_fn(|_| CachePadded(AtomicU64::new(INACTIVE))),
+                producer_claimed: AtomicBool::new(false),
+            }),
+        }
+    }
+    
+    /// Claim the producer side (`None` while another token is alive)
+    pub fn new_producer(&self) -> Option<ProducerToken<E, N>> {
+        if self.ring.producer_claimed.swap(true, Ordering::AcqRel) {
+            return None;
+        }
+        let next = self.ring.cursor.0.load(Ordering::Acquire);
+        Some(ProducerToken {
+            ring: self.ring.clone(),
+            next,
+            gating: self.ring.gating_sequence().unwrap_or(next),
+        })
+    }
+    
+    /// Register a consumer starting at the next published event
+    ///
+    /// Returns `None` if [`MAX_CONSUMERS`] consumers are already registered.
+    pub fn add_consumer(&self) -> Option<ConsumerToken<E, N>> {
+        let mut start = self.ring.cursor.0.load(Ordering::Acquire);
+        let (index, slot) = self.ring.consumers.iter()
+            .enumerate()
+            .find(|(_, cursor)| {
+                cursor.0.compare_exchange(INACTIVE, start, Ordering::AcqRel, Ordering::Relaxed).is_ok()
+            })?;
+        
+        // The producer may have checked for room without seeing this
+        // consumer; move the start up until it is at the cursor
+        loop {
+            fence(Ordering::SeqCst);
+            let current = self.ring.cursor.0.load(Ordering::Acquire);
+            if current == start {
+                break;
+            }
+            start = current;
+            slot.0.store(start, Ordering::Release);
+        }
+        
+        Some(ConsumerToken {
+            ring: self.ring.clone(),
+            index,
+            next: start,
+            available: start,
+        })
+    }
+    
+    /// Capacity of the ring
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+}
+
+impl<E, const N: usize> Default for DisruptorChannel<E, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E, const N: usize> Clone for DisruptorChannel<E, N> {
+    fn clone(&self) -> Self {
+        Self { ring: self.ring.clone() }
+    }
+}
+
+/// Producer side of a [`DisruptorChannel`]
+pub struct ProducerToken<E, const N: usize> {
+    ring: Arc<DisruptorRing<E, N>>,
+    /// Sequence of the next event to publish
+    next: u64,
+    /// Last observed lowest consumer cursor (refreshed only when the ring
+    /// looks full)
+    gating: u64,
+}
+
+impl<E, const N: usize> ProducerToken<E, N> {
+    /// Publish without waiting; returns false (dropping `value`) if the
+    /// slowest consumer is a full ring behind
+    ///
+    /// With no consumers registered, old events are overwritten freely.
+    #[inline]
+    pub fn try_publish(&mut self, value: E) -> bool {
+        if !self.claim() {
+            return false;
+        }
+        self.write(value);
+        true
+    }
+    
+    /// Publish, spinning with backoff while the ring is full
+    #[inline]
+    pub fn publish(&mut self, value: E) {
+        let mut backoff = Backoff::new();
+        while !self.claim() {
+            backoff.snooze();
+        }
+        self.write(value);
+    }
+    
+    /// Check there is room for sequence `next`
+    #[inline(always)]
+    fn claim(&mut self) -> bool {
+        if self.next.wrapping_sub(self.gating) < N as u64 {
+            return true;
+        }
+        self.gating = self.ring.gating_sequence().unwrap_or(self.next);
+        self.next.wrapping_sub(self.gating) < N as u64
+    }
+    
+    #[inline(always)]
+    fn write(&mut self, value: E) {
+        let slot = self.ring.slot(self.next);
+        if self.next >= N as u64 {
+            // SAFETY: sequence next - N was written to this slot, and every
+            // consumer has moved past it (`claim`), so no reader holds it
+            unsafe { (*slot).assume_init_drop() };
+        }
+        // SAFETY: as above, the slot is exclusively ours until the cursor
+        // store below publishes it
+        unsafe { (*slot).write(value) };
+        self.next += 1;
+        self.ring.cursor.0.store(self.next, Ordering::Release);
+    }
+}
+
+impl<E, const N: usize> Drop for ProducerToken<E, N> {
+    fn drop(&mut self) {
+        self.ring.producer_claimed.store(false, Ordering::Release);
+    }
+}
+
+/// Consumer side of a [`DisruptorChannel`]
+pub struct ConsumerToken<E, const N: usize> {
+    ring: Arc<DisruptorRing<E, N>>,
+    /// Index of this consumer's cursor in the ring
+    index: usize,
+    /// Sequence of the next event to read
+    next: u64,
+    /// Last observed producer cursor
+    available: u64,
+}
+
+impl<E: Clone, const N: usize> ConsumerToken<E, N> {
+    /// Take the next event, if one has been published
+    #[inline]
+    pub fn try_consume(&mut self) -> Option<E> {
+        if self.next == self.available {
+            self.available = self.ring.cursor.0.load(Ordering::Acquire);
+            if self.next == self.available {
+                return None;
+            }
+        }
+        // SAFETY: `next` is below the producer cursor, so the slot was
+        // written, and the producer cannot overwrite it before this
+        // consumer's cursor moves past `next` below
+        let value = unsafe { (*self.ring.slot(self.next)).assume_init_ref().clone() };
+        self.next += 1;
+        self.ring.consumers[self.index].0.store(self.next, Ordering::Release);
+        Some(value)
+    }
+    
+    /// Take the next event, spinning with backoff until one is published
+    #[inline]
+    pub fn consume(&mut self) -> E {
+        let mut backoff = Backoff::new();
+        loop {
+            if let Some(value) = self.try_consume() {
+                return value;
+            }
+            backoff.snooze();
+        }
+    }
+}
+
+impl<E, const N: usize> ConsumerToken<E, N> {
+    /// Events published but not yet consumed
+    pub fn lag(&self) -> u64 {
+        self.ring.cursor.0.load(Ordering::Acquire) - self.next
+    }
+}
+
+impl<E, const N: usize> Drop for ConsumerToken<E, N> {
+    fn drop(&mut self) {
+        self.ring.consumers[self.index].0.store(INACTIVE, Ordering::Release);
+    }
+}
+
 /// Multi-producer, single-consumer channel for MarketEvent
 pub struct MpscChannel<E: MarketEvent> {
     sender: Sender<E>,
@@ -228,6 +1024,26 @@ mod tests {
         assert!(matches!(result, Err(TrySendError::Full(_))));
     }
     
+    #[test]
+    fn test_fast_channel_dead_letter_on_full() {
+        let dead_letters = DeadLetterQueue::new(8);
+        let channel = FastChannel::<TradeV2>::bounded(4).with_dead_letter_queue(dead_letters.clone());
+        
+        for i in 0..6 {
+            let mut trade = create_test_trade();
+            trade.trade_id = i;
+            let result = channel.try_send(trade);
+            assert_eq!(result.is_ok(), i < 4);
+        }
+        
+        assert_eq!(channel.len(), 4);
+        let captured: Vec<_> = dead_letters.receiver().try_iter().collect();
+        assert_eq!(captured.len(), 2);
+        assert!(captured.iter().all(|e| e.reason == DeadLetterReason::ChannelFull));
+        assert_eq!(captured[0].envelope.trade_id, 4);
+        assert_eq!(captured[1].envelope.trade_id, 5);
+    }
+    
     #[test]
     fn test_fast_channel_empty() {
         let channel = FastChannel::<TradeV2>::bounded(100);
@@ -237,4 +1053,269 @@ mod tests {
         assert!(!channel.is_empty());
         assert_eq!(channel.len(), 1);
     }
+    
+    #[test]
+    fn test_spsc_capacity_validation() {
+        assert!(FastChannel::<TradeV2>::spsc(1024).is_ok());
+        assert_eq!(
+            FastChannel::<TradeV2>::spsc(1000).err(),
+            Some(CapacityError { capacity: 1000 })
+        );
+        assert!(FastChannel::<TradeV2>::spsc(0).is_err());
+    }
+    
+    #[test]
+    fn test_spsc_send_recv() {
+        let (mut tx, mut rx) = FastChannel::<TradeV2>::spsc(4).unwrap();
+        let trade = create_test_trade();
+        
+        assert!(matches!(rx.try_recv(), Err(TryRecvError::Empty)));
+        
+        for _ in 0..4 {
+            tx.try_send(trade).unwrap();
+        }
+        assert!(matches!(tx.try_send(trade), Err(TrySendError::Full(_))));
+        assert_eq!(rx.len(), 4);
+        
+        // Wrap around the ring several times
+        for _ in 0..10 {
+            assert_eq!(rx.try_recv().unwrap().price, trade.price);
+            tx.try_send(trade).unwrap();
+        }
+    }
+    
+    #[test]
+    fn test_spsc_disconnect() {
+        let (mut tx, mut rx) = FastChannel::<TradeV2>::spsc(8).unwrap();
+        tx.send(create_test_trade()).unwrap();
+        drop(tx);
+        
+        // Buffered event is still delivered before disconnect is reported
+        assert!(rx.recv().is_ok());
+        assert!(matches!(rx.try_recv(), Err(TryRecvError::Disconnected)));
+        
+        let (mut tx, rx) = FastChannel::<TradeV2>::spsc(8).unwrap();
+        drop(rx);
+        assert!(tx.send(create_test_trade()).is_err());
+    }
+    
+    #[test]
+    fn test_spsc_cross_thread_order() {
+        let (mut tx, mut rx) = FastChannel::<TradeV2>::spsc(64).unwrap();
+        
+        let producer = std::thread::spawn(move || {
+            for i in 0..100_000u64 {
+                let mut trade = create_test_trade();
+                trade.trade_id = i;
+                tx.send(trade).unwrap();
+            }
+        });
+        
+        for i in 0..100_000u64 {
+            assert_eq!(rx.recv().unwrap().trade_id, i);
+        }
+        producer.join().unwrap();
+    }
+    
+    #[test]
+    fn test_spsc_drops_buffered_events() {
+        use std::sync::atomic::AtomicUsize;
+        
+        static DROPS: AtomicUsize = AtomicUsize::new(0);
+        
+        struct Tracked;
+        impl Drop for Tracked {
+            fn drop(&mut self) {
+                DROPS.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+        
+        let ring = Arc::new(SpscRing::<Tracked>::new(4).unwrap());
+        let mut tx = SpscSender { ring: ring.clone() };
+        tx.try_send(Tracked).ok().unwrap();
+        tx.try_send(Tracked).ok().unwrap();
+        drop(tx);
+        drop(ring);
+        
+        assert_eq!(DROPS.load(Ordering::SeqCst), 2);
+    }
+    
+    #[test]
+    fn test_token_bucket_refill() {
+        let bucket = TokenBucket::new(3, 1_000);
+        assert!((0..3).all(|_| bucket.try_acquire()));
+        assert!(!bucket.try_acquire());
+        
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        // Refilled, but never past capacity
+        assert_eq!(bucket.available(), 3);
+    }
+    
+    #[test]
+    fn test_rate_limited_send() {
+        let channel = FastChannel::<TradeV2>::unbounded().with_rate_limit(10);
+        assert_eq!(channel.bucket().capacity(), 1);
+        channel.send(create_test_trade()).unwrap();
+        assert!(matches!(channel.send(create_test_trade()), Err(ThrottleError::RateLimited(_))));
+        assert!(matches!(channel.send_blocking(create_test_trade(), 0), Err(ThrottleError::RateLimited(_))));
+        
+        // A token arrives after 100 ms
+        channel.send_blocking(create_test_trade(), 1_000_000_000).unwrap();
+        assert_eq!(channel.channel().len(), 2);
+        assert!(channel.try_recv().is_ok());
+    }
+    
+    #[test]
+    fn test_rate_limited_throughput() {
+        let channel = FastChannel::<TradeV2>::unbounded().with_rate_limit(100_000);
+        let start = std::time::Instant::now();
+        for _ in 0..5_000 {
+            channel.send_blocking(create_test_trade(), u64::MAX).unwrap();
+        }
+        // 5k events at 100k/s, less the 100-event initial burst; only the
+        // lower bound is checked since a loaded machine can run slower
+        let elapsed = start.elapsed().as_secs_f64();
+        assert!(elapsed >= 0.045, "took {}s", elapsed);
+    }
+    
+    #[test]
+    fn test_disruptor_broadcast() {
+        let channel = DisruptorChannel::<u64, 4>::new();
+        let mut producer = channel.new_producer().unwrap();
+        let mut fast = channel.add_consumer().unwrap();
+        let mut slow = channel.add_consumer().unwrap();
+        
+        assert_eq!(fast.try_consume(), None);
+        for i in 0..4 {
+            assert!(producer.try_publish(i));
+        }
+        // The slowest consumer is a full ring behind
+        assert!(!producer.try_publish(4));
+        
+        for i in 0..4 {
+            assert_eq!(fast.try_consume(), Some(i));
+        }
+        assert!(!producer.try_publish(4));
+        assert_eq!(slow.try_consume(), Some(0));
+        assert!(producer.try_publish(5));
+        assert_eq!(slow.lag(), 4);
+        
+        // Consumers added later start at the next event
+        let mut late = channel.add_consumer().unwrap();
+        assert_eq!(late.try_consume(), None);
+        drop(slow);
+        assert!(producer.try_publish(6));
+        assert_eq!(fast.try_consume(), Some(5));
+        assert_eq!(late.try_consume(), Some(6));
+    }
+    
+    #[test]
+    fn test_disruptor_token_limits() {
+        let channel = DisruptorChannel::<u64, 8>::new();
+        let producer = channel.new_producer().unwrap();
+        assert!(channel.new_producer().is_none());
+        drop(producer);
+        assert!(channel.clone().new_producer().is_some());
+        
+        let mut consumers: Vec<_> = (0..MAX_CONSUMERS).map(|_| channel.add_consumer().unwrap()).collect();
+        assert!(channel.add_consumer().is_none());
+        consumers.pop();
+        assert!(channel.add_consumer().is_some());
+    }
+    
+    #[test]
+    fn test_disruptor_cross_thread_order() {
+        let channel = DisruptorChannel::<TradeV2, 64>::new();
+        let mut producer = channel.new_producer().unwrap();
+        let consumers: Vec<_> = (0..2)
+            .map(|_| {
+                let mut consumer = channel.add_consumer().unwrap();
+                std::thread::spawn(move || {
+                    for i in 0..100_000u64 {
+                        assert_eq!(consumer.consume().trade_id, i);
+                    }
+                })
+            })
+            .collect();
+        
+        for i in 0..100_000u64 {
+            let mut trade = create_test_trade();
+            trade.trade_id = i;
+            producer.publish(trade);
+        }
+        for consumer in consumers {
+            consumer.join().unwrap();
+        }
+    }
+    
+    #[test]
+    fn test_disruptor_drops_events() {
+        use std::sync::atomic::AtomicUsize;
+        
+        static DROPS: AtomicUsize = AtomicUsize::new(0);
+        
+        #[derive(Clone)]
+        struct Tracked;
+        impl Drop for Tracked {
+            fn drop(&mut self) {
+                DROPS.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+        
+        // Without consumers old events are overwritten
+        let channel = DisruptorChannel::<Tracked, 4>::new();
+        let mut producer = channel.new_producer().unwrap();
+        for _ in 0..6 {
+            assert!(producer.try_publish(Tracked));
+        }
+        assert_eq!(DROPS.load(Ordering::SeqCst), 2);
+        
+        drop(producer);
+        drop(channel);
+        assert_eq!(DROPS.load(Ordering::SeqCst), 6);
+    }
+    
+    #[tokio::test]
+    async fn test_async_send_recv_across_tasks() {
+        let (tx, rx) = FastChannel::<TradeV2>::bounded(8).into_async();
+        
+        // Capacity is far below the event count, so the producer must wait
+        let producer = tokio::spawn(async move {
+            for i in 0..1_000u64 {
+                let mut trade = create_test_trade();
+                trade.trade_id = i;
+                tx.send(trade).await.unwrap();
+            }
+        });
+        
+        for i in 0..1_000u64 {
+            assert_eq!(rx.recv().await.unwrap().trade_id, i);
+        }
+        producer.await.unwrap();
+        
+        // All senders gone
+        assert!(rx.recv().await.is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_async_recv_cancel_safe() {
+        let channel = FastChannel::<TradeV2>::bounded(8);
+        let rx = channel.async_receiver();
+        
+        // A timed-out recv must not swallow the event sent afterwards
+        let timed_out = tokio::time::timeout(std::time::Duration::from_millis(5), rx.recv()).await;
+        assert!(timed_out.is_err());
+        
+        let tx = channel.async_sender();
+        let consumer = tokio::spawn(async move { rx.recv().await.unwrap().trade_id });
+        tx.send(create_test_trade()).await.unwrap();
+        assert_eq!(consumer.await.unwrap(), 1);
+        
+        // Sync and async halves share the channel
+        channel.send(create_test_trade()).unwrap();
+        assert_eq!(channel.async_receiver().try_recv().unwrap().trade_id, 1);
+        
+        drop(channel);
+        assert!(tx.is_disconnected());
+    }
 }
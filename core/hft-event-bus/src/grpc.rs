@@ -0,0 +1,237 @@
+//! gRPC endpoint for remote subscribers (requires the `grpc` feature)
+//!
+//! Implements `EventBusService` from `proto/event_bus.proto`:
+//!
+//! - `Subscribe` streams every envelope published on the requested event
+//!   types, optionally limited to `priority <= priority_filter`.
+//! - `Publish` rebuilds the event through the `EventRegistry` and publishes
+//!   it on the bus. Unregistered event types are rejected.
+//!
+//! Payloads travel as JSON strings, in the same shape `to_json` produces.
+
+use crate::bus::EventBus;
+use crate::events::EventEnvelope;
+use crate::serde_support::EventRegistry;
+use anyhow::Result;
+use futures_util::Stream;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use tokio::net::TcpListener;
+use tokio::sync::{broadcast, mpsc};
+use tokio_stream::wrappers::{ReceiverStream, TcpListenerStream};
+use tonic::{Request, Response, Status};
+use tracing::{info, warn};
+
+/// Types generated from `proto/event_bus.proto`
+pub mod proto {
+    tonic::include_proto!("event_bus");
+}
+
+use proto::event_bus_service_server::{EventBusService, EventBusServiceServer};
+use proto::{EventResponse, PublishRequest, PublishResponse, SubscribeRequest};
+
+/// Responses buffered per subscription before forwarders wait
+const SUBSCRIPTION_BUFFER_SIZE: usize = 1024;
+
+/// Serves EventBus events over gRPC
+#[derive(Clone)]
+pub struct EventBusGrpcServer {
+    bus: EventBus,
+}
+
+impl EventBusGrpcServer {
+    /// Create server exposing `bus`
+    pub fn new(bus: EventBus) -> Self {
+        Self { bus }
+    }
+    
+    /// Bind `addr` and serve until an error occurs
+    pub async fn serve(self, addr: SocketAddr) -> Result<()> {
+        let listener = TcpListener::bind(addr).await?;
+        self.serve_with_listener(listener).await
+    }
+    
+    /// Serve on an already bound listener
+    pub async fn serve_with_listener(self, listener: TcpListener) -> Result<()> {
+        info!("gRPC endpoint listening on {}", listener.local_addr()?);
+        tonic::transport::Server::builder()
+            .add_service(EventBusServiceServer::new(self))
+            .serve_with_incoming(TcpListenerStream::new(listener))
+            .await?;
+        Ok(())
+    }
+}
+
+/// Convert an envelope into its wire form
+fn event_response(envelope: &EventEnvelope) -> EventResponse {
+    EventResponse {
+        id: envelope.id.to_string(),
+        timestamp_ns: envelope.timestamp_ns,
+        priority: envelope.priority as u32,
+        event_type: envelope.event.event_type().to_string(),
+        payload_json: envelope.event.to_json().unwrap_or(serde_json::Value::Null).to_string(),
+    }
+}
+
+/// Move matching envelopes from a bus subscription into a client stream
+///
+/// Returns as soon as the client drops the stream, even if no event arrives.
+async fn forward(
+    mut receiver: broadcast::Receiver<EventEnvelope>,
+    tx: mpsc::Sender<Result<EventResponse, Status>>,
+    priority_filter: Option<u8>,
+) {
+    loop {
+        let received = tokio::select! {
+            _ = tx.closed() => return,
+            received = receiver.recv() => received,
+        };
+        match received {
+            Ok(envelope) => {
+                if priority_filter.is_some_and(|max| envelope.priority > max) {
+                    continue;
+                }
+                if tx.send(Ok(event_response(&envelope))).await.is_err() {
+                    return;
+                }
+            }
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                warn!("gRPC forwarder lagged, skipped {} events", skipped);
+            }
+            Err(broadcast::error::RecvError::Closed) => return,
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl EventBusService for EventBusGrpcServer {
+    type SubscribeStream = Pin<Box<dyn Stream<Item = Result<EventResponse, Status>> + Send>>;
+    
+    async fn subscribe(&self, request: Request<SubscribeRequest>) -> Result<Response<Self::SubscribeStream>, Status> {
+        let request = request.into_inner();
+        if request.event_types.is_empty() {
+            return Err(Status::invalid_argument("event_types must not be empty"));
+        }
+        let priority_filter = request.priority_filter.map(|p| p.min(u8::MAX as u32) as u8);
+        
+        // Forwarders exit once the client drops the stream (see `forward`)
+        let (tx, rx) = mpsc::channel(SUBSCRIPTION_BUFFER_SIZE);
+        for event_type in &request.event_types {
+            let receiver = self.bus.subscribe(event_type).await;
+            tokio::spawn(forward(receiver, tx.clone(), priority_filter));
+        }
+        
+        Ok(Response::new(Box::pin(ReceiverStream::new(rx))))
+    }
+    
+    async fn publish(&self, request: Request<PublishRequest>) -> Result<Response<PublishResponse>, Status> {
+        let request = request.into_inner();
+        let priority = u8::try_from(request.priority)
+            .map_err(|_| Status::invalid_argument("priority must fit in u8"))?;
+        let payload: serde_json::Value = serde_json::from_str(&request.payload_json)
+            .map_err(|e| Status::invalid_argument(format!("invalid payload_json: {}", e)))?;
+        
+        if !EventRegistry::is_registered(&request.event_type) {
+            return Err(Status::invalid_argument(format!("unregistered event_type: {}", request.event_type)));
+        }
+        let event = EventRegistry::deserialize(&request.event_type, payload)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+        let envelope = EventEnvelope::from_boxed(event, priority);
+        let id = envelope.id.to_string();
+        
+        self.bus.publish_envelope(envelope).await
+            .map_err(|e| Status::internal(e.to_string()))?;
+        Ok(Response::new(PublishResponse { id }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{MarketDataEvent, OrderStatus, OrderUpdateEvent};
+    use crate::test_fixtures::quote;
+    use futures_util::StreamExt;
+    use proto::event_bus_service_client::EventBusServiceClient;
+    use tonic::transport::Channel;
+    
+    async fn start_server(bus: EventBus) -> EventBusServiceClient<Channel> {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(EventBusGrpcServer::new(bus).serve_with_listener(listener));
+        EventBusServiceClient::connect(format!("http://{}", addr)).await.unwrap()
+    }
+    
+    #[tokio::test]
+    async fn test_subscribe_streams_published_events() {
+        let bus = EventBus::new();
+        let mut client = start_server(bus.clone()).await;
+        
+        let mut stream = client.subscribe(SubscribeRequest {
+            event_types: vec!["market_data".to_string()],
+            priority_filter: Some(2),
+        }).await.unwrap().into_inner();
+        
+        // Subscription is registered once the response headers arrive
+        bus.publish(OrderUpdateEvent {
+            order_id: uuid::Uuid::new_v4(),
+            timestamp: 0,
+            status: OrderStatus::Filled,
+            filled_quantity: 1.0,
+            remaining_quantity: 0.0,
+        }).await.unwrap();
+        bus.publish(quote("ES", 1.0)).await.unwrap();
+        bus.publish_with_priority(quote("ES", 6000.0), 1).await.unwrap();
+        
+        let response = stream.next().await.unwrap().unwrap();
+        assert_eq!(response.event_type, "market_data");
+        assert_eq!(response.priority, 1);
+        let payload: serde_json::Value = serde_json::from_str(&response.payload_json).unwrap();
+        assert_eq!(payload["price"], 6000.0);
+    }
+    
+    #[tokio::test]
+    async fn test_publish_rebuilds_registered_events() {
+        let bus = EventBus::new();
+        let mut client = start_server(bus.clone()).await;
+        let mut receiver = bus.subscribe_market_data().await;
+        
+        let response = client.publish(PublishRequest {
+            event_type: "market_data".to_string(),
+            priority: 0,
+            payload_json: serde_json::to_string(&quote("ES", 6000.0)).unwrap(),
+        }).await.unwrap().into_inner();
+        
+        let envelope = receiver.recv().await.unwrap();
+        assert_eq!(envelope.id.to_string(), response.id);
+        assert_eq!(envelope.priority, 0);
+        assert_eq!(envelope.event.downcast_ref::<MarketDataEvent>().unwrap().price, 6000.0);
+        
+        let invalid = client.publish(PublishRequest {
+            event_type: "market_data".to_string(),
+            priority: 5,
+            payload_json: "not json".to_string(),
+        }).await;
+        assert_eq!(invalid.unwrap_err().code(), tonic::Code::InvalidArgument);
+        
+        let unregistered = client.publish(PublishRequest {
+            event_type: "no_such_event".to_string(),
+            priority: 5,
+            payload_json: "{}".to_string(),
+        }).await;
+        assert_eq!(unregistered.unwrap_err().code(), tonic::Code::InvalidArgument);
+    }
+    
+    #[tokio::test]
+    async fn test_forwarder_exits_when_client_drops_stream() {
+        let bus = EventBus::new();
+        let receiver = bus.subscribe("quiet").await;
+        let (tx, rx) = mpsc::channel(SUBSCRIPTION_BUFFER_SIZE);
+        let forwarder = tokio::spawn(forward(receiver, tx, None));
+        
+        // Nothing is ever published on "quiet"
+        drop(rx);
+        tokio::time::timeout(std::time::Duration::from_secs(1), forwarder).await
+            .expect("forwarder outlived its client")
+            .unwrap();
+    }
+}
@@ -0,0 +1,248 @@
+//! Aggregated component health
+//!
+//! `HealthMonitor` folds `HealthEvent`s into per-component state: current
+//! status, when it last changed, how many unhealthy reports arrived in a row
+//! and the most recent reports. Attach it to a bus with `watch_bus`.
+
+use crate::bus::EventBus;
+use crate::events::{EventEnvelope, HealthEvent, HealthStatus, StaticEventType};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, RwLock};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tracing::warn;
+
+/// Reports kept per component
+pub const HEALTH_HISTORY_LEN: usize = 100;
+
+/// Alert callback registered with `HealthMonitor::alert_on_degraded`
+type AlertFn = Box<dyn Fn(&str, HealthStatus) + Send>;
+
+/// Health state of one component
+#[derive(Debug, Clone)]
+pub struct ComponentHealth {
+    pub current_status: HealthStatus,
+    /// Timestamp of the report that set `current_status`
+    pub last_changed_ns: i64,
+    /// Unhealthy reports since the last non-unhealthy one
+    pub consecutive_unhealthy: u32,
+    /// Last `HEALTH_HISTORY_LEN` reports, oldest first
+    pub history: VecDeque<HealthEvent>,
+}
+
+/// Ordering used for worst-case aggregation
+fn severity(status: HealthStatus) -> u8 {
+    match status {
+        HealthStatus::Healthy => 0,
+        HealthStatus::Degraded => 1,
+        HealthStatus::Unhealthy => 2,
+    }
+}
+
+/// Per-component health built from `HealthEvent`s
+///
+/// Cloning yields a handle to the same state.
+#[derive(Clone, Default)]
+pub struct HealthMonitor {
+    components: Arc<RwLock<HashMap<String, ComponentHealth>>>,
+    alerts: Arc<Mutex<Vec<AlertFn>>>,
+}
+
+impl HealthMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply one health report
+    ///
+    /// Alerts fire when a component enters `Degraded` or `Unhealthy`, whether
+    /// from a better status or on its first report.
+    pub fn record(&self, event: &HealthEvent) {
+        let entered = {
+            let mut components = self.components.write().unwrap();
+            let health = components.entry(event.component.clone()).or_insert_with(|| ComponentHealth {
+                current_status: event.status,
+                last_changed_ns: event.timestamp,
+                consecutive_unhealthy: 0,
+                history: VecDeque::with_capacity(HEALTH_HISTORY_LEN),
+            });
+            // History is only empty before the first report
+            let entered = health.history.is_empty() || health.current_status != event.status;
+            if health.current_status != event.status {
+                health.current_status = event.status;
+                health.last_changed_ns = event.timestamp;
+            }
+
+            if event.status == HealthStatus::Unhealthy {
+                health.consecutive_unhealthy += 1;
+            } else {
+                health.consecutive_unhealthy = 0;
+            }
+            if health.history.len() == HEALTH_HISTORY_LEN {
+                health.history.pop_front();
+            }
+            health.history.push_back(event.clone());
+            entered && event.status != HealthStatus::Healthy
+        };
+
+        // Run callbacks without holding the component lock
+        if entered {
+            for alert in self.alerts.lock().unwrap().iter() {
+                alert(&event.component, event.status);
+            }
+        }
+    }
+
+    /// Worst status across all components (`Healthy` if none reported)
+    pub fn overall_status(&self) -> HealthStatus {
+        self.components.read().unwrap()
+            .values()
+            .map(|health| health.current_status)
+            .max_by_key(|status| severity(*status))
+            .unwrap_or(HealthStatus::Healthy)
+    }
+
+    /// Call `callback` with the component name and status whenever a
+    /// component becomes degraded or unhealthy
+    pub fn alert_on_degraded(&self, callback: impl Fn(&str, HealthStatus) + Send + 'static) {
+        self.alerts.lock().unwrap().push(Box::new(callback));
+    }
+
+    /// Names of all components that have reported, sorted
+    pub fn components(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.components.read().unwrap().keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Current state of `component`
+    pub fn component(&self, component: &str) -> Option<ComponentHealth> {
+        self.components.read().unwrap().get(component).cloned()
+    }
+
+    /// Record every `HealthEvent` published on `bus`
+    ///
+    /// The task ends when the bus's health channel closes; abort the handle
+    /// to stop earlier.
+    pub async fn watch_bus(&self, bus: &EventBus) -> JoinHandle<()> {
+        let mut receiver = bus.subscribe(HealthEvent::EVENT_TYPE).await;
+        let monitor = self.clone();
+        tokio::spawn(async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(envelope) => monitor.record_envelope(envelope),
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!("Health monitor lagged, skipped {} events", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return,
+                }
+            }
+        })
+    }
+
+    fn record_envelope(&self, envelope: EventEnvelope) {
+        match HealthEvent::try_from(envelope) {
+            Ok(event) => self.record(&event),
+            Err(e) => warn!("Health monitor ignored event: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn report(component: &str, status: HealthStatus, timestamp: i64) -> HealthEvent {
+        HealthEvent {
+            timestamp,
+            component: component.to_string(),
+            status,
+            message: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_state_transitions() {
+        let monitor = HealthMonitor::new();
+        monitor.record(&report("oms", HealthStatus::Healthy, 10));
+        monitor.record(&report("oms", HealthStatus::Healthy, 20));
+        let health = monitor.component("oms").unwrap();
+        assert_eq!(health.current_status, HealthStatus::Healthy);
+        assert_eq!(health.last_changed_ns, 10);
+
+        monitor.record(&report("oms", HealthStatus::Unhealthy, 30));
+        monitor.record(&report("oms", HealthStatus::Unhealthy, 40));
+        let health = monitor.component("oms").unwrap();
+        assert_eq!(health.last_changed_ns, 30);
+        assert_eq!(health.consecutive_unhealthy, 2);
+
+        // Recovery resets the streak
+        monitor.record(&report("oms", HealthStatus::Degraded, 50));
+        let health = monitor.component("oms").unwrap();
+        assert_eq!(health.current_status, HealthStatus::Degraded);
+        assert_eq!(health.last_changed_ns, 50);
+        assert_eq!(health.consecutive_unhealthy, 0);
+        assert_eq!(health.history.len(), 5);
+
+        for t in 0..150 {
+            monitor.record(&report("oms", HealthStatus::Healthy, 100 + t));
+        }
+        let health = monitor.component("oms").unwrap();
+        assert_eq!(health.history.len(), HEALTH_HISTORY_LEN);
+        assert_eq!(health.history.front().unwrap().timestamp, 150);
+        assert_eq!(health.last_changed_ns, 100);
+    }
+
+    #[test]
+    fn test_overall_status_and_alerts() {
+        let monitor = HealthMonitor::new();
+        assert_eq!(monitor.overall_status(), HealthStatus::Healthy);
+
+        let alerts = Arc::new(Mutex::new(Vec::new()));
+        let seen = alerts.clone();
+        monitor.alert_on_degraded(move |component, status| {
+            seen.lock().unwrap().push((component.to_string(), status));
+        });
+
+        monitor.record(&report("feed", HealthStatus::Healthy, 1));
+        monitor.record(&report("risk", HealthStatus::Degraded, 1));
+        assert_eq!(monitor.overall_status(), HealthStatus::Degraded);
+
+        monitor.record(&report("oms", HealthStatus::Unhealthy, 2));
+        monitor.record(&report("oms", HealthStatus::Unhealthy, 3));
+        assert_eq!(monitor.overall_status(), HealthStatus::Unhealthy);
+
+        monitor.record(&report("oms", HealthStatus::Healthy, 4));
+        monitor.record(&report("risk", HealthStatus::Healthy, 4));
+        assert_eq!(monitor.overall_status(), HealthStatus::Healthy);
+        assert_eq!(monitor.components(), vec!["feed", "oms", "risk"]);
+
+        // Only transitions into a bad state alert, repeats do not
+        assert_eq!(
+            *alerts.lock().unwrap(),
+            vec![
+                ("risk".to_string(), HealthStatus::Degraded),
+                ("oms".to_string(), HealthStatus::Unhealthy),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_watch_bus() {
+        let bus = EventBus::new();
+        let monitor = HealthMonitor::new();
+        let task = monitor.watch_bus(&bus).await;
+
+        bus.publish(report("gateway", HealthStatus::Degraded, 7)).await.unwrap();
+        for _ in 0..100 {
+            if monitor.component("gateway").is_some() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+        assert_eq!(monitor.component("gateway").unwrap().current_status, HealthStatus::Degraded);
+        assert_eq!(monitor.overall_status(), HealthStatus::Degraded);
+        task.abort();
+    }
+}
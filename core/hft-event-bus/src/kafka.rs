@@ -0,0 +1,214 @@
+//! Kafka bridge for cross-service events (requires the `kafka` feature)
+//!
+//! Outbound event types are produced to topics named
+//! `"{topic_prefix}.{event_type}"` as JSON `SerializableEnvelope`s, keyed by
+//! envelope ID. Inbound topics are consumed and the envelopes published on
+//! the local bus with their original ID and timestamp.
+//!
+//! Every produced message carries an `origin` header with the bridge's ID.
+//! A bridge ignores its own messages and does not re-produce envelopes it
+//! consumed, so one type may be both outbound and inbound without looping.
+
+use crate::bus::EventBus;
+use crate::serde_support::SerializableEnvelope;
+use anyhow::{anyhow, Result};
+use dashmap::DashMap;
+use rdkafka::config::ClientConfig;
+use rdkafka::consumer::{Consumer, StreamConsumer};
+use rdkafka::message::{Header, Headers, Message, OwnedHeaders};
+use rdkafka::producer::{FutureProducer, FutureRecord};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+/// Header naming the bridge that produced a message
+const ORIGIN_HEADER: &str = "origin";
+
+/// How long a produce call may wait for queue space
+const PRODUCE_QUEUE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Connection and routing settings for `KafkaBridge`
+#[derive(Debug, Clone)]
+pub struct KafkaBridgeConfig {
+    /// Bootstrap servers, e.g. `"localhost:9092"`
+    pub brokers: String,
+    pub topic_prefix: String,
+    pub consumer_group: String,
+    /// Event types produced to Kafka
+    pub outbound_types: Vec<String>,
+    /// Event types consumed from Kafka
+    pub inbound_types: Vec<String>,
+}
+
+impl KafkaBridgeConfig {
+    /// Topic carrying `event_type`
+    pub fn topic(&self, event_type: &str) -> String {
+        format!("{}.{}", self.topic_prefix, event_type)
+    }
+}
+
+/// Bridges an `EventBus` to Kafka topics
+pub struct KafkaBridge {
+    bus: EventBus,
+    config: KafkaBridgeConfig,
+    origin: String,
+    producer: FutureProducer,
+    consumer: Arc<StreamConsumer>,
+    /// (id, timestamp_ns) of consumed envelopes the outbound task must skip
+    consumed: Arc<DashMap<(Uuid, i64), ()>>,
+}
+
+impl KafkaBridge {
+    /// Create producer and consumer clients for `config`
+    pub fn new(bus: EventBus, config: KafkaBridgeConfig) -> Result<Self> {
+        let producer: FutureProducer = ClientConfig::new()
+            .set("bootstrap.servers", &config.brokers)
+            .set("message.timeout.ms", "5000")
+            .create()?;
+        let consumer: StreamConsumer = ClientConfig::new()
+            .set("bootstrap.servers", &config.brokers)
+            .set("group.id", &config.consumer_group)
+            .set("enable.auto.commit", "true")
+            .set("auto.offset.reset", "latest")
+            .create()?;
+        
+        Ok(Self {
+            bus,
+            config,
+            origin: Uuid::new_v4().to_string(),
+            producer,
+            consumer: Arc::new(consumer),
+            consumed: Arc::new(DashMap::new()),
+        })
+    }
+    
+    /// Bridge configuration
+    pub fn config(&self) -> &KafkaBridgeConfig {
+        &self.config
+    }
+    
+    /// Spawn the outbound and inbound tasks
+    ///
+    /// The returned handle resolves when both tasks finish or either fails,
+    /// in which case the other is aborted. Bus subscriptions are made when
+    /// the outbound task starts.
+    pub fn run(&self) -> JoinHandle<Result<()>> {
+        let outbound = tokio::spawn(outbound(
+            self.bus.clone(),
+            self.config.clone(),
+            self.origin.clone(),
+            self.producer.clone(),
+            self.consumed.clone(),
+        ));
+        let inbound = tokio::spawn(inbound(
+            self.bus.clone(),
+            self.config.clone(),
+            self.origin.clone(),
+            self.consumer.clone(),
+            self.consumed.clone(),
+        ));
+        
+        let abort = [outbound.abort_handle(), inbound.abort_handle()];
+        tokio::spawn(async move {
+            let result = tokio::try_join!(join(outbound), join(inbound)).map(|_| ());
+            for handle in abort {
+                handle.abort();
+            }
+            result
+        })
+    }
+}
+
+async fn join(task: JoinHandle<Result<()>>) -> Result<()> {
+    task.await.map_err(|e| anyhow!("Kafka bridge task failed: {}", e))?
+}
+
+/// Produce subscribed bus events to their topics
+async fn outbound(
+    bus: EventBus,
+    config: KafkaBridgeConfig,
+    origin: String,
+    producer: FutureProducer,
+    consumed: Arc<DashMap<(Uuid, i64), ()>>,
+) -> Result<()> {
+    if config.outbound_types.is_empty() {
+        return Ok(());
+    }
+    let event_types: Vec<&str> = config.outbound_types.iter().map(String::as_str).collect();
+    let mut receiver = bus.subscribe_merged(&event_types).await;
+    info!("Kafka bridge producing {:?}", config.outbound_types);
+    
+    while let Some(envelope) = receiver.recv().await {
+        if consumed.remove(&(envelope.id, envelope.timestamp_ns)).is_some() {
+            continue;
+        }
+        let Some(serializable) = envelope.to_serializable() else {
+            debug!("Skipping {} event without JSON form", envelope.event.event_type());
+            continue;
+        };
+        
+        let topic = config.topic(&serializable.event_type);
+        let key = serializable.id.to_string();
+        let payload = serde_json::to_vec(&serializable)?;
+        let record = FutureRecord::to(&topic)
+            .key(&key)
+            .payload(&payload)
+            .headers(OwnedHeaders::new().insert(Header { key: ORIGIN_HEADER, value: Some(origin.as_str()) }));
+        if let Err((e, _)) = producer.send(record, PRODUCE_QUEUE_TIMEOUT).await {
+            warn!("Failed to produce to {}: {}", topic, e);
+        }
+    }
+    Ok(())
+}
+
+/// Publish consumed envelopes on the bus
+async fn inbound(
+    bus: EventBus,
+    config: KafkaBridgeConfig,
+    origin: String,
+    consumer: Arc<StreamConsumer>,
+    consumed: Arc<DashMap<(Uuid, i64), ()>>,
+) -> Result<()> {
+    if config.inbound_types.is_empty() {
+        return Ok(());
+    }
+    let topics: Vec<String> = config.inbound_types.iter().map(|t| config.topic(t)).collect();
+    let topic_refs: Vec<&str> = topics.iter().map(String::as_str).collect();
+    consumer.subscribe(&topic_refs)?;
+    info!("Kafka bridge consuming {:?}", topics);
+    
+    loop {
+        let message = consumer.recv().await?;
+        let own = message.headers().is_some_and(|headers| {
+            headers.iter().any(|h| h.key == ORIGIN_HEADER && h.value == Some(origin.as_bytes()))
+        });
+        if own {
+            continue;
+        }
+        let Some(payload) = message.payload() else {
+            continue;
+        };
+        
+        // Skipped messages are still consumed, so auto-commit moves the
+        // offset past them
+        let decoded = serde_json::from_slice::<SerializableEnvelope>(payload)
+            .map_err(anyhow::Error::from)
+            .and_then(SerializableEnvelope::into_envelope_or_raw);
+        let envelope = match decoded {
+            Ok(envelope) => envelope,
+            Err(e) => {
+                warn!("Skipping malformed message on {}: {}", message.topic(), e);
+                continue;
+            }
+        };
+        if config.outbound_types.iter().any(|t| t == envelope.event.event_type()) {
+            consumed.insert((envelope.id, envelope.timestamp_ns), ());
+        }
+        let id = envelope.id;
+        if let Err(e) = bus.publish_envelope(envelope).await {
+            warn!("Failed to publish {} from {}: {}", id, message.topic(), e);
+        }
+    }
+}
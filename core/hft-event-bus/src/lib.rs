@@ -41,10 +41,44 @@
 // Legacy event system
 pub mod events;
 pub mod bus;
+pub mod backpressure;
+pub mod backpressure_bus;
 pub mod subscriber;
 pub mod publisher;
 pub mod replay;
+pub mod event_log;
 pub mod replay_mode;
+pub mod content_cache;
+pub mod causality;
+pub mod dead_letter;
+pub mod dedup;
+pub mod health;
+pub mod order_book;
+pub mod pattern;
+pub mod pnl;
+pub mod pool;
+pub mod priority_bus;
+pub mod retry;
+pub mod sharded_bus;
+pub mod metrics;
+pub mod middleware;
+pub mod serde_support;
+pub mod signal_schema;
+pub mod size_budget;
+#[cfg(feature = "websocket")]
+pub mod bridge;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+#[cfg(feature = "kafka")]
+pub mod kafka;
+#[cfg(feature = "nats")]
+pub mod nats;
+#[cfg(feature = "metrics")]
+pub mod metrics_endpoint;
+#[cfg(feature = "parquet")]
+pub mod parquet;
+#[cfg(feature = "arrow")]
+pub mod arrow_ipc;
 
 // New typed event system (zero-allocation)
 pub mod fast_channel;
@@ -53,17 +87,62 @@ pub mod typed_bus;
 // Research topic events
 pub mod research_topic;
 
+#[cfg(test)]
+pub(crate) mod test_fixtures;
+
 // Re-exports
 pub use events::*;
-pub use bus::EventBus;
-pub use subscriber::Subscriber;
+pub use bus::{
+    BridgeHandle, BusSnapshot, ChannelSnapshot, EventBus, EventBusTrait, ForkConfig, GroupHandle, GroupPublishResult, JoinReceiver,
+    MergedReceiver, MulticastGroup, PriorityBus, PublishDecision, PublishReceipt, SampledReceiver, TransactionEvent, TransactionReceipt,
+    TypedReceiver, WindowedReceiver, SAMPLING_WINDOW,
+};
+pub use backpressure::{BackpressureReceiver, BackpressureStrategy};
+pub use backpressure_bus::{BackpressureEventBus, PublishTimeoutError};
+pub use subscriber::{
+    AggregatingSubscriber, ContiguousSubscriber, FilteredSubscriber, GapError, MappedSubscriber, RecvTimeoutError, Subscriber, TypedSubscriber,
+};
 pub use publisher::Publisher;
 pub use replay::EventRecorder;
-pub use replay_mode::{EventReplay, EventReplayBuilder, ReplaySpeed, ReplayStats, VirtualClock};
+pub use content_cache::ContentAddressedCache;
+pub use causality::{CausalityGraph, CausalityTree};
+pub use dead_letter::{DeadLetterEntry, DeadLetterQueue, DeadLetterReason};
+pub use dedup::{BulkPublisher, DedupPublisher, DedupStats, SignalDeduplicator};
+pub use health::{ComponentHealth, HealthMonitor, HEALTH_HISTORY_LEN};
+pub use order_book::OrderBook;
+pub use pattern::PatternKind;
+pub use pnl::{AttributedPnl, PnlAttributor, StrategySummary, UNATTRIBUTED_STRATEGY};
+pub use pool::{EnvelopePool, PooledEnvelope};
+pub use priority_bus::PriorityEventBus;
+pub use retry::{BackoffStrategy, RetryOrchestrator, RetryPolicy, MAX_TRACKED_RETRIES};
+pub use sharded_bus::{ShardedEventBus, ShardingConfig, DEFAULT_SHARD_COUNT};
+#[cfg(feature = "websocket")]
+pub use bridge::WebSocketBridge;
+#[cfg(feature = "grpc")]
+pub use grpc::EventBusGrpcServer;
+#[cfg(feature = "kafka")]
+pub use kafka::{KafkaBridge, KafkaBridgeConfig};
+#[cfg(feature = "nats")]
+pub use nats::{NatsBridge, NatsBridgeConfig};
+#[cfg(feature = "metrics")]
+pub use metrics_endpoint::MetricsEndpoint;
+pub use metrics::{LatencyRecorder, LatencySummary, MetricEventBus, MetricSnapshot, MetricSubscriber};
+pub use middleware::{LoggingMiddleware, Middleware, MiddlewareResult, RateLimitMiddleware};
+pub use serde_support::{EventRegistry, SerializableEnvelope};
+pub use hft_event_bus_macros::register_event;
+pub use signal_schema::{SignalSchemaRegistry, ValidationError};
+pub use size_budget::{EventTooLargeError, SizeBudgetPolicy, TruncateFn};
+pub use replay_mode::{
+    EventReplay, EventReplayBuilder, PositionState, ReplayHandle, ReplaySnapshot, ReplaySpeed, ReplayStats,
+    SharedClock, SnapshotConsumer, SpeedSegment, TypeReplayStats, VirtualClock, ADAPTIVE_SAMPLE_INTERVAL,
+};
 
 // New typed exports
-pub use fast_channel::FastChannel;
-pub use typed_bus::TypedEventBus;
+pub use fast_channel::{
+    AsyncReceiver, AsyncSender, ConsumerToken, DisruptorChannel, FastChannel, ProducerToken, RateLimitedChannel,
+    ThrottleError, TokenBucket,
+};
+pub use typed_bus::{PartialSendResult, PipeHandle, TypedEventBus, TypedEventBusBuilder};
 
 // Research topic exports (temporarily commented out)
 // pub use research_topic::{ResearchEvent, SignalCreatedEvent, SignalUpdatedEvent, SignalDeletedEvent, AnalysisRequestedEvent, AnalysisStartedEvent, AnalysisProgressEvent, AnalysisCompletedEvent, AnalysisFailedEvent, FeatureExtractedEvent, FeaturePipelineUpdatedEvent, ModelTrainingStartedEvent, ModelTrainingProgressEvent, ModelTrainingCompletedEvent, ModelDeploymentRequestedEvent, ModelDeploymentCompletedEvent, RealTimeDataUpdateEvent, VisualizationUpdateEvent, StatisticalTestCompletedEvent, CorrelationMatrixUpdatedEvent, ResearchConfigUpdatedEvent, ResearchStateChangedEvent};
@@ -0,0 +1,388 @@
+//! Publish-to-receive latency instrumentation
+//!
+//! `MetricEventBus` wraps `EventBus`, times every publish call and measures
+//! latency from the envelope timestamp when subscribers receive it. Samples are
+//! accumulated per event type in a `LatencyRecorder`, a lock-free array of
+//! power-of-two buckets that can be exported as an `hdrhistogram::Histogram`.
+
+use crate::bus::EventBus;
+use crate::events::{Event, EventEnvelope};
+use anyhow::Result;
+use dashmap::DashMap;
+use hdrhistogram::Histogram;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::broadcast;
+use tracing::warn;
+
+/// Smallest latency tracked by the histogram (1 ns)
+pub const MIN_LATENCY_NS: u64 = 1;
+
+/// Largest latency tracked by the histogram (10 ms); larger samples are clamped
+pub const MAX_LATENCY_NS: u64 = 10_000_000;
+
+/// Number of power-of-two buckets (bucket `i` holds `[2^(i-1), 2^i)`)
+const BUCKETS: usize = 64;
+
+/// Significant figures kept by exported histograms
+const SIGNIFICANT_FIGURES: u8 = 3;
+
+/// Current wall-clock time in nanoseconds (same clock as `EventEnvelope`)
+fn now_ns() -> i64 {
+    chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0)
+}
+
+/// Lock-free latency accumulator with power-of-two buckets
+pub struct LatencyRecorder {
+    buckets: [AtomicU64; BUCKETS],
+    count: AtomicU64,
+    max_ns: AtomicU64,
+    /// Samples above `MAX_LATENCY_NS` (recorded as `MAX_LATENCY_NS`)
+    overflow: AtomicU64,
+}
+
+impl LatencyRecorder {
+    /// Create empty recorder
+    pub fn new() -> Self {
+        Self {
+            buckets: std::array::from_fn(|_| AtomicU64::new(0)),
+            count: AtomicU64::new(0),
+            max_ns: AtomicU64::new(0),
+            overflow: AtomicU64::new(0),
+        }
+    }
+    
+    /// Record one latency sample
+    #[inline]
+    pub fn record(&self, latency_ns: u64) {
+        let clamped = if latency_ns > MAX_LATENCY_NS {
+            self.overflow.fetch_add(1, Ordering::Relaxed);
+            MAX_LATENCY_NS
+        } else {
+            latency_ns.max(MIN_LATENCY_NS)
+        };
+        
+        self.buckets[Self::bucket_index(clamped)].fetch_add(1, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.max_ns.fetch_max(clamped, Ordering::Relaxed);
+    }
+    
+    /// Bucket holding `value` (values in `[2^(i-1), 2^i)` map to `i`)
+    #[inline]
+    fn bucket_index(value: u64) -> usize {
+        (u64::BITS - value.leading_zeros()) as usize
+    }
+    
+    /// Largest value that falls into bucket `index`
+    fn bucket_upper_bound(index: usize) -> u64 {
+        if index >= 64 {
+            u64::MAX
+        } else {
+            (1u64 << index) - 1
+        }
+    }
+    
+    /// Total samples recorded
+    pub fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+    
+    /// Samples that exceeded `MAX_LATENCY_NS`
+    pub fn overflow_count(&self) -> u64 {
+        self.overflow.load(Ordering::Relaxed)
+    }
+    
+    /// Largest (clamped) sample recorded
+    pub fn max_ns(&self) -> u64 {
+        self.max_ns.load(Ordering::Relaxed)
+    }
+    
+    /// Build a histogram from the buckets (each bucket reported at its upper bound)
+    pub fn histogram(&self) -> Histogram<u64> {
+        let mut histogram = Histogram::new_with_bounds(MIN_LATENCY_NS, MAX_LATENCY_NS, SIGNIFICANT_FIGURES)
+            .expect("valid histogram bounds");
+        
+        for (index, bucket) in self.buckets.iter().enumerate() {
+            let count = bucket.load(Ordering::Relaxed);
+            if count > 0 {
+                let value = Self::bucket_upper_bound(index).min(MAX_LATENCY_NS);
+                let _ = histogram.record_n(value, count);
+            }
+        }
+        
+        histogram
+    }
+    
+    /// Clear all samples
+    pub fn reset(&self) {
+        for bucket in &self.buckets {
+            bucket.store(0, Ordering::Relaxed);
+        }
+        self.count.store(0, Ordering::Relaxed);
+        self.max_ns.store(0, Ordering::Relaxed);
+        self.overflow.store(0, Ordering::Relaxed);
+    }
+    
+    /// Summarize the recorded samples
+    pub fn summary(&self) -> LatencySummary {
+        let histogram = self.histogram();
+        LatencySummary {
+            count: self.count(),
+            p50_ns: histogram.value_at_quantile(0.50),
+            p99_ns: histogram.value_at_quantile(0.99),
+            p999_ns: histogram.value_at_quantile(0.999),
+            max_ns: self.max_ns(),
+            overflow: self.overflow_count(),
+        }
+    }
+}
+
+impl Default for LatencyRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Latency percentiles for one event type
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LatencySummary {
+    pub count: u64,
+    pub p50_ns: u64,
+    pub p99_ns: u64,
+    pub p999_ns: u64,
+    pub max_ns: u64,
+    pub overflow: u64,
+}
+
+/// Exported latency metrics for all event types
+#[derive(Debug, Clone, Default)]
+pub struct MetricSnapshot {
+    /// Export time (nanoseconds)
+    pub taken_at_ns: i64,
+    /// Latency summary by event type
+    pub latencies: HashMap<String, LatencySummary>,
+    /// Publish call duration summary by event type
+    pub publish_latencies: HashMap<String, LatencySummary>,
+}
+
+/// Event bus that measures publish-to-receive latency
+///
+/// Cloning is cheap and yields a handle to the same bus and metrics.
+#[derive(Clone)]
+pub struct MetricEventBus {
+    bus: EventBus,
+    recorders: Arc<DashMap<String, Arc<LatencyRecorder>>>,
+    publish_recorders: Arc<DashMap<String, Arc<LatencyRecorder>>>,
+}
+
+impl MetricEventBus {
+    /// Wrap an event bus
+    pub fn new(bus: EventBus) -> Self {
+        Self {
+            bus,
+            recorders: Arc::new(DashMap::new()),
+            publish_recorders: Arc::new(DashMap::new()),
+        }
+    }
+    
+    /// Get the underlying bus
+    pub fn bus(&self) -> &EventBus {
+        &self.bus
+    }
+    
+    /// Publish an event with default priority
+    pub async fn publish<T: Event + Send + 'static>(&self, event: T) -> Result<()> {
+        self.publish_with_priority(event, 5).await
+    }
+    
+    /// Publish event with specific priority, recording how long the publish took
+    pub async fn publish_with_priority<T: Event + Send + 'static>(&self, event: T, priority: u8) -> Result<()> {
+        let recorder = Self::recorder_in(&self.publish_recorders, event.event_type());
+        let start = Instant::now();
+        let result = self.bus.publish_with_priority(event, priority).await;
+        recorder.record(start.elapsed().as_nanos() as u64);
+        result
+    }
+    
+    /// Subscribe to an event type, measuring latency of every received event
+    pub async fn subscribe(&self, event_type: &str) -> MetricSubscriber {
+        MetricSubscriber {
+            receiver: self.bus.subscribe(event_type).await,
+            recorder: self.recorder(event_type),
+        }
+    }
+    
+    /// Latency histogram for an event type (`None` if never subscribed)
+    pub fn latency_histogram(&self, event_type: &str) -> Option<Histogram<u64>> {
+        self.recorders.get(event_type).map(|recorder| recorder.histogram())
+    }
+    
+    /// Publish call duration histogram for an event type (`None` if never published)
+    pub fn publish_latency_histogram(&self, event_type: &str) -> Option<Histogram<u64>> {
+        self.publish_recorders.get(event_type).map(|recorder| recorder.histogram())
+    }
+    
+    /// Clear all latency samples
+    pub fn reset_metrics(&self) {
+        for recorder in self.recorders.iter().chain(self.publish_recorders.iter()) {
+            recorder.reset();
+        }
+    }
+    
+    /// Export latency summaries for all event types
+    pub fn export_metrics(&self) -> MetricSnapshot {
+        MetricSnapshot {
+            taken_at_ns: now_ns(),
+            latencies: Self::summaries(&self.recorders),
+            publish_latencies: Self::summaries(&self.publish_recorders),
+        }
+    }
+    
+    /// Get or create the receive latency recorder for an event type
+    fn recorder(&self, event_type: &str) -> Arc<LatencyRecorder> {
+        Self::recorder_in(&self.recorders, event_type)
+    }
+    
+    fn recorder_in(recorders: &DashMap<String, Arc<LatencyRecorder>>, event_type: &str) -> Arc<LatencyRecorder> {
+        recorders.entry(event_type.to_string())
+            .or_insert_with(|| Arc::new(LatencyRecorder::new()))
+            .clone()
+    }
+    
+    fn summaries(recorders: &DashMap<String, Arc<LatencyRecorder>>) -> HashMap<String, LatencySummary> {
+        recorders.iter()
+            .map(|entry| (entry.key().clone(), entry.value().summary()))
+            .collect()
+    }
+}
+
+/// Receiver that records publish-to-receive latency
+pub struct MetricSubscriber {
+    receiver: broadcast::Receiver<EventEnvelope>,
+    recorder: Arc<LatencyRecorder>,
+}
+
+impl MetricSubscriber {
+    /// Receive next event
+    pub async fn recv(&mut self) -> Option<EventEnvelope> {
+        match self.receiver.recv().await {
+            Ok(event) => {
+                self.observe(&event);
+                Some(event)
+            }
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                warn!("Metric subscriber lagged, skipped {} events", skipped);
+                None
+            }
+            Err(broadcast::error::RecvError::Closed) => None,
+        }
+    }
+    
+    /// Try to receive without blocking
+    pub fn try_recv(&mut self) -> Option<EventEnvelope> {
+        let event = self.receiver.try_recv().ok()?;
+        self.observe(&event);
+        Some(event)
+    }
+    
+    #[inline]
+    fn observe(&self, envelope: &EventEnvelope) {
+        let latency = now_ns().saturating_sub(envelope.timestamp_ns).max(0) as u64;
+        self.recorder.record(latency);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::MarketDataEvent;
+    
+    #[test]
+    fn test_bucket_index() {
+        assert_eq!(LatencyRecorder::bucket_index(1), 1);
+        assert_eq!(LatencyRecorder::bucket_index(2), 2);
+        assert_eq!(LatencyRecorder::bucket_index(3), 2);
+        assert_eq!(LatencyRecorder::bucket_index(1024), 11);
+        assert_eq!(LatencyRecorder::bucket_upper_bound(11), 2047);
+    }
+    
+    #[test]
+    fn test_bucket_overflow() {
+        let recorder = LatencyRecorder::new();
+        recorder.record(500);
+        recorder.record(MAX_LATENCY_NS + 1);
+        recorder.record(u64::MAX);
+        
+        assert_eq!(recorder.count(), 3);
+        assert_eq!(recorder.overflow_count(), 2);
+        assert_eq!(recorder.max_ns(), MAX_LATENCY_NS);
+        
+        // Overflowed samples land in the top of the histogram range
+        let histogram = recorder.histogram();
+        assert_eq!(histogram.len(), 3);
+        assert!(histogram.max() >= MAX_LATENCY_NS);
+        
+        recorder.reset();
+        assert_eq!(recorder.count(), 0);
+        assert_eq!(recorder.overflow_count(), 0);
+        assert_eq!(recorder.histogram().len(), 0);
+    }
+    
+    #[test]
+    fn test_p99_known_workload() {
+        let recorder = LatencyRecorder::new();
+        
+        // 99% of samples at 1 µs, 1% at 100 µs
+        for _ in 0..9900 {
+            recorder.record(1_000);
+        }
+        for _ in 0..100 {
+            recorder.record(100_000);
+        }
+        
+        // Buckets are reported at their upper bound: 1_000 -> [512, 1023], 100_000 -> [65536, 131071]
+        let summary = recorder.summary();
+        assert_eq!(summary.count, 10_000);
+        assert!((512..=1_024).contains(&summary.p50_ns), "p50 = {}", summary.p50_ns);
+        assert!((512..=1_024).contains(&summary.p99_ns), "p99 = {}", summary.p99_ns);
+        assert!((65_536..=131_200).contains(&summary.p999_ns), "p999 = {}", summary.p999_ns);
+    }
+    
+    #[tokio::test]
+    async fn test_metric_bus_records_latency() {
+        let bus = EventBus::new_with_metrics();
+        let mut rx = bus.subscribe("market_data").await;
+        
+        for i in 0..10 {
+            bus.publish(MarketDataEvent {
+                timestamp: i,
+                symbol: "ES".to_string(),
+                price: 6000.0,
+                volume: 1.0,
+                bid_price: 5999.5,
+                bid_size: 5.0,
+                ask_price: 6000.5,
+                ask_size: 5.0,
+            }).await.unwrap();
+        }
+        for _ in 0..10 {
+            rx.recv().await.unwrap();
+        }
+        
+        let histogram = bus.latency_histogram("market_data").unwrap();
+        assert_eq!(histogram.len(), 10);
+        assert!(bus.latency_histogram("fill").is_none());
+        
+        let snapshot = bus.export_metrics();
+        assert_eq!(snapshot.latencies["market_data"].count, 10);
+        assert_eq!(snapshot.publish_latencies["market_data"].count, 10);
+        assert_eq!(bus.publish_latency_histogram("market_data").unwrap().len(), 10);
+        
+        bus.reset_metrics();
+        let snapshot = bus.export_metrics();
+        assert_eq!(snapshot.latencies["market_data"].count, 0);
+        assert_eq!(snapshot.publish_latencies["market_data"].count, 0);
+    }
+}
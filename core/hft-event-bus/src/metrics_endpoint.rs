@@ -0,0 +1,136 @@
+//! Prometheus scrape endpoint (requires the `metrics` feature)
+//!
+//! Serves `EventBus::to_prometheus_metrics` on `GET /metrics`. Every other
+//! path answers 404.
+
+use crate::bus::EventBus;
+use anyhow::Result;
+use bytes::Bytes;
+use http_body_util::Full;
+use hyper::body::Incoming;
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{header, Method, Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use tokio::net::TcpListener;
+use tracing::{debug, info};
+
+/// Content type of the Prometheus text exposition format
+pub const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Serves EventBus channel metrics over HTTP
+pub struct MetricsEndpoint {
+    bus: EventBus,
+    addr: SocketAddr,
+}
+
+impl MetricsEndpoint {
+    /// Create endpoint serving metrics of `bus` on `addr`
+    pub fn new(bus: EventBus, addr: SocketAddr) -> Self {
+        Self { bus, addr }
+    }
+    
+    /// Bind the configured address and serve scrapes until an error occurs
+    pub async fn run(self) -> Result<()> {
+        let listener = TcpListener::bind(self.addr).await?;
+        self.serve(listener).await
+    }
+    
+    /// Serve scrapes on an already bound listener
+    pub async fn serve(self, listener: TcpListener) -> Result<()> {
+        info!("Metrics endpoint listening on {}", listener.local_addr()?);
+        
+        loop {
+            let (stream, peer) = listener.accept().await?;
+            let bus = self.bus.clone();
+            
+            tokio::spawn(async move {
+                let service = service_fn(move |request| {
+                    let bus = bus.clone();
+                    async move { Ok::<_, Infallible>(handle_request(&bus, request)) }
+                });
+                if let Err(e) = http1::Builder::new()
+                    .serve_connection(TokioIo::new(stream), service)
+                    .await
+                {
+                    debug!("Metrics client {} disconnected: {}", peer, e);
+                }
+            });
+        }
+    }
+}
+
+/// Answer one scrape request
+fn handle_request(bus: &EventBus, request: Request<Incoming>) -> Response<Full<Bytes>> {
+    if request.method() == Method::GET && request.uri().path() == "/metrics" {
+        Response::builder()
+            .header(header::CONTENT_TYPE, PROMETHEUS_CONTENT_TYPE)
+            .body(Full::new(Bytes::from(bus.to_prometheus_metrics())))
+            .expect("valid response")
+    } else {
+        Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Full::new(Bytes::from_static(b"not found\n")))
+            .expect("valid response")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{FillEvent, OrderSide};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpStream;
+    use uuid::Uuid;
+    
+    async fn start_endpoint(endpoint: MetricsEndpoint) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(endpoint.serve(listener));
+        addr
+    }
+    
+    async fn get(addr: SocketAddr, path: &str) -> String {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        let request = format!("GET {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n", path, addr);
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+    
+    #[tokio::test]
+    async fn test_serves_metrics() {
+        let bus = EventBus::new();
+        let _fills = bus.subscribe("fill").await;
+        bus.publish(FillEvent {
+            fill_id: Uuid::from_u128(2),
+            order_id: Uuid::from_u128(1),
+            signal_id: None,
+            timestamp: 0,
+            symbol: "ES".to_string(),
+            side: OrderSide::Buy,
+            filled_quantity: 1.0,
+            fill_price: 6000.0,
+            commission: 0.5,
+            slippage_bps: 0.0,
+        }).await.unwrap();
+        
+        let addr = start_endpoint(MetricsEndpoint::new(bus, "127.0.0.1:0".parse().unwrap())).await;
+        let response = get(addr, "/metrics").await;
+        
+        assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+        assert!(response.contains("content-type: text/plain; version=0.0.4"));
+        assert!(response.contains("hft_eventbus_published_total{event_type=\"fill\"} 1\n"));
+        assert!(response.contains("hft_eventbus_subscriber_count{event_type=\"fill\"} 1\n"));
+    }
+    
+    #[tokio::test]
+    async fn test_unknown_path_is_not_found() {
+        let addr = start_endpoint(MetricsEndpoint::new(EventBus::new(), "127.0.0.1:0".parse().unwrap())).await;
+        let response = get(addr, "/").await;
+        assert!(response.starts_with("HTTP/1.1 404"), "{}", response);
+    }
+}
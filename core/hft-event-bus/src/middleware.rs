@@ -0,0 +1,112 @@
+//! Publish-time middleware
+//!
+//! Middleware added with `EventBus::add_middleware` sees every envelope
+//! between stamping and delivery, in the order it was added. Each step can
+//! pass the envelope on (possibly edited in place), replace it, or drop it:
+//!
+//! ```rust,ignore
+//! bus.add_middleware(LoggingMiddleware::new(Level::DEBUG));
+//! bus.add_middleware(RateLimitMiddleware::new(10_000));
+//! bus.add_middleware(|envelope: &mut EventEnvelope| {
+//!     envelope.priority = envelope.priority.min(5);
+//!     MiddlewareResult::Continue
+//! });
+//! ```
+
+use crate::events::EventEnvelope;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tracing::{debug, error, info, trace, warn, Level};
+
+/// Outcome of one middleware step
+#[derive(Debug)]
+pub enum MiddlewareResult {
+    /// Pass the (possibly modified) envelope to the next step
+    Continue,
+    /// Stop the chain; the envelope is not delivered
+    Drop,
+    /// Replace the envelope and continue with the replacement
+    Transform(EventEnvelope),
+}
+
+/// Hook run on every published envelope before delivery
+pub trait Middleware {
+    fn before_publish(&self, envelope: &mut EventEnvelope) -> MiddlewareResult;
+}
+
+impl<F: Fn(&mut EventEnvelope) -> MiddlewareResult> Middleware for F {
+    fn before_publish(&self, envelope: &mut EventEnvelope) -> MiddlewareResult {
+        self(envelope)
+    }
+}
+
+/// Drops envelopes beyond `max_per_second` in each second of envelope time
+///
+/// Windows follow `EventEnvelope::timestamp_ns`, so a bus on a virtual clock
+/// is limited in virtual time. Clones share the same budget.
+#[derive(Debug, Clone)]
+pub struct RateLimitMiddleware {
+    pub max_per_second: u64,
+    /// Current window (upper 32 bits, in seconds) and count within it
+    pub state: Arc<AtomicU64>,
+}
+
+impl RateLimitMiddleware {
+    pub fn new(max_per_second: u64) -> Self {
+        Self {
+            max_per_second,
+            state: Arc::new(AtomicU64::new(0)),
+        }
+    }
+}
+
+impl Middleware for RateLimitMiddleware {
+    fn before_publish(&self, envelope: &mut EventEnvelope) -> MiddlewareResult {
+        let window = (envelope.timestamp_ns.max(0) / 1_000_000_000) as u64 & 0xFFFF_FFFF;
+        let limit = self.max_per_second.min(0xFFFF_FFFF);
+
+        let mut current = self.state.load(Ordering::Relaxed);
+        loop {
+            let count = if current >> 32 == window { current & 0xFFFF_FFFF } else { 0 };
+            if count >= limit {
+                return MiddlewareResult::Drop;
+            }
+            let next = (window << 32) | (count + 1);
+            match self.state.compare_exchange_weak(current, next, Ordering::AcqRel, Ordering::Relaxed) {
+                Ok(_) => return MiddlewareResult::Continue,
+                Err(actual) => current = actual,
+            }
+        }
+    }
+}
+
+/// Logs every envelope at `level`
+#[derive(Debug, Clone, Copy)]
+pub struct LoggingMiddleware {
+    pub level: Level,
+}
+
+impl LoggingMiddleware {
+    pub fn new(level: Level) -> Self {
+        Self { level }
+    }
+}
+
+impl Middleware for LoggingMiddleware {
+    fn before_publish(&self, envelope: &mut EventEnvelope) -> MiddlewareResult {
+        let (event_type, id, priority) = (envelope.event.event_type(), envelope.id, envelope.priority);
+        // `tracing` macros need the level at compile time
+        if self.level == Level::ERROR {
+            error!("Publishing {} {} (priority {})", event_type, id, priority);
+        } else if self.level == Level::WARN {
+            warn!("Publishing {} {} (priority {})", event_type, id, priority);
+        } else if self.level == Level::INFO {
+            info!("Publishing {} {} (priority {})", event_type, id, priority);
+        } else if self.level == Level::DEBUG {
+            debug!("Publishing {} {} (priority {})", event_type, id, priority);
+        } else {
+            trace!("Publishing {} {} (priority {})", event_type, id, priority);
+        }
+        MiddlewareResult::Continue
+    }
+}
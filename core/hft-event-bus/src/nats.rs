@@ -0,0 +1,367 @@
+//! NATS JetStream bridge for cross-datacenter events (requires the `nats` feature)
+//!
+//! Outbound event types are published to subjects named
+//! `"{subject_prefix}.{event_type}"` as JSON `SerializableEnvelope`s. The
+//! bridge creates a JetStream stream capturing `"{subject_prefix}.>"` if none
+//! exists, and consumes `inbound_subjects` from it, publishing the envelopes
+//! on the local bus with their original ID and timestamp.
+//!
+//! As with the Kafka bridge, every published message carries an `origin`
+//! header with the bridge's ID. A bridge ignores its own messages and does
+//! not re-publish envelopes it consumed, so one type may be both outbound
+//! and inbound without looping.
+//!
+//! The client reconnects on its own. When a publish fails because the
+//! connection dropped, the outbound task releases its bus subscriptions,
+//! waits for the connection to return and subscribes again; events
+//! published on the bus in between are not forwarded.
+
+use crate::bus::{EventBus, CHANNEL_CAPACITY};
+use crate::serde_support::SerializableEnvelope;
+use anyhow::{anyhow, Result};
+use async_nats::connection::State;
+use async_nats::jetstream::{self, consumer, stream};
+use async_nats::HeaderMap;
+use futures_util::StreamExt;
+use std::collections::{HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+/// Header naming the bridge that published a message
+const ORIGIN_HEADER: &str = "origin";
+
+/// How often the outbound task checks whether the connection is back
+const RECONNECT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Routing settings for `NatsBridge`
+#[derive(Debug, Clone)]
+pub struct NatsBridgeConfig {
+    pub subject_prefix: String,
+    /// Event types published to NATS
+    pub outbound_types: Vec<String>,
+    /// Subjects consumed from JetStream; wildcards are allowed, but every
+    /// subject must fall under `"{subject_prefix}.>"`
+    pub inbound_subjects: Vec<String>,
+    /// Durable consumer name; `None` uses an ephemeral consumer that does
+    /// not survive a server restart
+    pub durable_name: Option<String>,
+}
+
+impl NatsBridgeConfig {
+    /// Subject carrying `event_type`
+    pub fn subject(&self, event_type: &str) -> String {
+        format!("{}.{}", self.subject_prefix, event_type)
+    }
+    
+    /// JetStream stream holding the prefix's subjects
+    ///
+    /// Stream names may not contain `.`, `*`, `>` or whitespace, so those
+    /// become `_`.
+    pub fn stream_name(&self) -> String {
+        self.subject_prefix
+            .chars()
+            .map(|c| if matches!(c, '.' | '*' | '>') || c.is_whitespace() { '_' } else { c })
+            .collect()
+    }
+}
+
+/// (id, timestamp_ns) of consumed envelopes the outbound task must skip,
+/// oldest evicted first
+///
+/// An entry is normally taken as soon as the outbound task sees the
+/// envelope. One it never sees (the task lagged or was resubscribing) would
+/// otherwise stay forever; after `CHANNEL_CAPACITY` newer entries the
+/// outbound receiver has lagged past it anyway.
+#[derive(Default)]
+struct ConsumedTracker {
+    keys: HashSet<(Uuid, i64)>,
+    order: VecDeque<(Uuid, i64)>,
+}
+
+impl ConsumedTracker {
+    fn insert(&mut self, key: (Uuid, i64)) {
+        if self.keys.insert(key) {
+            self.order.push_back(key);
+        }
+        while self.order.len() > CHANNEL_CAPACITY {
+            if let Some(evicted) = self.order.pop_front() {
+                self.keys.remove(&evicted);
+            }
+        }
+    }
+    
+    /// Remove `key`, returning true if it was tracked
+    fn take(&mut self, key: (Uuid, i64)) -> bool {
+        self.keys.remove(&key)
+    }
+}
+
+/// Bridges an `EventBus` to NATS JetStream subjects
+pub struct NatsBridge {
+    bus: EventBus,
+    client: async_nats::Client,
+    config: NatsBridgeConfig,
+    origin: String,
+    consumed: Arc<Mutex<ConsumedTracker>>,
+    stop: watch::Sender<bool>,
+}
+
+impl NatsBridge {
+    /// Create bridge between `bus` and the server `client` is connected to
+    pub fn new(bus: EventBus, client: async_nats::Client, config: NatsBridgeConfig) -> Self {
+        Self {
+            bus,
+            client,
+            config,
+            origin: Uuid::new_v4().to_string(),
+            consumed: Arc::new(Mutex::new(ConsumedTracker::default())),
+            stop: watch::channel(false).0,
+        }
+    }
+    
+    /// Bridge configuration
+    pub fn config(&self) -> &NatsBridgeConfig {
+        &self.config
+    }
+    
+    /// Create the stream if needed, then spawn the outbound and inbound tasks
+    ///
+    /// The returned handle resolves when both tasks finish or either fails,
+    /// in which case the other is aborted. After `stop` it resolves `Ok(())`.
+    pub fn run(&self) -> JoinHandle<Result<()>> {
+        let bus = self.bus.clone();
+        let client = self.client.clone();
+        let config = self.config.clone();
+        let origin = self.origin.clone();
+        let consumed = self.consumed.clone();
+        let stop = self.stop.subscribe();
+        
+        tokio::spawn(async move {
+            let jetstream = jetstream::new(client.clone());
+            jetstream
+                .get_or_create_stream(stream::Config {
+                    name: config.stream_name(),
+                    subjects: vec![format!("{}.>", config.subject_prefix)],
+                    ..Default::default()
+                })
+                .await
+                .map_err(|e| anyhow!("Failed to create stream {}: {}", config.stream_name(), e))?;
+            
+            let outbound = tokio::spawn(outbound(
+                bus.clone(),
+                client,
+                jetstream.clone(),
+                config.clone(),
+                origin.clone(),
+                consumed.clone(),
+                stop.clone(),
+            ));
+            let inbound = tokio::spawn(inbound(bus, jetstream, config, origin, consumed, stop));
+            
+            let abort = [outbound.abort_handle(), inbound.abort_handle()];
+            let result = tokio::try_join!(join(outbound), join(inbound)).map(|_| ());
+            for handle in abort {
+                handle.abort();
+            }
+            result
+        })
+    }
+    
+    /// Stop the tasks started by `run`
+    ///
+    /// Stopping is permanent: tasks started by a later `run` exit at once.
+    pub fn stop(&self) {
+        self.stop.send_replace(true);
+    }
+}
+
+async fn join(task: JoinHandle<Result<()>>) -> Result<()> {
+    task.await.map_err(|e| anyhow!("NATS bridge task failed: {}", e))?
+}
+
+/// Resolve once `stop` is called (never, if the bridge is dropped first)
+async fn stopped(stop: &mut watch::Receiver<bool>) {
+    if stop.wait_for(|stopped| *stopped).await.is_err() {
+        std::future::pending::<()>().await;
+    }
+}
+
+/// Publish subscribed bus events to their subjects
+async fn outbound(
+    bus: EventBus,
+    client: async_nats::Client,
+    jetstream: jetstream::Context,
+    config: NatsBridgeConfig,
+    origin: String,
+    consumed: Arc<Mutex<ConsumedTracker>>,
+    mut stop: watch::Receiver<bool>,
+) -> Result<()> {
+    if config.outbound_types.is_empty() {
+        return Ok(());
+    }
+    let event_types: Vec<&str> = config.outbound_types.iter().map(String::as_str).collect();
+    
+    loop {
+        let mut receiver = bus.subscribe_merged(&event_types).await;
+        info!("NATS bridge publishing {:?}", config.outbound_types);
+        
+        loop {
+            let envelope = tokio::select! {
+                received = receiver.recv() => match received {
+                    Some(envelope) => envelope,
+                    None => return Ok(()),
+                },
+                _ = stopped(&mut stop) => return Ok(()),
+            };
+            if consumed.lock().unwrap().take((envelope.id, envelope.timestamp_ns)) {
+                continue;
+            }
+            let Some(serializable) = envelope.to_serializable() else {
+                debug!("Skipping {} event without JSON form", envelope.event.event_type());
+                continue;
+            };
+            
+            let subject = config.subject(&serializable.event_type);
+            let payload = serde_json::to_vec(&serializable)?;
+            let mut headers = HeaderMap::new();
+            headers.insert(ORIGIN_HEADER, origin.as_str());
+            let published = match jetstream.publish_with_headers(subject.clone(), headers, payload.into()).await {
+                Ok(ack) => ack.await.map(|_| ()).map_err(anyhow::Error::from),
+                Err(e) => Err(e.into()),
+            };
+            
+            if let Err(e) = published {
+                if client.connection_state() == State::Connected {
+                    warn!("Failed to publish to {}: {}", subject, e);
+                    continue;
+                }
+                warn!("NATS connection lost publishing to {}: {}", subject, e);
+                break;
+            }
+        }
+        
+        // Release the bus subscriptions while disconnected rather than let
+        // them fill up, and subscribe again once the client has reconnected
+        drop(receiver);
+        tokio::select! {
+            _ = wait_connected(&client) => info!("NATS connection restored"),
+            _ = stopped(&mut stop) => return Ok(()),
+        }
+    }
+}
+
+async fn wait_connected(client: &async_nats::Client) {
+    while client.connection_state() != State::Connected {
+        tokio::time::sleep(RECONNECT_POLL_INTERVAL).await;
+    }
+}
+
+/// Publish consumed envelopes on the bus
+async fn inbound(
+    bus: EventBus,
+    jetstream: jetstream::Context,
+    config: NatsBridgeConfig,
+    origin: String,
+    consumed: Arc<Mutex<ConsumedTracker>>,
+    mut stop: watch::Receiver<bool>,
+) -> Result<()> {
+    if config.inbound_subjects.is_empty() {
+        return Ok(());
+    }
+    let stream = jetstream.get_stream(config.stream_name()).await?;
+    let consumer_config = consumer::pull::Config {
+        durable_name: config.durable_name.clone(),
+        filter_subjects: config.inbound_subjects.clone(),
+        deliver_policy: consumer::DeliverPolicy::New,
+        ..Default::default()
+    };
+    let consumer = match &config.durable_name {
+        Some(name) => stream.get_or_create_consumer(name, consumer_config).await?,
+        None => stream.create_consumer(consumer_config).await?,
+    };
+    let mut messages = consumer.messages().await?;
+    info!("NATS bridge consuming {:?}", config.inbound_subjects);
+    
+    loop {
+        let message = tokio::select! {
+            next = messages.next() => match next {
+                Some(Ok(message)) => message,
+                // Heartbeat and reconnect errors; the stream keeps pulling
+                Some(Err(e)) => {
+                    warn!("NATS consumer error: {}", e);
+                    continue;
+                }
+                None => return Ok(()),
+            },
+            _ = stopped(&mut stop) => return Ok(()),
+        };
+        
+        let own = message
+            .headers
+            .as_ref()
+            .and_then(|headers| headers.get(ORIGIN_HEADER))
+            .is_some_and(|value| value.as_str() == origin);
+        // A message that fails to decode or publish is still acked, so
+        // JetStream does not redeliver it forever
+        if !own {
+            let decoded = serde_json::from_slice::<SerializableEnvelope>(&message.payload)
+                .map_err(anyhow::Error::from)
+                .and_then(SerializableEnvelope::into_envelope_or_raw);
+            match decoded {
+                Ok(envelope) => {
+                    let key = (envelope.id, envelope.timestamp_ns);
+                    let tracked = config.outbound_types.iter().any(|t| t == envelope.event.event_type());
+                    if tracked {
+                        consumed.lock().unwrap().insert(key);
+                    }
+                    if let Err(e) = bus.publish_envelope(envelope).await {
+                        warn!("Failed to publish {} from {}: {}", key.0, message.subject, e);
+                        if tracked {
+                            consumed.lock().unwrap().take(key);
+                        }
+                    }
+                }
+                Err(e) => warn!("Skipping malformed message on {}: {}", message.subject, e),
+            }
+        }
+        if let Err(e) = message.ack().await {
+            warn!("Failed to ack message on {}: {}", message.subject, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_subject_and_stream_names() {
+        let config = NatsBridgeConfig {
+            subject_prefix: "hft.prod".to_string(),
+            outbound_types: vec![],
+            inbound_subjects: vec![],
+            durable_name: None,
+        };
+        assert_eq!(config.subject("market_data"), "hft.prod.market_data");
+        assert_eq!(config.stream_name(), "hft_prod");
+    }
+    
+    #[test]
+    fn test_consumed_tracker_is_bounded() {
+        let mut tracker = ConsumedTracker::default();
+        let key = |i: usize| (Uuid::from_u128(i as u128), i as i64);
+        for i in 0..CHANNEL_CAPACITY + 10 {
+            tracker.insert(key(i));
+        }
+        assert_eq!(tracker.keys.len(), CHANNEL_CAPACITY);
+        
+        // The oldest entries were evicted, newer ones are taken once
+        assert!(!tracker.take(key(0)));
+        assert!(tracker.take(key(CHANNEL_CAPACITY + 9)));
+        assert!(!tracker.take(key(CHANNEL_CAPACITY + 9)));
+    }
+}
@@ -0,0 +1,329 @@
+//! Order book reconstruction from delta events
+//!
+//! Publishers send `OrderBookDeltaEvent`s carrying only the levels that
+//! changed; consumers keep an `OrderBook` per symbol, apply each delta and
+//! take an `OrderBookEvent` snapshot when they need the full depth.
+
+use crate::events::{OrderBookDeltaEvent, OrderBookEvent, PriceLevel};
+
+/// Full-depth order book for one symbol
+///
+/// Bids are kept best (highest) first and asks best (lowest) first, the same
+/// ordering `OrderBookEvent` uses.
+#[derive(Debug, Clone, Default)]
+pub struct OrderBook {
+    symbol: String,
+    timestamp: i64,
+    bids: Vec<(f64, f64)>,
+    asks: Vec<(f64, f64)>,
+}
+
+impl OrderBook {
+    /// Create empty book
+    pub fn new(symbol: &str) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Create book from a full snapshot
+    pub fn from_snapshot(snapshot: &OrderBookEvent) -> Self {
+        let mut bids = snapshot.bids.clone();
+        let mut asks = snapshot.asks.clone();
+        bids.retain(|&(_, size)| size > 0.0);
+        asks.retain(|&(_, size)| size > 0.0);
+        bids.sort_by(|a, b| b.0.total_cmp(&a.0));
+        asks.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        Self {
+            symbol: snapshot.symbol.clone(),
+            timestamp: snapshot.timestamp,
+            bids,
+            asks,
+        }
+    }
+
+    /// Apply changed levels; a `new_size` of 0.0 removes the level
+    pub fn apply_delta(&mut self, delta: &OrderBookDeltaEvent) {
+        for level in &delta.bid_changes {
+            Self::apply_level(&mut self.bids, level, |price, target| target.total_cmp(&price));
+        }
+        for level in &delta.ask_changes {
+            Self::apply_level(&mut self.asks, level, |price, target| price.total_cmp(&target));
+        }
+        self.timestamp = self.timestamp.max(delta.timestamp);
+    }
+
+    /// Insert, update or remove one level
+    ///
+    /// `order(price, target)` compares an existing level's price against the
+    /// changed price in the side's sort order.
+    fn apply_level(
+        side: &mut Vec<(f64, f64)>,
+        level: &PriceLevel,
+        order: impl Fn(f64, f64) -> std::cmp::Ordering,
+    ) {
+        match side.binary_search_by(|&(price, _)| order(price, level.price)) {
+            Ok(i) if level.new_size > 0.0 => side[i].1 = level.new_size,
+            Ok(i) => {
+                side.remove(i);
+            }
+            Err(i) if level.new_size > 0.0 => side.insert(i, (level.price, level.new_size)),
+            Err(_) => {}
+        }
+    }
+
+    /// Full-depth snapshot of the current state
+    pub fn snapshot(&self) -> OrderBookEvent {
+        OrderBookEvent {
+            timestamp: self.timestamp,
+            symbol: self.symbol.clone(),
+            bids: self.bids.clone(),
+            asks: self.asks.clone(),
+        }
+    }
+
+    /// Highest bid (price, size)
+    pub fn best_bid(&self) -> Option<(f64, f64)> {
+        self.bids.first().copied()
+    }
+
+    /// Lowest ask (price, size)
+    pub fn best_ask(&self) -> Option<(f64, f64)> {
+        self.asks.first().copied()
+    }
+
+    /// Number of (bid, ask) levels
+    pub fn depth(&self) -> (usize, usize) {
+        (self.bids.len(), self.asks.len())
+    }
+
+    /// Midpoint of the best bid and ask
+    pub fn mid_price(&self) -> Option<f64> {
+        Some((self.best_bid()?.0 + self.best_ask()?.0) / 2.0)
+    }
+
+    /// Best ask minus best bid
+    pub fn spread(&self) -> Option<f64> {
+        Some(self.best_ask()?.0 - self.best_bid()?.0)
+    }
+
+    /// Total bid size priced within `within_pct` percent below the mid
+    ///
+    /// `within_pct` is in percent, so 5 bps is `0.05`. A one-sided book
+    /// measures from its best bid instead of the mid.
+    pub fn total_bid_depth(&self, within_pct: f64) -> f64 {
+        let Some(reference) = self.mid_price().or(self.best_bid().map(|(price, _)| price)) else {
+            return 0.0;
+        };
+        let floor = reference * (1.0 - within_pct / 100.0);
+        self.bids.iter()
+            .take_while(|&&(price, _)| price >= floor)
+            .map(|&(_, size)| size)
+            .sum()
+    }
+
+    /// Total ask size priced within `within_pct` percent above the mid
+    ///
+    /// `within_pct` is in percent, so 5 bps is `0.05`. A one-sided book
+    /// measures from its best ask instead of the mid.
+    pub fn total_ask_depth(&self, within_pct: f64) -> f64 {
+        let Some(reference) = self.mid_price().or(self.best_ask().map(|(price, _)| price)) else {
+            return 0.0;
+        };
+        let ceiling = reference * (1.0 + within_pct / 100.0);
+        self.asks.iter()
+            .take_while(|&&(price, _)| price <= ceiling)
+            .map(|&(_, size)| size)
+            .sum()
+    }
+
+    /// Share of full-book size resting on the bid, `bid / (bid + ask)`
+    ///
+    /// 0.5 for an empty book.
+    pub fn bid_ask_imbalance(&self) -> f64 {
+        let bid: f64 = self.bids.iter().map(|&(_, size)| size).sum();
+        let ask: f64 = self.asks.iter().map(|&(_, size)| size).sum();
+        if bid + ask > 0.0 {
+            bid / (bid + ask)
+        } else {
+            0.5
+        }
+    }
+
+    /// Cost of sweeping `size` through the book, measured from the mid
+    ///
+    /// A positive `size` buys through the asks and returns the average fill
+    /// price minus the mid; a negative `size` sells through the bids and
+    /// returns the mid minus the average fill price. `None` when the book is
+    /// one-sided, `size` is zero or the side is too thin to fill it.
+    pub fn effective_spread(&self, size: f64) -> Option<f64> {
+        let mid = self.mid_price()?;
+        if size > 0.0 {
+            Some(Self::average_fill_price(&self.asks, size)? - mid)
+        } else if size < 0.0 {
+            Some(mid - Self::average_fill_price(&self.bids, -size)?)
+        } else {
+            None
+        }
+    }
+
+    /// Size-weighted price of taking `size` from the best levels of `side`
+    fn average_fill_price(side: &[(f64, f64)], size: f64) -> Option<f64> {
+        let mut remaining = size;
+        let mut notional = 0.0;
+        for &(price, level_size) in side {
+            let take = remaining.min(level_size);
+            notional += take * price;
+            remaining -= take;
+            if remaining <= 0.0 {
+                return Some(notional / size);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn delta(bids: &[(f64, f64)], asks: &[(f64, f64)]) -> OrderBookDeltaEvent {
+        let levels = |side: &[(f64, f64)]| {
+            side.iter().map(|&(price, new_size)| PriceLevel { price, new_size }).collect()
+        };
+        OrderBookDeltaEvent {
+            timestamp: 1,
+            symbol: "ES".to_string(),
+            bid_changes: levels(bids),
+            ask_changes: levels(asks),
+        }
+    }
+
+    #[test]
+    fn test_apply_delta_insert_update_remove() {
+        let mut book = OrderBook::new("ES");
+        book.apply_delta(&delta(&[(99.0, 1.0), (100.0, 2.0), (98.0, 3.0)], &[(102.0, 1.0), (101.0, 2.0)]));
+        assert_eq!(book.best_bid(), Some((100.0, 2.0)));
+        assert_eq!(book.best_ask(), Some((101.0, 2.0)));
+
+        book.apply_delta(&delta(&[(100.0, 0.0), (99.0, 5.0), (97.0, 0.0)], &[(101.5, 4.0)]));
+        let snapshot = book.snapshot();
+        assert_eq!(snapshot.bids, vec![(99.0, 5.0), (98.0, 3.0)]);
+        assert_eq!(snapshot.asks, vec![(101.0, 2.0), (101.5, 4.0), (102.0, 1.0)]);
+        assert_eq!(snapshot.symbol, "ES");
+    }
+
+    #[test]
+    fn test_reconstruct_1000_levels() {
+        // Build a 1000-level book, then churn it with deltas
+        let bids: Vec<(f64, f64)> = (0..1000).map(|i| (5000.0 - i as f64 * 0.25, 1.0 + i as f64)).collect();
+        let asks: Vec<(f64, f64)> = (0..1000).map(|i| (5000.25 + i as f64 * 0.25, 1.0 + i as f64)).collect();
+        let mut book = OrderBook::new("ES");
+        book.apply_delta(&delta(&bids, &asks));
+        assert_eq!(book.depth(), (1000, 1000));
+
+        let mut expected_bids = bids.clone();
+        let mut expected_asks = asks.clone();
+        for round in 0..50 {
+            let i = (round * 37) % 1000;
+            let removed = (round * 53 + 11) % 1000;
+            book.apply_delta(&delta(
+                &[(bids[i].0, 99.0), (bids[removed].0, 0.0)],
+                &[(asks[i].0, 0.0), (asks[removed].0, 7.0)],
+            ));
+
+            if let Some(level) = expected_bids.iter_mut().find(|l| l.0 == bids[i].0) {
+                level.1 = 99.0;
+            } else {
+                expected_bids.push((bids[i].0, 99.0));
+            }
+            expected_bids.retain(|l| l.0 != bids[removed].0);
+            expected_asks.retain(|l| l.0 != asks[i].0);
+            if let Some(level) = expected_asks.iter_mut().find(|l| l.0 == asks[removed].0) {
+                level.1 = 7.0;
+            } else {
+                expected_asks.push((asks[removed].0, 7.0));
+            }
+        }
+        expected_bids.sort_by(|a, b| b.0.total_cmp(&a.0));
+        expected_asks.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let snapshot = book.snapshot();
+        assert_eq!(snapshot.bids, expected_bids);
+        assert_eq!(snapshot.asks, expected_asks);
+
+        // Round-trips through a full snapshot
+        let rebuilt = OrderBook::from_snapshot(&snapshot);
+        assert_eq!(rebuilt.snapshot().bids, snapshot.bids);
+        assert_eq!(rebuilt.snapshot().asks, snapshot.asks);
+    }
+
+    fn synthetic_book() -> OrderBook {
+        OrderBook::from_snapshot(&OrderBookEvent {
+            timestamp: 1,
+            symbol: "ES".to_string(),
+            bids: vec![(99.9, 10.0), (99.8, 20.0), (99.5, 30.0)],
+            asks: vec![(100.1, 5.0), (100.2, 15.0), (100.6, 20.0)],
+        })
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "{} != {}", actual, expected);
+    }
+
+    #[test]
+    fn test_mid_and_spread() {
+        let book = synthetic_book();
+        assert_close(book.mid_price().unwrap(), 100.0);
+        assert_close(book.spread().unwrap(), 0.2);
+
+        let empty = OrderBook::new("ES");
+        assert_eq!(empty.mid_price(), None);
+        assert_eq!(empty.spread(), None);
+    }
+
+    #[test]
+    fn test_depth_within_range() {
+        let mut book = synthetic_book();
+        // 0.3% of a 100.0 mid reaches down to 99.7 and up to 100.3
+        assert_close(book.total_bid_depth(0.3), 30.0);
+        assert_close(book.total_ask_depth(0.3), 20.0);
+        assert_close(book.total_bid_depth(0.05), 0.0);
+        assert_close(book.total_bid_depth(1.0), 60.0);
+        assert_close(book.total_ask_depth(1.0), 40.0);
+
+        // Updates move the depth
+        book.apply_delta(&delta(&[(99.8, 0.0)], &[(100.25, 7.0)]));
+        assert_close(book.total_bid_depth(0.3), 10.0);
+        assert_close(book.total_ask_depth(0.3), 27.0);
+
+        // A one-sided book measures from its own best price
+        let mut bids_only = OrderBook::new("ES");
+        bids_only.apply_delta(&delta(&[(100.0, 1.0), (99.9, 2.0), (99.0, 3.0)], &[]));
+        assert_close(bids_only.total_bid_depth(0.2), 3.0);
+        assert_close(bids_only.total_ask_depth(0.2), 0.0);
+    }
+
+    #[test]
+    fn test_bid_ask_imbalance() {
+        assert_close(synthetic_book().bid_ask_imbalance(), 0.6);
+        assert_close(OrderBook::new("ES").bid_ask_imbalance(), 0.5);
+    }
+
+    #[test]
+    fn test_effective_spread() {
+        let book = synthetic_book();
+        // Buy 10: 5 @ 100.1 + 5 @ 100.2 = 100.15 average
+        assert_close(book.effective_spread(10.0).unwrap(), 0.15);
+        // Inside the top level it is half the quoted spread
+        assert_close(book.effective_spread(5.0).unwrap(), 0.1);
+        // Sell 30: 10 @ 99.9 + 20 @ 99.8 = 99.8333.. average
+        assert_close(book.effective_spread(-30.0).unwrap(), 100.0 - 2995.0 / 30.0);
+
+        assert_eq!(book.effective_spread(41.0), None);
+        assert_eq!(book.effective_spread(0.0), None);
+        assert_eq!(OrderBook::new("ES").effective_spread(1.0), None);
+    }
+}
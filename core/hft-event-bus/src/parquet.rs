@@ -0,0 +1,192 @@
+//! Parquet export/import for replay data (requires the `parquet` feature)
+//!
+//! Each envelope becomes one row:
+//!
+//! | column         | type   |
+//! |----------------|--------|
+//! | `id`           | utf8   |
+//! | `timestamp_ns` | int64  |
+//! | `priority`     | uint8  |
+//! | `event_type`   | utf8   |
+//! | `payload_json` | utf8   |
+//!
+//! Payloads are stored as JSON and rebuilt through the `EventRegistry`, so
+//! custom events must be registered before loading. Files are written with
+//! Snappy compression.
+
+use crate::bus::EventBus;
+use crate::events::EventEnvelope;
+use crate::replay_mode::{EventReplay, ReplaySpeed};
+use crate::serde_support::SerializableEnvelope;
+use anyhow::{anyhow, Result};
+use arrow2::array::{Array, Int64Array, UInt8Array, Utf8Array};
+use arrow2::chunk::Chunk;
+use arrow2::datatypes::{DataType, Field, Schema};
+use arrow2::io::parquet::read;
+use arrow2::io::parquet::write::{
+    transverse, CompressionOptions, Encoding, FileWriter, RowGroupIterator, Version, WriteOptions,
+};
+use std::fs::File;
+use std::path::PathBuf;
+use tracing::info;
+use uuid::Uuid;
+
+/// Schema of exported replay files
+pub fn replay_schema() -> Schema {
+    Schema::from(vec![
+        Field::new("id", DataType::Utf8, false),
+        Field::new("timestamp_ns", DataType::Int64, false),
+        Field::new("priority", DataType::UInt8, false),
+        Field::new("event_type", DataType::Utf8, false),
+        Field::new("payload_json", DataType::Utf8, false),
+    ])
+}
+
+impl EventReplay {
+    /// Write loaded events to a Parquet file, returning the number of rows
+    ///
+    /// Events without a JSON form are skipped.
+    pub fn export_to_parquet(&self, path: PathBuf) -> Result<usize> {
+        let rows: Vec<SerializableEnvelope> = self
+            .events
+            .iter()
+            .filter_map(EventEnvelope::to_serializable)
+            .collect();
+
+        let ids: Vec<String> = rows.iter().map(|r| r.id.to_string()).collect();
+        let payloads = rows
+            .iter()
+            .map(|r| serde_json::to_string(&r.payload))
+            .collect::<serde_json::Result<Vec<_>>>()?;
+
+        let columns: Vec<Box<dyn Array>> = vec![
+            Utf8Array::<i32>::from_slice(&ids).boxed(),
+            Int64Array::from_vec(rows.iter().map(|r| r.timestamp_ns).collect()).boxed(),
+            UInt8Array::from_vec(rows.iter().map(|r| r.priority).collect()).boxed(),
+            Utf8Array::<i32>::from_iter_values(rows.iter().map(|r| r.event_type.as_str())).boxed(),
+            Utf8Array::<i32>::from_slice(&payloads).boxed(),
+        ];
+        let chunk = Chunk::try_new(columns)?;
+
+        let schema = replay_schema();
+        let options = WriteOptions {
+            write_statistics: true,
+            compression: CompressionOptions::Snappy,
+            version: Version::V2,
+            data_pagesize_limit: None,
+        };
+        let encodings = schema
+            .fields
+            .iter()
+            .map(|f| transverse(&f.data_type, |_| Encoding::Plain))
+            .collect();
+        let row_groups = RowGroupIterator::try_new(vec![Ok(chunk)].into_iter(), &schema, options, encodings)?;
+
+        let file = File::create(&path)?;
+        let mut writer = FileWriter::try_new(file, schema, options)?;
+        for group in row_groups {
+            writer.write(group?)?;
+        }
+        writer.end(None)?;
+
+        info!("Exported {} events to {}", rows.len(), path.display());
+        Ok(rows.len())
+    }
+
+    /// Create a replayer from a file written by `export_to_parquet`
+    ///
+    /// Replays at `ReplaySpeed::Max` on `bus`; use `load_events` on an
+    /// existing replayer for other settings.
+    pub fn load_from_parquet(bus: EventBus, path: PathBuf) -> Result<Self> {
+        let mut reader = File::open(&path)?;
+        let metadata = read::read_metadata(&mut reader)?;
+        let schema = read::infer_schema(&metadata)?;
+        let chunks = read::FileReader::new(reader, metadata.row_groups, schema, None, None, None);
+
+        let mut events = Vec::new();
+        for chunk in chunks {
+            let chunk = chunk?;
+            let arrays = chunk.arrays();
+            let ids = column::<Utf8Array<i32>>(arrays, 0, "id")?;
+            let timestamps = column::<Int64Array>(arrays, 1, "timestamp_ns")?;
+            let priorities = column::<UInt8Array>(arrays, 2, "priority")?;
+            let event_types = column::<Utf8Array<i32>>(arrays, 3, "event_type")?;
+            let payloads = column::<Utf8Array<i32>>(arrays, 4, "payload_json")?;
+
+            for row in 0..chunk.len() {
+                let envelope = SerializableEnvelope {
+                    id: Uuid::parse_str(ids.value(row))?,
+                    timestamp_ns: timestamps.value(row),
+                    priority: priorities.value(row),
+                    causation_id: None,
+                    correlation_id: None,
+                    event_type: event_types.value(row).to_string(),
+                    payload: serde_json::from_str(payloads.value(row))?,
+                };
+                events.push(envelope.into_envelope()?);
+            }
+        }
+
+        let mut replay = EventReplay::new(bus, ReplaySpeed::Max);
+        replay.set_events(events);
+        Ok(replay)
+    }
+}
+
+/// Downcast column `index` to its expected array type
+fn column<'a, A: 'static>(arrays: &'a [Box<dyn Array>], index: usize, name: &str) -> Result<&'a A> {
+    arrays
+        .get(index)
+        .and_then(|array| array.as_any().downcast_ref::<A>())
+        .ok_or_else(|| anyhow!("Parquet column {} missing or has unexpected type", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::MarketDataEvent;
+
+    #[test]
+    fn test_parquet_roundtrip() {
+        let events: Vec<EventEnvelope> = (0..10_000)
+            .map(|i| {
+                EventEnvelope::new(MarketDataEvent {
+                    timestamp: i,
+                    symbol: if i % 2 == 0 { "ES" } else { "NQ" }.to_string(),
+                    price: 6000.0 + i as f64 * 0.25,
+                    volume: 1.0 + (i % 7) as f64,
+                    bid_price: 5999.75 + i as f64 * 0.25,
+                    bid_size: 3.0,
+                    ask_price: 6000.25 + i as f64 * 0.25,
+                    ask_size: 4.0,
+                }, (i % 10) as u8)
+            })
+            .collect();
+        let mut replay = EventReplay::new(EventBus::new(), ReplaySpeed::Max);
+        replay.load_events(events.clone()).unwrap();
+
+        let path = std::env::temp_dir().join(format!("replay_{}.parquet", Uuid::new_v4()));
+        assert_eq!(replay.export_to_parquet(path.clone()).unwrap(), 10_000);
+
+        // Schema matches the documented layout
+        let mut file = File::open(&path).unwrap();
+        let metadata = read::read_metadata(&mut file).unwrap();
+        let schema = read::infer_schema(&metadata).unwrap();
+        assert_eq!(schema.fields, replay_schema().fields);
+
+        let loaded = EventReplay::load_from_parquet(EventBus::new(), path.clone()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.event_count(), 10_000);
+
+        for (original, restored) in events.iter().zip(&loaded.events) {
+            assert_eq!(restored.id, original.id);
+            assert_eq!(restored.timestamp_ns, original.timestamp_ns);
+            assert_eq!(restored.priority, original.priority);
+            let restored = restored.event.downcast_ref::<MarketDataEvent>().unwrap();
+            let original = original.event.downcast_ref::<MarketDataEvent>().unwrap();
+            assert_eq!(restored.symbol, original.symbol);
+            assert_eq!(restored.price, original.price);
+            assert_eq!(restored.volume, original.volume);
+        }
+    }
+}
@@ -0,0 +1,75 @@
+//! Event type patterns for wildcard subscriptions
+
+/// Pattern used to match event type names
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatternKind {
+    /// Matches event types starting with the prefix
+    Prefix(String),
+    /// Glob pattern: `*` matches any sequence, `?` matches one character
+    Glob(String),
+}
+
+impl PatternKind {
+    /// Check if an event type matches this pattern
+    #[inline]
+    pub fn matches(&self, event_type: &str) -> bool {
+        match self {
+            PatternKind::Prefix(prefix) => event_type.starts_with(prefix.as_str()),
+            PatternKind::Glob(glob) => glob_match(glob.as_bytes(), event_type.as_bytes()),
+        }
+    }
+}
+
+/// Iterative glob matcher with single-star backtracking (no allocation)
+fn glob_match(pattern: &[u8], text: &[u8]) -> bool {
+    let (mut p, mut t) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == b'?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == b'*' {
+            star = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = star {
+            // Let the last star absorb one more character
+            p = star_p + 1;
+            t = star_t + 1;
+            star = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+    
+    pattern[p..].iter().all(|&c| c == b'*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_prefix() {
+        let pattern = PatternKind::Prefix("order".to_string());
+        assert!(pattern.matches("order"));
+        assert!(pattern.matches("order_update"));
+        assert!(!pattern.matches("fill"));
+    }
+    
+    #[test]
+    fn test_glob() {
+        let glob = |p: &str, t: &str| PatternKind::Glob(p.to_string()).matches(t);
+        
+        assert!(glob("order_*", "order_update"));
+        assert!(!glob("order_*", "order"));
+        assert!(glob("*", ""));
+        assert!(glob("*update", "order_update"));
+        assert!(glob("fil?", "fill"));
+        assert!(!glob("fil?", "fills"));
+        assert!(glob("*_*_*", "a_b_c"));
+        assert!(glob("m*t*a", "market_data"));
+        assert!(!glob("m*t*x", "market_data"));
+        assert!(glob("market_data", "market_data"));
+    }
+}
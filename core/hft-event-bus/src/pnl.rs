@@ -0,0 +1,435 @@
+//! P&L attribution of fills to the signals and strategies behind them
+//!
+//! `PnlAttributor` learns each signal's strategy from `SignalEvent`s and
+//! keeps an average-cost position per signal and symbol. Every fill is
+//! split into realized P&L, the unrealized P&L of the remaining position,
+//! and its slippage and commission. Attach it to a bus with `watch_bus`
+//! to publish an `AttributedPnlEvent` after each fill.
+//!
+//! Fill prices already include slippage, so `slippage_cost` is part of the
+//! realized and unrealized figures and only broken out for reporting;
+//! commission is not.
+
+use crate::bus::EventBus;
+use crate::events::{
+    AttributedPnlEvent, EventEnvelope, FillEvent, MarketDataEvent, MetricsEvent, OrderSide, SignalEvent,
+    StaticEventType,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tracing::warn;
+use uuid::Uuid;
+
+/// Strategy of fills without a signal, or whose signal was never seen
+pub const UNATTRIBUTED_STRATEGY: &str = "unattributed";
+
+/// P&L of one fill
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AttributedPnl {
+    /// `Uuid::nil()` for fills without a signal
+    pub signal_id: Uuid,
+    pub strategy_id: String,
+    /// P&L closed by this fill
+    pub realized_pnl: f64,
+    /// P&L of the signal's remaining position at the current price
+    pub unrealized_pnl: f64,
+    pub slippage_cost: f64,
+    pub commission_cost: f64,
+}
+
+/// Cumulative P&L of a strategy
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrategySummary {
+    pub strategy_id: String,
+    pub realized_pnl: f64,
+    /// Open positions marked at the latest known prices
+    pub unrealized_pnl: f64,
+    pub slippage_cost: f64,
+    pub commission_cost: f64,
+    pub fills: u64,
+    /// P&L from the strategy's latest `MetricsEvent`, for reconciliation
+    pub reported_pnl: Option<f64>,
+}
+
+impl StrategySummary {
+    fn new(strategy_id: &str) -> Self {
+        Self {
+            strategy_id: strategy_id.to_string(),
+            realized_pnl: 0.0,
+            unrealized_pnl: 0.0,
+            slippage_cost: 0.0,
+            commission_cost: 0.0,
+            fills: 0,
+            reported_pnl: None,
+        }
+    }
+
+    /// Realized plus unrealized P&L, less commission
+    pub fn net_pnl(&self) -> f64 {
+        self.realized_pnl + self.unrealized_pnl - self.commission_cost
+    }
+}
+
+/// Open position of one signal in one symbol
+#[derive(Debug, Clone)]
+struct SignalPosition {
+    strategy_id: String,
+    /// Signed: positive long, negative short
+    quantity: f64,
+    /// Average entry price of the open quantity
+    average_price: f64,
+    mark_price: f64,
+}
+
+impl SignalPosition {
+    fn unrealized_pnl(&self) -> f64 {
+        self.quantity * (self.mark_price - self.average_price)
+    }
+
+    /// Apply a signed fill, returning the realized P&L
+    fn apply(&mut self, quantity: f64, price: f64) -> f64 {
+        if quantity == 0.0 {
+            return 0.0;
+        }
+        if self.quantity == 0.0 || self.quantity.signum() == quantity.signum() {
+            let total = self.quantity + quantity;
+            self.average_price = (self.average_price * self.quantity.abs() + price * quantity.abs()) / total.abs();
+            self.quantity = total;
+            return 0.0;
+        }
+
+        let closed = quantity.abs().min(self.quantity.abs());
+        let realized = closed * (price - self.average_price) * self.quantity.signum();
+        self.quantity += quantity;
+        if self.quantity.abs() < f64::EPSILON {
+            self.quantity = 0.0;
+            self.average_price = 0.0;
+        } else if self.quantity.signum() == quantity.signum() {
+            // Flipped: the remainder opens at the fill price
+            self.average_price = price;
+        }
+        realized
+    }
+}
+
+#[derive(Default)]
+struct PnlState {
+    /// Signal ID -> strategy
+    signals: HashMap<Uuid, String>,
+    positions: HashMap<(Uuid, String), SignalPosition>,
+    strategies: HashMap<String, StrategySummary>,
+    /// Latest market price per symbol
+    marks: HashMap<String, f64>,
+}
+
+/// Attributes fill P&L to signals and strategies
+///
+/// Cloning yields a handle to the same state.
+#[derive(Clone, Default)]
+pub struct PnlAttributor {
+    state: Arc<Mutex<PnlState>>,
+}
+
+impl PnlAttributor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remember which strategy `signal` belongs to
+    pub fn record_signal(&self, signal: &SignalEvent) {
+        self.state.lock().unwrap().signals.insert(signal.signal_id, signal.strategy_id.clone());
+    }
+
+    /// Remember the P&L a strategy reported for itself
+    pub fn record_metrics(&self, metrics: &MetricsEvent) {
+        let Some(strategy_id) = &metrics.strategy_id else {
+            return;
+        };
+        let mut state = self.state.lock().unwrap();
+        state
+            .strategies
+            .entry(strategy_id.clone())
+            .or_insert_with(|| StrategySummary::new(strategy_id))
+            .reported_pnl = Some(metrics.pnl);
+    }
+
+    /// Mark open positions in `symbol` at `price`
+    pub fn mark(&self, symbol: &str, price: f64) {
+        let mut state = self.state.lock().unwrap();
+        state.marks.insert(symbol.to_string(), price);
+        for ((_, position_symbol), position) in state.positions.iter_mut() {
+            if position_symbol == symbol {
+                position.mark_price = price;
+            }
+        }
+    }
+
+    /// Apply `fill` to its signal's position, marked at `current_price`
+    pub fn update_fill(&self, fill: &FillEvent, current_price: f64) -> AttributedPnl {
+        let mut state = self.state.lock().unwrap();
+        let signal_id = fill.signal_id.unwrap_or(Uuid::nil());
+        let strategy_id = state
+            .signals
+            .get(&signal_id)
+            .cloned()
+            .unwrap_or_else(|| UNATTRIBUTED_STRATEGY.to_string());
+        state.marks.insert(fill.symbol.clone(), current_price);
+
+        let position = state
+            .positions
+            .entry((signal_id, fill.symbol.clone()))
+            .or_insert_with(|| SignalPosition {
+                strategy_id: strategy_id.clone(),
+                quantity: 0.0,
+                average_price: 0.0,
+                mark_price: current_price,
+            });
+        let signed_quantity = match fill.side {
+            OrderSide::Buy => fill.filled_quantity,
+            OrderSide::Sell => -fill.filled_quantity,
+        };
+        let realized_pnl = position.apply(signed_quantity, fill.fill_price);
+        position.mark_price = current_price;
+
+        let attributed = AttributedPnl {
+            signal_id,
+            strategy_id: strategy_id.clone(),
+            realized_pnl,
+            unrealized_pnl: position.unrealized_pnl(),
+            slippage_cost: fill.filled_quantity.abs() * fill.fill_price * fill.slippage_bps / 10_000.0,
+            commission_cost: fill.commission,
+        };
+
+        let summary = state
+            .strategies
+            .entry(strategy_id.clone())
+            .or_insert_with(|| StrategySummary::new(&strategy_id));
+        summary.realized_pnl += attributed.realized_pnl;
+        summary.slippage_cost += attributed.slippage_cost;
+        summary.commission_cost += attributed.commission_cost;
+        summary.fills += 1;
+        attributed
+    }
+
+    /// Cumulative P&L of `strategy_id` (`None` if it has no fills or metrics)
+    pub fn strategy_summary(&self, strategy_id: &str) -> Option<StrategySummary> {
+        let state = self.state.lock().unwrap();
+        let mut summary = state.strategies.get(strategy_id)?.clone();
+        summary.unrealized_pnl = state
+            .positions
+            .values()
+            .filter(|position| position.strategy_id == strategy_id)
+            .map(SignalPosition::unrealized_pnl)
+            .sum();
+        Some(summary)
+    }
+
+    /// Attribute every fill published on `bus` and publish the result
+    ///
+    /// Signals, market data and metrics on the bus update strategies and
+    /// marks; fills are marked at the latest market price of their symbol
+    /// (the fill price if none was seen). Each `AttributedPnlEvent` is
+    /// caused by its fill. The task ends when a watched channel closes;
+    /// abort the handle to stop earlier.
+    pub async fn watch_bus(&self, bus: &EventBus) -> JoinHandle<()> {
+        let mut signals = bus.subscribe(SignalEvent::EVENT_TYPE).await;
+        let mut fills = bus.subscribe(FillEvent::EVENT_TYPE).await;
+        let mut market_data = bus.subscribe(MarketDataEvent::EVENT_TYPE).await;
+        let mut metrics = bus.subscribe(MetricsEvent::EVENT_TYPE).await;
+        let attributor = self.clone();
+        let bus = bus.clone();
+        tokio::spawn(async move {
+            loop {
+                // Context first, so a fill sees the signals and prices published before it
+                let received = tokio::select! {
+                    biased;
+                    received = signals.recv() => received,
+                    received = market_data.recv() => received,
+                    received = metrics.recv() => received,
+                    received = fills.recv() => received,
+                };
+                match received {
+                    Ok(envelope) => attributor.handle_envelope(&bus, envelope).await,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!("P&L attributor lagged, skipped {} events", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return,
+                }
+            }
+        })
+    }
+
+    async fn handle_envelope(&self, bus: &EventBus, envelope: EventEnvelope) {
+        let event = &envelope.event;
+        if let Some(signal) = event.downcast_ref::<SignalEvent>() {
+            self.record_signal(signal);
+        } else if let Some(tick) = event.downcast_ref::<MarketDataEvent>() {
+            self.mark(&tick.symbol, tick.price);
+        } else if let Some(metrics) = event.downcast_ref::<MetricsEvent>() {
+            self.record_metrics(metrics);
+        } else if let Some(fill) = event.downcast_ref::<FillEvent>() {
+            let mark = self.state.lock().unwrap().marks.get(&fill.symbol).copied();
+            let attribution = self.update_fill(fill, mark.unwrap_or(fill.fill_price));
+            let attributed = AttributedPnlEvent {
+                timestamp: fill.timestamp,
+                fill_id: fill.fill_id,
+                symbol: fill.symbol.clone(),
+                attribution,
+            };
+            if let Err(e) = bus.publish_envelope(EventEnvelope::new(attributed, 5).caused_by(&envelope)).await {
+                warn!("Failed to publish attributed P&L: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::SignalDirection;
+    use std::time::Duration;
+
+    fn signal(strategy_id: &str) -> SignalEvent {
+        SignalEvent {
+            signal_id: Uuid::new_v4(),
+            timestamp: 0,
+            strategy_id: strategy_id.to_string(),
+            symbol: "ES".to_string(),
+            direction: SignalDirection::Long,
+            strength: 1.0,
+            target_price: None,
+            stop_loss: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    fn fill(signal_id: Option<Uuid>, side: OrderSide, quantity: f64, price: f64) -> FillEvent {
+        FillEvent {
+            fill_id: Uuid::new_v4(),
+            order_id: Uuid::new_v4(),
+            signal_id,
+            timestamp: 0,
+            symbol: "ES".to_string(),
+            side,
+            filled_quantity: quantity,
+            fill_price: price,
+            commission: 1.0,
+            slippage_bps: 5.0,
+        }
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "{} != {}", actual, expected);
+    }
+
+    #[test]
+    fn test_fill_sequence_arithmetic() {
+        let attributor = PnlAttributor::new();
+        let momentum = signal("momentum");
+        attributor.record_signal(&momentum);
+        let id = Some(momentum.signal_id);
+
+        // Open long 10 @ 100
+        let pnl = attributor.update_fill(&fill(id, OrderSide::Buy, 10.0, 100.0), 101.0);
+        assert_eq!(pnl.signal_id, momentum.signal_id);
+        assert_eq!(pnl.strategy_id, "momentum");
+        assert_close(pnl.realized_pnl, 0.0);
+        assert_close(pnl.unrealized_pnl, 10.0);
+        assert_close(pnl.slippage_cost, 10.0 * 100.0 * 5.0 / 10_000.0);
+        assert_close(pnl.commission_cost, 1.0);
+
+        // Add 10 @ 102: average 101
+        let pnl = attributor.update_fill(&fill(id, OrderSide::Buy, 10.0, 102.0), 103.0);
+        assert_close(pnl.unrealized_pnl, 20.0 * (103.0 - 101.0));
+
+        // Sell 15 @ 104: realize 15 * 3, 5 left
+        let pnl = attributor.update_fill(&fill(id, OrderSide::Sell, 15.0, 104.0), 104.0);
+        assert_close(pnl.realized_pnl, 45.0);
+        assert_close(pnl.unrealized_pnl, 5.0 * 3.0);
+
+        // Sell 10 @ 100: close 5 at a loss, then short 5 @ 100
+        let pnl = attributor.update_fill(&fill(id, OrderSide::Sell, 10.0, 100.0), 99.0);
+        assert_close(pnl.realized_pnl, -5.0);
+        assert_close(pnl.unrealized_pnl, 5.0);
+
+        let summary = attributor.strategy_summary("momentum").unwrap();
+        assert_eq!(summary.fills, 4);
+        assert_close(summary.realized_pnl, 40.0);
+        assert_close(summary.unrealized_pnl, 5.0);
+        assert_close(summary.commission_cost, 4.0);
+        assert_close(summary.slippage_cost, (1000.0 + 1020.0 + 1560.0 + 1000.0) * 5.0 / 10_000.0);
+        assert_close(summary.net_pnl(), 41.0);
+
+        // Marking moves the unrealized P&L only
+        attributor.mark("ES", 95.0);
+        let summary = attributor.strategy_summary("momentum").unwrap();
+        assert_close(summary.unrealized_pnl, 25.0);
+        assert_close(summary.realized_pnl, 40.0);
+        assert!(attributor.strategy_summary("carry").is_none());
+    }
+
+    #[test]
+    fn test_unattributed_fills() {
+        let attributor = PnlAttributor::new();
+        let pnl = attributor.update_fill(&fill(None, OrderSide::Sell, 2.0, 50.0), 49.0);
+        assert_eq!(pnl.signal_id, Uuid::nil());
+        assert_eq!(pnl.strategy_id, UNATTRIBUTED_STRATEGY);
+        assert_close(pnl.unrealized_pnl, 2.0);
+
+        // A signal that was never published is unattributed too
+        attributor.update_fill(&fill(Some(Uuid::new_v4()), OrderSide::Buy, 1.0, 50.0), 50.0);
+        assert_eq!(attributor.strategy_summary(UNATTRIBUTED_STRATEGY).unwrap().fills, 2);
+    }
+
+    #[tokio::test]
+    async fn test_watch_bus() {
+        let bus = EventBus::new();
+        let attributor = PnlAttributor::new();
+        let task = attributor.watch_bus(&bus).await;
+        let mut attributed = bus.subscribe(AttributedPnlEvent::EVENT_TYPE).await;
+
+        let momentum = signal("momentum");
+        bus.publish(momentum.clone()).await.unwrap();
+        bus.publish(MarketDataEvent {
+            timestamp: 0,
+            symbol: "ES".to_string(),
+            price: 103.0,
+            volume: 1.0,
+            bid_price: 102.75,
+            bid_size: 1.0,
+            ask_price: 103.25,
+            ask_size: 1.0,
+        })
+        .await
+        .unwrap();
+        bus.publish(MetricsEvent {
+            timestamp: 0,
+            strategy_id: Some("momentum".to_string()),
+            pnl: 29.0,
+            sharpe_ratio: 1.0,
+            max_drawdown: 0.0,
+            win_rate: 1.0,
+            total_trades: 1,
+        })
+        .await
+        .unwrap();
+        let fill_envelope = EventEnvelope::new(fill(Some(momentum.signal_id), OrderSide::Buy, 10.0, 100.0), 5);
+        let fill_id = fill_envelope.id;
+        bus.publish_envelope(fill_envelope).await.unwrap();
+
+        let envelope = tokio::time::timeout(Duration::from_secs(1), attributed.recv()).await.unwrap().unwrap();
+        assert_eq!(envelope.causation_id, Some(fill_id));
+        let event = AttributedPnlEvent::try_from(envelope).unwrap();
+        assert_eq!(event.attribution.strategy_id, "momentum");
+        // Marked at the last market price
+        assert_close(event.attribution.unrealized_pnl, 30.0);
+
+        let summary = attributor.strategy_summary("momentum").unwrap();
+        assert_eq!(summary.reported_pnl, Some(29.0));
+        assert_close(summary.net_pnl(), 29.0);
+        task.abort();
+    }
+}
@@ -0,0 +1,226 @@
+//! Recycled payload allocations for hot-path publishing
+//!
+//! `EnvelopePool` keeps boxes of previously published events. `acquire`
+//! moves a new value into a free box instead of allocating, and the box goes
+//! back to the pool when the last envelope holding it is dropped. Publishing
+//! still allocates the envelope's `Arc`, which only holds the box pointer, so
+//! the saving grows with the size of `T`:
+//!
+//! ```rust,ignore
+//! let pool = EnvelopePool::<MarketDataEvent>::new(4096);
+//! bus.publish_pooled(pool.acquire(tick)).await?;
+//! ```
+//!
+//! Subscribers see the original event type: `downcast_ref::<MarketDataEvent>()`
+//! looks through the pool wrapper.
+
+use crate::events::{Event, MarketEvent};
+use crossbeam::queue::SegQueue;
+use std::any::Any;
+use std::ops::Deref;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Shared state of one pool
+struct PoolInner<T> {
+    free: SegQueue<Box<T>>,
+    /// Boxes beyond this many free ones are deallocated on return
+    max_free: usize,
+    allocated: AtomicU64,
+    reused: AtomicU64,
+}
+
+/// Pool of reusable event boxes
+///
+/// Cloning yields a handle to the same pool.
+pub struct EnvelopePool<T> {
+    inner: Arc<PoolInner<T>>,
+}
+
+impl<T: Event + 'static> EnvelopePool<T> {
+    /// Create pool keeping at most `max_free` idle boxes
+    pub fn new(max_free: usize) -> Self {
+        Self {
+            inner: Arc::new(PoolInner {
+                free: SegQueue::new(),
+                max_free,
+                allocated: AtomicU64::new(0),
+                reused: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// Pre-allocate `count` boxes (capped at `max_free`)
+    pub fn prefill(&self, count: usize)
+    where
+        T: Default,
+    {
+        while self.inner.free.len() < count.min(self.inner.max_free) {
+            self.inner.free.push(Box::default());
+            self.inner.allocated.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Move `value` into a recycled box, allocating only if none is free
+    #[inline]
+    pub fn acquire(&self, value: T) -> PooledEnvelope<T> {
+        let boxed = match self.inner.free.pop() {
+            Some(mut boxed) => {
+                *boxed = value;
+                self.inner.reused.fetch_add(1, Ordering::Relaxed);
+                boxed
+            }
+            None => {
+                self.inner.allocated.fetch_add(1, Ordering::Relaxed);
+                Box::new(value)
+            }
+        };
+        PooledEnvelope {
+            value: Some(boxed),
+            pool: self.inner.clone(),
+        }
+    }
+
+    /// Number of idle boxes
+    pub fn available(&self) -> usize {
+        self.inner.free.len()
+    }
+
+    /// Boxes allocated since the pool was created
+    pub fn allocated(&self) -> u64 {
+        self.inner.allocated.load(Ordering::Relaxed)
+    }
+
+    /// `acquire` calls served from a recycled box
+    pub fn reused(&self) -> u64 {
+        self.inner.reused.load(Ordering::Relaxed)
+    }
+}
+
+impl<T> Clone for EnvelopePool<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+/// Event in a pooled box, returned to its pool on drop
+pub struct PooledEnvelope<T> {
+    /// Always `Some` until dropped
+    value: Option<Box<T>>,
+    pool: Arc<PoolInner<T>>,
+}
+
+impl<T> Deref for PooledEnvelope<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value.as_deref().expect("pooled value taken before drop")
+    }
+}
+
+impl<T> Drop for PooledEnvelope<T> {
+    fn drop(&mut self) {
+        if let Some(boxed) = self.value.take() {
+            if self.pool.free.len() < self.pool.max_free {
+                self.pool.free.push(boxed);
+            }
+        }
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for PooledEnvelope<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("PooledEnvelope").field(&**self).finish()
+    }
+}
+
+impl<T: Event + 'static> Event for PooledEnvelope<T> {
+    fn event_type(&self) -> &'static str {
+        (**self).event_type()
+    }
+
+    fn priority(&self) -> u8 {
+        (**self).priority()
+    }
+
+    fn to_json(&self) -> Option<serde_json::Value> {
+        (**self).to_json()
+    }
+
+    fn as_market_event(&self) -> Option<&dyn MarketEvent> {
+        (**self).as_market_event()
+    }
+
+    fn wrapped_payload(&self) -> Option<&dyn Any> {
+        Some((**self).as_any())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{EventEnvelope, MarketDataEvent};
+    use crate::test_fixtures::quote;
+
+    #[test]
+    fn test_boxes_are_recycled() {
+        let pool = EnvelopePool::<MarketDataEvent>::new(2);
+        let first = pool.acquire(quote("ES", 1.0));
+        let second = pool.acquire(quote("ES", 2.0));
+        let third = pool.acquire(quote("ES", 3.0));
+        assert_eq!(pool.allocated(), 3);
+        assert_eq!(second.price, 2.0);
+
+        // Only `max_free` boxes are kept
+        drop((first, second, third));
+        assert_eq!(pool.available(), 2);
+
+        let reused = pool.acquire(quote("ES", 4.0));
+        assert_eq!(reused.price, 4.0);
+        assert_eq!(pool.reused(), 1);
+        assert_eq!(pool.allocated(), 3);
+    }
+
+    #[derive(Debug, Default)]
+    struct Counter(u64);
+
+    impl Event for Counter {
+        fn event_type(&self) -> &'static str {
+            "counter"
+        }
+    }
+
+    #[test]
+    fn test_prefill() {
+        let pool = EnvelopePool::<Counter>::new(8);
+        pool.prefill(100);
+        assert_eq!(pool.available(), 8);
+        assert_eq!(pool.acquire(Counter(7)).0, 7);
+        assert_eq!(pool.allocated(), 8);
+        assert_eq!(pool.reused(), 1);
+    }
+
+    #[test]
+    fn test_box_returns_when_last_envelope_drops() {
+        let pool = EnvelopePool::<MarketDataEvent>::new(16);
+        drop((0..4).map(|i| pool.acquire(quote("ES", i as f64))).collect::<Vec<_>>());
+        assert_eq!(pool.available(), 4);
+
+        let envelope = EventEnvelope::new(pool.acquire(quote("ES", 6000.0)), 5);
+        let copy = envelope.clone();
+        assert_eq!(pool.available(), 3);
+
+        // Wrapper is transparent to consumers
+        assert_eq!(envelope.event.event_type(), "market_data");
+        assert_eq!(copy.event.downcast_ref::<MarketDataEvent>().unwrap().price, 6000.0);
+        assert_eq!(copy.event.to_json().unwrap()["price"], 6000.0);
+
+        drop(envelope);
+        assert_eq!(pool.available(), 3);
+        drop(copy);
+        assert_eq!(pool.available(), 4);
+        assert_eq!(pool.allocated(), 4);
+    }
+}
@@ -0,0 +1,238 @@
+//! Priority-ordered event delivery
+//!
+//! `EventBus` delivers events in publication order regardless of their
+//! priority. `PriorityEventBus` queues published events in a binary heap and
+//! a dispatcher task delivers them lowest priority value first (0 = highest),
+//! FIFO within a priority level.
+
+use crate::bus::CHANNEL_CAPACITY;
+use crate::events::{Event, EventEnvelope};
+use anyhow::Result;
+use dashmap::DashMap;
+use std::cmp::{Ordering as CmpOrdering, Reverse};
+use std::collections::BinaryHeap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Weak};
+use tokio::sync::{broadcast, Mutex, Notify};
+use tracing::debug;
+
+/// Heap entry ordered by (priority, publication sequence)
+struct QueuedEnvelope {
+    priority: u8,
+    sequence: u64,
+    envelope: EventEnvelope,
+}
+
+impl PartialEq for QueuedEnvelope {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == CmpOrdering::Equal
+    }
+}
+
+impl Eq for QueuedEnvelope {}
+
+impl PartialOrd for QueuedEnvelope {
+    fn partial_cmp(&self, other: &Self) -> Option<CmpOrdering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for QueuedEnvelope {
+    fn cmp(&self, other: &Self) -> CmpOrdering {
+        (self.priority, self.sequence).cmp(&(other.priority, other.sequence))
+    }
+}
+
+struct Inner {
+    /// Pending events, smallest (priority, sequence) on top
+    heap: Mutex<BinaryHeap<Reverse<QueuedEnvelope>>>,
+    
+    /// Broadcast channels for each event type
+    channels: DashMap<String, broadcast::Sender<EventEnvelope>>,
+    
+    /// Publication counter (FIFO tie-break within a priority)
+    sequence: AtomicU64,
+    
+    /// Dispatch is held while true
+    paused: AtomicBool,
+    
+    /// Wakes the dispatcher
+    notify: Arc<Notify>,
+}
+
+impl Drop for Inner {
+    fn drop(&mut self) {
+        // Wake the dispatcher so it notices the bus is gone and exits
+        self.notify.notify_one();
+    }
+}
+
+/// Event bus that delivers queued events in priority order
+///
+/// Must be created inside a Tokio runtime (spawns the dispatcher task).
+/// Cloning is cheap and yields a handle to the same bus.
+#[derive(Clone)]
+pub struct PriorityEventBus {
+    inner: Arc<Inner>,
+}
+
+impl PriorityEventBus {
+    /// Create bus and spawn its dispatcher task
+    pub fn new() -> Self {
+        let notify = Arc::new(Notify::new());
+        let inner = Arc::new(Inner {
+            heap: Mutex::new(BinaryHeap::new()),
+            channels: DashMap::new(),
+            sequence: AtomicU64::new(0),
+            paused: AtomicBool::new(false),
+            notify: notify.clone(),
+        });
+        
+        tokio::spawn(dispatch(Arc::downgrade(&inner), notify));
+        
+        Self { inner }
+    }
+    
+    /// Publish an event with default priority
+    pub async fn publish<T: Event + Send + 'static>(&self, event: T) -> Result<()> {
+        self.publish_with_priority(event, 5).await
+    }
+    
+    /// Publish event with specific priority (0 = highest)
+    pub async fn publish_with_priority<T: Event + Send + 'static>(&self, event: T, priority: u8) -> Result<()> {
+        let queued = QueuedEnvelope {
+            priority,
+            sequence: self.inner.sequence.fetch_add(1, Ordering::Relaxed),
+            envelope: EventEnvelope::new(event, priority),
+        };
+        
+        self.inner.heap.lock().await.push(Reverse(queued));
+        self.inner.notify.notify_one();
+        Ok(())
+    }
+    
+    /// Subscribe to a specific event type
+    pub async fn subscribe(&self, event_type: &str) -> broadcast::Receiver<EventEnvelope> {
+        self.inner.channels.entry(event_type.to_string())
+            .or_insert_with(|| {
+                debug!("Creating new priority channel for subscription: {}", event_type);
+                broadcast::channel(CHANNEL_CAPACITY).0
+            })
+            .subscribe()
+    }
+    
+    /// Hold delivery; published events keep queuing in priority order
+    pub fn pause(&self) {
+        self.inner.paused.store(true, Ordering::Release);
+    }
+    
+    /// Resume delivery of queued events
+    pub fn resume(&self) {
+        self.inner.paused.store(false, Ordering::Release);
+        self.inner.notify.notify_one();
+    }
+    
+    /// Number of events waiting for dispatch
+    pub async fn pending(&self) -> usize {
+        self.inner.heap.lock().await.len()
+    }
+}
+
+impl Default for PriorityEventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Dispatcher loop: drain the heap in priority order on every wake-up
+async fn dispatch(inner: Weak<Inner>, notify: Arc<Notify>) {
+    loop {
+        notify.notified().await;
+        
+        let inner = match inner.upgrade() {
+            Some(inner) => inner,
+            None => return,
+        };
+        
+        while !inner.paused.load(Ordering::Acquire) {
+            let next = inner.heap.lock().await.pop();
+            let queued = match next {
+                Some(Reverse(queued)) => queued,
+                None => break,
+            };
+            
+            let event_type = queued.envelope.event.event_type();
+            if let Some(sender) = inner.channels.get(event_type) {
+                // No receivers is not an error
+                let _ = sender.send(queued.envelope);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::quote;
+    
+    #[tokio::test]
+    async fn test_priority_order_delivery() {
+        let bus = crate::bus::EventBus::new_priority_ordered();
+        let mut rx1 = bus.subscribe("market_data").await;
+        let mut rx2 = bus.clone().subscribe("market_data").await;
+        
+        // Queue everything first so ordering is decided by the heap alone
+        bus.pause();
+        let mut seed: u64 = 0x2545_F491_4F6C_DD1D;
+        for i in 0..1000 {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            let priority = ((seed >> 33) % 10) as u8;
+            bus.publish_with_priority(quote("ES", i as f64), priority).await.unwrap();
+        }
+        assert_eq!(bus.pending().await, 1000);
+        bus.resume();
+        
+        for rx in [&mut rx1, &mut rx2] {
+            let mut last = 0u8;
+            for _ in 0..1000 {
+                let envelope = rx.recv().await.unwrap();
+                assert!(envelope.priority >= last, "priority {} after {}", envelope.priority, last);
+                last = envelope.priority;
+            }
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_fifo_within_priority() {
+        let bus = PriorityEventBus::new();
+        let mut rx = bus.subscribe("market_data").await;
+        
+        bus.pause();
+        for i in 0..10 {
+            bus.publish(quote("ES", i as f64)).await.unwrap();
+        }
+        bus.resume();
+        
+        let mut ids = Vec::new();
+        for _ in 0..10 {
+            ids.push(rx.recv().await.unwrap().id);
+        }
+        let mut sorted = ids.clone();
+        sorted.sort();
+        assert_eq!(ids, sorted);
+    }
+    
+    #[tokio::test]
+    async fn test_delivery_without_pause() {
+        let bus = PriorityEventBus::new();
+        let mut rx = bus.subscribe("market_data").await;
+        
+        bus.publish_with_priority(quote("ES", 1.0), 0).await.unwrap();
+        
+        let envelope = tokio::time::timeout(std::time::Duration::from_secs(1), rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(envelope.priority, 0);
+    }
+}
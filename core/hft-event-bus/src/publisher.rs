@@ -1,26 +1,163 @@
 //! Publisher utilities and helpers
 
-use crate::bus::EventBus;
-use crate::events::Event;
-use anyhow::Result;
+use crate::bus::{EventBus, PublishDecision, PublishReceipt, TransactionEvent, TransactionReceipt};
+use crate::dedup::{BulkPublisher, DedupPublisher};
+use crate::events::{Event, EventEnvelope, SignalEvent};
+use crate::replay_mode::SharedClock;
+use crate::research_topic::{ResearchEvent, ResearchStateChangedEvent};
+use crate::signal_schema::SignalSchemaRegistry;
+use anyhow::{bail, Result};
+use dashmap::DashMap;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicI64, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
 
 /// Helper for publishing events
 pub struct Publisher {
     bus: Arc<EventBus>,
+    /// Validates signal metadata in `publish_signal` (optional)
+    signal_schemas: Option<SignalSchemaRegistry>,
+    /// Last `publish_throttled` time per event type (bus clock)
+    last_throttled: DashMap<&'static str, AtomicI64>,
 }
 
 impl Publisher {
     /// Create new publisher
     pub fn new(bus: Arc<EventBus>) -> Self {
-        Self { bus }
+        Self { bus, signal_schemas: None, last_throttled: DashMap::new() }
+    }
+    
+    /// Validate signals passed to `publish_signal` against `registry`
+    pub fn with_signal_schemas(mut self, registry: SignalSchemaRegistry) -> Self {
+        self.signal_schemas = Some(registry);
+        self
+    }
+    
+    /// Wrap in a publisher that drops signals repeated within `window_ns`
+    pub fn with_signal_deduplication(self, window_ns: u64) -> DedupPublisher {
+        DedupPublisher::new(self, window_ns)
+    }
+    
+    /// Wrap in a publisher that drops batch events whose key repeats within `window_ns`
+    pub fn with_bulk_deduplication<K: Hash + Eq>(self, window_ns: u64) -> BulkPublisher<K> {
+        BulkPublisher::new(self, window_ns)
+    }
+    
+    /// Current time on the bus clock
+    pub(crate) fn now_ns(&self) -> i64 {
+        self.bus.now_ns()
     }
     
     /// Publish event with default priority
-    pub async fn publish<T: Event + Send + 'static>(&self, event: T) -> Result<()> {
+    pub async fn publish<T: Event + Send + 'static>(&self, event: T) -> Result<PublishReceipt> {
         self.bus.publish(event).await
     }
     
+    /// Publish event with default priority, discarding the receipt
+    pub async fn publish_fire_and_forget<T: Event + Send + 'static>(&self, event: T) -> Result<()> {
+        self.bus.publish_fire_and_forget(event).await
+    }
+    
+    /// Wait until every subscriber counted in `receipt` has consumed the event
+    ///
+    /// Polls the event's channel; fails if the event is still queued after
+    /// `timeout`. Returns immediately for receipts with no subscribers.
+    pub async fn await_delivery(&self, receipt: PublishReceipt, timeout: Duration) -> Result<()> {
+        let deadline = tokio::time::Instant::now() + timeout;
+        while !receipt.is_delivered() {
+            if tokio::time::Instant::now() >= deadline {
+                bail!(
+                    "Event {} not consumed by all {} subscribers within {:?}",
+                    receipt.event_id,
+                    receipt.subscriber_count,
+                    timeout
+                );
+            }
+            tokio::time::sleep(Duration::from_micros(100)).await;
+        }
+        Ok(())
+    }
+    
+    /// Publish `events` all or nothing, each at its own priority
+    ///
+    /// See `EventBus::publish_transaction`.
+    pub async fn publish_transactional(&self, events: Vec<Box<dyn Event>>) -> Result<TransactionReceipt> {
+        let events = events
+            .into_iter()
+            .map(|event| {
+                let priority = event.priority();
+                TransactionEvent { event, priority }
+            })
+            .collect();
+        self.bus.publish_transaction(events).await
+    }
+    
+    /// Publish only if `condition` holds (see `EventBus::publish_if`)
+    pub async fn publish_if<T: Event + Send + 'static>(
+        &self,
+        event: T,
+        condition: impl Fn() -> bool + Send + 'static,
+    ) -> Result<PublishDecision> {
+        self.bus.publish_if(event, condition).await
+    }
+    
+    /// Publish unless this publisher sent the same event type less than
+    /// `min_interval_ns` ago (on the bus's clock)
+    ///
+    /// Concurrent callers race for the slot; exactly one of them publishes.
+    pub async fn publish_throttled<T: Event + Send + 'static>(&self, event: T, min_interval_ns: u64) -> Result<PublishDecision> {
+        let event_type = event.event_type();
+        let now = self.bus.now_ns();
+        if !self.last_throttled.contains_key(event_type) {
+            self.last_throttled.entry(event_type).or_insert_with(|| AtomicI64::new(i64::MIN));
+        }
+        
+        let claimed = {
+            let last = self.last_throttled.get(event_type).expect("inserted above");
+            let mut previous = last.load(Ordering::Acquire);
+            loop {
+                if (now as i128 - previous as i128) < min_interval_ns as i128 {
+                    break false;
+                }
+                match last.compare_exchange_weak(previous, now, Ordering::AcqRel, Ordering::Acquire) {
+                    Ok(_) => break true,
+                    Err(actual) => previous = actual,
+                }
+            }
+        };
+        if !claimed {
+            return Ok(PublishDecision::Suppressed);
+        }
+        self.bus.publish(event).await.map(PublishDecision::Published)
+    }
+    
+    /// Publish a slice of events, returning how many were delivered
+    pub async fn publish_batch<T: Event + Send + Clone + 'static>(&self, events: &[T]) -> Result<usize> {
+        self.bus.publish_batch(events).await
+    }
+    
+    /// Publish a signal after checking its metadata schema
+    ///
+    /// Fails with a `ValidationError` (and publishes nothing) if the
+    /// metadata violates the schema registered for the signal's strategy.
+    pub async fn publish_signal(&self, signal: SignalEvent) -> Result<()> {
+        if let Some(registry) = &self.signal_schemas {
+            registry.validate(&signal)?;
+        }
+        self.bus.publish_fire_and_forget(signal).await
+    }
+    
+    /// Publish a research state change after checking the transition
+    ///
+    /// Fails (and publishes nothing) if the states are unknown or the
+    /// transition is not in the default `StateTransitionValidator` table.
+    pub async fn publish_research_state_change(&self, event: ResearchStateChangedEvent) -> Result<()> {
+        event.validate()?;
+        self.bus.publish_fire_and_forget(ResearchEvent::ResearchStateChanged(event)).await
+    }
+    
     /// Publish event with high priority
     pub async fn publish_high_priority<T: Event + Send + 'static>(&self, event: T) -> Result<()> {
         self.bus.publish_with_priority(event, 0).await
@@ -30,4 +167,406 @@ impl Publisher {
     pub async fn publish_low_priority<T: Event + Send + 'static>(&self, event: T) -> Result<()> {
         self.bus.publish_with_priority(event, 9).await
     }
+    
+    /// Publish an event caused by `parent`
+    ///
+    /// Sets `causation_id` to the parent's ID and carries its correlation ID
+    /// (or starts one from the parent's ID), so the chain can be followed with
+    /// `EventBus::subscribe_correlation`.
+    pub async fn publish_caused_by<T: Event + Send + 'static>(&self, event: T, parent_envelope: &EventEnvelope) -> Result<()> {
+        self.bus.publish_caused_by(event, parent_envelope).await
+    }
+    
+    /// Publish `event` after `delay_ns` nanoseconds
+    ///
+    /// Runs on a spawned task; the handle resolves to the publish result and
+    /// can be passed to `cancel_scheduled`.
+    pub fn publish_scheduled<T: Event + Send + 'static>(&self, event: T, delay_ns: u64, priority: u8) -> JoinHandle<Result<()>> {
+        let bus = self.bus.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_nanos(delay_ns)).await;
+            bus.publish_with_priority(event, priority).await
+        })
+    }
+    
+    /// Publish `event` at wall-clock time `at_ns` (immediately if already past)
+    pub fn publish_at<T: Event + Send + 'static>(&self, event: T, at_ns: i64, priority: u8) -> JoinHandle<Result<()>> {
+        let now_ns = chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0);
+        let delay_ns = u64::try_from(at_ns.saturating_sub(now_ns)).unwrap_or(0);
+        self.publish_scheduled(event, delay_ns, priority)
+    }
+    
+    /// Publish `event` once virtual time on `clock` reaches `virtual_ns`
+    ///
+    /// For replays, where wall-clock delays are meaningless. The task polls
+    /// the clock, yielding between polls, so it fires at the first replayed
+    /// instant at or after `virtual_ns`.
+    pub fn publish_at_virtual_time<T: Event + Send + 'static>(&self, event: T, virtual_ns: i64, clock: SharedClock, priority: u8) -> JoinHandle<Result<()>> {
+        let bus = self.bus.clone();
+        tokio::spawn(async move {
+            while clock.now() < virtual_ns {
+                tokio::task::yield_now().await;
+            }
+            bus.publish_with_priority(event, priority).await
+        })
+    }
+    
+    /// Publish `event` `delay_ns` of virtual time after the clock's current value
+    pub fn publish_after_virtual_delay<T: Event + Send + 'static>(&self, event: T, delay_ns: u64, clock: SharedClock, priority: u8) -> JoinHandle<Result<()>> {
+        let virtual_ns = clock.now().saturating_add(i64::try_from(delay_ns).unwrap_or(i64::MAX));
+        self.publish_at_virtual_time(event, virtual_ns, clock, priority)
+    }
+    
+    /// Cancel a scheduled publish (no-op if it already ran)
+    pub fn cancel_scheduled(&self, handle: JoinHandle<Result<()>>) {
+        handle.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{OrderSide, OrderType, OrderEvent};
+    use tokio::time::Instant;
+    use uuid::Uuid;
+    
+    fn order_event() -> OrderEvent {
+        OrderEvent {
+            order_id: Uuid::from_u128(1),
+            signal_id: None,
+            timestamp: 0,
+            symbol: "ES".to_string(),
+            side: OrderSide::Buy,
+            order_type: OrderType::Market,
+            quantity: 1.0,
+            price: None,
+        }
+    }
+    
+    #[tokio::test(start_paused = true)]
+    async fn test_publish_scheduled_delay() {
+        let bus = Arc::new(EventBus::new());
+        let publisher = Publisher::new(bus.clone());
+        let mut rx = bus.subscribe_orders().await;
+        
+        let delay = Duration::from_millis(20);
+        let start = Instant::now();
+        let handle = publisher.publish_scheduled(order_event(), delay.as_nanos() as u64, 3);
+        
+        let envelope = rx.recv().await.unwrap();
+        assert_eq!(start.elapsed(), delay);
+        assert_eq!(envelope.priority, 3);
+        handle.await.unwrap().unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_publish_at() {
+        let bus = Arc::new(EventBus::new());
+        let publisher = Publisher::new(bus.clone());
+        let mut rx = bus.subscribe_orders().await;
+        
+        let at_ns = chrono::Utc::now().timestamp_nanos_opt().unwrap() + 10_000_000;
+        publisher.publish_at(order_event(), at_ns, 5).await.unwrap().unwrap();
+        let envelope = rx.recv().await.unwrap();
+        assert!(envelope.timestamp_ns >= at_ns);
+    }
+    
+    #[tokio::test]
+    async fn test_cancel_scheduled() {
+        let bus = Arc::new(EventBus::new());
+        let publisher = Publisher::new(bus.clone());
+        let mut rx = bus.subscribe_orders().await;
+        
+        let handle = publisher.publish_scheduled(order_event(), 10_000_000, 5);
+        publisher.cancel_scheduled(handle);
+        
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert!(rx.try_recv().is_err());
+    }
+    
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_publish_at_virtual_time() {
+        use crate::events::FillEvent;
+        use crate::replay_mode::{EventReplay, ReplaySpeed};
+        
+        // Fills every 200ms of virtual time for 2s, replayed at 100x
+        let fills: Vec<EventEnvelope> = (0..=10)
+            .map(|i| {
+                let mut envelope = EventEnvelope::new(FillEvent {
+                    fill_id: Uuid::new_v4(),
+                    order_id: Uuid::new_v4(),
+                    signal_id: None,
+                    timestamp: 0,
+                    symbol: "ES".to_string(),
+                    side: OrderSide::Buy,
+                    filled_quantity: 1.0,
+                    fill_price: 6000.0,
+                    commission: 0.0,
+                    slippage_bps: 0.0,
+                }, 5);
+                envelope.timestamp_ns = i * 200_000_000;
+                envelope
+            })
+            .collect();
+        let mut replay = EventReplay::new(EventBus::new(), ReplaySpeed::Multiplier(100.0));
+        replay.load_events(fills).unwrap();
+        
+        // Strategy side: bus stamped with the replay's virtual clock
+        let clock = replay.shared_clock();
+        let bus = Arc::new(EventBus::with_shared_clock(clock.clone()));
+        let publisher = Publisher::new(bus.clone());
+        let mut rx = bus.subscribe_orders().await;
+        
+        let at = publisher.publish_at_virtual_time(order_event(), 1_050_000_000, clock.clone(), 1);
+        let after = publisher.publish_after_virtual_delay(order_event(), 500_000_000, clock.clone(), 2);
+        
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        assert!(rx.try_recv().is_err(), "published before virtual time reached target");
+        
+        replay.run().await;
+        at.await.unwrap().unwrap();
+        after.await.unwrap().unwrap();
+        
+        // Fired at the first replayed instant at or after the target
+        let mut received: Vec<EventEnvelope> = std::iter::from_fn(|| rx.try_recv().ok()).collect();
+        received.sort_by_key(|e| e.priority);
+        assert_eq!(received.len(), 2);
+        assert!((1_050_000_000..=1_400_000_000).contains(&received[0].timestamp_ns), "{}", received[0].timestamp_ns);
+        assert!((500_000_000..=800_000_000).contains(&received[1].timestamp_ns), "{}", received[1].timestamp_ns);
+    }
+    
+    #[tokio::test]
+    async fn test_publish_transactional() {
+        use crate::bus::CHANNEL_CAPACITY;
+        use crate::events::{HealthEvent, HealthStatus};
+        
+        let health = || HealthEvent {
+            timestamp: 0,
+            component: "oms".to_string(),
+            status: HealthStatus::Healthy,
+            message: String::new(),
+        };
+        let bus = Arc::new(EventBus::new());
+        let publisher = Publisher::new(bus.clone());
+        let mut orders = bus.subscribe_orders().await;
+        let mut health_rx = bus.subscribe("health").await;
+        
+        let receipt = publisher
+            .publish_transactional(vec![Box::new(order_event()), Box::new(health()), Box::new(order_event())])
+            .await
+            .unwrap();
+        assert_eq!(receipt.events_published, 3);
+        for _ in 0..2 {
+            assert_eq!(orders.try_recv().unwrap().transaction_id, Some(receipt.transaction_id));
+        }
+        assert_eq!(health_rx.try_recv().unwrap().transaction_id, Some(receipt.transaction_id));
+        
+        // Leave room for exactly one more order: a two-order transaction
+        // would evict an unread one, so none of its events go out
+        for _ in 0..CHANNEL_CAPACITY - 1 {
+            bus.publish_fire_and_forget(order_event()).await.unwrap();
+        }
+        let rejected = publisher
+            .publish_transactional(vec![Box::new(health()), Box::new(order_event()), Box::new(order_event())])
+            .await;
+        assert!(rejected.is_err());
+        assert!(health_rx.try_recv().is_err());
+        
+        let receipt = publisher.publish_transactional(vec![Box::new(order_event())]).await.unwrap();
+        assert_eq!(receipt.events_published, 1);
+        // Nothing was evicted, and the rejected orders never arrived
+        let queued = std::iter::from_fn(|| orders.try_recv().ok()).collect::<Vec<_>>();
+        assert_eq!(queued.len(), CHANNEL_CAPACITY);
+        assert_eq!(queued.last().unwrap().transaction_id, Some(receipt.transaction_id));
+        
+        // No subscriber for fills: rejected before anything is sent
+        let fill = crate::events::FillEvent {
+            fill_id: Uuid::new_v4(),
+            order_id: Uuid::new_v4(),
+            signal_id: None,
+            timestamp: 0,
+            symbol: "ES".to_string(),
+            side: OrderSide::Buy,
+            filled_quantity: 1.0,
+            fill_price: 6000.0,
+            commission: 0.0,
+            slippage_bps: 0.0,
+        };
+        assert!(publisher.publish_transactional(vec![Box::new(health()), Box::new(fill)]).await.is_err());
+        assert!(health_rx.try_recv().is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_transaction_keeps_correlation_chain() {
+        use crate::middleware::MiddlewareResult;
+        
+        // Middleware ties every order into the caller's chain
+        let chain = Uuid::new_v4();
+        let bus = Arc::new(EventBus::with_recording(16));
+        bus.add_middleware(move |envelope: &mut EventEnvelope| {
+            envelope.correlation_id = Some(chain);
+            MiddlewareResult::Continue
+        });
+        let publisher = Publisher::new(bus.clone());
+        let mut orders = bus.subscribe_orders().await;
+        
+        let receipt = publisher.publish_transactional(vec![Box::new(order_event())]).await.unwrap();
+        let envelope = orders.try_recv().unwrap();
+        assert_eq!(envelope.correlation_id, Some(chain));
+        assert_eq!(envelope.transaction_id, Some(receipt.transaction_id));
+        
+        let recorded = bus.recorder().unwrap().get_events().await;
+        assert_eq!(recorded.len(), 1);
+        assert_eq!(recorded[0].id, envelope.id);
+    }
+    
+    #[tokio::test]
+    async fn test_publish_signal_validates_metadata() {
+        use crate::events::SignalDirection;
+        use crate::signal_schema::ValidationError;
+        
+        let registry = SignalSchemaRegistry::new();
+        registry.register("momentum", r#"{"type": "object", "required": ["reason"]}"#).unwrap();
+        let bus = Arc::new(EventBus::new());
+        let publisher = Publisher::new(bus.clone()).with_signal_schemas(registry);
+        let mut rx = bus.subscribe_signals().await;
+        
+        let mut signal = SignalEvent {
+            signal_id: Uuid::new_v4(),
+            timestamp: 0,
+            strategy_id: "momentum".to_string(),
+            symbol: "ES".to_string(),
+            direction: SignalDirection::Long,
+            strength: 0.7,
+            target_price: None,
+            stop_loss: None,
+            metadata: Default::default(),
+        };
+        
+        let err = publisher.publish_signal(signal.clone()).await.unwrap_err();
+        assert!(err.downcast_ref::<ValidationError>().is_some());
+        assert!(rx.try_recv().is_err());
+        
+        signal.metadata.insert("reason".to_string(), "breakout".to_string());
+        publisher.publish_signal(signal).await.unwrap();
+        assert!(rx.recv().await.unwrap().event.downcast_ref::<SignalEvent>().is_some());
+    }
+    
+    #[tokio::test]
+    async fn test_publish_receipt_subscriber_count() {
+        let bus = Arc::new(EventBus::new());
+        let publisher = Publisher::new(bus.clone());
+        
+        let receipt = publisher.publish(order_event()).await.unwrap();
+        assert_eq!(receipt.subscriber_count, 0);
+        assert!(receipt.is_delivered());
+        
+        let mut receivers = vec![bus.subscribe_orders().await];
+        let receipt = publisher.publish(order_event()).await.unwrap();
+        assert_eq!(receipt.subscriber_count, 1);
+        let envelope = receivers[0].recv().await.unwrap();
+        assert_eq!(envelope.id, receipt.event_id);
+        assert_eq!(envelope.timestamp_ns, receipt.timestamp_ns);
+        
+        for _ in 0..4 {
+            receivers.push(bus.subscribe_orders().await);
+        }
+        let receipt = publisher.publish(order_event()).await.unwrap();
+        assert_eq!(receipt.subscriber_count, 5);
+        for rx in &mut receivers {
+            assert_eq!(rx.recv().await.unwrap().id, receipt.event_id);
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_await_delivery() {
+        let bus = Arc::new(EventBus::new());
+        let publisher = Publisher::new(bus.clone());
+        let mut fast = bus.subscribe_orders().await;
+        let mut slow = bus.subscribe_orders().await;
+        
+        let first = publisher.publish(order_event()).await.unwrap();
+        let second = publisher.publish(order_event()).await.unwrap();
+        fast.recv().await.unwrap();
+        fast.recv().await.unwrap();
+        
+        // `slow` has not read either event yet
+        assert!(publisher.await_delivery(first.clone(), Duration::from_millis(5)).await.is_err());
+        
+        slow.recv().await.unwrap();
+        publisher.await_delivery(first, Duration::from_millis(5)).await.unwrap();
+        assert!(!second.is_delivered());
+        
+        let reader = tokio::spawn(async move { slow.recv().await.unwrap() });
+        publisher.await_delivery(second, Duration::from_secs(1)).await.unwrap();
+        reader.await.unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_publish_research_state_change_validates() {
+        let bus = Arc::new(EventBus::new());
+        let publisher = Publisher::new(bus.clone());
+        let mut rx = bus.subscribe("research_state_changed").await;
+        
+        let change = |old_state: &str, new_state: &str| ResearchStateChangedEvent {
+            component: "backtester".to_string(),
+            old_state: old_state.to_string(),
+            new_state: new_state.to_string(),
+            timestamp: 0,
+        };
+        assert!(publisher.publish_research_state_change(change("idle", "paused")).await.is_err());
+        publisher.publish_research_state_change(change("running", "paused")).await.unwrap();
+        
+        let envelope = rx.recv().await.unwrap();
+        match envelope.event.downcast_ref::<ResearchEvent>() {
+            Some(ResearchEvent::ResearchStateChanged(e)) => assert_eq!(e.new_state, "paused"),
+            other => panic!("unexpected event: {:?}", other),
+        }
+        assert!(rx.try_recv().is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_publish_throttled() {
+        let clock = SharedClock::new();
+        let bus = Arc::new(EventBus::with_shared_clock(clock.clone()));
+        let publisher = Publisher::new(bus.clone());
+        let mut rx = bus.subscribe_orders().await;
+        
+        assert!(publisher.publish_throttled(order_event(), 1_000).await.unwrap().is_published());
+        clock.advance_to(999);
+        assert!(matches!(
+            publisher.publish_throttled(order_event(), 1_000).await.unwrap(),
+            PublishDecision::Suppressed
+        ));
+        clock.advance_to(1_000);
+        assert!(publisher.publish_throttled(order_event(), 1_000).await.unwrap().is_published());
+        
+        assert!(rx.recv().await.is_ok());
+        assert!(rx.recv().await.is_ok());
+        assert!(rx.try_recv().is_err());
+    }
+    
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_publish_throttled_concurrent() {
+        let clock = SharedClock::new();
+        let bus = Arc::new(EventBus::with_shared_clock(clock));
+        let publisher = Arc::new(Publisher::new(bus.clone()));
+        let mut rx = bus.subscribe_orders().await;
+        
+        let tasks: Vec<_> = (0..8)
+            .map(|_| {
+                let publisher = publisher.clone();
+                tokio::spawn(async move { publisher.publish_throttled(order_event(), 1_000).await.unwrap().is_published() })
+            })
+            .collect();
+        let mut published = 0;
+        for task in tasks {
+            if task.await.unwrap() {
+                published += 1;
+            }
+        }
+        assert_eq!(published, 1);
+        assert!(rx.recv().await.is_ok());
+        assert!(rx.try_recv().is_err());
+    }
 }
@@ -1,8 +1,27 @@
 //! Event recording and replay for debugging and backtesting
 
+use crate::event_log::EventLog;
 use crate::events::EventEnvelope;
-use std::sync::Arc;
-use tokio::sync::RwLock;
+use crate::serde_support::SerializableEnvelope;
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tokio::sync::{RwLock, RwLockReadGuard};
+use uuid::Uuid;
+
+/// One row of a CSV export
+#[derive(Debug, Serialize, Deserialize)]
+struct CsvRow {
+    id: Uuid,
+    timestamp_ns: i64,
+    priority: u8,
+    event_type: String,
+    payload_json: String,
+}
 
 /// Records events for replay
 pub struct EventRecorder {
@@ -14,6 +33,12 @@ pub struct EventRecorder {
     
     /// Current write position
     position: Arc<RwLock<usize>>,
+    
+    /// Buffer positions per event type, oldest first
+    type_index: Arc<RwLock<HashMap<String, VecDeque<usize>>>>,
+    
+    /// Append-only file backend (optional)
+    log: Option<Arc<Mutex<EventLog>>>,
 }
 
 impl EventRecorder {
@@ -23,21 +48,169 @@ impl EventRecorder {
             events: Arc::new(RwLock::new(Vec::with_capacity(capacity))),
             capacity,
             position: Arc::new(RwLock::new(0)),
+            type_index: Arc::new(RwLock::new(HashMap::new())),
+            log: None,
+        }
+    }
+    
+    /// Create a full in-memory recorder holding `events` (oldest first)
+    ///
+    /// Capacity is the number of events (at least 1).
+    pub(crate) fn from_events(events: Vec<EventEnvelope>) -> Self {
+        let mut type_index: HashMap<String, VecDeque<usize>> = HashMap::new();
+        for (i, event) in events.iter().enumerate() {
+            type_index.entry(event.event.event_type().to_string()).or_default().push_back(i);
+        }
+        Self {
+            capacity: events.len().max(1),
+            events: Arc::new(RwLock::new(events)),
+            position: Arc::new(RwLock::new(0)),
+            type_index: Arc::new(RwLock::new(type_index)),
+            log: None,
+        }
+    }
+    
+    /// Create recorder persisted to an append-only file
+    ///
+    /// Events already in the file are loaded (the most recent `capacity`
+    /// are kept in memory) and new events are appended to it. Fails when
+    /// `capacity` is 0.
+    pub fn with_file(path: PathBuf, capacity: usize) -> Result<Self> {
+        if capacity == 0 {
+            bail!("recorder capacity must be at least 1");
+        }
+        
+        let (log, existing) = EventLog::open(&path)?;
+        
+        let skip = existing.len().saturating_sub(capacity);
+        let mut events = Vec::with_capacity(capacity);
+        events.extend(existing.into_iter().skip(skip));
+        let position = events.len() % capacity;
+        
+        let mut type_index: HashMap<String, VecDeque<usize>> = HashMap::new();
+        for (i, event) in events.iter().enumerate() {
+            type_index.entry(event.event.event_type().to_string()).or_default().push_back(i);
+        }
+        
+        Ok(Self {
+            events: Arc::new(RwLock::new(events)),
+            capacity,
+            position: Arc::new(RwLock::new(position)),
+            type_index: Arc::new(RwLock::new(type_index)),
+            log: Some(Arc::new(Mutex::new(log))),
+        })
+    }
+    
+    /// Read all valid events from a recorder file
+    ///
+    /// Corrupt records are skipped with a logged warning.
+    pub fn load_from_file(path: PathBuf) -> Result<Vec<EventEnvelope>> {
+        EventLog::load(path)
+    }
+    
+    /// Write recorded events as CSV, oldest first, returning the number of rows
+    ///
+    /// Columns are `id,timestamp_ns,priority,event_type,payload_json`. With a
+    /// filter, only events of that type are written. Events without a JSON
+    /// form are skipped.
+    pub async fn export_csv(&self, writer: impl Write, event_type_filter: Option<&str>) -> Result<usize> {
+        let events = match event_type_filter {
+            Some(event_type) => self.get_events_by_type(event_type).await,
+            None => self.get_events_ordered().await,
+        };
+        
+        let mut csv = csv::Writer::from_writer(writer);
+        let mut written = 0;
+        for envelope in events.iter().filter_map(EventEnvelope::to_serializable) {
+            csv.serialize(CsvRow {
+                id: envelope.id,
+                timestamp_ns: envelope.timestamp_ns,
+                priority: envelope.priority,
+                event_type: envelope.event_type,
+                payload_json: serde_json::to_string(&envelope.payload)?,
+            })?;
+            written += 1;
+        }
+        csv.flush()?;
+        Ok(written)
+    }
+    
+    /// Write recorded events to a CSV file (see `export_csv`)
+    pub async fn export_csv_file(&self, path: PathBuf, event_type_filter: Option<&str>) -> Result<usize> {
+        self.export_csv(File::create(path)?, event_type_filter).await
+    }
+    
+    /// Read envelopes written by `export_csv`
+    ///
+    /// Payloads are rebuilt through the `EventRegistry`; unregistered event
+    /// types come back as `RawEvent`.
+    pub fn import_csv(reader: impl Read) -> Result<Vec<EventEnvelope>> {
+        csv::Reader::from_reader(reader)
+            .deserialize::<CsvRow>()
+            .map(|row| {
+                let row = row?;
+                SerializableEnvelope {
+                    id: row.id,
+                    timestamp_ns: row.timestamp_ns,
+                    priority: row.priority,
+                    causation_id: None,
+                    correlation_id: None,
+                    event_type: row.event_type,
+                    payload: serde_json::from_str(&row.payload_json)?,
+                }
+                .into_envelope_or_raw()
+            })
+            .collect()
+    }
+    
+    /// Flush the file backend to disk (no-op for in-memory recorders)
+    pub fn flush(&self) -> Result<()> {
+        match &self.log {
+            Some(log) => log.lock().unwrap().flush(),
+            None => Ok(()),
         }
     }
     
     /// Record an event
+    ///
+    /// With a file backend the append runs on the blocking pool.
     pub async fn record(&self, event: EventEnvelope) {
+        if let Some(log) = &self.log {
+            let log = log.clone();
+            let envelope = event.clone();
+            let appended = tokio::task::spawn_blocking(move || log.lock().unwrap().append(&envelope)).await;
+            match appended {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => tracing::warn!("Failed to append event {} to log: {}", event.id, e),
+                Err(e) => tracing::warn!("Log append task for event {} failed: {}", event.id, e),
+            }
+        }
+        
         let mut events = self.events.write().await;
         let mut pos = self.position.write().await;
+        let mut type_index = self.type_index.write().await;
         
+        let event_type = event.event.event_type();
         if events.len() < self.capacity {
             events.push(event);
         } else {
-            // Circular buffer - overwrite oldest
+            // Circular buffer - overwrite oldest, which is also the oldest
+            // entry in its type's index
+            let evicted = events[*pos].event.event_type();
+            if let Some(positions) = type_index.get_mut(evicted) {
+                debug_assert_eq!(positions.front(), Some(&*pos));
+                positions.pop_front();
+            }
             events[*pos] = event;
         }
         
+        match type_index.get_mut(event_type) {
+            Some(positions) => positions.push_back(*pos),
+            None => {
+                type_index.insert(event_type.to_string(), VecDeque::from([*pos]));
+            }
+        }
+        
         *pos = (*pos + 1) % self.capacity;
     }
     
@@ -46,6 +219,31 @@ impl EventRecorder {
         self.events.read().await.clone()
     }
     
+    /// Get all recorded events, oldest first
+    pub async fn get_events_ordered(&self) -> Vec<EventEnvelope> {
+        let events = self.events.read().await;
+        let pos = *self.position.read().await;
+        if events.len() < self.capacity {
+            return events.clone();
+        }
+        // Full buffer: the oldest event sits at the write position
+        events[pos..].iter().chain(&events[..pos]).cloned().collect()
+    }
+    
+    /// Hold a read lock on the recorded events (blocks concurrent `record` calls)
+    pub(crate) async fn read(&self) -> RwLockReadGuard<'_, Vec<EventEnvelope>> {
+        self.events.read().await
+    }
+    
+    /// Replace the recorded events (oldest first), keeping at most `capacity`
+    pub async fn restore(&self, events: Vec<EventEnvelope>) {
+        self.clear().await;
+        let skip = events.len().saturating_sub(self.capacity);
+        for event in events.into_iter().skip(skip) {
+            self.record(event).await;
+        }
+    }
+    
     /// Get events in time range
     pub async fn get_events_in_range(&self, start_ns: i64, end_ns: i64) -> Vec<EventEnvelope> {
         self.events.read().await
@@ -55,11 +253,26 @@ impl EventRecorder {
             .collect()
     }
     
+    /// Get recorded events of one type, oldest first
+    ///
+    /// Uses the per-type index, so the cost is proportional to the number of
+    /// matching events rather than the buffer size.
+    pub async fn get_events_by_type(&self, event_type: &str) -> Vec<EventEnvelope> {
+        let events = self.events.read().await;
+        let type_index = self.type_index.read().await;
+        
+        type_index.get(event_type)
+            .map(|positions| positions.iter().map(|&i| events[i].clone()).collect())
+            .unwrap_or_default()
+    }
+    
     /// Clear all recorded events
     pub async fn clear(&self) {
         let mut events = self.events.write().await;
         let mut pos = self.position.write().await;
+        let mut type_index = self.type_index.write().await;
         events.clear();
+        type_index.clear();
         *pos = 0;
     }
     
@@ -68,6 +281,11 @@ impl EventRecorder {
         self.events.read().await.len()
     }
     
+    /// Get maximum number of events retained
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+    
     /// Check if recorder is empty
     pub async fn is_empty(&self) -> bool {
         self.events.read().await.is_empty()
@@ -77,14 +295,14 @@ impl EventRecorder {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::events::{Event, MarketDataEvent};
+    use crate::events::MarketDataEvent;
     
     #[tokio::test]
     async fn test_record_and_retrieve() {
         let recorder = EventRecorder::new(100);
         
         let event = EventEnvelope::new(
-            Event::MarketData(MarketDataEvent {
+            MarketDataEvent {
                 timestamp: 1234567890,
                 symbol: "ES".to_string(),
                 price: 6000.0,
@@ -93,7 +311,7 @@ mod tests {
                 bid_size: 5.0,
                 ask_price: 6000.5,
                 ask_size: 5.0,
-            }),
+            },
             5,
         );
         
@@ -110,7 +328,7 @@ mod tests {
         
         for i in 0..5 {
             let event = EventEnvelope::new(
-                Event::MarketData(MarketDataEvent {
+                MarketDataEvent {
                     timestamp: i,
                     symbol: "ES".to_string(),
                     price: 6000.0 + i as f64,
@@ -119,7 +337,7 @@ mod tests {
                     bid_size: 5.0,
                     ask_price: 6000.5,
                     ask_size: 5.0,
-                }),
+                },
                 5,
             );
             recorder.record(event).await;
@@ -128,4 +346,210 @@ mod tests {
         let events = recorder.get_events().await;
         assert_eq!(events.len(), 2); // Only keeps last 2
     }
+    
+    fn temp_log_path() -> PathBuf {
+        std::env::temp_dir().join(format!("hft-event-log-{}.bin", uuid::Uuid::new_v4()))
+    }
+    
+    fn market_data(i: i64) -> EventEnvelope {
+        EventEnvelope::new(
+            MarketDataEvent {
+                timestamp: i,
+                symbol: "ES".to_string(),
+                price: 6000.0 + i as f64,
+                volume: 10.0,
+                bid_price: 5999.5,
+                bid_size: 5.0,
+                ask_price: 6000.5,
+                ask_size: 5.0,
+            },
+            5,
+        )
+    }
+    
+    #[tokio::test]
+    async fn test_file_round_trip() {
+        let path = temp_log_path();
+        let originals: Vec<EventEnvelope> = (0..3).map(market_data).collect();
+        
+        {
+            let recorder = EventRecorder::with_file(path.clone(), 100).unwrap();
+            for event in &originals {
+                recorder.record(event.clone()).await;
+            }
+            recorder.flush().unwrap();
+        }
+        
+        let loaded = EventRecorder::load_from_file(path.clone()).unwrap();
+        assert_eq!(loaded.len(), 3);
+        for (original, loaded) in originals.iter().zip(&loaded) {
+            assert_eq!(loaded.id, original.id);
+            assert_eq!(loaded.timestamp_ns, original.timestamp_ns);
+            assert_eq!(loaded.priority, original.priority);
+            assert_eq!(loaded.event.event_type(), "market_data");
+            assert_eq!(loaded.event.to_json(), original.event.to_json());
+        }
+        
+        // Reopening restores history and keeps appending
+        let recorder = EventRecorder::with_file(path.clone(), 100).unwrap();
+        assert_eq!(recorder.len().await, 3);
+        recorder.record(market_data(3)).await;
+        recorder.flush().unwrap();
+        assert_eq!(EventRecorder::load_from_file(path.clone()).unwrap().len(), 4);
+        
+        std::fs::remove_file(path).unwrap();
+    }
+    
+    #[test]
+    fn test_with_file_rejects_zero_capacity() {
+        let path = temp_log_path();
+        assert!(EventRecorder::with_file(path.clone(), 0).is_err());
+        assert!(!path.exists());
+    }
+    
+    #[test]
+    fn test_truncated_trailing_record_skipped() {
+        let path = temp_log_path();
+        
+        let end = {
+            let (mut log, _) = EventLog::open(&path).unwrap();
+            for i in 0..3 {
+                log.append(&market_data(i)).unwrap();
+            }
+            log.flush().unwrap();
+            log.len_bytes()
+        };
+        
+        // Cut the last record short
+        let file = std::fs::OpenOptions::new().write(true).open(&path).unwrap();
+        file.set_len((end - 5) as u64).unwrap();
+        drop(file);
+        
+        let loaded = EventRecorder::load_from_file(path.clone()).unwrap();
+        assert_eq!(loaded.len(), 2);
+        
+        std::fs::remove_file(path).unwrap();
+    }
+    
+    #[test]
+    fn test_corrupt_record_skipped() {
+        let path = temp_log_path();
+        let originals: Vec<EventEnvelope> = (0..3).map(market_data).collect();
+        
+        let first_len = {
+            let (mut log, _) = EventLog::open(&path).unwrap();
+            log.append(&originals[0]).unwrap();
+            let first_len = log.len_bytes();
+            log.append(&originals[1]).unwrap();
+            log.append(&originals[2]).unwrap();
+            log.flush().unwrap();
+            first_len
+        };
+        
+        // Flip a byte in the body of the second record
+        let mut bytes = std::fs::read(&path).unwrap();
+        bytes[first_len + crate::event_log::HEADER_LEN + 4] ^= 0xFF;
+        std::fs::write(&path, bytes).unwrap();
+        
+        let loaded = EventRecorder::load_from_file(path.clone()).unwrap();
+        let ids: Vec<_> = loaded.iter().map(|e| e.id).collect();
+        assert_eq!(ids, vec![originals[0].id, originals[2].id]);
+        
+        std::fs::remove_file(path).unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_csv_roundtrip() {
+        use crate::events::{FillEvent, OrderSide, RawEvent};
+        
+        // Wraps the buffer, so export must restore chronological order
+        let recorder = EventRecorder::new(1000);
+        let mut originals = Vec::new();
+        for i in 0..1200u64 {
+            let mut envelope = match i % 3 {
+                0 => EventEnvelope::new(FillEvent {
+                    fill_id: uuid::Uuid::new_v4(),
+                    order_id: uuid::Uuid::new_v4(),
+                    signal_id: None,
+                    timestamp: i as i64,
+                    symbol: "ES".to_string(),
+                    side: OrderSide::Sell,
+                    filled_quantity: 2.0,
+                    fill_price: 6000.0 + i as f64 * 0.25,
+                    commission: 0.5,
+                    slippage_bps: 0.1,
+                }, 3),
+                // Quoting and commas survive the CSV round trip
+                _ => EventEnvelope::new(RawEvent::new("note", 7, serde_json::json!({ "seq": i, "text": "a, \"b\"" })), 7),
+            };
+            envelope.timestamp_ns = i as i64 * 1_000;
+            originals.push(envelope.clone());
+            recorder.record(envelope).await;
+        }
+        
+        let mut buffer = Vec::new();
+        assert_eq!(recorder.export_csv(&mut buffer, None).await.unwrap(), 1000);
+        let text = String::from_utf8(buffer.clone()).unwrap();
+        assert!(text.starts_with("id,timestamp_ns,priority,event_type,payload_json\n"));
+        
+        let imported = EventRecorder::import_csv(buffer.as_slice()).unwrap();
+        assert_eq!(imported.len(), 1000);
+        for (original, restored) in originals[200..].iter().zip(&imported) {
+            assert_eq!(restored.id, original.id);
+            assert_eq!(restored.timestamp_ns, original.timestamp_ns);
+            assert_eq!(restored.priority, original.priority);
+            assert_eq!(restored.event.event_type(), original.event.event_type());
+            assert_eq!(restored.event.to_json(), original.event.to_json());
+        }
+        assert!(imported[1].event.downcast_ref::<FillEvent>().is_some());
+        
+        // Filtered export to a file
+        let path = std::env::temp_dir().join(format!("recording_{}.csv", uuid::Uuid::new_v4()));
+        let fills = recorder.export_csv_file(path.clone(), Some("fill")).await.unwrap();
+        let imported = EventRecorder::import_csv(File::open(&path).unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(fills, imported.len());
+        assert!(imported.iter().all(|e| e.event.event_type() == "fill"));
+    }
+    
+    #[tokio::test]
+    async fn test_get_events_by_type() {
+        const TYPES: [&str; 5] = ["type_a", "type_b", "type_c", "type_d", "type_e"];
+        let recorder = EventRecorder::new(4096);
+        
+        // Uneven type mix, wrapping the buffer more than twice
+        let sequence: Vec<usize> = (0..10_000).map(|i| (i * i + i / 7) % TYPES.len()).collect();
+        for (i, &t) in sequence.iter().enumerate() {
+            let event = crate::events::RawEvent::new(TYPES[t], 5, serde_json::json!({ "seq": i }));
+            recorder.record(EventEnvelope::new(event, 5)).await;
+        }
+        
+        let retained = &sequence[sequence.len() - 4096..];
+        let first_seq = sequence.len() - 4096;
+        let mut total = 0;
+        for (t, name) in TYPES.iter().enumerate() {
+            let expected: Vec<u64> = retained.iter()
+                .enumerate()
+                .filter(|(_, ty)| **ty == t)
+                .map(|(i, _)| (first_seq + i) as u64)
+                .collect();
+            
+            let events = recorder.get_events_by_type(name).await;
+            let seqs: Vec<u64> = events.iter()
+                .map(|e| e.event.to_json().unwrap()["seq"].as_u64().unwrap())
+                .collect();
+            assert_eq!(seqs, expected, "index mismatch for {}", name);
+            assert!(events.iter().all(|e| e.event.event_type() == *name));
+            total += events.len();
+        }
+        assert_eq!(total, 4096);
+        
+        // Index entries match the buffer size, so lookups touch only k events
+        let index_len: usize = recorder.type_index.read().await.values().map(VecDeque::len).sum();
+        assert_eq!(index_len, recorder.len().await);
+        
+        assert!(recorder.get_events_by_type("missing").await.is_empty());
+        recorder.clear().await;
+        assert!(recorder.get_events_by_type("type_a").await.is_empty());
+    }
 }
@@ -9,6 +9,17 @@
 //! - `ReplaySpeed::Max` — as fast as possible (no sleeps)
 //! - `ReplaySpeed::Realtime` — replay at original wall-clock speed
 //! - `ReplaySpeed::Multiplier(n)` — n× real-time speed
+//! - `ReplaySpeed::StepByStep` — manual advance with `step()`, one event at a time
+//! - `ReplaySpeed::Adaptive { .. }` — multiplier tuned to keep subscriber queues
+//!   near a target depth
+//!
+//! `shared_clock()` hands strategies a `SharedClock` that tracks virtual time;
+//! pass it to `EventBus::with_shared_clock` so events published in response
+//! to replayed data are stamped with virtual rather than wall-clock time.
+//!
+//! `speed_curve` overrides the global speed per virtual-time segment, e.g.
+//! 100× through pre-market and 1× around the open. Gaps outside every
+//! segment replay at `ReplaySpeed::Max`.
 //!
 //! ## Example
 //!
@@ -19,17 +30,77 @@
 //! let mut replay = EventReplay::new(bus.clone(), ReplaySpeed::Max);
 //!
 //! // Load events (pre-sorted by timestamp)
-//! replay.load_events(events);
+//! replay.load_events(events)?;
 //!
 //! // Run replay — publishes events through the bus
 //! let stats = replay.run().await;
 //! println!("Replayed {} events in {:?}", stats.events_replayed, stats.wall_time);
 //! ```
+//!
+//! ## Snapshots
+//!
+//! A `ReplaySnapshot` captures open positions and order books at a virtual
+//! timestamp. `run_from_snapshot` hands a snapshot to the registered
+//! `SnapshotConsumer` and replays only the events after it, so a backtest can
+//! start mid-session. With `.snapshot_every(n)` the replayer records a
+//! snapshot every `n` events for use in later runs.
+//!
+//! ```rust,ignore
+//! let mut replay = EventReplayBuilder::new(bus.clone())
+//!     .events(events)
+//!     .snapshot_every(100_000)
+//!     .build();
+//! replay.run().await;
+//!
+//! let checkpoint = replay.snapshots()[3].id;
+//! replay.set_snapshot_consumer(Box::new(|snapshot: &ReplaySnapshot| {
+//!     strategy.restore(snapshot);
+//! }));
+//! let stats = replay.run_from_snapshot(checkpoint).await?;
+//! ```
+//!
+//! ## Seeking
+//!
+//! `run()` starts from a cursor and leaves it in place, so one segment can
+//! be replayed repeatedly, e.g. with different strategy parameters:
+//!
+//! ```rust,ignore
+//! replay.seek(window_start_ns)?;
+//! for params in grid {
+//!     strategy.configure(params);
+//!     replay.run_until(window_end_ns).await;
+//! }
+//! ```
+//!
+//! ## Pause and inject
+//!
+//! `pause_at` returns a `ReplayHandle` that stays usable while `run` is in
+//! progress on another task. Replay stops before the first event after the
+//! pause timestamp; injected events are published in timestamp order as the
+//! natural stream reaches them.
+//!
+//! ```rust,ignore
+//! let handle = replay.pause_at(fill_time_ns);
+//! let task = tokio::spawn(async move { replay.run().await });
+//!
+//! handle.paused().await;
+//! handle.inject_event(simulated_fill);
+//! handle.resume();
+//! let stats = task.await?;
+//! ```
 
-use crate::events::{Event, EventEnvelope};
-use crate::bus::EventBus;
+use crate::events::{Event, EventEnvelope, FillEvent, OrderBookEvent, OrderSide};
+use crate::bus::{EventBus, CHANNEL_CAPACITY};
+use crate::sharded_bus::fnv1a;
+use anyhow::{anyhow, bail, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
+use tokio::sync::watch;
 use tracing::{info, debug};
+use uuid::Uuid;
 
 /// Replay speed control
 #[derive(Debug, Clone)]
@@ -40,14 +111,32 @@ pub enum ReplaySpeed {
     Realtime,
     /// Multiplied speed (e.g., 10.0 = 10× real-time)
     Multiplier(f64),
+    /// Manual advance via `EventReplay::step()` (`run()` treats this as `Max`)
+    StepByStep,
+    /// Multiplied speed adjusted every `ADAPTIVE_SAMPLE_INTERVAL` events
+    ///
+    /// Starts at `max_multiplier`. Each sample reads the deepest subscriber
+    /// queue among the event types being replayed and scales the multiplier by
+    /// `1 + gain * (target_queue_depth - depth) / target_queue_depth`, at
+    /// most `1 + gain` and at least 0.5. A full queue, where subscribers
+    /// start losing events, always halves it. The result is kept within
+    /// `[min_multiplier, max_multiplier]`.
+    Adaptive {
+        target_queue_depth: usize,
+        min_multiplier: f64,
+        max_multiplier: f64,
+        /// Proportional gain; 0.1 speeds up 10% per sample while queues are empty
+        gain: f64,
+    },
 }
 
 impl ReplaySpeed {
-    /// Parse from string: "max", "realtime", "10x", "2.5x"
+    /// Parse from string: "max", "realtime", "step", "10x", "2.5x"
     pub fn from_str(s: &str) -> Self {
         match s.to_lowercase().as_str() {
             "max" => ReplaySpeed::Max,
             "realtime" | "1x" => ReplaySpeed::Realtime,
+            "step" => ReplaySpeed::StepByStep,
             other => {
                 if let Some(num_str) = other.strip_suffix('x') {
                     if let Ok(mult) = num_str.parse::<f64>() {
@@ -60,6 +149,45 @@ impl ReplaySpeed {
     }
 }
 
+/// Events between two queue depth samples in `ReplaySpeed::Adaptive`
+pub const ADAPTIVE_SAMPLE_INTERVAL: usize = 1000;
+
+/// Next adaptive multiplier given the sampled queue depth
+fn adapt_multiplier(current: f64, queue_depth: usize, target_queue_depth: usize, gain: f64) -> f64 {
+    if queue_depth >= CHANNEL_CAPACITY {
+        return current * 0.5;
+    }
+    let target = target_queue_depth.max(1) as f64;
+    let error = (target - queue_depth as f64) / target;
+    current * (1.0 + gain * error).min(1.0 + gain).max(0.5)
+}
+
+/// Replay speed for a virtual time range `[start_ns, end_ns)`
+#[derive(Debug, Clone)]
+pub struct SpeedSegment {
+    pub start_ns: i64,
+    pub end_ns: i64,
+    pub speed: ReplaySpeed,
+}
+
+/// Check segments (sorted by `start_ns`) are non-empty and do not overlap
+fn validate_speed_curve(segments: &[SpeedSegment]) -> Result<()> {
+    for segment in segments {
+        if segment.start_ns >= segment.end_ns {
+            bail!("Empty speed segment [{}, {})", segment.start_ns, segment.end_ns);
+        }
+    }
+    for pair in segments.windows(2) {
+        if pair[1].start_ns < pair[0].end_ns {
+            bail!(
+                "Overlapping speed segments [{}, {}) and [{}, {})",
+                pair[0].start_ns, pair[0].end_ns, pair[1].start_ns, pair[1].end_ns
+            );
+        }
+    }
+    Ok(())
+}
+
 /// Virtual time tracker for replay
 #[derive(Debug, Clone)]
 pub struct VirtualClock {
@@ -92,6 +220,11 @@ impl VirtualClock {
         self.current_ns = timestamp_ns;
     }
 
+    /// Jump forward to `timestamp_ns` (no-op if it is not ahead of the current time)
+    pub fn skip_to(&mut self, timestamp_ns: i64) {
+        self.current_ns = self.current_ns.max(timestamp_ns);
+    }
+
     /// Current virtual time
     pub fn current(&self) -> i64 {
         self.current_ns
@@ -112,6 +245,35 @@ impl Default for VirtualClock {
     }
 }
 
+/// Virtual time shared between a replay and the strategies it drives
+///
+/// Cloning is cheap and yields a handle to the same clock.
+#[derive(Debug, Clone, Default)]
+pub struct SharedClock {
+    now_ns: Arc<AtomicI64>,
+}
+
+impl SharedClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Current virtual time (nanoseconds)
+    pub fn now(&self) -> i64 {
+        self.now_ns.load(Ordering::Acquire)
+    }
+
+    /// Set the current virtual time
+    pub fn advance_to(&self, ns: i64) {
+        self.now_ns.store(ns, Ordering::Release);
+    }
+
+    /// Reset to zero
+    pub fn reset(&self) {
+        self.now_ns.store(0, Ordering::Release);
+    }
+}
+
 /// Replay statistics
 #[derive(Debug, Clone)]
 pub struct ReplayStats {
@@ -125,6 +287,251 @@ pub struct ReplayStats {
     pub events_per_second: f64,
     /// Effective speed multiplier vs real-time
     pub effective_speed: f64,
+    /// Publish latency per event type (empty unless `with_per_type_stats(true)`)
+    pub per_type_stats: HashMap<String, TypeReplayStats>,
+}
+
+/// Publish latency of one event type during a replay
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TypeReplayStats {
+    pub count: usize,
+    pub total_publish_ns: u64,
+    pub min_publish_ns: u64,
+    pub max_publish_ns: u64,
+    pub p99_publish_ns: u64,
+}
+
+impl TypeReplayStats {
+    /// Summarize publish durations (sorted in place)
+    fn from_samples(samples: &mut [u64]) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+        samples.sort_unstable();
+        // Nearest-rank percentile
+        let p99_rank = (samples.len() * 99).div_ceil(100);
+        Self {
+            count: samples.len(),
+            total_publish_ns: samples.iter().sum(),
+            min_publish_ns: samples[0],
+            max_publish_ns: samples[samples.len() - 1],
+            p99_publish_ns: samples[p99_rank - 1],
+        }
+    }
+
+    /// Mean publish duration
+    pub fn mean_publish_ns(&self) -> u64 {
+        match self.count {
+            0 => 0,
+            n => self.total_publish_ns / n as u64,
+        }
+    }
+}
+
+impl ReplayStats {
+    /// Summary plus per-type latency table, one type per row
+    pub fn report(&self) -> String {
+        let mut report = format!(
+            "Replayed {} events in {:.3}s ({:.0} events/sec, {:.1}x real-time)\n",
+            self.events_replayed,
+            self.wall_time.as_secs_f64(),
+            self.events_per_second,
+            self.effective_speed
+        );
+        if self.per_type_stats.is_empty() {
+            return report;
+        }
+
+        let mut types: Vec<_> = self.per_type_stats.iter().collect();
+        types.sort_by(|a, b| b.1.count.cmp(&a.1.count).then(a.0.cmp(b.0)));
+        let width = types.iter().map(|(name, _)| name.len()).max().unwrap_or(0).max("event_type".len());
+        report.push_str(&format!(
+            "{:<width$}  {:>10}  {:>10}  {:>10}  {:>10}  {:>10}\n",
+            "event_type", "count", "mean_ns", "min_ns", "p99_ns", "max_ns"
+        ));
+        for (name, stats) in types {
+            report.push_str(&format!(
+                "{:<width$}  {:>10}  {:>10}  {:>10}  {:>10}  {:>10}\n",
+                name,
+                stats.count,
+                stats.mean_publish_ns(),
+                stats.min_publish_ns,
+                stats.p99_publish_ns,
+                stats.max_publish_ns
+            ));
+        }
+        report
+    }
+
+    /// Print `report()` to stdout
+    pub fn print_report(&self) {
+        print!("{}", self.report());
+    }
+}
+
+/// Net open position in one symbol
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PositionState {
+    /// Signed quantity (positive = long, negative = short)
+    pub quantity: f64,
+    /// Average entry price of the open quantity
+    pub avg_price: f64,
+}
+
+/// Replay checkpoint: open positions and order books at a virtual timestamp
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReplaySnapshot {
+    /// Snapshot identifier
+    pub id: Uuid,
+    /// Virtual time covered by the snapshot (events at or before it are applied)
+    pub timestamp_ns: i64,
+    /// Open positions by symbol
+    pub positions: HashMap<String, PositionState>,
+    /// Latest order book by symbol
+    pub order_books: HashMap<String, OrderBookEvent>,
+}
+
+impl ReplaySnapshot {
+    /// Create empty snapshot at the given virtual time
+    pub fn new(timestamp_ns: i64) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            timestamp_ns,
+            ..Default::default()
+        }
+    }
+    
+    /// Fold an event into the snapshot state (fills and order books only)
+    pub fn apply(&mut self, envelope: &EventEnvelope) {
+        match envelope.event.event_type() {
+            "fill" => {
+                if let Some(fill) = decode::<FillEvent>(envelope) {
+                    self.apply_fill(&fill);
+                }
+            }
+            "order_book" => {
+                if let Some(book) = decode::<OrderBookEvent>(envelope) {
+                    self.order_books.insert(book.symbol.clone(), book);
+                }
+            }
+            _ => {}
+        }
+        self.timestamp_ns = self.timestamp_ns.max(envelope.timestamp_ns);
+    }
+    
+    fn apply_fill(&mut self, fill: &FillEvent) {
+        let delta = match fill.side {
+            OrderSide::Buy => fill.filled_quantity,
+            OrderSide::Sell => -fill.filled_quantity,
+        };
+        
+        let position = self.positions.entry(fill.symbol.clone())
+            .or_insert(PositionState { quantity: 0.0, avg_price: 0.0 });
+        let new_quantity = position.quantity + delta;
+        
+        if position.quantity == 0.0 || position.quantity.signum() != new_quantity.signum() {
+            // Opened or flipped: the remainder was entered at the fill price
+            position.avg_price = fill.fill_price;
+        } else if position.quantity.signum() == delta.signum() {
+            // Increased: weighted average entry
+            position.avg_price = (position.avg_price * position.quantity.abs()
+                + fill.fill_price * delta.abs()) / new_quantity.abs();
+        }
+        position.quantity = new_quantity;
+        
+        if position.quantity == 0.0 {
+            self.positions.remove(&fill.symbol);
+        }
+    }
+}
+
+/// Decode a concrete event from an envelope via its JSON payload
+fn decode<T: serde::de::DeserializeOwned>(envelope: &EventEnvelope) -> Option<T> {
+    envelope.event.to_json().and_then(|json| serde_json::from_value(json).ok())
+}
+
+/// Receives snapshot state before `run_from_snapshot` resumes replay
+pub trait SnapshotConsumer: Send {
+    /// Restore positions and order books from the snapshot
+    fn restore(&mut self, snapshot: &ReplaySnapshot);
+}
+
+impl<F: FnMut(&ReplaySnapshot) + Send> SnapshotConsumer for F {
+    fn restore(&mut self, snapshot: &ReplaySnapshot) {
+        self(snapshot)
+    }
+}
+
+/// Pause/abort state shared between `EventReplay` and its handles
+struct ReplayControl {
+    /// Pause before the first event after this timestamp (`i64::MAX` = none)
+    pause_at_ns: AtomicI64,
+    /// True while replay is waiting for `resume`
+    paused: watch::Sender<bool>,
+    /// Stop replay before the next event
+    aborted: AtomicBool,
+    /// Injected events, kept sorted by timestamp
+    injected: Mutex<VecDeque<EventEnvelope>>,
+    /// `f64` bits of the multiplier in effect (infinite at max speed)
+    effective_speed: AtomicU64,
+}
+
+impl ReplayControl {
+    fn new() -> Self {
+        Self {
+            pause_at_ns: AtomicI64::new(i64::MAX),
+            paused: watch::channel(false).0,
+            aborted: AtomicBool::new(false),
+            injected: Mutex::new(VecDeque::new()),
+            effective_speed: AtomicU64::new(f64::INFINITY.to_bits()),
+        }
+    }
+    
+    /// Pop the earliest injected event at or before `up_to_ns`
+    fn pop_injected(&self, up_to_ns: i64) -> Option<EventEnvelope> {
+        let mut injected = self.injected.lock().unwrap();
+        match injected.front() {
+            Some(front) if front.timestamp_ns <= up_to_ns => injected.pop_front(),
+            _ => None,
+        }
+    }
+}
+
+/// Handle for pausing, injecting into and aborting a running replay
+#[derive(Clone)]
+pub struct ReplayHandle {
+    control: Arc<ReplayControl>,
+}
+
+impl ReplayHandle {
+    /// Wait until replay reaches the pause point
+    pub async fn paused(&self) {
+        let mut rx = self.control.paused.subscribe();
+        let _ = rx.wait_for(|paused| *paused).await;
+    }
+    
+    /// Check if replay is currently paused
+    pub fn is_paused(&self) -> bool {
+        *self.control.paused.borrow()
+    }
+    
+    /// Continue replay after a pause
+    pub fn resume(&self) {
+        self.control.paused.send_replace(false);
+    }
+    
+    /// Queue a synthetic event, published at its position in the stream
+    pub fn inject_event(&self, envelope: EventEnvelope) {
+        let mut injected = self.control.injected.lock().unwrap();
+        let index = injected.partition_point(|e| e.timestamp_ns <= envelope.timestamp_ns);
+        injected.insert(index, envelope);
+    }
+    
+    /// Stop replay before the next event (also releases a pause)
+    pub fn abort(&self) {
+        self.control.aborted.store(true, Ordering::Release);
+        self.control.paused.send_replace(false);
+    }
 }
 
 /// Callback invoked after each event is published
@@ -137,11 +544,27 @@ pub type OnProgressCallback = Box<dyn FnMut(f64, usize) + Send>;
 pub struct EventReplay {
     bus: EventBus,
     speed: ReplaySpeed,
-    events: Vec<EventEnvelope>,
+    pub(crate) events: Vec<EventEnvelope>,
     clock: VirtualClock,
+    /// Mirrors `clock.current()` for other tasks
+    shared_clock: SharedClock,
     on_event: Option<OnEventCallback>,
     on_progress: Option<OnProgressCallback>,
     progress_interval: usize,
+    snapshots: Vec<ReplaySnapshot>,
+    snapshot_interval: Option<usize>,
+    snapshot_consumer: Option<Box<dyn SnapshotConsumer>>,
+    /// State the next run starts from (set by `run_from_snapshot`)
+    resume_state: Option<ReplaySnapshot>,
+    control: Arc<ReplayControl>,
+    /// Per-segment speeds, sorted by `start_ns` (empty = use `speed`)
+    speed_curve: Vec<SpeedSegment>,
+    /// Index of the event `run()` starts from and `step()` publishes next
+    cursor: usize,
+    /// Time each publish in `run()` per event type
+    per_type_stats: bool,
+    /// Publish durations collected by the current run
+    type_timings: HashMap<&'static str, Vec<u64>>,
 }
 
 impl EventReplay {
@@ -152,23 +575,64 @@ impl EventReplay {
             speed,
             events: Vec::new(),
             clock: VirtualClock::new(),
+            shared_clock: SharedClock::new(),
             on_event: None,
             on_progress: None,
             progress_interval: 10_000,
+            snapshots: Vec::new(),
+            snapshot_interval: None,
+            snapshot_consumer: None,
+            resume_state: None,
+            control: Arc::new(ReplayControl::new()),
+            speed_curve: Vec::new(),
+            cursor: 0,
+            per_type_stats: false,
+            type_timings: HashMap::new(),
         }
     }
 
-    /// Load events for replay (must be sorted by timestamp_ns)
-    pub fn load_events(&mut self, mut events: Vec<EventEnvelope>) {
+    /// Load events for replay
+    ///
+    /// Fails if the configured speed curve has overlapping segments.
+    pub fn load_events(&mut self, events: Vec<EventEnvelope>) -> Result<()> {
+        validate_speed_curve(&self.speed_curve)?;
+        self.set_events(events);
+        Ok(())
+    }
+
+    /// Sort and store events (no speed curve validation)
+    pub(crate) fn set_events(&mut self, mut events: Vec<EventEnvelope>) {
         // Sort by timestamp to ensure chronological order
         events.sort_by_key(|e| e.timestamp_ns);
 
         if let (Some(first), Some(last)) = (events.first(), events.last()) {
             self.clock.set_bounds(first.timestamp_ns, last.timestamp_ns);
+            self.shared_clock.advance_to(first.timestamp_ns);
         }
 
         info!("Loaded {} events for replay", events.len());
         self.events = events;
+        self.cursor = 0;
+    }
+
+    /// Use a different replay speed per virtual time segment
+    ///
+    /// Segments are validated when events are loaded.
+    pub fn speed_curve(&mut self, mut segments: Vec<SpeedSegment>) {
+        segments.sort_by_key(|s| s.start_ns);
+        self.speed_curve = segments;
+    }
+
+    /// Speed in effect at virtual time `timestamp_ns`
+    fn speed_at(&self, timestamp_ns: i64) -> &ReplaySpeed {
+        if self.speed_curve.is_empty() {
+            return &self.speed;
+        }
+        let index = self.speed_curve.partition_point(|s| s.start_ns <= timestamp_ns);
+        match index.checked_sub(1).map(|i| &self.speed_curve[i]) {
+            Some(segment) if timestamp_ns < segment.end_ns => &segment.speed,
+            _ => &ReplaySpeed::Max,
+        }
     }
 
     /// Set callback invoked after each event
@@ -182,75 +646,278 @@ impl EventReplay {
         self.on_progress = Some(callback);
     }
 
+    /// Speed multiplier currently applied by `run()`
+    ///
+    /// Follows the controller under `ReplaySpeed::Adaptive`; 1.0 for
+    /// `Realtime`, and infinite for `Max` and `StepByStep`. After a run it
+    /// holds the last multiplier used.
+    pub fn current_effective_speed(&self) -> f64 {
+        f64::from_bits(self.control.effective_speed.load(Ordering::Relaxed))
+    }
+
+    /// Collect per-event-type publish latency in `run()` (off by default)
+    pub fn with_per_type_stats(&mut self, enabled: bool) -> &mut Self {
+        self.per_type_stats = enabled;
+        self
+    }
+
+    /// Publish one envelope, timing it when per-type stats are enabled
+    async fn publish_timed(&mut self, envelope: EventEnvelope) -> Result<()> {
+        if !self.per_type_stats {
+            return self.bus.publish_envelope(envelope).await;
+        }
+        let event_type = envelope.event.event_type();
+        let start = Instant::now();
+        let result = self.bus.publish_envelope(envelope).await;
+        let elapsed_ns = start.elapsed().as_nanos() as u64;
+        self.type_timings.entry(event_type).or_default().push(elapsed_ns);
+        result
+    }
+
+    /// Register a snapshot taken at virtual time `at_ns`, returning its id
+    pub fn add_snapshot(&mut self, at_ns: i64, mut snapshot: ReplaySnapshot) -> Uuid {
+        snapshot.timestamp_ns = at_ns;
+        let id = snapshot.id;
+        self.snapshots.push(snapshot);
+        id
+    }
+
+    /// Get registered and auto-generated snapshots
+    pub fn snapshots(&self) -> &[ReplaySnapshot] {
+        &self.snapshots
+    }
+
+    /// Record a snapshot every `n` events during replay (0 disables)
+    pub fn snapshot_every(&mut self, n: usize) {
+        self.snapshot_interval = if n == 0 { None } else { Some(n) };
+    }
+
+    /// Set consumer that receives snapshot state in `run_from_snapshot`
+    pub fn set_snapshot_consumer(&mut self, consumer: Box<dyn SnapshotConsumer>) {
+        self.snapshot_consumer = Some(consumer);
+    }
+
+    /// Pause the next run before the first event after `timestamp_ns`
+    ///
+    /// The returned handle resumes, injects events into or aborts the replay.
+    pub fn pause_at(&mut self, timestamp_ns: i64) -> ReplayHandle {
+        self.control.pause_at_ns.store(timestamp_ns, Ordering::Release);
+        ReplayHandle {
+            control: self.control.clone(),
+        }
+    }
+
+    /// Block while paused at the configured timestamp; false if aborted
+    async fn wait_at_pause_point(&self, next_ns: i64) -> bool {
+        if next_ns > self.control.pause_at_ns.load(Ordering::Acquire) {
+            self.control.pause_at_ns.store(i64::MAX, Ordering::Release);
+            info!("Replay paused at {}", self.clock.current());
+
+            let mut rx = self.control.paused.subscribe();
+            self.control.paused.send_replace(true);
+            let _ = rx.wait_for(|paused| !*paused).await;
+        }
+        !self.control.aborted.load(Ordering::Acquire)
+    }
+
+    /// Publish injected events due at or before `up_to_ns`
+    async fn publish_injected(&mut self, up_to_ns: i64) -> usize {
+        let mut published = 0;
+        while let Some(envelope) = self.control.pop_injected(up_to_ns) {
+            self.advance_clock(envelope.timestamp_ns);
+            if let Err(e) = self.publish_timed(envelope).await {
+                debug!("Failed to publish injected event: {}", e);
+            }
+            published += 1;
+        }
+        published
+    }
+
     /// Get virtual clock reference
     pub fn clock(&self) -> &VirtualClock {
         &self.clock
     }
 
+    /// Handle to the virtual clock that other tasks can read during replay
+    pub fn shared_clock(&self) -> SharedClock {
+        self.shared_clock.clone()
+    }
+
+    /// Advance the virtual clock and its shared mirror
+    fn advance_clock(&mut self, timestamp_ns: i64) {
+        self.clock.advance_to(timestamp_ns);
+        self.shared_clock.advance_to(timestamp_ns);
+    }
+
     /// Get number of loaded events
     pub fn event_count(&self) -> usize {
         self.events.len()
     }
 
-    /// Run the replay — publishes all events through the bus
+    /// Move the cursor to the first event at or after `timestamp_ns`
+    ///
+    /// Returns the new cursor index (`event_count()` if every event is
+    /// earlier) and moves the virtual clock to that event. Fails if no events
+    /// are loaded.
+    pub fn seek(&mut self, timestamp_ns: i64) -> Result<usize> {
+        if self.events.is_empty() {
+            bail!("No events loaded");
+        }
+        self.cursor = self.events.partition_point(|e| e.timestamp_ns < timestamp_ns);
+        if let Some(position) = self.position() {
+            self.advance_clock(position);
+        }
+        Ok(self.cursor)
+    }
+
+    /// Move the cursor back to the first event
+    pub fn seek_to_beginning(&mut self) {
+        self.cursor = 0;
+        if let Some(position) = self.position() {
+            self.advance_clock(position);
+        }
+    }
+
+    /// Move the cursor past the last event
+    pub fn seek_to_end(&mut self) {
+        self.cursor = self.events.len();
+    }
+
+    /// Timestamp of the event at the cursor (`None` at the end)
+    pub fn position(&self) -> Option<i64> {
+        self.events.get(self.cursor).map(|e| e.timestamp_ns)
+    }
+
+    /// Run the replay — publishes every event from the cursor on through the bus
+    ///
+    /// The cursor is left in place, so running again replays the same
+    /// segment.
     pub async fn run(&mut self) -> ReplayStats {
         let total = self.events.len();
-        if total == 0 {
+        let start = self.cursor.min(total);
+        if start == total {
             return ReplayStats {
                 events_replayed: 0,
                 wall_time: Duration::ZERO,
                 virtual_time_span_ns: 0,
                 events_per_second: 0.0,
                 effective_speed: 0.0,
+                per_type_stats: HashMap::new(),
             };
         }
 
         let wall_start = Instant::now();
-        let first_event_ns = self.events[0].timestamp_ns;
+        let first_event_ns = self.events[start].timestamp_ns;
         let last_event_ns = self.events[total - 1].timestamp_ns;
         let virtual_span = last_event_ns - first_event_ns;
 
         info!(
             "Starting replay: {} events, virtual span {:.3}s, speed {:?}",
-            total,
+            total - start,
             virtual_span as f64 / 1e9,
             self.speed
         );
 
         // Take events out to avoid borrow issues
         let events = std::mem::take(&mut self.events);
+        self.type_timings.clear();
+
+        // Snapshot tracking (only when enabled)
+        let resume_state = self.resume_state.take();
+        let mut state = self.snapshot_interval
+            .map(|_| resume_state.unwrap_or_else(|| ReplaySnapshot::new(first_event_ns)));
+        let mut since_snapshot = 0usize;
+        let mut dispatched = 0usize;
+        // Adaptive controller state: live multiplier, events since the last
+        // queue sample and wall delay owed but not yet slept
+        let mut adaptive_multiplier: Option<f64> = None;
+        let mut replayed_types: Option<HashSet<&'static str>> = None;
+        let mut since_sample = 0usize;
+        let mut owed_delay_ns = 0u64;
+
+        for (i, envelope) in events.iter().enumerate().skip(start) {
+            // Cooperative pause/abort point
+            if !self.wait_at_pause_point(envelope.timestamp_ns).await {
+                info!("Replay aborted after {} events", dispatched);
+                break;
+            }
+            dispatched += self.publish_injected(envelope.timestamp_ns).await;
 
-        for (i, envelope) in events.iter().enumerate() {
             // Advance virtual clock
-            self.clock.advance_to(envelope.timestamp_ns);
-
-            // Speed control
-            match &self.speed {
-                ReplaySpeed::Max => { /* no delay */ }
-                ReplaySpeed::Realtime | ReplaySpeed::Multiplier(_) => {
-                    let multiplier = match &self.speed {
-                        ReplaySpeed::Realtime => 1.0,
-                        ReplaySpeed::Multiplier(m) => *m,
-                        _ => unreachable!(),
-                    };
+            self.advance_clock(envelope.timestamp_ns);
 
-                    if i > 0 {
-                        let virtual_delta_ns = envelope.timestamp_ns - events[i - 1].timestamp_ns;
-                        if virtual_delta_ns > 0 {
-                            let wall_delay_ns = (virtual_delta_ns as f64 / multiplier) as u64;
-                            if wall_delay_ns > 1_000_000 {
-                                // Only sleep if > 1ms to avoid overhead
-                                tokio::time::sleep(Duration::from_nanos(wall_delay_ns)).await;
-                            }
-                        }
+            // Speed control (speed in effect at the start of the gap)
+            let speed = if i == start {
+                &self.speed
+            } else {
+                self.speed_at(events[i - 1].timestamp_ns)
+            };
+            let multiplier = match speed {
+                ReplaySpeed::Max | ReplaySpeed::StepByStep => None,
+                ReplaySpeed::Realtime => Some(1.0),
+                ReplaySpeed::Multiplier(m) => Some(*m),
+                ReplaySpeed::Adaptive { target_queue_depth, min_multiplier, max_multiplier, gain } => {
+                    let mut current = adaptive_multiplier.unwrap_or(*max_multiplier);
+                    if since_sample >= ADAPTIVE_SAMPLE_INTERVAL {
+                        // Only the replayed types' channels: a slow subscriber
+                        // to unrelated live traffic must not throttle replay
+                        let types = replayed_types.get_or_insert_with(|| {
+                            events[start..].iter().map(|e| e.event.event_type()).collect()
+                        });
+                        let depth = self.bus.max_queue_depth(types.iter().copied());
+                        current = adapt_multiplier(current, depth, *target_queue_depth, *gain);
+                        since_sample = 0;
+                    }
+                    let current = current.min(*max_multiplier).max(*min_multiplier);
+                    adaptive_multiplier = Some(current);
+                    Some(current)
+                }
+            };
+            let adaptive = matches!(speed, ReplaySpeed::Adaptive { .. });
+            self.control.effective_speed
+                .store(multiplier.unwrap_or(f64::INFINITY).to_bits(), Ordering::Relaxed);
+
+            if let (Some(multiplier), true) = (multiplier, i > start) {
+                let virtual_delta_ns = envelope.timestamp_ns - events[i - 1].timestamp_ns;
+                if virtual_delta_ns > 0 {
+                    let mut wall_delay_ns = (virtual_delta_ns as f64 / multiplier) as u64;
+                    if adaptive {
+                        // Adaptive gaps are mostly under 1ms; carry them over
+                        // rather than skip them, or the controller has no effect
+                        owed_delay_ns += wall_delay_ns;
+                        wall_delay_ns = owed_delay_ns;
+                    }
+                    if wall_delay_ns > 1_000_000 {
+                        // Only sleep if > 1ms to avoid overhead
+                        tokio::time::sleep(Duration::from_nanos(wall_delay_ns)).await;
+                        owed_delay_ns = 0;
                     }
                 }
             }
 
             // Publish event through the bus
-            if let Err(e) = self.bus.publish_envelope(envelope.clone()).await {
+            if let Err(e) = self.publish_timed(envelope.clone()).await {
                 debug!("Failed to publish event {}: {}", i, e);
             }
+            dispatched += 1;
+            since_sample += 1;
+
+            // Auto snapshot, only at a timestamp boundary so resuming
+            // after `timestamp_ns` neither skips nor repeats events
+            if let (Some(state), Some(interval)) = (state.as_mut(), self.snapshot_interval) {
+                state.apply(envelope);
+                since_snapshot += 1;
+                let at_boundary = match events.get(i + 1) {
+                    Some(next) => next.timestamp_ns > envelope.timestamp_ns,
+                    None => true,
+                };
+                if since_snapshot >= interval && at_boundary {
+                    let mut snapshot = state.clone();
+                    snapshot.id = Uuid::new_v4();
+                    self.snapshots.push(snapshot);
+                    since_snapshot = 0;
+                }
+            }
 
             // Per-event callback
             if let Some(ref mut cb) = self.on_event {
@@ -266,9 +933,15 @@ impl EventReplay {
             }
         }
 
+        // Injected events past the end of the natural stream
+        if !self.control.aborted.swap(false, Ordering::AcqRel) {
+            dispatched += self.publish_injected(i64::MAX).await;
+        }
+        self.control.pause_at_ns.store(i64::MAX, Ordering::Release);
+
         let wall_time = wall_start.elapsed();
         let events_per_second = if wall_time.as_secs_f64() > 0.0 {
-            total as f64 / wall_time.as_secs_f64()
+            dispatched as f64 / wall_time.as_secs_f64()
         } else {
             0.0
         };
@@ -281,12 +954,17 @@ impl EventReplay {
         // Put events back
         self.events = events;
 
+        let per_type_stats = self.type_timings.drain()
+            .map(|(event_type, mut samples)| (event_type.to_string(), TypeReplayStats::from_samples(&mut samples)))
+            .collect();
+
         let stats = ReplayStats {
-            events_replayed: total,
+            events_replayed: dispatched,
             wall_time,
             virtual_time_span_ns: virtual_span,
             events_per_second,
             effective_speed,
+            per_type_stats,
         };
 
         info!(
@@ -300,6 +978,53 @@ impl EventReplay {
         stats
     }
 
+    /// Publish the event at the cursor and advance it, or return `None` at the end
+    ///
+    /// Advances the virtual clock and fires the `on_event` callback, but
+    /// never sleeps.
+    pub async fn step(&mut self) -> Option<ReplayStats> {
+        let index = self.cursor;
+        let envelope = self.events.get(index)?.clone();
+        let wall_start = Instant::now();
+        let previous_ns = match index {
+            0 => envelope.timestamp_ns,
+            _ => self.events[index - 1].timestamp_ns,
+        };
+
+        self.advance_clock(envelope.timestamp_ns);
+        if let Err(e) = self.bus.publish_envelope(envelope.clone()).await {
+            debug!("Failed to publish event {}: {}", index, e);
+        }
+        self.cursor += 1;
+
+        if let Some(ref mut cb) = self.on_event {
+            cb(index, &envelope);
+        }
+
+        let wall_time = wall_start.elapsed();
+        Some(ReplayStats {
+            events_replayed: 1,
+            wall_time,
+            virtual_time_span_ns: envelope.timestamp_ns - previous_ns,
+            events_per_second: match wall_time.as_secs_f64() {
+                secs if secs > 0.0 => 1.0 / secs,
+                _ => 0.0,
+            },
+            effective_speed: 0.0,
+            per_type_stats: HashMap::new(),
+        })
+    }
+
+    /// Next event `step()` would publish, without consuming it
+    pub fn peek(&self) -> Option<&EventEnvelope> {
+        self.events.get(self.cursor)
+    }
+
+    /// Number of events left for `step()`
+    pub fn remaining(&self) -> usize {
+        self.events.len().saturating_sub(self.cursor)
+    }
+
     /// Run replay up to a specific virtual timestamp
     pub async fn run_until(&mut self, end_ns: i64) -> ReplayStats {
         // Filter events to only those before end_ns
@@ -315,6 +1040,157 @@ impl EventReplay {
 
         stats
     }
+
+    /// Restore a snapshot into the consumer, then replay the events after it
+    pub async fn run_from_snapshot(&mut self, snapshot_id: Uuid) -> Result<ReplayStats> {
+        let snapshot = self.snapshots.iter()
+            .find(|s| s.id == snapshot_id)
+            .cloned()
+            .ok_or_else(|| anyhow!("Unknown snapshot {}", snapshot_id))?;
+
+        match self.snapshot_consumer.as_mut() {
+            Some(consumer) => consumer.restore(&snapshot),
+            None => bail!("No snapshot consumer set"),
+        }
+
+        info!("Resuming replay from snapshot {} at {}", snapshot.id, snapshot.timestamp_ns);
+
+        // Skip everything the snapshot already covers
+        let cursor = self.cursor;
+        self.cursor = self.events.partition_point(|e| e.timestamp_ns <= snapshot.timestamp_ns);
+
+        self.resume_state = Some(snapshot);
+        let stats = self.run().await;
+        self.resume_state = None;
+        self.cursor = cursor;
+
+        Ok(stats)
+    }
+
+    /// Replay from the cursor on `parallelism` threads, sharded by symbol
+    ///
+    /// Builds a multi-threaded runtime with `parallelism` workers and feeds
+    /// each event into one of `parallelism` shard channels, chosen by an
+    /// FNV-1a hash of its symbol. Every shard is published by its own task,
+    /// in timestamp order, so events of one symbol keep their order while
+    /// different symbols interleave freely. Events without a symbol all go
+    /// to the first shard.
+    ///
+    /// Always replays at `ReplaySpeed::Max`, and ignores pause points,
+    /// injected events, snapshots and callbacks. The virtual clock moves to
+    /// the last event when the run ends. Like `run`, the cursor stays put.
+    ///
+    /// # Panics
+    ///
+    /// If `parallelism` is zero, or the runtime cannot be built.
+    pub async fn run_parallel(&mut self, parallelism: usize) -> ReplayStats {
+        assert!(parallelism > 0, "parallelism must be non-zero");
+        let total = self.events.len();
+        let start = self.cursor.min(total);
+        if start == total {
+            return ReplayStats {
+                events_replayed: 0,
+                wall_time: Duration::ZERO,
+                virtual_time_span_ns: 0,
+                events_per_second: 0.0,
+                effective_speed: 0.0,
+                per_type_stats: HashMap::new(),
+            };
+        }
+
+        let first_event_ns = self.events[start].timestamp_ns;
+        let last_event_ns = self.events[total - 1].timestamp_ns;
+        let virtual_span = last_event_ns - first_event_ns;
+        info!(
+            "Starting parallel replay: {} events on {} threads, virtual span {:.3}s",
+            total - start,
+            parallelism,
+            virtual_span as f64 / 1e9
+        );
+
+        let events = self.events[start..].to_vec();
+        let bus = self.bus.clone();
+        let wall_start = Instant::now();
+
+        // The runtime must be built and dropped outside async context
+        let dispatched = tokio::task::spawn_blocking(move || {
+            let runtime = tokio::runtime::Builder::new_multi_thread()
+                .worker_threads(parallelism)
+                .thread_name("replay-shard")
+                .enable_all()
+                .build()
+                .expect("failed to build replay runtime");
+
+            runtime.block_on(async move {
+                let (senders, shards): (Vec<_>, Vec<_>) = (0..parallelism)
+                    .map(|_| {
+                        let (tx, rx) = flume::bounded::<EventEnvelope>(CHANNEL_CAPACITY);
+                        let bus = bus.clone();
+                        let shard = tokio::spawn(async move {
+                            let mut published = 0usize;
+                            while let Ok(envelope) = rx.recv_async().await {
+                                if let Err(e) = bus.publish_envelope(envelope).await {
+                                    debug!("Failed to publish event: {}", e);
+                                }
+                                published += 1;
+                            }
+                            published
+                        });
+                        (tx, shard)
+                    })
+                    .unzip();
+
+                for envelope in events {
+                    let shard = match envelope.event.as_market_event().and_then(|e| e.symbol()) {
+                        Some(symbol) => (fnv1a(symbol.as_bytes()) % parallelism as u64) as usize,
+                        None => 0,
+                    };
+                    if senders[shard].send_async(envelope).await.is_err() {
+                        break;
+                    }
+                }
+                drop(senders);
+
+                let mut dispatched = 0;
+                for shard in shards {
+                    dispatched += shard.await.expect("replay shard panicked");
+                }
+                dispatched
+            })
+        })
+        .await
+        .expect("parallel replay panicked");
+
+        self.advance_clock(last_event_ns);
+
+        let wall_time = wall_start.elapsed();
+        let events_per_second = if wall_time.as_secs_f64() > 0.0 {
+            dispatched as f64 / wall_time.as_secs_f64()
+        } else {
+            0.0
+        };
+        let effective_speed = if wall_time.as_nanos() > 0 && virtual_span > 0 {
+            virtual_span as f64 / wall_time.as_nanos() as f64
+        } else {
+            0.0
+        };
+
+        info!(
+            "Parallel replay complete: {} events in {:.3}s ({:.0} events/sec)",
+            dispatched,
+            wall_time.as_secs_f64(),
+            events_per_second
+        );
+
+        ReplayStats {
+            events_replayed: dispatched,
+            wall_time,
+            virtual_time_span_ns: virtual_span,
+            events_per_second,
+            effective_speed,
+            per_type_stats: HashMap::new(),
+        }
+    }
 }
 
 /// Builder for constructing EventReplay with fluent API
@@ -323,6 +1199,7 @@ pub struct EventReplayBuilder {
     speed: ReplaySpeed,
     events: Vec<EventEnvelope>,
     progress_interval: usize,
+    snapshot_interval: usize,
 }
 
 impl EventReplayBuilder {
@@ -332,6 +1209,7 @@ impl EventReplayBuilder {
             speed: ReplaySpeed::Max,
             events: Vec::new(),
             progress_interval: 10_000,
+            snapshot_interval: 0,
         }
     }
 
@@ -350,11 +1228,17 @@ impl EventReplayBuilder {
         self
     }
 
+    pub fn snapshot_every(mut self, n: usize) -> Self {
+        self.snapshot_interval = n;
+        self
+    }
+
     pub fn build(self) -> EventReplay {
         let mut replay = EventReplay::new(self.bus, self.speed);
         replay.progress_interval = self.progress_interval;
+        replay.snapshot_every(self.snapshot_interval);
         if !self.events.is_empty() {
-            replay.load_events(self.events);
+            replay.set_events(self.events);
         }
         replay
     }
@@ -364,10 +1248,11 @@ impl EventReplayBuilder {
 mod tests {
     use super::*;
     use crate::events::MarketDataEvent;
+    use crate::test_fixtures::quote;
 
     fn make_envelope(ts_ns: i64, price: f64) -> EventEnvelope {
         let mut env = EventEnvelope::new(
-            Event::MarketData(MarketDataEvent {
+            MarketDataEvent {
                 timestamp: ts_ns,
                 symbol: "ES".to_string(),
                 price,
@@ -376,7 +1261,7 @@ mod tests {
                 bid_size: 10.0,
                 ask_price: price + 0.125,
                 ask_size: 10.0,
-            }),
+            },
             5,
         );
         // Override envelope timestamp to match logical event time
@@ -384,6 +1269,26 @@ mod tests {
         env
     }
 
+    fn make_fill(ts_ns: i64, side: OrderSide, quantity: f64, price: f64) -> EventEnvelope {
+        let mut env = EventEnvelope::new(
+            FillEvent {
+                fill_id: Uuid::new_v4(),
+                order_id: Uuid::new_v4(),
+                signal_id: None,
+                timestamp: ts_ns,
+                symbol: "ES".to_string(),
+                side,
+                filled_quantity: quantity,
+                fill_price: price,
+                commission: 0.0,
+                slippage_bps: 0.0,
+            },
+            5,
+        );
+        env.timestamp_ns = ts_ns;
+        env
+    }
+
     #[test]
     fn test_replay_speed_parse() {
         assert!(matches!(ReplaySpeed::from_str("max"), ReplaySpeed::Max));
@@ -422,7 +1327,7 @@ mod tests {
             .collect();
 
         let mut replay = EventReplay::new(bus, ReplaySpeed::Max);
-        replay.load_events(events);
+        replay.load_events(events).unwrap();
 
         assert_eq!(replay.event_count(), 100);
 
@@ -472,7 +1377,7 @@ mod tests {
         ];
 
         let mut replay = EventReplay::new(bus, ReplaySpeed::Max);
-        replay.load_events(events);
+        replay.load_events(events).unwrap();
 
         let stats = replay.run().await;
         assert_eq!(stats.events_replayed, 3);
@@ -482,8 +1387,12 @@ mod tests {
         let e2 = rx.recv().await.unwrap();
         let e3 = rx.recv().await.unwrap();
 
-        match (&e1.event, &e2.event, &e3.event) {
-            (Event::MarketData(m1), Event::MarketData(m2), Event::MarketData(m3)) => {
+        match (
+            e1.event.downcast_ref::<MarketDataEvent>(),
+            e2.event.downcast_ref::<MarketDataEvent>(),
+            e3.event.downcast_ref::<MarketDataEvent>(),
+        ) {
+            (Some(m1), Some(m2), Some(m3)) => {
                 assert!((m1.price - 6001.0).abs() < 1e-10);
                 assert!((m2.price - 6002.0).abs() < 1e-10);
                 assert!((m3.price - 6003.0).abs() < 1e-10);
@@ -491,4 +1400,495 @@ mod tests {
             _ => panic!("Expected MarketData events"),
         }
     }
+
+    #[test]
+    fn test_snapshot_position_tracking() {
+        let mut snapshot = ReplaySnapshot::new(0);
+        snapshot.apply(&make_fill(1, OrderSide::Buy, 2.0, 6000.0));
+        snapshot.apply(&make_fill(2, OrderSide::Buy, 2.0, 6010.0));
+        assert_eq!(snapshot.positions["ES"], PositionState { quantity: 4.0, avg_price: 6005.0 });
+
+        snapshot.apply(&make_fill(3, OrderSide::Sell, 1.0, 6020.0));
+        assert_eq!(snapshot.positions["ES"], PositionState { quantity: 3.0, avg_price: 6005.0 });
+
+        snapshot.apply(&make_fill(4, OrderSide::Sell, 5.0, 6030.0));
+        assert_eq!(snapshot.positions["ES"], PositionState { quantity: -2.0, avg_price: 6030.0 });
+
+        snapshot.apply(&make_fill(5, OrderSide::Buy, 2.0, 6000.0));
+        assert!(snapshot.positions.is_empty());
+        assert_eq!(snapshot.timestamp_ns, 5);
+
+        // Serializable round trip
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let restored: ReplaySnapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.id, snapshot.id);
+    }
+
+    #[tokio::test]
+    async fn test_run_from_snapshot() {
+        let bus = EventBus::new();
+        let mut rx = bus.subscribe_market_data().await;
+
+        let mut events = Vec::new();
+        for i in 0..10 {
+            events.push(make_envelope(i * 2_000_000, 6000.0 + i as f64));
+            events.push(make_fill(i * 2_000_000 + 1_000_000, OrderSide::Buy, 1.0, 6000.0));
+        }
+
+        let mut replay = EventReplayBuilder::new(bus)
+            .events(events)
+            .snapshot_every(10)
+            .build();
+        replay.run().await;
+        while rx.try_recv().is_ok() {}
+
+        assert_eq!(replay.snapshots().len(), 2);
+        let checkpoint = replay.snapshots()[0].clone();
+        assert_eq!(checkpoint.timestamp_ns, 9_000_000);
+        assert_eq!(checkpoint.positions["ES"].quantity, 5.0);
+
+        let restored = std::sync::Arc::new(std::sync::Mutex::new(None));
+        let sink = restored.clone();
+        replay.set_snapshot_consumer(Box::new(move |snapshot: &ReplaySnapshot| {
+            *sink.lock().unwrap() = Some(snapshot.positions["ES"].quantity);
+        }));
+
+        let stats = replay.run_from_snapshot(checkpoint.id).await.unwrap();
+        assert_eq!(stats.events_replayed, 10);
+        assert_eq!(*restored.lock().unwrap(), Some(5.0));
+
+        // Only market data after the checkpoint is republished
+        let first = rx.recv().await.unwrap();
+        assert_eq!(first.timestamp_ns, 10_000_000);
+
+        // Snapshots taken while resuming continue from the restored state
+        let last = replay.snapshots().last().unwrap();
+        assert_eq!(last.positions["ES"].quantity, 10.0);
+
+        // Full event stream is kept for later runs
+        assert_eq!(replay.event_count(), 20);
+    }
+
+    #[tokio::test]
+    async fn test_run_from_unknown_snapshot() {
+        let bus = EventBus::new();
+        let mut replay = EventReplay::new(bus, ReplaySpeed::Max);
+        replay.set_snapshot_consumer(Box::new(|_: &ReplaySnapshot| {}));
+        assert!(replay.run_from_snapshot(Uuid::new_v4()).await.is_err());
+
+        let id = replay.add_snapshot(42, ReplaySnapshot::new(0));
+        assert_eq!(replay.snapshots()[0].timestamp_ns, 42);
+        assert!(replay.run_from_snapshot(id).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_pause_inject_resume() {
+        let bus = EventBus::new();
+        let mut rx = bus.subscribe_fills().await;
+
+        let events: Vec<EventEnvelope> = (1..=10)
+            .map(|i| make_fill(i * 1_000_000, OrderSide::Buy, 1.0, 6000.0 + i as f64))
+            .collect();
+
+        let mut replay = EventReplay::new(bus, ReplaySpeed::Max);
+        replay.load_events(events).unwrap();
+        let handle = replay.pause_at(5_000_000);
+        let task = tokio::spawn(async move { replay.run().await });
+
+        handle.paused().await;
+        assert!(handle.is_paused());
+        let received: Vec<i64> = std::iter::from_fn(|| rx.try_recv().ok())
+            .map(|e| e.timestamp_ns)
+            .collect();
+        assert_eq!(received, vec![1_000_000, 2_000_000, 3_000_000, 4_000_000, 5_000_000]);
+
+        // Injected out of order; delivered by timestamp
+        handle.inject_event(make_fill(7_500_000, OrderSide::Sell, 1.0, 7.5));
+        handle.inject_event(make_fill(5_500_000, OrderSide::Sell, 1.0, 5.5));
+        handle.inject_event(make_fill(12_000_000, OrderSide::Sell, 1.0, 12.0));
+        handle.resume();
+
+        let stats = task.await.unwrap();
+        assert_eq!(stats.events_replayed, 13);
+
+        let received: Vec<i64> = std::iter::from_fn(|| rx.try_recv().ok())
+            .map(|e| e.timestamp_ns)
+            .collect();
+        assert_eq!(received, vec![
+            5_500_000, 6_000_000, 7_000_000, 7_500_000, 8_000_000,
+            9_000_000, 10_000_000, 12_000_000,
+        ]);
+    }
+
+    #[tokio::test]
+    async fn test_pause_then_abort() {
+        let bus = EventBus::new();
+        let events: Vec<EventEnvelope> = (1..=10)
+            .map(|i| make_fill(i * 1_000_000, OrderSide::Buy, 1.0, 6000.0))
+            .collect();
+
+        let mut replay = EventReplay::new(bus, ReplaySpeed::Max);
+        replay.load_events(events).unwrap();
+        let handle = replay.pause_at(3_000_000);
+        let task = tokio::spawn(async move {
+            let stats = replay.run().await;
+            (stats, replay)
+        });
+
+        handle.paused().await;
+        handle.inject_event(make_fill(3_500_000, OrderSide::Sell, 1.0, 6000.0));
+        handle.abort();
+
+        let (stats, mut replay) = task.await.unwrap();
+        assert_eq!(stats.events_replayed, 3);
+
+        // Abort does not carry over; the injected event is still pending
+        let stats = replay.run().await;
+        assert_eq!(stats.events_replayed, 11);
+    }
+
+    #[test]
+    fn test_speed_curve_lookup_and_overlap() {
+        let bus = EventBus::new();
+        let mut replay = EventReplay::new(bus, ReplaySpeed::Realtime);
+        replay.speed_curve(vec![
+            SpeedSegment { start_ns: 100, end_ns: 200, speed: ReplaySpeed::Multiplier(2.0) },
+            SpeedSegment { start_ns: 0, end_ns: 100, speed: ReplaySpeed::Multiplier(100.0) },
+        ]);
+        assert!(replay.load_events(Vec::new()).is_ok());
+
+        assert!(matches!(replay.speed_at(0), ReplaySpeed::Multiplier(m) if *m == 100.0));
+        assert!(matches!(replay.speed_at(100), ReplaySpeed::Multiplier(m) if *m == 2.0));
+        assert!(matches!(replay.speed_at(250), ReplaySpeed::Max));
+        assert!(matches!(replay.speed_at(-1), ReplaySpeed::Max));
+
+        replay.speed_curve(vec![
+            SpeedSegment { start_ns: 0, end_ns: 150, speed: ReplaySpeed::Realtime },
+            SpeedSegment { start_ns: 100, end_ns: 200, speed: ReplaySpeed::Realtime },
+        ]);
+        assert!(replay.load_events(Vec::new()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_speed_curve_wall_clock_ratio() {
+        let bus = EventBus::new();
+        let events: Vec<EventEnvelope> = [0, 200_000_000, 400_000_000]
+            .into_iter()
+            .map(|ts| make_fill(ts, OrderSide::Buy, 1.0, 6000.0))
+            .collect();
+
+        let mut replay = EventReplay::new(bus, ReplaySpeed::Max);
+        replay.speed_curve(vec![
+            SpeedSegment { start_ns: 0, end_ns: 200_000_000, speed: ReplaySpeed::Multiplier(10.0) },
+            SpeedSegment { start_ns: 200_000_000, end_ns: 400_000_000, speed: ReplaySpeed::Multiplier(2.0) },
+        ]);
+        replay.load_events(events).unwrap();
+
+        let times = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = times.clone();
+        replay.on_event(Box::new(move |_, _| sink.lock().unwrap().push(Instant::now())));
+        replay.run().await;
+
+        // 200 ms at 10x = 20 ms, then 200 ms at 2x = 100 ms
+        let times = times.lock().unwrap();
+        let fast = times[1] - times[0];
+        let slow = times[2] - times[1];
+        let ratio = slow.as_secs_f64() / fast.as_secs_f64();
+        assert!((3.5..=6.5).contains(&ratio), "ratio {} ({:?} vs {:?})", ratio, slow, fast);
+    }
+
+    #[tokio::test]
+    async fn test_step_by_step_peek_matches_step() {
+        let bus = EventBus::new();
+        let mut rx = bus.subscribe_fills().await;
+        let events: Vec<EventEnvelope> = (0..100)
+            .map(|i| make_fill(i * 1_000, OrderSide::Buy, 1.0, 6000.0 + i as f64))
+            .collect();
+        let mut replay = EventReplayBuilder::new(bus)
+            .speed(ReplaySpeed::StepByStep)
+            .events(events)
+            .build();
+        assert!(matches!(ReplaySpeed::from_str("step"), ReplaySpeed::StepByStep));
+
+        for i in 0..100 {
+            assert_eq!(replay.remaining(), 100 - i);
+            let expected = replay.peek().unwrap().id;
+            assert_eq!(replay.peek().unwrap().id, expected);
+
+            let stats = replay.step().await.unwrap();
+            assert_eq!(stats.events_replayed, 1);
+            assert_eq!(rx.try_recv().unwrap().id, expected);
+            assert!(rx.try_recv().is_err());
+            assert_eq!(replay.clock().current(), i as i64 * 1_000);
+        }
+
+        assert_eq!(replay.remaining(), 0);
+        assert!(replay.peek().is_none());
+        assert!(replay.step().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_shared_clock_stamps_envelopes() {
+        let replay_bus = EventBus::new();
+        let mut replay = EventReplay::new(replay_bus.clone(), ReplaySpeed::StepByStep);
+        replay.load_events(vec![
+            make_fill(5_000, OrderSide::Buy, 1.0, 6000.0),
+            make_fill(9_000, OrderSide::Sell, 1.0, 6001.0),
+        ]).unwrap();
+
+        let clock = replay.shared_clock();
+        assert_eq!(clock.now(), 5_000);
+
+        // A strategy publishing on a bus driven by the replay clock
+        let strategy_bus = EventBus::with_shared_clock(clock.clone());
+        let mut fills = strategy_bus.subscribe_fills().await;
+
+        for expected_ns in [5_000, 9_000] {
+            replay.step().await.unwrap();
+            assert_eq!(clock.now(), expected_ns);
+            let response = FillEvent::try_from(make_fill(0, OrderSide::Buy, 1.0, 6000.0)).unwrap();
+            strategy_bus.publish(response).await.unwrap();
+            assert_eq!(fills.try_recv().unwrap().timestamp_ns, clock.now());
+        }
+
+        clock.reset();
+        assert_eq!(clock.now(), 0);
+
+        let mut virtual_clock = VirtualClock::new();
+        virtual_clock.skip_to(100);
+        virtual_clock.skip_to(50);
+        assert_eq!(virtual_clock.current(), 100);
+    }
+
+    #[tokio::test]
+    async fn test_per_type_stats() {
+        let mut events: Vec<EventEnvelope> = (0..200)
+            .map(|i| make_fill(i * 1_000, OrderSide::Buy, 1.0, 6000.0))
+            .collect();
+        for i in 0..50 {
+            let mut health = EventEnvelope::new(
+                crate::events::HealthEvent {
+                    timestamp: i,
+                    component: "feed".to_string(),
+                    status: crate::events::HealthStatus::Healthy,
+                    message: String::new(),
+                },
+                5,
+            );
+            health.timestamp_ns = i * 4_000 + 500;
+            events.push(health);
+        }
+
+        let mut replay = EventReplay::new(EventBus::new(), ReplaySpeed::Max);
+        replay.load_events(events).unwrap();
+
+        // Off by default
+        assert!(replay.run().await.per_type_stats.is_empty());
+
+        let stats = replay.with_per_type_stats(true).run().await;
+        assert_eq!(stats.per_type_stats.len(), 2);
+        assert_eq!(stats.per_type_stats["fill"].count, 200);
+        assert_eq!(stats.per_type_stats["health"].count, 50);
+        let counted: usize = stats.per_type_stats.values().map(|s| s.count).sum();
+        assert_eq!(counted, stats.events_replayed);
+
+        for type_stats in stats.per_type_stats.values() {
+            assert!(type_stats.p99_publish_ns >= type_stats.min_publish_ns);
+            assert!(type_stats.p99_publish_ns <= type_stats.max_publish_ns);
+            assert!(type_stats.total_publish_ns >= type_stats.max_publish_ns);
+        }
+
+        let report = stats.report();
+        assert!(report.lines().nth(2).unwrap().starts_with("fill"));
+        assert!(report.contains("health"));
+    }
+
+    #[tokio::test]
+    async fn test_seek_and_rerun_segment() {
+        let bus = EventBus::new();
+        let mut rx = bus.subscribe("fill").await;
+        let events: Vec<EventEnvelope> = (0..10)
+            .map(|i| make_fill(i * 1_000, OrderSide::Buy, (i + 1) as f64, 6000.0))
+            .collect();
+        let mut replay = EventReplay::new(bus, ReplaySpeed::Max);
+        assert!(replay.seek(0).is_err());
+        replay.load_events(events).unwrap();
+        assert_eq!(replay.position(), Some(0));
+
+        fn received(rx: &mut tokio::sync::broadcast::Receiver<EventEnvelope>) -> Vec<f64> {
+            std::iter::from_fn(|| rx.try_recv().ok())
+                .map(|e| e.event.downcast_ref::<FillEvent>().unwrap().filled_quantity)
+                .collect()
+        }
+
+        // Between events: lands on the next one
+        assert_eq!(replay.seek(5_500).unwrap(), 6);
+        assert_eq!(replay.position(), Some(6_000));
+        assert_eq!(replay.clock().current(), 6_000);
+        assert_eq!(replay.seek(4_000).unwrap(), 4);
+
+        let stats = replay.run().await;
+        assert_eq!(stats.events_replayed, 6);
+        assert_eq!(stats.virtual_time_span_ns, 5_000);
+        let first_pass = received(&mut rx);
+        assert_eq!(first_pass, vec![5.0, 6.0, 7.0, 8.0, 9.0, 10.0]);
+
+        // Cursor stays put; seeking back replays the same segment
+        assert_eq!(replay.position(), Some(4_000));
+        replay.seek_to_end();
+        assert_eq!(replay.position(), None);
+        assert_eq!(replay.run().await.events_replayed, 0);
+        assert_eq!(replay.seek(4_000).unwrap(), 4);
+        replay.run().await;
+        assert_eq!(received(&mut rx), first_pass);
+
+        // Past the last event
+        assert_eq!(replay.seek(i64::MAX).unwrap(), 10);
+
+        replay.seek_to_beginning();
+        assert_eq!(replay.remaining(), 10);
+        replay.step().await.unwrap();
+        assert_eq!(replay.position(), Some(1_000));
+        assert_eq!(replay.run_until(2_000).await.events_replayed, 2);
+        assert_eq!(received(&mut rx), vec![1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_type_replay_stats_percentile() {
+        let mut samples: Vec<u64> = (1..=1000).rev().collect();
+        let stats = TypeReplayStats::from_samples(&mut samples);
+        assert_eq!(stats.count, 1000);
+        assert_eq!(stats.min_publish_ns, 1);
+        assert_eq!(stats.max_publish_ns, 1000);
+        assert_eq!(stats.p99_publish_ns, 990);
+        assert_eq!(stats.mean_publish_ns(), 500);
+        assert_eq!(TypeReplayStats::from_samples(&mut []), TypeReplayStats::default());
+    }
+
+    #[tokio::test]
+    async fn test_run_parallel_delivers_each_event_once() {
+        const SYMBOLS: [&str; 8] = ["ES", "NQ", "YM", "RTY", "CL", "GC", "ZN", "6E"];
+        let bus = EventBus::new();
+        let mut rx = bus.subscribe_market_data().await;
+
+        // Interleaved symbols with increasing timestamps (loaded shuffled)
+        let mut events: Vec<EventEnvelope> = (0..8_000i64)
+            .map(|i| {
+                let symbol = SYMBOLS[(i as usize * 7) % SYMBOLS.len()];
+                let mut envelope = EventEnvelope::new(
+                    MarketDataEvent {
+                        timestamp: i,
+                        symbol: symbol.to_string(),
+                        price: i as f64,
+                        volume: 1.0,
+                        bid_price: 0.0,
+                        bid_size: 1.0,
+                        ask_price: 0.0,
+                        ask_size: 1.0,
+                    },
+                    5,
+                );
+                envelope.timestamp_ns = i;
+                envelope
+            })
+            .collect();
+        events.reverse();
+
+        for parallelism in [1, 4, 16] {
+            let mut replay = EventReplay::new(bus.clone(), ReplaySpeed::Max);
+            replay.load_events(events.clone()).unwrap();
+            let stats = replay.run_parallel(parallelism).await;
+            assert_eq!(stats.events_replayed, 8_000);
+            assert_eq!(replay.clock().current(), 7_999);
+
+            // Count every delivery by envelope ID
+            let mut deliveries: HashMap<Uuid, usize> = HashMap::new();
+            let mut last_per_symbol: HashMap<String, i64> = HashMap::new();
+            while let Ok(envelope) = rx.try_recv() {
+                *deliveries.entry(envelope.id).or_default() += 1;
+                let md = envelope.event.downcast_ref::<MarketDataEvent>().unwrap();
+                if let Some(&previous) = last_per_symbol.get(&md.symbol) {
+                    assert!(previous < md.timestamp, "{} out of order at {}", md.symbol, md.timestamp);
+                }
+                last_per_symbol.insert(md.symbol.clone(), md.timestamp);
+            }
+            assert_eq!(deliveries.len(), 8_000, "parallelism {}", parallelism);
+            assert!(deliveries.values().all(|&count| count == 1));
+            assert_eq!(last_per_symbol.len(), SYMBOLS.len());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_parallel_empty() {
+        let mut replay = EventReplay::new(EventBus::new(), ReplaySpeed::Max);
+        assert_eq!(replay.run_parallel(4).await.events_replayed, 0);
+    }
+
+    #[test]
+    fn test_adapt_multiplier() {
+        // Empty queue: grow by the gain
+        assert!((adapt_multiplier(100.0, 0, 500, 0.1) - 110.0).abs() < 1e-9);
+        // On target: unchanged
+        assert!((adapt_multiplier(100.0, 500, 500, 0.1) - 100.0).abs() < 1e-9);
+        // Twice the target: shrink proportionally
+        assert!((adapt_multiplier(100.0, 1_000, 500, 0.1) - 90.0).abs() < 1e-9);
+        // Far over target: at most halved per sample
+        assert!((adapt_multiplier(100.0, 9_000, 500, 0.1) - 50.0).abs() < 1e-9);
+        // Full channel halves even with a high target
+        assert!((adapt_multiplier(100.0, CHANNEL_CAPACITY, 1_000_000, 0.1) - 50.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_adaptive_speed_slow_subscriber() {
+        // Several times the channel capacity, so a replay that ignored the
+        // subscriber would overrun its queue and lose events
+        const EVENTS: usize = 5 * CHANNEL_CAPACITY;
+        const MAX_MULTIPLIER: f64 = 1_000.0;
+        let bus = EventBus::new();
+        let mut rx = bus.subscribe_market_data().await;
+
+        // Subscriber handling about 20 000 events/s, with events 1ms apart
+        // in virtual time; at the maximum multiplier the replay publishes
+        // 1 000 000 events/s
+        let subscriber = std::thread::spawn(move || {
+            let (mut received, mut lost) = (0usize, 0usize);
+            while received + lost < EVENTS {
+                match rx.blocking_recv() {
+                    Ok(_) => {
+                        received += 1;
+                        std::thread::sleep(Duration::from_micros(50));
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => lost += n as usize,
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                }
+            }
+            (received, lost)
+        });
+
+        let events: Vec<EventEnvelope> = (0..EVENTS as i64)
+            .map(|i| {
+                let event = MarketDataEvent { timestamp: i * 1_000_000, ..quote("ES", 6000.0) };
+                let mut envelope = EventEnvelope::new(event, 5);
+                envelope.timestamp_ns = i * 1_000_000;
+                envelope
+            })
+            .collect();
+        let mut replay = EventReplay::new(bus, ReplaySpeed::Adaptive {
+            target_queue_depth: 200,
+            min_multiplier: 1.0,
+            max_multiplier: MAX_MULTIPLIER,
+            gain: 0.1,
+        });
+        replay.load_events(events).unwrap();
+        assert!(replay.current_effective_speed().is_infinite());
+
+        let stats = replay.run().await;
+        assert_eq!(stats.events_replayed, EVENTS);
+        let speed = replay.current_effective_speed();
+        assert!(speed < MAX_MULTIPLIER && speed >= 1.0, "multiplier {}", speed);
+
+        let (received, lost) = tokio::task::spawn_blocking(move || subscriber.join().unwrap()).await.unwrap();
+        assert_eq!(received + lost, EVENTS);
+        assert!(lost * 100 <= EVENTS, "subscriber lost {} of {} events", lost, EVENTS);
+    }
 }
@@ -1,12 +1,20 @@
-/*
 //! Research Topic Events
 //!
 //! Dedicated event types for research plugin integration with the HFT ecosystem.
 //! Follows the standardization and trait approach from hft-event-bus.
 
+use anyhow::{anyhow, bail, Result};
+use dashmap::DashMap;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::future::Future;
+use std::pin::Pin;
+use std::process::Stdio;
+use std::sync::{Arc, RwLock};
+use std::time::Instant;
+use tokio::io::AsyncWriteExt;
 
 /// Research-specific events for alpha signal analysis and ML model management
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,6 +30,7 @@ pub enum ResearchEvent {
     AnalysisProgress(AnalysisProgressEvent),
     AnalysisCompleted(AnalysisCompletedEvent),
     AnalysisFailed(AnalysisFailedEvent),
+    BacktestCompleted(BacktestCompletedEvent),
     
     /// Feature engineering events
     FeatureExtracted(FeatureExtractedEvent),
@@ -45,6 +54,9 @@ pub enum ResearchEvent {
     /// Configuration and state events
     ResearchConfigUpdated(ResearchConfigUpdatedEvent),
     ResearchStateChanged(ResearchStateChangedEvent),
+    
+    /// Data quality events
+    AnomalyDetected(AnomalyDetectedEvent),
 }
 
 impl crate::Event for ResearchEvent {
@@ -58,6 +70,7 @@ impl crate::Event for ResearchEvent {
             ResearchEvent::AnalysisProgress(_) => "analysis_progress",
             ResearchEvent::AnalysisCompleted(_) => "analysis_completed",
             ResearchEvent::AnalysisFailed(_) => "analysis_failed",
+            ResearchEvent::BacktestCompleted(_) => "backtest_completed",
             ResearchEvent::FeatureExtracted(_) => "feature_extracted",
             ResearchEvent::FeaturePipelineUpdated(_) => "feature_pipeline_updated",
             ResearchEvent::ModelTrainingStarted(_) => "model_training_started",
@@ -71,7 +84,30 @@ impl crate::Event for ResearchEvent {
             ResearchEvent::CorrelationMatrixUpdated(_) => "correlation_matrix_updated",
             ResearchEvent::ResearchConfigUpdated(_) => "research_config_updated",
             ResearchEvent::ResearchStateChanged(_) => "research_state_changed",
+            ResearchEvent::AnomalyDetected(_) => "anomaly_detected",
+        }
+    }
+
+    fn priority(&self) -> u8 {
+        match self {
+            ResearchEvent::AnomalyDetected(e) => e.severity.priority(),
+            ResearchEvent::RealTimeDataUpdate(_) => 1, // Highest priority for real-time
+            ResearchEvent::AnalysisStarted(_) | ResearchEvent::AnalysisProgress(_) => 2,
+            ResearchEvent::SignalCreated(_) | ResearchEvent::SignalUpdated(_) => 3,
+            ResearchEvent::AnalysisCompleted(_)
+            | ResearchEvent::AnalysisFailed(_)
+            | ResearchEvent::BacktestCompleted(_) => 4,
+            _ => 5, // Default priority
         }
+    }
+
+    fn to_json(&self) -> Option<serde_json::Value> {
+        serde_json::to_value(self).ok()
+    }
+
+    fn as_market_event(&self) -> Option<&dyn crate::MarketEvent> {
+        Some(self)
+    }
 }
 
 // ============================================================================
@@ -87,6 +123,9 @@ pub struct SignalCreatedEvent {
     pub parameters: HashMap<String, serde_json::Value>,
     pub created_by: String,
     pub timestamp: i64,
+    pub tags: Vec<String>,
+    #[serde(default = "schema_v1")]
+    pub schema_version: u8,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -136,6 +175,22 @@ pub enum AnalysisType {
     ModelTraining,
     Backtest,
     MonteCarlo,
+    PortfolioOptimization,
+    /// User-supplied analyzer, run by the executor registered under `executor`
+    Custom {
+        name: String,
+        executor: String,
+        config: serde_json::Value,
+    },
+}
+
+/// Mean-variance optimizer settings for `AnalysisType::PortfolioOptimization`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeanVarianceConfig {
+    pub risk_aversion: f64,
+    pub min_weight: f64,
+    pub max_weight: f64,
+    pub target_return: Option<f64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -155,23 +210,255 @@ pub struct AnalysisProgressEvent {
     pub timestamp: i64,
 }
 
+/// Default number of samples `EtaEstimator` fits over
+pub const DEFAULT_ETA_WINDOW: usize = 16;
+
+/// Remaining-time estimate from a linear fit over recent progress samples
+///
+/// Fitting the last `window` (timestamp, progress) samples smooths out
+/// uneven step durations while still adapting when the rate changes.
+#[derive(Debug, Clone)]
+pub struct EtaEstimator {
+    window: usize,
+    /// (timestamp in ns, progress in 0.0..=1.0)
+    samples: VecDeque<(i64, f64)>,
+}
+
+impl EtaEstimator {
+    pub fn new(window: usize) -> Self {
+        let window = window.max(2);
+        Self {
+            window,
+            samples: VecDeque::with_capacity(window),
+        }
+    }
+
+    /// Record progress now and return the estimated seconds remaining
+    pub fn update(&mut self, progress: f64) -> Option<i64> {
+        self.update_at(chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0), progress)
+    }
+
+    /// Record progress at `timestamp_ns` and return the estimated seconds remaining
+    ///
+    /// Returns `None` until two samples exist or while progress is not increasing.
+    pub fn update_at(&mut self, timestamp_ns: i64, progress: f64) -> Option<i64> {
+        let progress = progress.clamp(0.0, 1.0);
+        if self.samples.len() == self.window {
+            self.samples.pop_front();
+        }
+        self.samples.push_back((timestamp_ns, progress));
+
+        if progress >= 1.0 {
+            return Some(0);
+        }
+        if self.samples.len() < 2 {
+            return None;
+        }
+
+        // Least squares fit of progress = intercept + slope * seconds
+        let origin = self.samples[0].0;
+        let n = self.samples.len() as f64;
+        let seconds = |ts: i64| (ts - origin) as f64 / 1e9;
+        let mean_t = self.samples.iter().map(|&(ts, _)| seconds(ts)).sum::<f64>() / n;
+        let mean_p = self.samples.iter().map(|&(_, p)| p).sum::<f64>() / n;
+        let (mut covariance, mut variance) = (0.0, 0.0);
+        for &(ts, p) in &self.samples {
+            let dt = seconds(ts) - mean_t;
+            covariance += dt * (p - mean_p);
+            variance += dt * dt;
+        }
+        if variance <= 0.0 {
+            return None;
+        }
+        let slope = covariance / variance;
+        if slope <= 0.0 {
+            return None;
+        }
+
+        let intercept = mean_p - slope * mean_t;
+        let finish = (1.0 - intercept) / slope;
+        Some((finish - seconds(timestamp_ns)).max(0.0).round() as i64)
+    }
+
+    /// Drop all samples
+    pub fn reset(&mut self) {
+        self.samples.clear();
+    }
+}
+
+impl Default for EtaEstimator {
+    fn default() -> Self {
+        Self::new(DEFAULT_ETA_WINDOW)
+    }
+}
+
+/// Produces `AnalysisProgressEvent`s with a refined `estimated_remaining`
+#[derive(Debug, Clone)]
+pub struct AnalysisTracker {
+    pub analysis_id: Uuid,
+    pub signal_id: Uuid,
+    pub dataset_id: Uuid,
+    current_step: String,
+    estimator: EtaEstimator,
+}
+
+impl AnalysisTracker {
+    pub fn new(analysis_id: Uuid, signal_id: Uuid, dataset_id: Uuid) -> Self {
+        Self {
+            analysis_id,
+            signal_id,
+            dataset_id,
+            current_step: String::new(),
+            estimator: EtaEstimator::default(),
+        }
+    }
+
+    /// Set the step name reported by following ticks
+    pub fn set_step(&mut self, step: impl Into<String>) {
+        self.current_step = step.into();
+    }
+
+    /// Progress event for `progress` reached now
+    pub fn tick(&mut self, progress: f64) -> AnalysisProgressEvent {
+        self.tick_at(chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0), progress)
+    }
+
+    /// Progress event for `progress` reached at `timestamp_ns`
+    pub fn tick_at(&mut self, timestamp_ns: i64, progress: f64) -> AnalysisProgressEvent {
+        AnalysisProgressEvent {
+            analysis_id: self.analysis_id,
+            progress: progress.clamp(0.0, 1.0),
+            current_step: self.current_step.clone(),
+            estimated_remaining: self.estimator.update_at(timestamp_ns, progress),
+            timestamp: timestamp_ns,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnalysisCompletedEvent {
     pub analysis_id: Uuid,
     pub signal_id: Uuid,
     pub dataset_id: Uuid,
+    #[cfg_attr(
+        feature = "compress",
+        deprecated(note = "large results travel in `compressed_results`; read both through `decoded_results()`")
+    )]
     pub results: AnalysisResults,
+    /// zstd-compressed results; when set, `results` is left empty
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compressed_results: Option<CompressedAnalysisResults>,
     pub completed_at: i64,
     pub duration_ms: u64,
+    #[serde(default = "schema_v1")]
+    pub schema_version: u8,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl AnalysisCompletedEvent {
+    /// Completion event carrying `results` compressed at zstd `level`
+    ///
+    /// The ids are nil and `duration_ms` is 0; callers fill them in on the
+    /// returned event.
+    #[cfg(feature = "compress")]
+    #[allow(deprecated)]
+    pub fn new_compressed(results: &AnalysisResults, level: i32) -> Result<Self> {
+        Ok(Self {
+            analysis_id: Uuid::nil(),
+            signal_id: Uuid::nil(),
+            dataset_id: Uuid::nil(),
+            results: AnalysisResults::default(),
+            compressed_results: Some(CompressedAnalysisResults::from_results(results, level)?),
+            completed_at: chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0),
+            duration_ms: 0,
+            schema_version: 1,
+        })
+    }
+
+    /// Move `results` into `compressed_results` when their JSON exceeds
+    /// `threshold` bytes
+    #[cfg(feature = "compress")]
+    #[allow(deprecated)]
+    pub fn compress_above(mut self, threshold: usize, level: i32) -> Result<Self> {
+        if self.compressed_results.is_none() && serde_json::to_vec(&self.results)?.len() > threshold {
+            let results = std::mem::take(&mut self.results);
+            self.compressed_results = Some(CompressedAnalysisResults::from_results(&results, level)?);
+        }
+        Ok(self)
+    }
+
+    /// Results from `compressed_results` if set, otherwise from `results`
+    ///
+    /// Without the `compress` feature, compressed results cannot be decoded
+    /// and are reported as an error.
+    #[allow(deprecated)]
+    pub fn decoded_results(&self) -> Result<AnalysisResults> {
+        match &self.compressed_results {
+            Some(compressed) => compressed.to_results(),
+            None => Ok(self.results.clone()),
+        }
+    }
+}
+
+/// zstd-compressed JSON of an `AnalysisResults`, held base64-encoded so the
+/// saving survives JSON transport
+///
+/// Building and decoding it requires the `compress` feature; without it the
+/// encoded text is still carried through serialization unchanged.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct CompressedAnalysisResults(String);
+
+impl CompressedAnalysisResults {
+    /// Base64 text as serialized
+    pub fn as_encoded(&self) -> &str {
+        &self.0
+    }
+
+    /// True if no bytes are held
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+#[cfg(feature = "compress")]
+impl CompressedAnalysisResults {
+    /// Compress the JSON of `results` at zstd `level` (0 uses zstd's default)
+    pub fn from_results(results: &AnalysisResults, level: i32) -> Result<Self> {
+        use base64::Engine;
+        let json = serde_json::to_vec(results)?;
+        let compressed = zstd::encode_all(json.as_slice(), level)?;
+        Ok(Self(base64::engine::general_purpose::STANDARD.encode(compressed)))
+    }
+
+    /// Decompress and parse the results
+    pub fn to_results(&self) -> Result<AnalysisResults> {
+        let json = zstd::decode_all(self.to_bytes()?.as_slice())?;
+        Ok(serde_json::from_slice(&json)?)
+    }
+
+    /// Compressed bytes
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        use base64::Engine;
+        Ok(base64::engine::general_purpose::STANDARD.decode(&self.0)?)
+    }
+}
+
+#[cfg(not(feature = "compress"))]
+impl CompressedAnalysisResults {
+    /// Always fails: decoding requires the `compress` feature
+    pub fn to_results(&self) -> Result<AnalysisResults> {
+        bail!("decoding compressed analysis results requires the `compress` feature")
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct AnalysisResults {
     pub ic_results: Option<IcResults>,
     pub statistical_tests: Option<StatisticalTestResults>,
     pub correlation_matrix: Option<CorrelationMatrix>,
     pub feature_importance: Option<FeatureImportance>,
     pub model_metrics: Option<ModelMetrics>,
+    pub portfolio_optimization: Option<PortfolioOptimizationResult>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -183,6 +470,206 @@ pub struct IcResults {
     pub ic_series: Vec<(i64, f64)>, // (timestamp, ic_value)
 }
 
+/// Spearman rank correlation of paired samples, ties given average ranks
+///
+/// Returns 0 when either side is constant or fewer than two pairs are given.
+pub fn spearman_correlation(signals: &[f64], returns: &[f64]) -> f64 {
+    let n = signals.len().min(returns.len());
+    if n < 2 {
+        return 0.0;
+    }
+    pearson_of_ranks(&average_ranks(&signals[..n]), &average_ranks(&returns[..n]))
+}
+
+/// 1-based ranks of `values`, equal values sharing their average rank
+fn average_ranks(values: &[f64]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+    let mut ranks = vec![0.0; values.len()];
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start + 1;
+        while end < order.len() && values[order[end]] == values[order[start]] {
+            end += 1;
+        }
+        // Positions start..end hold ranks start+1..=end
+        let rank = (start + end + 1) as f64 / 2.0;
+        for &i in &order[start..end] {
+            ranks[i] = rank;
+        }
+        start = end;
+    }
+    ranks
+}
+
+/// Pearson correlation of two rank vectors of equal length
+///
+/// Average ranks of `n` values always sum to n(n+1)/2, so both means are
+/// (n+1)/2 without summing.
+fn pearson_of_ranks<'a>(
+    x: impl IntoIterator<Item = &'a f64>,
+    y: impl IntoIterator<Item = &'a f64>,
+) -> f64 {
+    let (mut n, mut sxy, mut sxx, mut syy) = (0usize, 0.0, 0.0, 0.0);
+    for (rx, ry) in x.into_iter().zip(y) {
+        n += 1;
+        sxy += rx * ry;
+        sxx += rx * rx;
+        syy += ry * ry;
+    }
+    let mean = (n + 1) as f64 / 2.0;
+    let nm2 = n as f64 * mean * mean;
+    let (cov, var_x, var_y) = (sxy - nm2, sxx - nm2, syy - nm2);
+    if var_x <= f64::EPSILON || var_y <= f64::EPSILON {
+        return 0.0;
+    }
+    (cov / (var_x * var_y).sqrt()).clamp(-1.0, 1.0)
+}
+
+/// Rolling Spearman IC of one signal against its realized returns
+///
+/// Each return is paired with the oldest unpaired signal value stamped at or
+/// before it; returns with no such signal are ignored. The IC is taken over
+/// the last `window` pairs and every emitted IC is kept in `ic_series` (up to
+/// `window` entries), from which the summary statistics are computed.
+///
+/// Ranks are kept up to date as pairs enter and leave the window, so an
+/// update is a single O(window) pass with no sorting.
+#[derive(Debug, Clone)]
+pub struct IcStreamCalculator {
+    pub signal_id: Uuid,
+    pub window: usize,
+    /// Signal values of the pairs in the window, oldest first
+    signal_buffer: VecDeque<f64>,
+    /// Return values of the pairs in the window, oldest first
+    return_buffer: VecDeque<f64>,
+    /// Average ranks of `signal_buffer` / `return_buffer` within the window
+    signal_ranks: VecDeque<f64>,
+    return_ranks: VecDeque<f64>,
+    /// Signals waiting for a return, with their timestamps (at most `window`)
+    pending: VecDeque<(f64, i64)>,
+    ic_series: VecDeque<(i64, f64)>,
+}
+
+impl IcStreamCalculator {
+    /// `window` is clamped to at least 2 pairs
+    pub fn new(signal_id: Uuid, window: usize) -> Self {
+        let window = window.max(2);
+        Self {
+            signal_id,
+            window,
+            signal_buffer: VecDeque::with_capacity(window),
+            return_buffer: VecDeque::with_capacity(window),
+            signal_ranks: VecDeque::with_capacity(window),
+            return_ranks: VecDeque::with_capacity(window),
+            pending: VecDeque::new(),
+            ic_series: VecDeque::with_capacity(window),
+        }
+    }
+
+    /// Record a signal value awaiting its realized return
+    ///
+    /// If `window` signals are already waiting, the oldest is discarded.
+    pub fn update_signal(&mut self, value: f64, timestamp: i64) {
+        if self.pending.len() >= self.window {
+            self.pending.pop_front();
+        }
+        self.pending.push_back((value, timestamp));
+    }
+
+    /// Record a realized return, returning updated results once the window is full
+    pub fn update_return(&mut self, value: f64, timestamp: i64) -> Option<IcResults> {
+        let &(signal, signal_ts) = self.pending.front()?;
+        // A signal stamped after the return cannot have predicted it
+        if signal_ts > timestamp {
+            return None;
+        }
+        self.pending.pop_front();
+
+        if self.signal_buffer.len() == self.window {
+            let old_signal = self.signal_buffer.pop_front()?;
+            let old_return = self.return_buffer.pop_front()?;
+            self.signal_ranks.pop_front();
+            self.return_ranks.pop_front();
+            Self::remove_rank(&self.signal_buffer, &mut self.signal_ranks, old_signal);
+            Self::remove_rank(&self.return_buffer, &mut self.return_ranks, old_return);
+        }
+        let signal_rank = Self::insert_rank(&self.signal_buffer, &mut self.signal_ranks, signal);
+        let return_rank = Self::insert_rank(&self.return_buffer, &mut self.return_ranks, value);
+        self.signal_buffer.push_back(signal);
+        self.return_buffer.push_back(value);
+        self.signal_ranks.push_back(signal_rank);
+        self.return_ranks.push_back(return_rank);
+
+        let ic = self.current_ic()?;
+        if self.ic_series.len() == self.window {
+            self.ic_series.pop_front();
+        }
+        self.ic_series.push_back((timestamp, ic));
+        Some(self.results())
+    }
+
+    /// Spearman IC of the current window (`None` until it holds `window` pairs)
+    pub fn current_ic(&self) -> Option<f64> {
+        if self.signal_buffer.len() < self.window {
+            return None;
+        }
+        Some(pearson_of_ranks(&self.signal_ranks, &self.return_ranks))
+    }
+
+    /// Forget all pairs, waiting signals and emitted ICs
+    pub fn reset(&mut self) {
+        self.signal_buffer.clear();
+        self.return_buffer.clear();
+        self.signal_ranks.clear();
+        self.return_ranks.clear();
+        self.pending.clear();
+        self.ic_series.clear();
+    }
+
+    /// Shift existing ranks for `value` joining `values`; returns its rank
+    fn insert_rank(values: &VecDeque<f64>, ranks: &mut VecDeque<f64>, value: f64) -> f64 {
+        let (mut below, mut equal) = (0usize, 0usize);
+        for (existing, rank) in values.iter().zip(ranks.iter_mut()) {
+            if *existing < value {
+                below += 1;
+            } else if *existing > value {
+                *rank += 1.0;
+            } else {
+                equal += 1;
+                *rank += 0.5;
+            }
+        }
+        below as f64 + 1.0 + equal as f64 / 2.0
+    }
+
+    /// Shift remaining ranks for `value` having left `values`
+    fn remove_rank(values: &VecDeque<f64>, ranks: &mut VecDeque<f64>, value: f64) {
+        for (existing, rank) in values.iter().zip(ranks.iter_mut()) {
+            if *existing > value {
+                *rank -= 1.0;
+            } else if *existing == value {
+                *rank -= 0.5;
+            }
+        }
+    }
+
+    /// Summary of the emitted ICs (population standard deviation)
+    fn results(&self) -> IcResults {
+        let n = self.ic_series.len() as f64;
+        let mean_ic = self.ic_series.iter().map(|(_, ic)| ic).sum::<f64>() / n;
+        let std_ic = (self.ic_series.iter().map(|(_, ic)| (ic - mean_ic).powi(2)).sum::<f64>() / n).sqrt();
+        let hits = self.ic_series.iter().filter(|(_, ic)| *ic > 0.0).count();
+        IcResults {
+            mean_ic,
+            std_ic,
+            ir: if std_ic > 0.0 { mean_ic / std_ic } else { 0.0 },
+            hit_rate: hits as f64 / n,
+            ic_series: self.ic_series.iter().copied().collect(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StatisticalTestResults {
     pub normality_test: Option<NormalityTestResult>,
@@ -221,6 +708,170 @@ pub struct CorrelationMatrix {
     pub p_values: Option<Vec<Vec<f64>>>,
 }
 
+impl CorrelationMatrix {
+    /// Copy with correlations whose p-value exceeds `alpha` set to 0.0
+    ///
+    /// Missing (NaN) p-values count as not significant; the diagonal is
+    /// kept. Without `p_values` the matrix is returned unchanged.
+    pub fn filter_significant(&self, alpha: f64) -> CorrelationMatrix {
+        let mut filtered = self.clone();
+        if let Some(p_values) = &self.p_values {
+            for (i, (row, p_row)) in filtered.matrix.iter_mut().zip(p_values).enumerate() {
+                for (j, (value, &p)) in row.iter_mut().zip(p_row).enumerate() {
+                    if i != j && (p.is_nan() || p > alpha) {
+                        *value = 0.0;
+                    }
+                }
+            }
+        }
+        filtered
+    }
+
+    /// The `k` signal pairs with the largest absolute correlation
+    ///
+    /// Pairs with equal |correlation| keep matrix order; NaN correlations
+    /// are skipped.
+    pub fn top_k_pairs(&self, k: usize) -> Vec<CorrelationPair> {
+        let n = self.signals.len();
+        let mut pairs: Vec<CorrelationPair> = (0..n)
+            .flat_map(|i| (i + 1..n).map(move |j| (i, j)))
+            .filter(|&(i, j)| !self.matrix[i][j].is_nan())
+            .map(|(i, j)| CorrelationPair {
+                signal_a: self.signals[i].clone(),
+                signal_b: self.signals[j].clone(),
+                correlation: self.matrix[i][j],
+                p_value: self.p_values.as_ref().map(|p| p[i][j]),
+            })
+            .collect();
+        pairs.sort_by(|a, b| b.correlation.abs().total_cmp(&a.correlation.abs()));
+        pairs.truncate(k);
+        pairs
+    }
+
+    /// Signal indices ordered for a clustered heatmap
+    ///
+    /// Single-linkage agglomerative clustering with the correlation as
+    /// similarity: the two clusters holding the most correlated pair are
+    /// merged until one remains, and the leaves are read off left to
+    /// right. Ties merge the clusters with the lowest indices first, so the
+    /// order is stable for a given matrix.
+    pub fn clustermap_order(&self) -> Vec<usize> {
+        let n = self.signals.len();
+        let mut clusters: Vec<Option<Vec<usize>>> = (0..n).map(|i| Some(vec![i])).collect();
+        // Similarity between clusters, indexed by their first member
+        let mut similarity: Vec<Vec<f64>> = self
+            .matrix
+            .iter()
+            .map(|row| row.iter().map(|&c| if c.is_nan() { f64::NEG_INFINITY } else { c }).collect())
+            .collect();
+
+        for _ in 1..n {
+            let mut best: Option<(usize, usize)> = None;
+            let mut best_similarity = f64::NEG_INFINITY;
+            for a in (0..n).filter(|&a| clusters[a].is_some()) {
+                for b in (a + 1..n).filter(|&b| clusters[b].is_some()) {
+                    if best.is_none() || similarity[a][b] > best_similarity {
+                        best = Some((a, b));
+                        best_similarity = similarity[a][b];
+                    }
+                }
+            }
+            let Some((a, b)) = best else { break };
+
+            let merged = clusters[b].take().unwrap_or_default();
+            if let Some(cluster) = clusters[a].as_mut() {
+                cluster.extend(merged);
+            }
+            for k in 0..n {
+                let linked = similarity[a][k].max(similarity[b][k]);
+                similarity[a][k] = linked;
+                similarity[k][a] = linked;
+            }
+        }
+        clusters.into_iter().flatten().flatten().collect()
+    }
+}
+
+/// Two signals and their correlation, from `CorrelationMatrix::top_k_pairs`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CorrelationPair {
+    pub signal_a: String,
+    pub signal_b: String,
+    pub correlation: f64,
+    pub p_value: Option<f64>,
+}
+
+/// Correlation matrix stored as its strict upper triangle
+///
+/// Holds n(n-1)/2 values instead of n² (the diagonal is always 1.0).
+/// P-values are not kept.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompactCorrelationMatrix {
+    /// Upper triangle above the diagonal, row-major
+    pub values: Vec<f64>,
+    pub signals: Vec<String>,
+    pub method: String,
+}
+
+impl CompactCorrelationMatrix {
+    /// Number of signals
+    pub fn len(&self) -> usize {
+        self.signals.len()
+    }
+    
+    /// Check if the matrix has no signals
+    pub fn is_empty(&self) -> bool {
+        self.signals.is_empty()
+    }
+    
+    /// Correlation between signals `i` and `j` (symmetric)
+    pub fn get(&self, i: usize, j: usize) -> f64 {
+        let n = self.len();
+        assert!(i < n && j < n, "index ({}, {}) out of bounds for {} signals", i, j, n);
+        
+        match i.cmp(&j) {
+            std::cmp::Ordering::Equal => 1.0,
+            std::cmp::Ordering::Less => self.values[Self::offset(n, i, j)],
+            std::cmp::Ordering::Greater => self.values[Self::offset(n, j, i)],
+        }
+    }
+    
+    /// Position of (i, j), i < j, in `values`
+    fn offset(n: usize, i: usize, j: usize) -> usize {
+        i * n - i * (i + 1) / 2 + (j - i - 1)
+    }
+    
+    /// Compress a full matrix (only the upper triangle is read)
+    pub fn from_full(m: &CorrelationMatrix) -> Self {
+        let n = m.signals.len();
+        let mut values = Vec::with_capacity(n * n.saturating_sub(1) / 2);
+        for i in 0..n {
+            values.extend_from_slice(&m.matrix[i][i + 1..n]);
+        }
+        
+        Self {
+            values,
+            signals: m.signals.clone(),
+            method: m.method.clone(),
+        }
+    }
+    
+    /// Expand to a full matrix (without p-values)
+    pub fn to_full(&self) -> CorrelationMatrix {
+        let n = self.len();
+        let matrix = (0..n)
+            .map(|i| (0..n).map(|j| self.get(i, j)).collect())
+            .collect();
+        
+        CorrelationMatrix {
+            signals: self.signals.clone(),
+            matrix,
+            method: self.method.clone(),
+            p_values: None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FeatureImportance {
     pub features: Vec<String>,
@@ -239,6 +890,26 @@ pub struct ModelMetrics {
     pub r_squared: Option<f64>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortfolioOptimizationResult {
+    pub weights: HashMap<String, f64>,
+    pub expected_return: f64,
+    pub expected_volatility: f64,
+    pub sharpe: f64,
+    pub efficient_frontier: Vec<(f64, f64)>, // (volatility, return)
+}
+
+impl PortfolioOptimizationResult {
+    /// Tolerance used when checking that weights are fully invested
+    pub const WEIGHT_SUM_EPSILON: f64 = 1e-6;
+
+    /// Check that the weights sum to 1.0 (fully invested portfolio)
+    pub fn is_feasible(&self) -> bool {
+        let total: f64 = self.weights.values().sum();
+        (total - 1.0).abs() <= Self::WEIGHT_SUM_EPSILON
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnalysisFailedEvent {
     pub analysis_id: Uuid,
@@ -248,6 +919,132 @@ pub struct AnalysisFailedEvent {
     pub failed_at: i64,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BacktestCompletedEvent {
+    pub backtest_id: Uuid,
+    pub signal_id: Uuid,
+    pub start_date: i64,
+    pub end_date: i64,
+    pub total_return: f64,
+    pub annualized_return: f64,
+    pub sharpe_ratio: f64,
+    pub sortino_ratio: f64,
+    pub max_drawdown: f64, // positive fraction of peak equity
+    pub calmar_ratio: f64,
+    pub win_rate: f64,
+    pub avg_win: f64,
+    pub avg_loss: f64, // negative
+    pub profit_factor: f64,
+    pub num_trades: u64,
+    pub turnover: f64,
+    pub benchmark_return: Option<f64>,
+    pub alpha: Option<f64>,
+    pub beta: Option<f64>,
+    pub equity_curve: Vec<(i64, f64)>, // (timestamp, equity)
+    #[serde(default = "schema_v1")]
+    pub schema_version: u8,
+}
+
+impl BacktestCompletedEvent {
+    /// Empty result for a backtest over `[start_date, end_date]`; all metrics zero
+    pub fn new(backtest_id: Uuid, signal_id: Uuid, start_date: i64, end_date: i64) -> Self {
+        Self {
+            backtest_id,
+            signal_id,
+            start_date,
+            end_date,
+            total_return: 0.0,
+            annualized_return: 0.0,
+            sharpe_ratio: 0.0,
+            sortino_ratio: 0.0,
+            max_drawdown: 0.0,
+            calmar_ratio: 0.0,
+            win_rate: 0.0,
+            avg_win: 0.0,
+            avg_loss: 0.0,
+            profit_factor: 0.0,
+            num_trades: 0,
+            turnover: 0.0,
+            benchmark_return: None,
+            alpha: None,
+            beta: None,
+            equity_curve: Vec::new(),
+            schema_version: 1,
+        }
+    }
+
+    /// Set the equity curve and derive return, risk and drawdown metrics from it
+    ///
+    /// `periods_per_year` is the number of curve points per year (252 for daily bars).
+    /// Ratios with a zero denominator are reported as 0.0.
+    pub fn with_equity_curve(mut self, equity_curve: Vec<(i64, f64)>, periods_per_year: f64) -> Self {
+        let returns: Vec<f64> = equity_curve
+            .windows(2)
+            .filter(|w| w[0].1 != 0.0)
+            .map(|w| w[1].1 / w[0].1 - 1.0)
+            .collect();
+
+        if let (Some(first), Some(last)) = (equity_curve.first(), equity_curve.last()) {
+            if first.1 != 0.0 {
+                self.total_return = last.1 / first.1 - 1.0;
+            }
+        }
+        if !returns.is_empty() {
+            let years = returns.len() as f64 / periods_per_year;
+            self.annualized_return = (1.0 + self.total_return).powf(1.0 / years) - 1.0;
+
+            let n = returns.len() as f64;
+            let mean = returns.iter().sum::<f64>() / n;
+            let std = (returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / n).sqrt();
+            let downside = (returns.iter().map(|r| r.min(0.0).powi(2)).sum::<f64>() / n).sqrt();
+            let scale = periods_per_year.sqrt();
+            self.sharpe_ratio = if std > 0.0 { mean / std * scale } else { 0.0 };
+            self.sortino_ratio = if downside > 0.0 { mean / downside * scale } else { 0.0 };
+        }
+
+        let mut peak = f64::MIN;
+        self.max_drawdown = 0.0;
+        for &(_, equity) in &equity_curve {
+            peak = peak.max(equity);
+            if peak > 0.0 {
+                self.max_drawdown = self.max_drawdown.max(1.0 - equity / peak);
+            }
+        }
+        self.calmar_ratio = if self.max_drawdown > 0.0 {
+            self.annualized_return / self.max_drawdown
+        } else {
+            0.0
+        };
+
+        self.equity_curve = equity_curve;
+        self
+    }
+
+    /// Derive trade statistics from per-trade PnL
+    ///
+    /// Profit factor is 0.0 when there are no losing trades.
+    pub fn with_trades(mut self, trade_pnls: &[f64]) -> Self {
+        let (wins, losses): (Vec<f64>, Vec<f64>) = trade_pnls.iter().partition(|&&pnl| pnl > 0.0);
+        let gross_win: f64 = wins.iter().sum();
+        let gross_loss: f64 = losses.iter().sum();
+
+        self.num_trades = trade_pnls.len() as u64;
+        self.win_rate = if trade_pnls.is_empty() { 0.0 } else { wins.len() as f64 / trade_pnls.len() as f64 };
+        self.avg_win = if wins.is_empty() { 0.0 } else { gross_win / wins.len() as f64 };
+        self.avg_loss = if losses.is_empty() { 0.0 } else { gross_loss / losses.len() as f64 };
+        self.profit_factor = if gross_loss < 0.0 { gross_win / -gross_loss } else { 0.0 };
+        self
+    }
+
+    /// Attach benchmark comparison
+    pub fn with_benchmark(mut self, benchmark_return: f64, alpha: f64, beta: f64) -> Self {
+        self.benchmark_return = Some(benchmark_return);
+        self.alpha = Some(alpha);
+        self.beta = Some(beta);
+        self
+    }
+}
+
 // ============================================================================
 // Feature Engineering Events
 // ============================================================================
@@ -276,6 +1073,241 @@ pub struct PipelineUpdate {
     pub config: HashMap<String, serde_json::Value>,
 }
 
+impl PipelineUpdate {
+    /// Features listed under `config["depends_on"]` (`None` if absent)
+    pub fn dependencies(&self) -> Option<Vec<String>> {
+        let deps = self.config.get("depends_on")?.as_array()?;
+        Some(deps.iter().filter_map(|dep| dep.as_str().map(str::to_string)).collect())
+    }
+}
+
+impl FeaturePipelineUpdatedEvent {
+    /// Apply the updates to `graph` and return the resulting execution order
+    ///
+    /// Dependencies are read from each update's `config["depends_on"]`
+    /// (an array of feature names). `add_feature` fails for a feature that
+    /// already exists, `remove_feature` and `modify_feature` for one that
+    /// does not; every dependency must exist once all updates are applied.
+    /// `graph` itself is not changed.
+    pub fn validate_pipeline(&self, graph: &FeatureDependencyGraph) -> Result<Vec<String>, PipelineError> {
+        let mut features = graph.features.clone();
+        for update in &self.updates {
+            let name = &update.feature_name;
+            match update.operation.as_str() {
+                "add_feature" => {
+                    if features.contains_key(name) {
+                        return Err(PipelineError::DuplicateFeature(name.clone()));
+                    }
+                    features.insert(name.clone(), update.dependencies().unwrap_or_default());
+                }
+                "remove_feature" => {
+                    if features.remove(name).is_none() {
+                        return Err(PipelineError::MissingFeature(name.clone()));
+                    }
+                }
+                "modify_feature" => {
+                    let Some(deps) = features.get_mut(name) else {
+                        return Err(PipelineError::MissingFeature(name.clone()));
+                    };
+                    if let Some(new_deps) = update.dependencies() {
+                        *deps = new_deps;
+                    }
+                }
+                other => return Err(PipelineError::UnknownOperation(other.to_string())),
+            }
+        }
+
+        let mut names: Vec<&String> = features.keys().collect();
+        names.sort();
+        for name in names {
+            if let Some(missing) = features[name].iter().find(|dep| !features.contains_key(*dep)) {
+                return Err(PipelineError::MissingFeature(missing.clone()));
+            }
+        }
+        Ok(FeatureDependencyGraph { features }.execution_order()?)
+    }
+}
+
+/// Features whose dependencies loop back to themselves
+///
+/// `cycle` starts and ends with the same feature; each feature depends on
+/// the next one.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("dependency cycle: {}", cycle.join(" -> "))]
+pub struct CycleError {
+    pub cycle: Vec<String>,
+}
+
+/// Invalid feature pipeline update
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum PipelineError {
+    #[error(transparent)]
+    CyclicDependency(#[from] CycleError),
+    #[error("feature {0} does not exist")]
+    MissingFeature(String),
+    #[error("feature {0} already exists")]
+    DuplicateFeature(String),
+    #[error("unknown pipeline operation {0}")]
+    UnknownOperation(String),
+}
+
+/// Features of a pipeline and the features each one depends on
+///
+/// Dependencies need not be added before their dependents, but the graph
+/// never contains a cycle.
+#[derive(Debug, Clone, Default)]
+pub struct FeatureDependencyGraph {
+    pub features: HashMap<String, Vec<String>>,
+}
+
+impl FeatureDependencyGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `name` (replacing its dependencies if present)
+    ///
+    /// Leaves the graph unchanged and returns the cycle if the new edges
+    /// would create one.
+    pub fn add_feature(&mut self, name: &str, deps: Vec<String>) -> Result<(), CycleError> {
+        let previous = self.features.insert(name.to_string(), deps);
+        if let Err(cycle) = self.execution_order() {
+            match previous {
+                Some(deps) => self.features.insert(name.to_string(), deps),
+                None => self.features.remove(name),
+            };
+            return Err(cycle);
+        }
+        Ok(())
+    }
+
+    /// Features in an order where each comes after its dependencies
+    ///
+    /// Kahn's algorithm; ties are broken alphabetically. Dependencies that
+    /// are not features of the graph are ignored.
+    pub fn execution_order(&self) -> Result<Vec<String>, CycleError> {
+        let mut pending: HashMap<&str, usize> = HashMap::with_capacity(self.features.len());
+        let mut dependents: HashMap<&str, Vec<&str>> = HashMap::new();
+        for (name, deps) in &self.features {
+            let known: BTreeSet<&str> = deps.iter().map(String::as_str).filter(|dep| self.features.contains_key(*dep)).collect();
+            pending.insert(name, known.len());
+            for dep in known {
+                dependents.entry(dep).or_default().push(name);
+            }
+        }
+
+        let mut ready: BTreeSet<&str> = pending.iter().filter(|(_, &count)| count == 0).map(|(&name, _)| name).collect();
+        let mut order = Vec::with_capacity(self.features.len());
+        while let Some(name) = ready.pop_first() {
+            order.push(name.to_string());
+            for &dependent in dependents.get(name).into_iter().flatten() {
+                let count = pending.get_mut(dependent).expect("every dependent is a feature");
+                *count -= 1;
+                if *count == 0 {
+                    ready.insert(dependent);
+                }
+            }
+        }
+
+        if order.len() == self.features.len() {
+            return Ok(order);
+        }
+        Err(self.find_cycle(&pending))
+    }
+
+    /// Walk unresolved features (each still has an unresolved dependency)
+    /// until one repeats
+    fn find_cycle(&self, pending: &HashMap<&str, usize>) -> CycleError {
+        let unresolved = |name: &str| pending.get(name).is_some_and(|&count| count > 0);
+        let start = pending.iter().filter(|(_, &count)| count > 0).map(|(&name, _)| name).min().expect("a feature is unresolved");
+
+        let mut path = vec![start];
+        loop {
+            let current = *path.last().unwrap();
+            let next = self.features[current]
+                .iter()
+                .map(String::as_str)
+                .filter(|dep| unresolved(dep))
+                .min()
+                .expect("unresolved features have an unresolved dependency");
+            if let Some(at) = path.iter().position(|&name| name == next) {
+                let mut cycle: Vec<String> = path[at..].iter().map(|name| name.to_string()).collect();
+                cycle.push(next.to_string());
+                return CycleError { cycle };
+            }
+            path.push(next);
+        }
+    }
+}
+
+/// Last `window_size` values of each feature extracted for one signal
+///
+/// Statistics return `None` for unknown features. Features missing from an
+/// event keep their previous values.
+#[derive(Debug, Clone)]
+pub struct FeatureWindow {
+    pub signal_id: Uuid,
+    pub window_size: usize,
+    pub features: HashMap<String, VecDeque<f64>>,
+}
+
+impl FeatureWindow {
+    pub fn new(signal_id: Uuid, window_size: usize) -> Self {
+        Self {
+            signal_id,
+            window_size,
+            features: HashMap::new(),
+        }
+    }
+
+    /// Add the event's feature values (events for other signals are ignored)
+    pub fn update(&mut self, event: &FeatureExtractedEvent) {
+        if event.signal_id != self.signal_id {
+            return;
+        }
+        let window_size = self.window_size;
+        for (name, &value) in &event.features {
+            let values = self.features.entry(name.clone())
+                .or_insert_with(|| VecDeque::with_capacity(window_size));
+            values.push_back(value);
+            while values.len() > window_size {
+                values.pop_front();
+            }
+        }
+    }
+
+    fn values(&self, feature: &str) -> Option<&VecDeque<f64>> {
+        self.features.get(feature).filter(|values| !values.is_empty())
+    }
+
+    /// Mean of the window
+    pub fn mean(&self, feature: &str) -> Option<f64> {
+        let values = self.values(feature)?;
+        Some(values.iter().sum::<f64>() / values.len() as f64)
+    }
+
+    /// Population standard deviation of the window
+    pub fn std(&self, feature: &str) -> Option<f64> {
+        let values = self.values(feature)?;
+        let mean = self.mean(feature)?;
+        let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64;
+        Some(variance.sqrt())
+    }
+
+    pub fn min(&self, feature: &str) -> Option<f64> {
+        self.values(feature)?.iter().copied().reduce(f64::min)
+    }
+
+    pub fn max(&self, feature: &str) -> Option<f64> {
+        self.values(feature)?.iter().copied().reduce(f64::max)
+    }
+
+    /// Most recent value
+    pub fn latest(&self, feature: &str) -> Option<f64> {
+        self.values(feature)?.back().copied()
+    }
+}
+
 // ============================================================================
 // ML Model Events
 // ============================================================================
@@ -301,19 +1333,183 @@ pub struct ModelTrainingProgressEvent {
     pub timestamp: i64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ModelTrainingCompletedEvent {
-    pub model_id: Uuid,
-    pub final_metrics: ModelMetrics,
-    pub training_time_ms: u64,
-    pub model_path: String,
-    pub completed_at: i64,
+/// Lifecycle state of a job tracked by `ProgressDashboard`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrainingJobStatus {
+    Running,
+    Completed,
+    Failed(String),
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ModelDeploymentRequestedEvent {
+/// Latest known state of one training job
+#[derive(Debug, Clone)]
+pub struct TrainingJob {
     pub model_id: Uuid,
-    pub deployment_config: HashMap<String, serde_json::Value>,
+    pub status: TrainingJobStatus,
+    pub epoch: u32,
+    pub total_epochs: u32,
+    pub training_loss: f64,
+    /// Timestamp and progress of the first progress event seen
+    pub first_update_ns: i64,
+    pub first_progress: f64,
+    pub last_update_ns: i64,
+}
+
+impl TrainingJob {
+    fn new(model_id: Uuid, status: TrainingJobStatus) -> Self {
+        Self {
+            model_id,
+            status,
+            epoch: 0,
+            total_epochs: 0,
+            training_loss: 0.0,
+            first_update_ns: 0,
+            first_progress: 0.0,
+            last_update_ns: 0,
+        }
+    }
+
+    /// Fraction of epochs done (1 once completed)
+    pub fn progress(&self) -> f64 {
+        if self.status == TrainingJobStatus::Completed {
+            return 1.0;
+        }
+        if self.total_epochs == 0 {
+            return 0.0;
+        }
+        (self.epoch as f64 / self.total_epochs as f64).clamp(0.0, 1.0)
+    }
+
+    /// Progress per nanosecond since the first update (`None` until time has passed)
+    pub fn progress_rate(&self) -> Option<f64> {
+        let elapsed = self.last_update_ns - self.first_update_ns;
+        (elapsed > 0).then(|| (self.progress() - self.first_progress) / elapsed as f64)
+    }
+}
+
+/// Fleet-wide view produced by `ProgressDashboard::summary`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DashboardSummary {
+    pub active_jobs: usize,
+    pub completed_jobs: usize,
+    pub failed_jobs: usize,
+    /// Mean progress of running and completed jobs
+    pub overall_progress: f64,
+    /// Running jobs with the lowest and highest progress rate
+    pub slowest_job: Option<Uuid>,
+    pub fastest_job: Option<Uuid>,
+    /// Mean latest training loss of running jobs
+    pub avg_training_loss: f64,
+}
+
+/// Aggregates `ModelTrainingProgressEvent`s from concurrent training jobs
+///
+/// Jobs are added on their first progress event (or when marked completed
+/// or failed). Progress events for finished jobs are ignored.
+#[derive(Debug, Clone, Default)]
+pub struct ProgressDashboard {
+    pub jobs: HashMap<Uuid, TrainingJob>,
+}
+
+impl ProgressDashboard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, event: &ModelTrainingProgressEvent) {
+        let mut created = false;
+        let job = self.jobs.entry(event.model_id).or_insert_with(|| {
+            created = true;
+            TrainingJob::new(event.model_id, TrainingJobStatus::Running)
+        });
+        if job.status != TrainingJobStatus::Running {
+            return;
+        }
+        job.epoch = event.epoch;
+        job.total_epochs = event.total_epochs;
+        job.training_loss = event.training_loss;
+        job.last_update_ns = event.timestamp;
+        if created {
+            job.first_update_ns = event.timestamp;
+            job.first_progress = job.progress();
+        }
+    }
+
+    pub fn mark_completed(&mut self, model_id: Uuid) {
+        self.jobs.entry(model_id)
+            .or_insert_with(|| TrainingJob::new(model_id, TrainingJobStatus::Running))
+            .status = TrainingJobStatus::Completed;
+    }
+
+    pub fn mark_failed(&mut self, model_id: Uuid, reason: String) {
+        self.jobs.entry(model_id)
+            .or_insert_with(|| TrainingJob::new(model_id, TrainingJobStatus::Running))
+            .status = TrainingJobStatus::Failed(reason);
+    }
+
+    fn running(&self) -> impl Iterator<Item = &TrainingJob> {
+        self.jobs.values().filter(|job| job.status == TrainingJobStatus::Running)
+    }
+
+    pub fn summary(&self) -> DashboardSummary {
+        let active: Vec<&TrainingJob> = self.running().collect();
+        let completed_jobs = self.jobs.values().filter(|job| job.status == TrainingJobStatus::Completed).count();
+        let failed_jobs = self.jobs.len() - active.len() - completed_jobs;
+
+        let tracked = active.len() + completed_jobs;
+        let overall_progress = if tracked == 0 {
+            0.0
+        } else {
+            (active.iter().map(|job| job.progress()).sum::<f64>() + completed_jobs as f64) / tracked as f64
+        };
+        let avg_training_loss = if active.is_empty() {
+            0.0
+        } else {
+            active.iter().map(|job| job.training_loss).sum::<f64>() / active.len() as f64
+        };
+
+        // Ties go to the lower model ID so the result does not depend on map order
+        let rates = || active.iter().filter_map(|job| Some((job.progress_rate()?, job.model_id)));
+        let slowest_job = rates().min_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1))).map(|(_, id)| id);
+        let fastest_job = rates().max_by(|a, b| a.0.total_cmp(&b.0).then(b.1.cmp(&a.1))).map(|(_, id)| id);
+
+        DashboardSummary {
+            active_jobs: active.len(),
+            completed_jobs,
+            failed_jobs,
+            overall_progress,
+            slowest_job,
+            fastest_job,
+            avg_training_loss,
+        }
+    }
+
+    /// Running jobs with no progress within `timeout_ns` of `now_ns`, sorted by ID
+    pub fn stalled_jobs(&self, timeout_ns: i64, now_ns: i64) -> Vec<Uuid> {
+        let mut stalled: Vec<Uuid> = self.running()
+            .filter(|job| now_ns.saturating_sub(job.last_update_ns) > timeout_ns)
+            .map(|job| job.model_id)
+            .collect();
+        stalled.sort();
+        stalled
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelTrainingCompletedEvent {
+    pub model_id: Uuid,
+    pub final_metrics: ModelMetrics,
+    pub training_time_ms: u64,
+    pub model_path: String,
+    pub completed_at: i64,
+    #[serde(default = "schema_v1")]
+    pub schema_version: u8,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelDeploymentRequestedEvent {
+    pub model_id: Uuid,
+    pub deployment_config: HashMap<String, serde_json::Value>,
     pub requested_by: String,
     pub timestamp: i64,
 }
@@ -374,10 +1570,15 @@ pub struct StatisticalTestCompletedEvent {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[allow(deprecated)]
 pub struct CorrelationMatrixUpdatedEvent {
     pub matrix_id: Uuid,
     pub signals: Vec<String>,
+    #[deprecated(note = "use `compact`; the full matrix is O(n²) per event")]
     pub correlation_matrix: CorrelationMatrix,
+    /// Upper-triangle form of the matrix
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compact: Option<CompactCorrelationMatrix>,
     pub updated_at: i64,
 }
 
@@ -401,11 +1602,537 @@ pub struct ResearchStateChangedEvent {
     pub timestamp: i64,
 }
 
+impl ResearchStateChangedEvent {
+    /// Check `old_state -> new_state` against the default transition table
+    ///
+    /// Fails if either state is not a `ResearchState` name.
+    pub fn validate(&self) -> Result<()> {
+        self.validate_with(&StateTransitionValidator::default())
+    }
+
+    /// Check `old_state -> new_state` against `validator`
+    pub fn validate_with(&self, validator: &StateTransitionValidator) -> Result<()> {
+        let from: ResearchState = self.old_state.parse()?;
+        let to: ResearchState = self.new_state.parse()?;
+        validator.validate(from, to)?;
+        Ok(())
+    }
+}
+
+/// Lifecycle state of a research component
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ResearchState {
+    Idle,
+    Loading,
+    Running,
+    Paused,
+    Completed,
+    Failed,
+}
+
+impl ResearchState {
+    pub const ALL: [ResearchState; 6] = [
+        ResearchState::Idle,
+        ResearchState::Loading,
+        ResearchState::Running,
+        ResearchState::Paused,
+        ResearchState::Completed,
+        ResearchState::Failed,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ResearchState::Idle => "idle",
+            ResearchState::Loading => "loading",
+            ResearchState::Running => "running",
+            ResearchState::Paused => "paused",
+            ResearchState::Completed => "completed",
+            ResearchState::Failed => "failed",
+        }
+    }
+}
+
+impl std::fmt::Display for ResearchState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Parses state names case-insensitively (`"running"`, `"Running"`)
+impl std::str::FromStr for ResearchState {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        ResearchState::ALL
+            .into_iter()
+            .find(|state| state.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| anyhow!("Unknown research state: {}", s))
+    }
+}
+
+/// Transition not allowed by a `StateTransitionValidator`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("invalid research state transition: {from} -> {to}")]
+pub struct InvalidTransitionError {
+    pub from: ResearchState,
+    pub to: ResearchState,
+}
+
+/// Allowed `ResearchState` transitions
+///
+/// The default table is:
+///
+/// | from      | to                           |
+/// |-----------|------------------------------|
+/// | Idle      | Loading                      |
+/// | Loading   | Running, Failed              |
+/// | Running   | Paused, Completed, Failed    |
+/// | Paused    | Running, Failed              |
+/// | Completed | Idle                         |
+/// | Failed    | Idle                         |
+#[derive(Debug, Clone)]
+pub struct StateTransitionValidator {
+    allowed: HashSet<(ResearchState, ResearchState)>,
+}
+
+impl StateTransitionValidator {
+    /// Validator allowing exactly the `allowed` transitions
+    pub fn custom(allowed: Vec<(ResearchState, ResearchState)>) -> Self {
+        Self { allowed: allowed.into_iter().collect() }
+    }
+
+    pub fn is_allowed(&self, from: ResearchState, to: ResearchState) -> bool {
+        self.allowed.contains(&(from, to))
+    }
+
+    pub fn validate(&self, from: ResearchState, to: ResearchState) -> Result<(), InvalidTransitionError> {
+        if self.is_allowed(from, to) {
+            Ok(())
+        } else {
+            Err(InvalidTransitionError { from, to })
+        }
+    }
+}
+
+impl Default for StateTransitionValidator {
+    fn default() -> Self {
+        use ResearchState::*;
+        Self::custom(vec![
+            (Idle, Loading),
+            (Loading, Running),
+            (Loading, Failed),
+            (Running, Paused),
+            (Running, Completed),
+            (Running, Failed),
+            (Paused, Running),
+            (Paused, Failed),
+            (Completed, Idle),
+            (Failed, Idle),
+        ])
+    }
+}
+
 // ============================================================================
-// Trait Implementation for Event Bus Integration
+// Anomaly Detection Events
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnomalyDetectedEvent {
+    pub anomaly_id: Uuid,
+    pub signal_id: Uuid,
+    pub anomaly_type: AnomalyType,
+    pub severity: AnomalySeverity,
+    pub description: String,
+    pub affected_timestamps: Vec<i64>,
+    pub suggested_action: Option<String>,
+    pub detected_at: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AnomalyType {
+    DataGap,
+    PriceSpike,
+    VolumeSpike,
+    StaleData,
+    NegativeVolume,
+    DuplicateTick,
+    OutOfOrderTimestamp,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum AnomalySeverity {
+    Info,
+    Warning,
+    Critical,
+}
+
+impl AnomalySeverity {
+    /// Bus priority (0 = most urgent)
+    pub fn priority(&self) -> u8 {
+        match self {
+            AnomalySeverity::Critical => 0,
+            AnomalySeverity::Warning => 2,
+            AnomalySeverity::Info => 5,
+        }
+    }
+}
+
+/// Thresholds used by `AnomalyDetector`
+///
+/// A measured anomaly (gap, spike, staleness) is a `Warning` once it exceeds
+/// its threshold and `Critical` once it exceeds `critical_multiplier` times
+/// the threshold.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnomalyThresholds {
+    /// Largest allowed time between ticks of one symbol
+    pub max_gap_ns: i64,
+    /// Largest allowed relative price move between consecutive ticks
+    pub price_spike_pct: f64,
+    /// Largest allowed volume relative to the running average
+    pub volume_spike_ratio: f64,
+    /// Longest time price, bid and ask may stay unchanged
+    pub stale_after_ns: i64,
+    pub critical_multiplier: f64,
+    /// Ticks per symbol before volume spikes are reported
+    pub warmup_ticks: usize,
+}
+
+impl Default for AnomalyThresholds {
+    fn default() -> Self {
+        Self {
+            max_gap_ns: 5_000_000_000,
+            price_spike_pct: 0.05,
+            volume_spike_ratio: 10.0,
+            stale_after_ns: 30_000_000_000,
+            critical_multiplier: 3.0,
+            warmup_ticks: 20,
+        }
+    }
+}
+
+/// Last tick seen for a symbol
+#[derive(Debug, Clone)]
+struct SymbolState {
+    last: RealTimeDataUpdateEvent,
+    /// Timestamp of the last price/quote change
+    changed_at: i64,
+    avg_volume: f64,
+    ticks: usize,
+    stale_reported: bool,
+}
+
+/// Watches `RealTimeDataUpdateEvent` streams for data quality problems
+pub struct AnomalyDetector {
+    signal_id: Uuid,
+    thresholds: AnomalyThresholds,
+    symbols: HashMap<String, SymbolState>,
+}
+
+impl AnomalyDetector {
+    pub fn new(signal_id: Uuid, thresholds: AnomalyThresholds) -> Self {
+        Self {
+            signal_id,
+            thresholds,
+            symbols: HashMap::new(),
+        }
+    }
+
+    pub fn thresholds(&self) -> &AnomalyThresholds {
+        &self.thresholds
+    }
+
+    /// Check one update against the previous tick of its symbol
+    ///
+    /// Out-of-order and duplicate ticks are reported and otherwise ignored,
+    /// so they do not disturb the symbol's state.
+    pub fn observe(&mut self, update: &RealTimeDataUpdateEvent) -> Vec<AnomalyDetectedEvent> {
+        let mut anomalies = Vec::new();
+        let ts = update.timestamp;
+
+        if update.volume < 0.0 {
+            anomalies.push(self.anomaly(
+                AnomalyType::NegativeVolume,
+                AnomalySeverity::Critical,
+                format!("{}: negative volume {}", update.symbol, update.volume),
+                vec![ts],
+                Some("Drop the tick and check the feed handler"),
+                ts,
+            ));
+        }
+
+        let Some(state) = self.symbols.get(&update.symbol) else {
+            self.symbols.insert(update.symbol.clone(), SymbolState {
+                last: update.clone(),
+                changed_at: ts,
+                avg_volume: update.volume.max(0.0),
+                ticks: 1,
+                stale_reported: false,
+            });
+            return anomalies;
+        };
+        let last = &state.last;
+
+        if ts < last.timestamp {
+            anomalies.push(self.anomaly(
+                AnomalyType::OutOfOrderTimestamp,
+                AnomalySeverity::Warning,
+                format!("{}: tick at {} after {}", update.symbol, ts, last.timestamp),
+                vec![last.timestamp, ts],
+                Some("Re-sort the stream before analysis"),
+                ts,
+            ));
+            return anomalies;
+        }
+
+        if ts == last.timestamp && update.price == last.price && update.volume == last.volume {
+            anomalies.push(self.anomaly(
+                AnomalyType::DuplicateTick,
+                AnomalySeverity::Info,
+                format!("{}: duplicate tick at {}", update.symbol, ts),
+                vec![ts],
+                None,
+                ts,
+            ));
+            return anomalies;
+        }
+
+        let gap = ts - last.timestamp;
+        if gap > self.thresholds.max_gap_ns {
+            let severity = self.severity(gap as f64 / self.thresholds.max_gap_ns as f64);
+            anomalies.push(self.anomaly(
+                AnomalyType::DataGap,
+                severity,
+                format!("{}: no data for {} ms", update.symbol, gap / 1_000_000),
+                vec![last.timestamp, ts],
+                Some("Backfill the missing interval"),
+                ts,
+            ));
+        }
+
+        if last.price > 0.0 {
+            let change = (update.price - last.price).abs() / last.price;
+            if change > self.thresholds.price_spike_pct {
+                let severity = self.severity(change / self.thresholds.price_spike_pct);
+                anomalies.push(self.anomaly(
+                    AnomalyType::PriceSpike,
+                    severity,
+                    format!("{}: price moved {:.2}% to {}", update.symbol, change * 100.0, update.price),
+                    vec![last.timestamp, ts],
+                    Some("Verify against a second source"),
+                    ts,
+                ));
+            }
+        }
+
+        if state.ticks >= self.thresholds.warmup_ticks && state.avg_volume > 0.0 {
+            let ratio = update.volume / state.avg_volume;
+            if ratio > self.thresholds.volume_spike_ratio {
+                let severity = self.severity(ratio / self.thresholds.volume_spike_ratio);
+                anomalies.push(self.anomaly(
+                    AnomalyType::VolumeSpike,
+                    severity,
+                    format!("{}: volume {} is {:.1}x average", update.symbol, update.volume, ratio),
+                    vec![ts],
+                    None,
+                    ts,
+                ));
+            }
+        }
+
+        // Report staleness once per unchanged stretch
+        let unchanged = update.price == last.price && update.bid == last.bid && update.ask == last.ask;
+        let stale_for = ts - state.changed_at;
+        let stale = unchanged && !state.stale_reported && stale_for > self.thresholds.stale_after_ns;
+        if stale {
+            let severity = self.severity(stale_for as f64 / self.thresholds.stale_after_ns as f64);
+            anomalies.push(self.anomaly(
+                AnomalyType::StaleData,
+                severity,
+                format!("{}: quote unchanged for {} ms", update.symbol, stale_for / 1_000_000),
+                vec![state.changed_at, ts],
+                Some("Check that the feed is still live"),
+                ts,
+            ));
+        }
+
+        let stale_reported = unchanged && (state.stale_reported || stale);
+        let state = self.symbols.get_mut(&update.symbol).expect("symbol state exists");
+        if !unchanged {
+            state.changed_at = ts;
+        }
+        state.stale_reported = stale_reported;
+        if update.volume >= 0.0 {
+            state.ticks += 1;
+            state.avg_volume += (update.volume - state.avg_volume) / state.ticks as f64;
+        }
+        state.last = update.clone();
+
+        anomalies
+    }
+
+    /// Forget all per-symbol state
+    pub fn reset(&mut self) {
+        self.symbols.clear();
+    }
+
+    fn severity(&self, excess: f64) -> AnomalySeverity {
+        if excess > self.thresholds.critical_multiplier {
+            AnomalySeverity::Critical
+        } else {
+            AnomalySeverity::Warning
+        }
+    }
+
+    fn anomaly(
+        &self,
+        anomaly_type: AnomalyType,
+        severity: AnomalySeverity,
+        description: String,
+        affected_timestamps: Vec<i64>,
+        suggested_action: Option<&str>,
+        detected_at: i64,
+    ) -> AnomalyDetectedEvent {
+        AnomalyDetectedEvent {
+            anomaly_id: Uuid::new_v4(),
+            signal_id: self.signal_id,
+            anomaly_type,
+            severity,
+            description,
+            affected_timestamps,
+            suggested_action: suggested_action.map(str::to_string),
+            detected_at,
+        }
+    }
+}
+
+// ============================================================================
+// Custom Analysis Executors
 // ============================================================================
 
-// ResearchEvent implements MarketEvent which extends Event, so no separate Event impl needed
+/// Future returned by `AnalysisExecutor::execute`
+pub type AnalysisFuture = Pin<Box<dyn Future<Output = Result<AnalysisResults>> + Send>>;
+
+/// Runs `AnalysisType::Custom` requests
+pub trait AnalysisExecutor: Send + Sync {
+    fn execute(&self, req: &AnalysisRequestedEvent) -> AnalysisFuture;
+}
+
+/// Executors for custom analyses, keyed by executor name
+#[derive(Default)]
+pub struct CustomAnalysisRegistry {
+    executors: HashMap<String, Box<dyn AnalysisExecutor>>,
+}
+
+impl CustomAnalysisRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register an executor, replacing any previous one with the same name
+    pub fn register(&mut self, name: &str, executor: Box<dyn AnalysisExecutor>) {
+        self.executors.insert(name.to_string(), executor);
+    }
+
+    /// Check if an executor is registered under `name`
+    pub fn contains(&self, name: &str) -> bool {
+        self.executors.contains_key(name)
+    }
+
+    /// Run a custom analysis request and wrap the results in a completion event
+    ///
+    /// Fails if the request is not `AnalysisType::Custom`, names an unknown
+    /// executor, or the executor itself fails.
+    #[allow(deprecated)]
+    pub async fn run(&self, req: &AnalysisRequestedEvent) -> Result<AnalysisCompletedEvent> {
+        let executor_name = match &req.analysis_type {
+            AnalysisType::Custom { executor, .. } => executor,
+            other => bail!("Not a custom analysis: {:?}", other),
+        };
+        let executor = self.executors.get(executor_name)
+            .ok_or_else(|| anyhow!("Unknown analysis executor: {}", executor_name))?;
+
+        let started = Instant::now();
+        let results = executor.execute(req).await?;
+
+        Ok(AnalysisCompletedEvent {
+            analysis_id: req.analysis_id,
+            signal_id: req.signal_id,
+            dataset_id: req.dataset_id,
+            results,
+            compressed_results: None,
+            completed_at: chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0),
+            duration_ms: started.elapsed().as_millis() as u64,
+            schema_version: 1,
+        })
+    }
+}
+
+/// Executor that runs an external process (e.g. a Python script)
+///
+/// The request's custom `config` is written to stdin as JSON and the
+/// process must print `AnalysisResults` JSON to stdout.
+pub struct ShellExecutor {
+    program: String,
+    args: Vec<String>,
+}
+
+impl ShellExecutor {
+    pub fn new(program: &str, args: &[&str]) -> Self {
+        Self {
+            program: program.to_string(),
+            args: args.iter().map(|a| a.to_string()).collect(),
+        }
+    }
+}
+
+impl AnalysisExecutor for ShellExecutor {
+    fn execute(&self, req: &AnalysisRequestedEvent) -> AnalysisFuture {
+        let program = self.program.clone();
+        let args = self.args.clone();
+        let config = match &req.analysis_type {
+            AnalysisType::Custom { config, .. } => config.clone(),
+            _ => serde_json::Value::Null,
+        };
+
+        Box::pin(async move {
+            let mut child = tokio::process::Command::new(&program)
+                .args(&args)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .kill_on_drop(true)
+                .spawn()
+                .map_err(|e| anyhow!("Failed to start {}: {}", program, e))?;
+
+            // Feed stdin while collecting output so a child that fills its
+            // stdout pipe before reading all of its input cannot deadlock us
+            let mut stdin = child.stdin.take().ok_or_else(|| anyhow!("No stdin for {}", program))?;
+            let input = serde_json::to_vec(&config)?;
+            let writer = tokio::spawn(async move { stdin.write_all(&input).await });
+
+            let output = child.wait_with_output().await?;
+            match writer.await? {
+                // The child may exit without reading its input
+                Err(e) if e.kind() != std::io::ErrorKind::BrokenPipe => {
+                    bail!("Failed to write config to {}: {}", program, e)
+                }
+                _ => {}
+            }
+            if !output.status.success() {
+                bail!(
+                    "{} exited with {}: {}",
+                    program, output.status, String::from_utf8_lossy(&output.stderr).trim()
+                );
+            }
+            serde_json::from_slice(&output.stdout)
+                .map_err(|e| anyhow!("Invalid results from {}: {}", program, e))
+        })
+    }
+}
+
+// ============================================================================
+// Trait Implementation for Event Bus Integration
+// ============================================================================
 
 impl crate::MarketEvent for ResearchEvent {
     fn event_type(&self) -> crate::EventType {
@@ -425,6 +2152,7 @@ impl crate::MarketEvent for ResearchEvent {
             ResearchEvent::AnalysisProgress(e) => e.timestamp,
             ResearchEvent::AnalysisCompleted(e) => e.completed_at,
             ResearchEvent::AnalysisFailed(e) => e.failed_at,
+            ResearchEvent::BacktestCompleted(e) => e.end_date,
             ResearchEvent::FeatureExtracted(e) => e.timestamp,
             ResearchEvent::FeaturePipelineUpdated(e) => e.timestamp,
             ResearchEvent::ModelTrainingStarted(e) => e.started_at,
@@ -438,6 +2166,7 @@ impl crate::MarketEvent for ResearchEvent {
             ResearchEvent::CorrelationMatrixUpdated(e) => e.updated_at,
             ResearchEvent::ResearchConfigUpdated(e) => e.timestamp,
             ResearchEvent::ResearchStateChanged(e) => e.timestamp,
+            ResearchEvent::AnomalyDetected(e) => e.detected_at,
         }
     }
 
@@ -447,15 +2176,1290 @@ impl crate::MarketEvent for ResearchEvent {
             _ => None,
         }
     }
-    
-    fn priority(&self) -> u8 {
-        match self {
-            ResearchEvent::RealTimeDataUpdate(_) => 1, // Highest priority for real-time
-            ResearchEvent::AnalysisStarted(_) | ResearchEvent::AnalysisProgress(_) => 2,
-            ResearchEvent::SignalCreated(_) | ResearchEvent::SignalUpdated(_) => 3,
-            ResearchEvent::AnalysisCompleted(_) | ResearchEvent::AnalysisFailed(_) => 4,
-            _ => 5, // Default priority
+}
+
+// ============================================================================
+// Topic Routing
+// ============================================================================
+
+/// Research event streams keyed by signal or analysis
+///
+/// Each subscriber gets its own unbounded channel, so every subscriber to a
+/// topic sees every event published to it. `subscribe_all` receivers see
+/// events from every topic. Cloning yields a handle to the same topics.
+#[derive(Clone, Default)]
+pub struct TypedResearchBus {
+    signals: Arc<DashMap<Uuid, Vec<flume::Sender<ResearchEvent>>>>,
+    analyses: Arc<DashMap<Uuid, Vec<flume::Sender<ResearchEvent>>>>,
+    all: Arc<RwLock<Vec<flume::Sender<ResearchEvent>>>>,
+}
+
+impl TypedResearchBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Publish to subscribers of `signal_id` and catch-all subscribers
+    ///
+    /// Returns the number of receivers reached.
+    pub fn publish_for_signal(&self, signal_id: Uuid, event: ResearchEvent) -> usize {
+        Self::publish_to(&self.signals, signal_id, event, &self.all)
+    }
+
+    /// Publish to subscribers of `analysis_id` and catch-all subscribers
+    ///
+    /// Returns the number of receivers reached.
+    pub fn publish_for_analysis(&self, analysis_id: Uuid, event: ResearchEvent) -> usize {
+        Self::publish_to(&self.analyses, analysis_id, event, &self.all)
+    }
+
+    /// Receive events published for `signal_id`
+    pub fn subscribe_for_signal(&self, signal_id: Uuid) -> flume::Receiver<ResearchEvent> {
+        let (tx, rx) = flume::unbounded();
+        self.signals.entry(signal_id).or_default().push(tx);
+        rx
+    }
+
+    /// Receive events published for `analysis_id`
+    pub fn subscribe_for_analysis(&self, analysis_id: Uuid) -> flume::Receiver<ResearchEvent> {
+        let (tx, rx) = flume::unbounded();
+        self.analyses.entry(analysis_id).or_default().push(tx);
+        rx
+    }
+
+    /// Receive events published to any signal or analysis
+    pub fn subscribe_all(&self) -> flume::Receiver<ResearchEvent> {
+        let (tx, rx) = flume::unbounded();
+        self.all.write().unwrap().push(tx);
+        rx
+    }
+
+    /// Drop the topic for `signal_id`, disconnecting its subscribers
+    ///
+    /// Returns false if the signal had no subscribers.
+    pub fn remove_signal_channel(&self, signal_id: Uuid) -> bool {
+        self.signals.remove(&signal_id).is_some()
+    }
+
+    /// Drop the topic for `analysis_id`, disconnecting its subscribers
+    ///
+    /// Returns false if the analysis had no subscribers.
+    pub fn remove_analysis_channel(&self, analysis_id: Uuid) -> bool {
+        self.analyses.remove(&analysis_id).is_some()
+    }
+
+    /// Number of signals with subscribers
+    pub fn signal_channel_count(&self) -> usize {
+        self.signals.len()
+    }
+
+    fn publish_to(
+        topics: &DashMap<Uuid, Vec<flume::Sender<ResearchEvent>>>,
+        id: Uuid,
+        event: ResearchEvent,
+        all: &RwLock<Vec<flume::Sender<ResearchEvent>>>,
+    ) -> usize {
+        let mut delivered = 0;
+        if let Some(mut senders) = topics.get_mut(&id) {
+            senders.retain(|tx| tx.send(event.clone()).is_ok());
+            delivered += senders.len();
         }
+        // Keep topics whose subscribers are all gone from accumulating
+        topics.remove_if(&id, |_, senders| senders.is_empty());
+
+        let mut all = all.write().unwrap();
+        all.retain(|tx| tx.send(event.clone()).is_ok());
+        delivered + all.len()
+    }
+}
+
+// ============================================================================
+// Schema Versioning
+// ============================================================================
+
+/// Version assumed for events recorded before `schema_version` existed
+fn schema_v1() -> u8 {
+    1
+}
+
+/// `SignalCreatedEvent` as recorded before tags were added
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct V1SignalCreatedEvent {
+    pub signal_id: Uuid,
+    pub name: String,
+    pub formula: String,
+    pub description: Option<String>,
+    pub parameters: HashMap<String, serde_json::Value>,
+    pub created_by: String,
+    pub timestamp: i64,
+}
+
+/// Current `SignalCreatedEvent` layout
+pub type V2SignalCreatedEvent = SignalCreatedEvent;
+
+/// Conversion of an event from one schema version to the next
+pub trait EventVersionMigrator<From, To> {
+    fn migrate(old: From) -> To;
+}
+
+/// V1 -> V2: adds an empty `tags` list
+pub struct SignalCreatedV1ToV2;
+
+impl EventVersionMigrator<V1SignalCreatedEvent, V2SignalCreatedEvent> for SignalCreatedV1ToV2 {
+    fn migrate(old: V1SignalCreatedEvent) -> V2SignalCreatedEvent {
+        SignalCreatedEvent {
+            signal_id: old.signal_id,
+            name: old.name,
+            formula: old.formula,
+            description: old.description,
+            parameters: old.parameters,
+            created_by: old.created_by,
+            timestamp: old.timestamp,
+            tags: Vec::new(),
+            schema_version: 2,
+        }
+    }
+}
+
+/// Event carrying a `schema_version` field
+pub trait VersionedEvent: DeserializeOwned {
+    /// Version written by this build
+    const CURRENT_VERSION: u8;
+
+    /// Deserialize `value` written at an older `version`, migrating it to
+    /// `CURRENT_VERSION`
+    fn migrate_from(version: u8, _value: serde_json::Value) -> Result<Self> {
+        bail!(
+            "No migration from schema version {} to {}",
+            version,
+            Self::CURRENT_VERSION
+        )
+    }
+}
+
+impl VersionedEvent for SignalCreatedEvent {
+    const CURRENT_VERSION: u8 = 2;
+
+    fn migrate_from(version: u8, value: serde_json::Value) -> Result<Self> {
+        match version {
+            1 => Ok(SignalCreatedV1ToV2::migrate(serde_json::from_value(value)?)),
+            _ => bail!("No migration from schema version {} to {}", version, Self::CURRENT_VERSION),
+        }
+    }
+}
+
+impl VersionedEvent for AnalysisCompletedEvent {
+    const CURRENT_VERSION: u8 = 1;
+}
+
+impl VersionedEvent for ModelTrainingCompletedEvent {
+    const CURRENT_VERSION: u8 = 1;
+}
+
+impl VersionedEvent for BacktestCompletedEvent {
+    const CURRENT_VERSION: u8 = 1;
+}
+
+/// Deserializes research events of any known schema version
+///
+/// A missing `schema_version` is read as version 1. Older versions are
+/// migrated step by step to the current layout; newer ones are rejected.
+pub struct VersionedDeserializer;
+
+impl VersionedDeserializer {
+    pub fn deserialize<T: VersionedEvent>(json: &str) -> Result<T> {
+        let value: serde_json::Value = serde_json::from_str(json)?;
+        let version = match value.get("schema_version") {
+            Some(v) => v
+                .as_u64()
+                .and_then(|v| u8::try_from(v).ok())
+                .ok_or_else(|| anyhow!("Invalid schema_version: {}", v))?,
+            None => schema_v1(),
+        };
+
+        match version.cmp(&T::CURRENT_VERSION) {
+            std::cmp::Ordering::Equal => Ok(serde_json::from_value(value)?),
+            std::cmp::Ordering::Less => T::migrate_from(version, value),
+            std::cmp::Ordering::Greater => bail!(
+                "Schema version {} is newer than supported version {}",
+                version,
+                T::CURRENT_VERSION
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_result(weights: &[(&str, f64)]) -> PortfolioOptimizationResult {
+        PortfolioOptimizationResult {
+            weights: weights.iter().map(|(k, v)| (k.to_string(), *v)).collect(),
+            expected_return: 0.08,
+            expected_volatility: 0.12,
+            sharpe: 0.67,
+            efficient_frontier: vec![(0.10, 0.05), (0.12, 0.08), (0.15, 0.10)],
+        }
+    }
+
+    #[test]
+    fn test_portfolio_result_feasible() {
+        let result = make_result(&[("ES", 0.5), ("NQ", 0.3), ("CL", 0.2)]);
+        assert!(result.is_feasible());
+    }
+
+    #[test]
+    fn test_portfolio_result_infeasible() {
+        let under = make_result(&[("ES", 0.5), ("NQ", 0.3)]);
+        assert!(!under.is_feasible());
+
+        let over = make_result(&[("ES", 0.7), ("NQ", 0.5)]);
+        assert!(!over.is_feasible());
+
+        let empty = make_result(&[]);
+        assert!(!empty.is_feasible());
+    }
+
+    #[test]
+    fn test_portfolio_result_within_epsilon() {
+        let result = make_result(&[("ES", 0.5), ("NQ", 0.5 + 1e-9)]);
+        assert!(result.is_feasible());
+    }
+
+    #[test]
+    fn test_portfolio_optimization_serialization() {
+        let results = AnalysisResults {
+            ic_results: None,
+            statistical_tests: None,
+            correlation_matrix: None,
+            feature_importance: None,
+            model_metrics: None,
+            portfolio_optimization: Some(make_result(&[("ES", 0.6), ("NQ", 0.4)])),
+        };
+
+        let json = serde_json::to_string(&results).unwrap();
+        let decoded: AnalysisResults = serde_json::from_str(&json).unwrap();
+        let portfolio = decoded.portfolio_optimization.unwrap();
+        assert!(portfolio.is_feasible());
+        assert_eq!(portfolio.efficient_frontier.len(), 3);
+
+        let config = MeanVarianceConfig {
+            risk_aversion: 3.0,
+            min_weight: 0.0,
+            max_weight: 0.4,
+            target_return: None,
+        };
+        let json = serde_json::to_string(&config).unwrap();
+        let decoded: MeanVarianceConfig = serde_json::from_str(&json).unwrap();
+        assert!((decoded.max_weight - 0.4).abs() < 1e-12);
+        assert!(decoded.target_return.is_none());
+
+        let analysis_type: AnalysisType =
+            serde_json::from_str(&serde_json::to_string(&AnalysisType::PortfolioOptimization).unwrap()).unwrap();
+        assert!(matches!(analysis_type, AnalysisType::PortfolioOptimization));
+    }
+
+    fn full_matrix(n: usize) -> CorrelationMatrix {
+        // Symmetric with unit diagonal; off-diagonal values unique per pair
+        let matrix = (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|j| if i == j { 1.0 } else { 1.0 / (1.0 + (i * j + i + j) as f64).sqrt() })
+                    .collect()
+            })
+            .collect();
+        CorrelationMatrix {
+            signals: (0..n).map(|i| format!("sig_{}", i)).collect(),
+            matrix,
+            method: "pearson".to_string(),
+            p_values: None,
+        }
+    }
+
+    #[test]
+    fn test_compact_correlation_round_trip() {
+        let full = full_matrix(50);
+        let compact = CompactCorrelationMatrix::from_full(&full);
+        assert_eq!(compact.values.len(), 50 * 49 / 2);
+
+        for i in 0..50 {
+            for j in 0..50 {
+                assert!((compact.get(i, j) - full.matrix[i][j]).abs() < 1e-10);
+                assert_eq!(compact.get(i, j), compact.get(j, i));
+            }
+        }
+
+        let restored = compact.to_full();
+        assert_eq!(restored.signals, full.signals);
+        assert_eq!(restored.method, "pearson");
+        for (restored_row, row) in restored.matrix.iter().zip(&full.matrix) {
+            for (a, b) in restored_row.iter().zip(row) {
+                assert!((a - b).abs() < 1e-10);
+            }
+        }
+    }
+
+    fn block_matrix() -> CorrelationMatrix {
+        // Signals 0 and 2 move together, as do 1 and 3
+        CorrelationMatrix {
+            signals: vec!["a".to_string(), "b".to_string(), "c".to_string(), "d".to_string()],
+            matrix: vec![
+                vec![1.0, 0.1, 0.9, -0.2],
+                vec![0.1, 1.0, 0.05, -0.8],
+                vec![0.9, 0.05, 1.0, 0.3],
+                vec![-0.2, -0.8, 0.3, 1.0],
+            ],
+            method: "pearson".to_string(),
+            p_values: Some(vec![
+                vec![0.0, 0.4, 0.001, 0.05],
+                vec![0.4, 0.0, 0.7, 0.01],
+                vec![0.001, 0.7, 0.0, f64::NAN],
+                vec![0.05, 0.01, f64::NAN, 0.0],
+            ]),
+        }
+    }
+
+    #[test]
+    fn test_correlation_filter_significant() {
+        let matrix = block_matrix();
+        let filtered = matrix.filter_significant(0.05);
+        assert_eq!(
+            filtered.matrix,
+            vec![
+                vec![1.0, 0.0, 0.9, -0.2],
+                vec![0.0, 1.0, 0.0, -0.8],
+                vec![0.9, 0.0, 1.0, 0.0],
+                vec![-0.2, -0.8, 0.0, 1.0],
+            ]
+        );
+        // The diagonal is kept even when alpha is below every p-value
+        let strict = matrix.filter_significant(-1.0);
+        for (i, row) in strict.matrix.iter().enumerate() {
+            for (j, &value) in row.iter().enumerate() {
+                assert_eq!(value, if i == j { 1.0 } else { 0.0 });
+            }
+        }
+
+        let unknown = CorrelationMatrix { p_values: None, ..matrix };
+        assert_eq!(unknown.filter_significant(0.05).matrix, unknown.matrix);
+    }
+
+    #[test]
+    fn test_correlation_top_k_pairs() {
+        let pairs = block_matrix().top_k_pairs(3);
+        let names: Vec<(&str, &str)> = pairs.iter().map(|p| (p.signal_a.as_str(), p.signal_b.as_str())).collect();
+        assert_eq!(names, vec![("a", "c"), ("b", "d"), ("c", "d")]);
+        assert_eq!(pairs[1].correlation, -0.8);
+        assert_eq!(pairs[1].p_value, Some(0.01));
+        assert!(pairs[2].p_value.unwrap().is_nan());
+
+        assert_eq!(block_matrix().top_k_pairs(100).len(), 6);
+        assert!(block_matrix().top_k_pairs(0).is_empty());
+    }
+
+    #[test]
+    fn test_correlation_clustermap_order() {
+        assert_eq!(block_matrix().clustermap_order(), vec![0, 2, 3, 1]);
+
+        // Stable across calls and a permutation of the signals
+        let full = full_matrix(30);
+        let order = full.clustermap_order();
+        assert_eq!(order, full.clustermap_order());
+        let mut sorted = order.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..30).collect::<Vec<_>>());
+
+        // Without structure the original order is kept
+        let mut flat = full_matrix(5);
+        for (i, row) in flat.matrix.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value = if i == j { 1.0 } else { 0.0 };
+            }
+        }
+        assert_eq!(flat.clustermap_order(), vec![0, 1, 2, 3, 4]);
+        assert!(full_matrix(0).clustermap_order().is_empty());
+    }
+
+    #[test]
+    fn test_compact_correlation_small() {
+        let empty = CompactCorrelationMatrix::from_full(&full_matrix(0));
+        assert!(empty.is_empty());
+        assert!(empty.to_full().matrix.is_empty());
+
+        let single = CompactCorrelationMatrix::from_full(&full_matrix(1));
+        assert!(single.values.is_empty());
+        assert_eq!(single.get(0, 0), 1.0);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_correlation_event_compact_field() {
+        let full = full_matrix(3);
+        let event = CorrelationMatrixUpdatedEvent {
+            matrix_id: Uuid::new_v4(),
+            signals: full.signals.clone(),
+            compact: Some(CompactCorrelationMatrix::from_full(&full)),
+            correlation_matrix: full,
+            updated_at: 0,
+        };
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["compact"]["values"].as_array().unwrap().len(), 3);
+
+        // Payloads without the compact field still deserialize
+        let mut legacy = json.clone();
+        legacy.as_object_mut().unwrap().remove("compact");
+        let parsed: CorrelationMatrixUpdatedEvent = serde_json::from_value(legacy).unwrap();
+        assert!(parsed.compact.is_none());
+    }
+
+    #[test]
+    fn test_v1_signal_created_migrates_to_v2() {
+        let v1 = r#"{
+            "signal_id": "00000000-0000-0000-0000-000000000001",
+            "name": "momentum",
+            "formula": "close / delay(close, 20) - 1",
+            "description": null,
+            "parameters": {"lookback": 20},
+            "created_by": "research",
+            "timestamp": 1000
+        }"#;
+
+        // The current layout requires tags
+        assert!(serde_json::from_str::<SignalCreatedEvent>(v1).is_err());
+
+        let event: SignalCreatedEvent = VersionedDeserializer::deserialize(v1).unwrap();
+        assert_eq!(event.schema_version, 2);
+        assert!(event.tags.is_empty());
+        assert_eq!(event.name, "momentum");
+        assert_eq!(event.parameters["lookback"], 20);
+
+        // Current version passes through unchanged
+        let mut current = event.clone();
+        current.tags.push("trend".to_string());
+        let json = serde_json::to_string(&current).unwrap();
+        let parsed: SignalCreatedEvent = VersionedDeserializer::deserialize(&json).unwrap();
+        assert_eq!(parsed.tags, vec!["trend".to_string()]);
+
+        let future = json.replace("\"schema_version\":2", "\"schema_version\":3");
+        assert!(VersionedDeserializer::deserialize::<SignalCreatedEvent>(&future).is_err());
+    }
+
+    #[test]
+    fn test_unversioned_backtest_reads_as_v1() {
+        let mut json = serde_json::to_value(make_backtest()).unwrap();
+        json.as_object_mut().unwrap().remove("schema_version");
+        let event: BacktestCompletedEvent = VersionedDeserializer::deserialize(&json.to_string()).unwrap();
+        assert_eq!(event.schema_version, 1);
+        assert_eq!(event.equity_curve.len(), 4);
+    }
+
+    #[test]
+    fn test_research_topics_are_isolated() {
+        let bus = TypedResearchBus::new();
+        let (signal_a, signal_b) = (Uuid::from_u128(1), Uuid::from_u128(2));
+        let analysis = Uuid::from_u128(3);
+
+        let rx_a = bus.subscribe_for_signal(signal_a);
+        let rx_a2 = bus.subscribe_for_signal(signal_a);
+        let rx_b = bus.subscribe_for_signal(signal_b);
+        let rx_analysis = bus.subscribe_for_analysis(analysis);
+        let rx_all = bus.subscribe_all();
+
+        let backtest = |signal_id| ResearchEvent::BacktestCompleted(BacktestCompletedEvent::new(Uuid::new_v4(), signal_id, 0, 1));
+        assert_eq!(bus.publish_for_signal(signal_a, backtest(signal_a)), 3);
+        assert_eq!(bus.publish_for_signal(signal_b, backtest(signal_b)), 2);
+        assert_eq!(bus.publish_for_analysis(analysis, backtest(signal_b)), 2);
+
+        let signal_of = |event: ResearchEvent| match event {
+            ResearchEvent::BacktestCompleted(e) => e.signal_id,
+            other => panic!("unexpected event {:?}", other),
+        };
+        // Every subscriber to a topic gets its events, and only those
+        for rx in [&rx_a, &rx_a2] {
+            assert_eq!(signal_of(rx.try_recv().unwrap()), signal_a);
+            assert!(rx.try_recv().is_err());
+        }
+        assert_eq!(signal_of(rx_b.try_recv().unwrap()), signal_b);
+        assert!(rx_b.try_recv().is_err());
+        assert!(rx_analysis.try_recv().is_ok());
+        assert!(rx_analysis.try_recv().is_err());
+        assert_eq!(rx_all.len(), 3);
+
+        // Removing a topic disconnects its subscribers
+        assert!(bus.remove_signal_channel(signal_a));
+        assert!(!bus.remove_signal_channel(signal_a));
+        assert!(rx_a.recv().is_err());
+        assert_eq!(bus.publish_for_signal(signal_a, backtest(signal_a)), 1);
+
+        // Topics whose receivers were dropped are pruned on publish
+        drop(rx_b);
+        assert_eq!(bus.publish_for_signal(signal_b, backtest(signal_b)), 1);
+        assert_eq!(bus.signal_channel_count(), 0);
+    }
+
+    fn make_backtest() -> BacktestCompletedEvent {
+        let curve = vec![(1, 100.0), (2, 110.0), (3, 99.0), (4, 121.0)];
+        BacktestCompletedEvent::new(Uuid::new_v4(), Uuid::new_v4(), 1, 4)
+            .with_equity_curve(curve, 252.0)
+            .with_trades(&[10.0, -11.0, 22.0, 0.0])
+            .with_benchmark(0.05, 0.01, 0.9)
+    }
+
+    #[test]
+    fn test_backtest_metrics() {
+        let backtest = make_backtest();
+        assert!((backtest.total_return - 0.21).abs() < 1e-12);
+        assert!((backtest.max_drawdown - 0.1).abs() < 1e-12);
+        assert!(backtest.sharpe_ratio > 0.0);
+        assert!(backtest.sortino_ratio > backtest.sharpe_ratio);
+        assert!((backtest.calmar_ratio - backtest.annualized_return / 0.1).abs() < 1e-9);
+
+        assert_eq!(backtest.num_trades, 4);
+        assert_eq!(backtest.win_rate, 0.5);
+        assert_eq!(backtest.avg_win, 16.0);
+        assert!((backtest.avg_loss + 5.5).abs() < 1e-12);
+        assert!((backtest.profit_factor - 32.0 / 11.0).abs() < 1e-12);
+
+        // Degenerate inputs leave ratios at zero
+        let flat = BacktestCompletedEvent::new(Uuid::nil(), Uuid::nil(), 0, 0)
+            .with_equity_curve(vec![(0, 100.0), (1, 100.0)], 252.0)
+            .with_trades(&[]);
+        assert_eq!(flat.sharpe_ratio, 0.0);
+        assert_eq!(flat.calmar_ratio, 0.0);
+        assert_eq!(flat.profit_factor, 0.0);
+    }
+
+    #[test]
+    fn test_backtest_completed_serialization() {
+        use crate::{Event, MarketEvent};
+
+        let event = ResearchEvent::BacktestCompleted(make_backtest());
+        assert_eq!(Event::event_type(&event), "backtest_completed");
+        assert_eq!(event.priority(), 4);
+        assert_eq!(MarketEvent::timestamp(&event), 4);
+
+        let json = serde_json::to_string(&event).unwrap();
+        let decoded: ResearchEvent = serde_json::from_str(&json).unwrap();
+        let ResearchEvent::BacktestCompleted(decoded) = decoded else {
+            panic!("expected BacktestCompleted");
+        };
+        assert_eq!(decoded.equity_curve.len(), 4);
+        assert_eq!(decoded.equity_curve[3], (4, 121.0));
+        assert_eq!(decoded.beta, Some(0.9));
+
+        let mut json = serde_json::to_value(&decoded).unwrap();
+        json["benchmark_return"] = serde_json::Value::Null;
+        let decoded: BacktestCompletedEvent = serde_json::from_value(json).unwrap();
+        assert!(decoded.benchmark_return.is_none());
+    }
+
+    struct MockExecutor;
+
+    impl AnalysisExecutor for MockExecutor {
+        fn execute(&self, req: &AnalysisRequestedEvent) -> AnalysisFuture {
+            let weight = match &req.analysis_type {
+                AnalysisType::Custom { config, .. } => config["weight"].as_f64().unwrap_or(0.0),
+                _ => 0.0,
+            };
+            Box::pin(async move {
+                Ok(AnalysisResults {
+                    ic_results: None,
+                    statistical_tests: None,
+                    correlation_matrix: None,
+                    feature_importance: None,
+                    model_metrics: None,
+                    portfolio_optimization: Some(make_result(&[("ES", weight), ("NQ", 1.0 - weight)])),
+                })
+            })
+        }
+    }
+
+    fn custom_request(executor: &str, config: serde_json::Value) -> AnalysisRequestedEvent {
+        AnalysisRequestedEvent {
+            analysis_id: Uuid::new_v4(),
+            signal_id: Uuid::new_v4(),
+            dataset_id: Uuid::new_v4(),
+            analysis_type: AnalysisType::Custom {
+                name: "my_analyzer".to_string(),
+                executor: executor.to_string(),
+                config,
+            },
+            config: HashMap::new(),
+            requested_by: "test".to_string(),
+            timestamp: 0,
+        }
+    }
+
+    #[cfg_attr(feature = "compress", allow(deprecated))]
+    #[tokio::test]
+    async fn test_custom_analysis_mock_executor() {
+        let mut registry = CustomAnalysisRegistry::new();
+        registry.register("mock", Box::new(MockExecutor));
+        assert!(registry.contains("mock"));
+
+        let req = custom_request("mock", serde_json::json!({ "weight": 0.25 }));
+        let completed = registry.run(&req).await.unwrap();
+        assert_eq!(completed.analysis_id, req.analysis_id);
+        assert_eq!(completed.signal_id, req.signal_id);
+        let portfolio = completed.results.portfolio_optimization.unwrap();
+        assert!(portfolio.is_feasible());
+        assert_eq!(portfolio.weights["ES"], 0.25);
+
+        assert!(registry.run(&custom_request("missing", serde_json::Value::Null)).await.is_err());
+        let mut builtin = req.clone();
+        builtin.analysis_type = AnalysisType::Backtest;
+        assert!(registry.run(&builtin).await.is_err());
+
+        // The custom variant round-trips through serde
+        let json = serde_json::to_string(&req.analysis_type).unwrap();
+        let decoded: AnalysisType = serde_json::from_str(&json).unwrap();
+        assert!(matches!(decoded, AnalysisType::Custom { ref executor, .. } if executor == "mock"));
+    }
+
+    #[cfg(unix)]
+    #[cfg_attr(feature = "compress", allow(deprecated))]
+    #[tokio::test]
+    async fn test_shell_executor() {
+        let mut registry = CustomAnalysisRegistry::new();
+        // `cat` echoes the config back, which must parse as AnalysisResults
+        registry.register("cat", Box::new(ShellExecutor::new("cat", &[])));
+        registry.register("false", Box::new(ShellExecutor::new("false", &[])));
+
+        let config = serde_json::json!({ "model_metrics": { "accuracy": 0.9 } });
+        let completed = registry.run(&custom_request("cat", config)).await.unwrap();
+        assert_eq!(completed.results.model_metrics.unwrap().accuracy, Some(0.9));
+
+        assert!(registry.run(&custom_request("false", serde_json::Value::Null)).await.is_err());
+    }
+
+    fn tick(timestamp: i64, price: f64, volume: f64) -> RealTimeDataUpdateEvent {
+        RealTimeDataUpdateEvent {
+            symbol: "ES".to_string(),
+            timestamp,
+            price,
+            volume,
+            bid: price - 0.25,
+            ask: price + 0.25,
+            bid_size: 1.0,
+            ask_size: 1.0,
+            features: None,
+        }
+    }
+
+    #[test]
+    fn test_anomaly_detector() {
+        let thresholds = AnomalyThresholds {
+            max_gap_ns: 1_000,
+            price_spike_pct: 0.05,
+            volume_spike_ratio: 5.0,
+            stale_after_ns: 500,
+            critical_multiplier: 3.0,
+            warmup_ticks: 3,
+        };
+        let mut detector = AnomalyDetector::new(Uuid::nil(), thresholds);
+        let kinds = |anomalies: Vec<AnomalyDetectedEvent>| {
+            anomalies.iter().map(|a| (a.anomaly_type, a.severity)).collect::<Vec<_>>()
+        };
+
+        for i in 0..4 {
+            assert!(detector.observe(&tick(i * 100, 100.0 + i as f64, 1.0)).is_empty());
+        }
+        assert_eq!(
+            kinds(detector.observe(&tick(300, 103.0, 1.0))),
+            vec![(AnomalyType::DuplicateTick, AnomalySeverity::Info)]
+        );
+        assert_eq!(
+            kinds(detector.observe(&tick(200, 104.0, 1.0))),
+            vec![(AnomalyType::OutOfOrderTimestamp, AnomalySeverity::Warning)]
+        );
+        assert_eq!(
+            kinds(detector.observe(&tick(400, 109.0, 1.0))),
+            vec![(AnomalyType::PriceSpike, AnomalySeverity::Warning)]
+        );
+        assert_eq!(
+            kinds(detector.observe(&tick(500, 140.0, 100.0))),
+            vec![
+                (AnomalyType::PriceSpike, AnomalySeverity::Critical),
+                (AnomalyType::VolumeSpike, AnomalySeverity::Critical),
+            ]
+        );
+        assert_eq!(
+            kinds(detector.observe(&tick(2_500, 140.0, -1.0))),
+            vec![
+                (AnomalyType::NegativeVolume, AnomalySeverity::Critical),
+                (AnomalyType::DataGap, AnomalySeverity::Warning),
+                (AnomalyType::StaleData, AnomalySeverity::Critical),
+            ]
+        );
+        // Staleness is reported once per unchanged stretch
+        assert!(detector.observe(&tick(3_000, 140.0, 1.0)).is_empty());
+    }
+
+    #[test]
+    fn test_anomaly_priority() {
+        use crate::Event;
+
+        let mut detector = AnomalyDetector::new(Uuid::new_v4(), AnomalyThresholds::default());
+        detector.observe(&tick(0, 100.0, 1.0));
+        let anomaly = detector.observe(&tick(1, 100.0, -5.0)).remove(0);
+        assert_eq!(anomaly.affected_timestamps, vec![1]);
+
+        let event = ResearchEvent::AnomalyDetected(anomaly);
+        assert_eq!(event.event_type(), "anomaly_detected");
+        assert_eq!(event.priority(), 0);
+        assert_eq!(AnomalySeverity::Warning.priority(), 2);
+        assert_eq!(AnomalySeverity::Info.priority(), 5);
+
+        let json = serde_json::to_string(&event).unwrap();
+        let ResearchEvent::AnomalyDetected(decoded) = serde_json::from_str(&json).unwrap() else {
+            panic!("expected AnomalyDetected");
+        };
+        assert_eq!(decoded.anomaly_type, AnomalyType::NegativeVolume);
+        assert_eq!(decoded.severity, AnomalySeverity::Critical);
+    }
+
+    fn feature_event(signal_id: Uuid, features: &[(&str, f64)]) -> FeatureExtractedEvent {
+        FeatureExtractedEvent {
+            signal_id,
+            timestamp: 0,
+            features: features.iter().map(|&(name, value)| (name.to_string(), value)).collect(),
+            feature_names: features.iter().map(|&(name, _)| name.to_string()).collect(),
+            extraction_time_ms: 0,
+        }
+    }
+
+    #[test]
+    fn test_feature_window() {
+        let signal_id = Uuid::new_v4();
+        let mut window = FeatureWindow::new(signal_id, 3);
+        assert_eq!(window.mean("rsi"), None);
+
+        for (rsi, momentum) in [(10.0, 1.0), (20.0, -1.0), (30.0, 2.0), (40.0, 0.5)] {
+            window.update(&feature_event(signal_id, &[("rsi", rsi), ("momentum", momentum)]));
+        }
+        window.update(&feature_event(Uuid::new_v4(), &[("rsi", 1000.0)]));
+
+        assert_eq!(window.features["rsi"], vec![20.0, 30.0, 40.0]);
+        assert_eq!(window.mean("rsi"), Some(30.0));
+        assert!((window.std("rsi").unwrap() - (200.0f64 / 3.0).sqrt()).abs() < 1e-12);
+        assert_eq!(window.min("momentum"), Some(-1.0));
+        assert_eq!(window.max("momentum"), Some(2.0));
+        assert_eq!(window.latest("momentum"), Some(0.5));
+        assert_eq!(window.latest("volume"), None);
+    }
+
+    #[test]
+    fn test_eta_linear_workload() {
+        // 1% per second with +-200ms jitter on each sample
+        let mut estimator = EtaEstimator::new(16);
+        assert_eq!(estimator.update_at(0, 0.0), None);
+        for second in 1..=60i64 {
+            let jitter_ns = if second % 2 == 0 { 200_000_000 } else { -200_000_000 };
+            let eta = estimator.update_at(second * 1_000_000_000 + jitter_ns, second as f64 / 100.0).unwrap();
+            if second >= 16 {
+                assert!((eta - (100 - second)).abs() <= 2, "eta {} at {}s", eta, second);
+            }
+        }
+        assert_eq!(estimator.update_at(61_000_000_000, 1.0), Some(0));
+
+        // Stalled progress has no estimate
+        estimator.reset();
+        estimator.update_at(0, 0.5);
+        assert_eq!(estimator.update_at(1_000_000_000, 0.5), None);
+    }
+
+    #[test]
+    fn test_eta_adapts_to_rate_change() {
+        let mut estimator = EtaEstimator::default();
+        for second in 0..20i64 {
+            estimator.update_at(second * 1_000_000_000, second as f64 * 0.01);
+        }
+        // Rate rises to 2.5%/s; the window forgets the slow phase
+        let mut eta = None;
+        for step in 1..=20i64 {
+            eta = estimator.update_at((19 + step) * 1_000_000_000, 0.19 + step as f64 * 0.025);
+        }
+        assert_eq!(eta, Some(12));
+    }
+
+    #[test]
+    fn test_analysis_tracker() {
+        let analysis_id = Uuid::new_v4();
+        let mut tracker = AnalysisTracker::new(analysis_id, Uuid::new_v4(), Uuid::new_v4());
+        tracker.set_step("loading");
+        let first = tracker.tick_at(0, 0.0);
+        assert_eq!(first.analysis_id, analysis_id);
+        assert_eq!(first.current_step, "loading");
+        assert_eq!(first.estimated_remaining, None);
+
+        tracker.set_step("computing ic");
+        let second = tracker.tick_at(10_000_000_000, 0.25);
+        assert_eq!(second.current_step, "computing ic");
+        assert_eq!(second.estimated_remaining, Some(30));
+        assert_eq!(second.timestamp, 10_000_000_000);
+    }
+
+    /// Textbook Spearman: naive ranks, then Pearson with explicit means
+    fn reference_spearman(x: &[f64], y: &[f64]) -> f64 {
+        let ranks = |v: &[f64]| -> Vec<f64> {
+            v.iter()
+                .map(|a| {
+                    let below = v.iter().filter(|b| *b < a).count() as f64;
+                    let equal = v.iter().filter(|b| *b == a).count() as f64;
+                    below + (equal + 1.0) / 2.0
+                })
+                .collect()
+        };
+        let (rx, ry) = (ranks(x), ranks(y));
+        let n = rx.len() as f64;
+        let (mx, my) = (rx.iter().sum::<f64>() / n, ry.iter().sum::<f64>() / n);
+        let cov: f64 = rx.iter().zip(&ry).map(|(a, b)| (a - mx) * (b - my)).sum();
+        let vx: f64 = rx.iter().map(|a| (a - mx).powi(2)).sum();
+        let vy: f64 = ry.iter().map(|b| (b - my).powi(2)).sum();
+        cov / (vx * vy).sqrt()
+    }
+
+    #[test]
+    fn test_spearman_known_values() {
+        // IQ vs hours of TV per week: rho = -29/165
+        let iq = [106.0, 100.0, 86.0, 101.0, 99.0, 103.0, 97.0, 113.0, 112.0, 110.0];
+        let tv = [7.0, 27.0, 2.0, 50.0, 28.0, 29.0, 20.0, 12.0, 6.0, 17.0];
+        assert!((spearman_correlation(&iq, &tv) + 29.0 / 165.0).abs() < 1e-12);
+
+        // Tied pair shares rank 2.5: rho = 3 / sqrt(10)
+        let rho = spearman_correlation(&[1.0, 2.0, 2.0, 3.0], &[1.0, 2.0, 3.0, 4.0]);
+        assert!((rho - 3.0 / 10f64.sqrt()).abs() < 1e-12);
+
+        assert_eq!(spearman_correlation(&[1.0, 1.0, 1.0], &[1.0, 2.0, 3.0]), 0.0);
+        assert_eq!(spearman_correlation(&[1.0], &[1.0]), 0.0);
+    }
+
+    #[test]
+    fn test_ic_stream_pairing() {
+        let mut ic = IcStreamCalculator::new(Uuid::new_v4(), 3);
+
+        // No signal yet, then a signal stamped after the return
+        assert!(ic.update_return(0.01, 10).is_none());
+        ic.update_signal(1.0, 20);
+        assert!(ic.update_return(0.01, 15).is_none());
+
+        assert!(ic.update_return(0.01, 25).is_none());
+        ic.update_signal(2.0, 30);
+        ic.update_signal(3.0, 31);
+        assert!(ic.update_return(0.02, 40).is_none());
+        assert_eq!(ic.current_ic(), None);
+
+        // Window full: perfectly ranked
+        let results = ic.update_return(0.03, 41).unwrap();
+        assert_eq!(ic.current_ic(), Some(1.0));
+        assert_eq!(results.ic_series, vec![(41, 1.0)]);
+        assert_eq!(results.hit_rate, 1.0);
+
+        // Oldest pair (1.0, 0.01) leaves; (4.0, -0.05) ranks last on returns
+        ic.update_signal(4.0, 50);
+        let results = ic.update_return(-0.05, 51).unwrap();
+        assert!((ic.current_ic().unwrap() + 0.5).abs() < 1e-12);
+        assert_eq!(results.ic_series.len(), 2);
+        assert!((results.mean_ic - 0.25).abs() < 1e-12);
+        assert!((results.std_ic - 0.75).abs() < 1e-12);
+        assert!((results.ir - 1.0 / 3.0).abs() < 1e-12);
+        assert_eq!(results.hit_rate, 0.5);
+
+        ic.reset();
+        assert_eq!(ic.current_ic(), None);
+        assert!(ic.update_return(0.01, 60).is_none());
+    }
+
+    mod ic_stream_props {
+        use super::*;
+        use proptest::prelude::*;
+
+        proptest! {
+            #[test]
+            fn streaming_matches_reference(
+                window in 2usize..12,
+                // Small integer range so ties are common
+                pairs in prop::collection::vec((-5i32..5, -5i32..5), 0..64),
+            ) {
+                let mut ic = IcStreamCalculator::new(Uuid::nil(), window);
+                for (i, &(signal, ret)) in pairs.iter().enumerate() {
+                    ic.update_signal(signal as f64, i as i64);
+                    let results = ic.update_return(ret as f64, i as i64);
+                    prop_assert_eq!(results.is_some(), i + 1 >= window);
+
+                    let start = (i + 1).saturating_sub(window);
+                    let x: Vec<f64> = pairs[start..=i].iter().map(|p| p.0 as f64).collect();
+                    let y: Vec<f64> = pairs[start..=i].iter().map(|p| p.1 as f64).collect();
+                    let batch = spearman_correlation(&x, &y);
+                    match ic.current_ic() {
+                        Some(streamed) => {
+                            prop_assert!((streamed - batch).abs() < 1e-9, "{} vs {}", streamed, batch);
+                            let reference = reference_spearman(&x, &y);
+                            if reference.is_finite() {
+                                prop_assert!((batch - reference).abs() < 1e-9, "{} vs {}", batch, reference);
+                            } else {
+                                prop_assert_eq!(batch, 0.0);
+                            }
+                        }
+                        None => prop_assert!(x.len() < window),
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_progress_dashboard_concurrent_jobs() {
+        const SECOND: i64 = 1_000_000_000;
+        let ids: Vec<Uuid> = (1..=5).map(Uuid::from_u128).collect();
+        let progress = |model_id, epoch: u32, t: i64| ModelTrainingProgressEvent {
+            model_id,
+            epoch,
+            total_epochs: 10,
+            training_loss: 1.0 / (epoch + 1) as f64,
+            validation_loss: None,
+            metrics: HashMap::new(),
+            timestamp: t * SECOND,
+        };
+
+        // Job i reports one more epoch every i+1 seconds
+        let mut dashboard = ProgressDashboard::new();
+        for t in 0..=6 {
+            for (i, &id) in ids.iter().enumerate() {
+                let period = i as i64 + 1;
+                if t % period == 0 {
+                    dashboard.update(&progress(id, (t / period) as u32, t));
+                }
+            }
+        }
+
+        let summary = dashboard.summary();
+        assert_eq!(summary.active_jobs, 5);
+        assert_eq!(summary.completed_jobs, 0);
+        assert!((summary.overall_progress - 0.26).abs() < 1e-12);
+        assert_eq!(summary.fastest_job, Some(ids[0]));
+        assert_eq!(summary.slowest_job, Some(ids[4]));
+
+        // Last reports: job 4 at 4s, job 5 at 5s
+        assert_eq!(dashboard.stalled_jobs(3 * SECOND / 2, 6 * SECOND), vec![ids[3]]);
+        assert_eq!(dashboard.stalled_jobs(SECOND / 2, 6 * SECOND), vec![ids[3], ids[4]]);
+
+        dashboard.mark_completed(ids[0]);
+        dashboard.mark_failed(ids[3], "out of memory".to_string());
+        // Late progress for a finished job is ignored
+        dashboard.update(&progress(ids[0], 7, 7));
+
+        let summary = dashboard.summary();
+        assert_eq!((summary.active_jobs, summary.completed_jobs, summary.failed_jobs), (3, 1, 1));
+        assert!((summary.overall_progress - 0.4).abs() < 1e-12);
+        assert_eq!(summary.fastest_job, Some(ids[1]));
+        assert!((summary.avg_training_loss - (0.25 + 1.0 / 3.0 + 0.5) / 3.0).abs() < 1e-12);
+        assert_eq!(dashboard.jobs[&ids[3]].status, TrainingJobStatus::Failed("out of memory".to_string()));
+        assert_eq!(dashboard.stalled_jobs(3 * SECOND / 2, 6 * SECOND), Vec::<Uuid>::new());
+    }
+
+    mod feature_window_props {
+        use super::*;
+        use proptest::prelude::*;
+
+        proptest! {
+            #[test]
+            fn window_invariants(
+                window_size in 1usize..16,
+                events in prop::collection::vec(
+                    prop::collection::hash_map("[a-c]", -1e6f64..1e6, 0..3),
+                    0..64,
+                ),
+            ) {
+                let signal_id = Uuid::nil();
+                let mut window = FeatureWindow::new(signal_id, window_size);
+                let mut history: HashMap<String, Vec<f64>> = HashMap::new();
+
+                for features in &events {
+                    window.update(&FeatureExtractedEvent {
+                        signal_id,
+                        timestamp: 0,
+                        features: features.clone(),
+                        feature_names: features.keys().cloned().collect(),
+                        extraction_time_ms: 0,
+                    });
+                    for (name, &value) in features {
+                        history.entry(name.clone()).or_default().push(value);
+                    }
+                }
+
+                for name in ["a", "b", "c"] {
+                    let Some(all) = history.get(name) else {
+                        prop_assert!(window.mean(name).is_none());
+                        prop_assert!(window.latest(name).is_none());
+                        continue;
+                    };
+                    // Window holds exactly the most recent values, in order
+                    let expected = &all[all.len().saturating_sub(window_size)..];
+                    let values: Vec<f64> = window.features[name].iter().copied().collect();
+                    prop_assert_eq!(&values[..], expected);
+
+                    let min = window.min(name).unwrap();
+                    let max = window.max(name).unwrap();
+                    let mean = window.mean(name).unwrap();
+                    prop_assert!(min <= mean + 1e-6 && mean <= max + 1e-6);
+                    prop_assert!(window.std(name).unwrap() >= 0.0);
+                    prop_assert!(window.std(name).unwrap() <= (max - min) + 1e-6);
+                    prop_assert_eq!(window.latest(name), all.last().copied());
+                }
+            }
+        }
+    }
+
+    fn state_change(old_state: &str, new_state: &str) -> ResearchStateChangedEvent {
+        ResearchStateChangedEvent {
+            component: "backtester".to_string(),
+            old_state: old_state.to_string(),
+            new_state: new_state.to_string(),
+            timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn test_default_state_transitions() {
+        use ResearchState::*;
+        let validator = StateTransitionValidator::default();
+        let valid = [
+            (Idle, Loading),
+            (Loading, Running),
+            (Loading, Failed),
+            (Running, Paused),
+            (Running, Completed),
+            (Running, Failed),
+            (Paused, Running),
+            (Paused, Failed),
+            (Completed, Idle),
+            (Failed, Idle),
+        ];
+        for from in ResearchState::ALL {
+            for to in ResearchState::ALL {
+                let result = validator.validate(from, to);
+                if valid.contains(&(from, to)) {
+                    assert!(result.is_ok(), "{} -> {} rejected", from, to);
+                } else {
+                    assert_eq!(result, Err(InvalidTransitionError { from, to }));
+                }
+            }
+        }
+        assert_eq!(
+            validator.validate(Idle, Paused).unwrap_err().to_string(),
+            "invalid research state transition: idle -> paused"
+        );
+    }
+
+    #[test]
+    fn test_state_changed_event_validate() {
+        assert!(state_change("running", "paused").validate().is_ok());
+        assert!(state_change("Paused", "RUNNING").validate().is_ok());
+        assert!(state_change("idle", "paused").validate().is_err());
+        assert!(state_change("completed", "running").validate().is_err());
+        assert!(state_change("running", "sleeping").validate().is_err());
+
+        let custom = StateTransitionValidator::custom(vec![(ResearchState::Completed, ResearchState::Running)]);
+        assert!(state_change("completed", "running").validate_with(&custom).is_ok());
+        assert!(state_change("running", "paused").validate_with(&custom).is_err());
+    }
+
+    fn deps(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    fn add(feature: &str, depends_on: &[&str]) -> PipelineUpdate {
+        PipelineUpdate {
+            operation: "add_feature".to_string(),
+            feature_name: feature.to_string(),
+            config: HashMap::from([("depends_on".to_string(), serde_json::json!(depends_on))]),
+        }
+    }
+
+    fn pipeline(updates: Vec<PipelineUpdate>) -> FeaturePipelineUpdatedEvent {
+        FeaturePipelineUpdatedEvent {
+            pipeline_id: "alpha".to_string(),
+            updates,
+            updated_by: "research".to_string(),
+            timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn test_dependency_graph_diamond() {
+        // returns -> {momentum, volatility} -> sharpe
+        let mut graph = FeatureDependencyGraph::new();
+        graph.add_feature("sharpe", deps(&["momentum", "volatility"])).unwrap();
+        graph.add_feature("momentum", deps(&["returns"])).unwrap();
+        graph.add_feature("volatility", deps(&["returns"])).unwrap();
+        graph.add_feature("returns", vec![]).unwrap();
+        assert_eq!(graph.execution_order().unwrap(), vec!["returns", "momentum", "volatility", "sharpe"]);
+    }
+
+    #[test]
+    fn test_dependency_graph_rejects_cycles() {
+        let mut graph = FeatureDependencyGraph::new();
+        graph.add_feature("a", deps(&["b"])).unwrap();
+        graph.add_feature("b", deps(&["c"])).unwrap();
+        let err = graph.add_feature("c", deps(&["a"])).unwrap_err();
+        assert_eq!(err.cycle, vec!["a", "b", "c", "a"]);
+        assert_eq!(err.to_string(), "dependency cycle: a -> b -> c -> a");
+        // The graph is unchanged
+        assert!(!graph.features.contains_key("c"));
+
+        let err = graph.add_feature("a", deps(&["a"])).unwrap_err();
+        assert_eq!(err.cycle, vec!["a", "a"]);
+        assert_eq!(graph.features["a"], deps(&["b"]));
+    }
+
+    #[test]
+    fn test_validate_pipeline() {
+        let mut graph = FeatureDependencyGraph::new();
+        graph.add_feature("returns", vec![]).unwrap();
+
+        let order = pipeline(vec![add("zscore", &["momentum"]), add("momentum", &["returns"])])
+            .validate_pipeline(&graph)
+            .unwrap();
+        assert_eq!(order, vec!["returns", "momentum", "zscore"]);
+
+        assert_eq!(
+            pipeline(vec![add("returns", &[])]).validate_pipeline(&graph),
+            Err(PipelineError::DuplicateFeature("returns".to_string()))
+        );
+        assert_eq!(
+            pipeline(vec![add("momentum", &["prices"])]).validate_pipeline(&graph),
+            Err(PipelineError::MissingFeature("prices".to_string()))
+        );
+
+        // Removing a dependency leaves its dependents dangling
+        let mut remove = add("returns", &[]);
+        remove.operation = "remove_feature".to_string();
+        assert_eq!(
+            pipeline(vec![add("momentum", &["returns"]), remove]).validate_pipeline(&graph),
+            Err(PipelineError::MissingFeature("returns".to_string()))
+        );
+
+        let mut modify = add("returns", &["momentum"]);
+        modify.operation = "modify_feature".to_string();
+        let err = pipeline(vec![add("momentum", &["returns"]), modify]).validate_pipeline(&graph).unwrap_err();
+        assert!(matches!(err, PipelineError::CyclicDependency(CycleError { cycle }) if cycle == vec!["momentum", "returns", "momentum"]));
+        assert_eq!(graph.features.len(), 1);
+    }
+
+    #[cfg_attr(feature = "compress", allow(deprecated))]
+    #[test]
+    fn test_compressed_results_carried_through() {
+        let event = AnalysisCompletedEvent {
+            analysis_id: Uuid::nil(),
+            signal_id: Uuid::nil(),
+            dataset_id: Uuid::nil(),
+            results: AnalysisResults::default(),
+            compressed_results: Some(CompressedAnalysisResults("AQID".to_string())),
+            completed_at: 0,
+            duration_ms: 0,
+            schema_version: 1,
+        };
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["compressed_results"], "AQID");
+        let parsed: AnalysisCompletedEvent = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.compressed_results, event.compressed_results);
+        // Not zstd data, and builds without `compress` cannot decode at all
+        assert!(parsed.decoded_results().is_err());
+    }
+
+    /// Results carrying `n` daily IC values
+    #[cfg(feature = "compress")]
+    fn ic_results(n: usize) -> AnalysisResults {
+        let mut state = 0x9e37_79b9_7f4a_7c15u64;
+        let ic_series = (0..n)
+            .map(|i| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                let ic = ((state >> 11) as f64 / (1u64 << 53) as f64 - 0.5) * 0.2;
+                (1_600_000_000_000_000_000 + i as i64 * 86_400_000_000_000, ic)
+            })
+            .collect();
+        AnalysisResults {
+            ic_results: Some(IcResults {
+                mean_ic: 0.01,
+                std_ic: 0.05,
+                ir: 0.2,
+                hit_rate: 0.52,
+                ic_series,
+            }),
+            ..AnalysisResults::default()
+        }
+    }
+
+    #[cfg(feature = "compress")]
+    #[test]
+    fn test_compressed_results_round_trip() {
+        let results = ic_results(500);
+        let compressed = CompressedAnalysisResults::from_results(&results, 3).unwrap();
+        let decoded = compressed.to_results().unwrap();
+        assert_eq!(decoded.ic_results.unwrap().ic_series, results.ic_results.clone().unwrap().ic_series);
+
+        // The event round-trips through JSON with the bytes as base64
+        let mut event = AnalysisCompletedEvent::new_compressed(&results, 3).unwrap();
+        event.analysis_id = Uuid::new_v4();
+        let json = serde_json::to_value(&event).unwrap();
+        assert!(json["compressed_results"].is_string());
+        let parsed: AnalysisCompletedEvent = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.analysis_id, event.analysis_id);
+        assert_eq!(parsed.compressed_results, event.compressed_results);
+        let decoded = parsed.decoded_results().unwrap();
+        assert_eq!(decoded.ic_results.unwrap().ic_series.len(), 500);
+
+        // Events written without the field still parse
+        let mut legacy = serde_json::to_value(&parsed).unwrap();
+        legacy.as_object_mut().unwrap().remove("compressed_results");
+        let legacy: AnalysisCompletedEvent = serde_json::from_value(legacy).unwrap();
+        assert!(legacy.compressed_results.is_none());
+
+        assert!(CompressedAnalysisResults("AQID".to_string()).to_results().is_err());
+    }
+
+    #[cfg(feature = "compress")]
+    #[test]
+    fn test_compression_ratio() {
+        // 10 000 daily IC values at level 3 shrink by at least 60%
+        let results = ic_results(10_000);
+        let raw = serde_json::to_vec(&results).unwrap().len();
+        let compressed = CompressedAnalysisResults::from_results(&results, 3).unwrap().to_bytes().unwrap().len();
+        assert!(
+            (compressed as f64) < raw as f64 * 0.4,
+            "compressed {} of {} bytes",
+            compressed,
+            raw
+        );
+    }
+
+    #[cfg(feature = "compress")]
+    #[allow(deprecated)]
+    #[test]
+    fn test_compress_above_threshold() {
+        let small = AnalysisCompletedEvent {
+            analysis_id: Uuid::nil(),
+            signal_id: Uuid::nil(),
+            dataset_id: Uuid::nil(),
+            results: ic_results(10),
+            compressed_results: None,
+            completed_at: 0,
+            duration_ms: 5,
+            schema_version: 1,
+        };
+        let size = serde_json::to_vec(&small.results).unwrap().len();
+
+        let kept = small.clone().compress_above(size, 3).unwrap();
+        assert!(kept.compressed_results.is_none());
+        assert_eq!(kept.results.ic_results.as_ref().unwrap().ic_series.len(), 10);
+
+        let moved = small.compress_above(size - 1, 3).unwrap();
+        assert!(moved.results.ic_results.is_none());
+        assert_eq!(moved.duration_ms, 5);
+        assert_eq!(moved.decoded_results().unwrap().ic_results.unwrap().ic_series.len(), 10);
     }
 }
-*/
@@ -0,0 +1,337 @@
+//! Automatic retries driven by `ErrorEvent`s
+//!
+//! `RetryOrchestrator` watches the error channel. For an error with a
+//! matching `RetryPolicy` it waits out the policy's backoff and publishes a
+//! `RetryAttemptEvent`; the failing component retries when it sees one.
+//! Errors caused by a retry (see `EventEnvelope::caused_by`) count as the
+//! next attempt of the original error, and once `max_attempts` retries have
+//! failed a `GaveUpEvent` is published instead.
+
+use crate::bus::EventBus;
+use crate::events::{ErrorEvent, EventEnvelope, GaveUpEvent, RetryAttemptEvent, StaticEventType};
+use rand::Rng;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+/// Scheduled retries remembered so that follow-up errors can be matched
+pub const MAX_TRACKED_RETRIES: usize = 10_000;
+
+/// Delay before a retry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackoffStrategy {
+    /// Same delay before every attempt
+    Fixed(Duration),
+    /// `base * 2^(attempt - 1)`, capped at `max`
+    Exponential { base: Duration, max: Duration },
+    /// Uniformly random delay between zero and the given duration
+    Jittered(Duration),
+}
+
+impl BackoffStrategy {
+    /// Delay before retry `attempt` (1 for the first retry)
+    pub fn delay(&self, attempt: u32) -> Duration {
+        match *self {
+            BackoffStrategy::Fixed(delay) => delay,
+            BackoffStrategy::Exponential { base, max } => 2u32
+                .checked_pow(attempt.saturating_sub(1))
+                .map_or(max, |factor| base.saturating_mul(factor))
+                .min(max),
+            BackoffStrategy::Jittered(max) => {
+                let max_nanos = u64::try_from(max.as_nanos()).unwrap_or(u64::MAX);
+                Duration::from_nanos(rand::thread_rng().gen_range(0..=max_nanos))
+            }
+        }
+    }
+}
+
+/// How errors of some types are retried
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Retries before giving up (0 gives up on the first error)
+    pub max_attempts: u32,
+    pub backoff: BackoffStrategy,
+    /// Further error types the policy applies to, besides the one it is
+    /// registered under
+    pub retryable_error_types: Vec<String>,
+}
+
+impl RetryPolicy {
+    pub fn new(max_attempts: u32, backoff: BackoffStrategy) -> Self {
+        Self {
+            max_attempts,
+            backoff,
+            retryable_error_types: Vec::new(),
+        }
+    }
+
+    /// Also apply to errors of `error_type`
+    pub fn retry_on(mut self, error_type: impl Into<String>) -> Self {
+        self.retryable_error_types.push(error_type.into());
+        self
+    }
+
+    /// Whether `error_type` is listed in `retryable_error_types`
+    pub fn is_retryable(&self, error_type: &str) -> bool {
+        self.retryable_error_types.iter().any(|t| t == error_type)
+    }
+}
+
+/// Scheduled retries, oldest evicted first
+#[derive(Default)]
+struct RetryTracker {
+    /// Retry envelope ID -> (original error ID, attempt)
+    attempts: HashMap<Uuid, (Uuid, u32)>,
+    order: VecDeque<Uuid>,
+}
+
+impl RetryTracker {
+    fn insert(&mut self, retry_id: Uuid, original_error_id: Uuid, attempt: u32) {
+        self.attempts.insert(retry_id, (original_error_id, attempt));
+        self.order.push_back(retry_id);
+        while self.order.len() > MAX_TRACKED_RETRIES {
+            if let Some(evicted) = self.order.pop_front() {
+                self.attempts.remove(&evicted);
+            }
+        }
+    }
+
+    fn take(&mut self, retry_id: Uuid) -> Option<(Uuid, u32)> {
+        self.attempts.remove(&retry_id)
+    }
+}
+
+/// Retries failed operations according to per-error-type policies
+///
+/// Cloning yields a handle to the same policies, so policies registered
+/// after `run` still take effect.
+#[derive(Clone, Default)]
+pub struct RetryOrchestrator {
+    policies: Arc<RwLock<BTreeMap<String, RetryPolicy>>>,
+}
+
+impl RetryOrchestrator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Retry errors of `error_type` (and the policy's `retryable_error_types`)
+    /// with `policy`, replacing any policy registered under `error_type`
+    pub fn register_policy(&self, error_type: &str, policy: RetryPolicy) {
+        self.policies.write().unwrap().insert(error_type.to_string(), policy);
+    }
+
+    /// Policy applied to errors of `error_type`
+    ///
+    /// A policy registered under `error_type` wins over others listing it
+    /// as retryable.
+    pub fn policy_for(&self, error_type: &str) -> Option<RetryPolicy> {
+        let policies = self.policies.read().unwrap();
+        policies
+            .get(error_type)
+            .or_else(|| policies.values().find(|policy| policy.is_retryable(error_type)))
+            .cloned()
+    }
+
+    /// Handle every `ErrorEvent` published on `bus`
+    ///
+    /// The task ends when the bus's error channel closes; abort the handle to
+    /// stop earlier. Retries already scheduled are still published.
+    pub async fn run(&self, bus: EventBus) -> JoinHandle<()> {
+        let mut receiver = bus.subscribe(ErrorEvent::EVENT_TYPE).await;
+        let orchestrator = self.clone();
+        tokio::spawn(async move {
+            let mut tracker = RetryTracker::default();
+            loop {
+                match receiver.recv().await {
+                    Ok(envelope) => orchestrator.handle_error(&bus, &mut tracker, envelope).await,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!("Retry orchestrator lagged, skipped {} errors", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return,
+                }
+            }
+        })
+    }
+
+    async fn handle_error(&self, bus: &EventBus, tracker: &mut RetryTracker, envelope: EventEnvelope) {
+        let error = match ErrorEvent::try_from(envelope.clone()) {
+            Ok(error) => error,
+            Err(e) => {
+                warn!("Retry orchestrator ignored event: {}", e);
+                return;
+            }
+        };
+        let Some(policy) = self.policy_for(&error.error_type) else {
+            return;
+        };
+
+        // An error caused by one of our retries continues that chain
+        let (original_error_id, attempt) = match envelope.causation_id.and_then(|id| tracker.take(id)) {
+            Some((original_error_id, previous)) => (original_error_id, previous + 1),
+            None => (envelope.id, 1),
+        };
+        let now_ns = bus.now_ns();
+
+        if attempt > policy.max_attempts {
+            debug!("Giving up on {} error {} after {} retries", error.error_type, original_error_id, policy.max_attempts);
+            let gave_up = GaveUpEvent {
+                timestamp: now_ns,
+                original_error_id,
+                component: error.component,
+                error_type: error.error_type,
+                attempts: attempt - 1,
+                message: error.message,
+            };
+            if let Err(e) = bus.publish_envelope(EventEnvelope::new(gave_up, 5).caused_by(&envelope)).await {
+                warn!("Failed to publish gave-up event: {}", e);
+            }
+            return;
+        }
+
+        let delay = policy.backoff.delay(attempt);
+        let retry = RetryAttemptEvent {
+            timestamp: now_ns,
+            original_error_id,
+            component: error.component,
+            error_type: error.error_type,
+            attempt,
+            next_attempt_at_ns: now_ns.saturating_add(i64::try_from(delay.as_nanos()).unwrap_or(i64::MAX)),
+        };
+        let mut retry = EventEnvelope::new(retry, 5).caused_by(&envelope);
+        tracker.insert(retry.id, original_error_id, attempt);
+
+        let bus = bus.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            retry.timestamp_ns = bus.now_ns();
+            if let Err(e) = bus.publish_envelope(retry).await {
+                warn!("Failed to publish retry attempt: {}", e);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::time::Instant;
+
+    fn error(error_type: &str) -> ErrorEvent {
+        ErrorEvent {
+            timestamp: 0,
+            component: "oms".to_string(),
+            error_type: error_type.to_string(),
+            message: "rejected".to_string(),
+            context: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_backoff_delays() {
+        let fixed = BackoffStrategy::Fixed(Duration::from_millis(5));
+        assert_eq!(fixed.delay(1), Duration::from_millis(5));
+        assert_eq!(fixed.delay(10), Duration::from_millis(5));
+
+        let exponential = BackoffStrategy::Exponential {
+            base: Duration::from_millis(100),
+            max: Duration::from_secs(1),
+        };
+        let delays: Vec<u64> = (1..=6).map(|n| exponential.delay(n).as_millis() as u64).collect();
+        assert_eq!(delays, vec![100, 200, 400, 800, 1000, 1000]);
+        assert_eq!(exponential.delay(u32::MAX), Duration::from_secs(1));
+
+        let jittered = BackoffStrategy::Jittered(Duration::from_millis(10));
+        assert!((0..100).all(|n| jittered.delay(n) <= Duration::from_millis(10)));
+        assert_eq!(BackoffStrategy::Jittered(Duration::ZERO).delay(1), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_policy_lookup() {
+        let orchestrator = RetryOrchestrator::new();
+        let timeouts = RetryPolicy::new(3, BackoffStrategy::Fixed(Duration::from_millis(1))).retry_on("io");
+        let rejects = RetryPolicy::new(1, BackoffStrategy::Fixed(Duration::from_millis(2)));
+        orchestrator.register_policy("timeout", timeouts.clone());
+        orchestrator.register_policy("reject", rejects.clone());
+
+        assert_eq!(orchestrator.policy_for("timeout"), Some(timeouts.clone()));
+        assert_eq!(orchestrator.policy_for("io"), Some(timeouts));
+        assert_eq!(orchestrator.policy_for("reject"), Some(rejects.clone()));
+        assert_eq!(orchestrator.policy_for("parse"), None);
+
+        // An exact registration wins
+        orchestrator.register_policy("io", rejects.clone());
+        assert_eq!(orchestrator.policy_for("io"), Some(rejects));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_exponential_backoff_timing() {
+        let bus = EventBus::new();
+        let orchestrator = RetryOrchestrator::new();
+        orchestrator.register_policy("timeout", RetryPolicy {
+            max_attempts: 4,
+            backoff: BackoffStrategy::Exponential {
+                base: Duration::from_millis(100),
+                max: Duration::from_millis(350),
+            },
+            retryable_error_types: Vec::new(),
+        });
+        let task = orchestrator.run(bus.clone()).await;
+        let mut retries = bus.subscribe(RetryAttemptEvent::EVENT_TYPE).await;
+        let mut gave_up = bus.subscribe(GaveUpEvent::EVENT_TYPE).await;
+
+        let first = EventEnvelope::new(error("timeout"), 5);
+        let original_error_id = first.id;
+        let mut cause = first.clone();
+        bus.publish_envelope(first).await.unwrap();
+
+        for (attempt, expected_ms) in [(1, 100), (2, 200), (3, 350), (4, 350)] {
+            let start = Instant::now();
+            let envelope = retries.recv().await.unwrap();
+            assert_eq!(start.elapsed(), Duration::from_millis(expected_ms));
+            assert_eq!(envelope.causation_id, Some(cause.id));
+            let retry = RetryAttemptEvent::try_from(envelope.clone()).unwrap();
+            assert_eq!(retry.original_error_id, original_error_id);
+            assert_eq!(retry.attempt, attempt);
+            assert_eq!(retry.next_attempt_at_ns - retry.timestamp, expected_ms as i64 * 1_000_000);
+
+            // The retry fails again
+            cause = EventEnvelope::new(error("timeout"), 5).caused_by(&envelope);
+            bus.publish_envelope(cause.clone()).await.unwrap();
+        }
+
+        let start = Instant::now();
+        let envelope = gave_up.recv().await.unwrap();
+        assert_eq!(start.elapsed(), Duration::ZERO);
+        let event = GaveUpEvent::try_from(envelope).unwrap();
+        assert_eq!(event.original_error_id, original_error_id);
+        assert_eq!(event.attempts, 4);
+        assert_eq!(event.error_type, "timeout");
+        assert!(retries.try_recv().is_err());
+        task.abort();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_unmatched_errors_are_ignored() {
+        let bus = EventBus::new();
+        let orchestrator = RetryOrchestrator::new();
+        orchestrator.register_policy("timeout", RetryPolicy::new(0, BackoffStrategy::Fixed(Duration::from_millis(10))));
+        let task = orchestrator.run(bus.clone()).await;
+        let mut retries = bus.subscribe(RetryAttemptEvent::EVENT_TYPE).await;
+        let mut gave_up = bus.subscribe(GaveUpEvent::EVENT_TYPE).await;
+
+        bus.publish(error("parse")).await.unwrap();
+        assert!(tokio::time::timeout(Duration::from_secs(60), retries.recv()).await.is_err());
+        assert!(gave_up.try_recv().is_err());
+
+        // No retries allowed: give up straight away
+        bus.publish(error("timeout")).await.unwrap();
+        assert_eq!(GaveUpEvent::try_from(gave_up.recv().await.unwrap()).unwrap().attempts, 0);
+        assert!(retries.try_recv().is_err());
+        task.abort();
+    }
+}
@@ -0,0 +1,397 @@
+//! Serde support for type-erased event envelopes
+//!
+//! `EventEnvelope` holds its payload as `dyn Event`, which cannot be
+//! deserialized without knowing the concrete type. `SerializableEnvelope`
+//! stores the payload as `(event_type, JSON)` and the global `EventRegistry`
+//! maps each event type name back to a deserializer.
+//!
+//! Built-in events from `events.rs` are registered automatically. Custom
+//! events either register at startup with `register_event_type!`, or are
+//! declared with `#[register_event]`, which registers them at link time:
+//!
+//! ```rust,ignore
+//! register_event_type!(MyEvent);              // MyEvent: StaticEventType
+//! register_event_type!("my_event", MyEvent);  // explicit name
+//!
+//! #[register_event]                           // "spread_alert"
+//! #[derive(Debug, Clone)]
+//! pub struct SpreadAlertEvent { pub symbol: String, pub spread_bps: f64 }
+//! ```
+//!
+//! Either way the first deserializer registered for a name wins; later ones
+//! are logged and ignored. Call `EventRegistry::validate` at startup to turn
+//! a clash into an error.
+
+use crate::events::*;
+use anyhow::{anyhow, Result};
+use dashmap::mapref::entry::Entry;
+use dashmap::DashMap;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::sync::{Mutex, OnceLock};
+use tracing::warn;
+use uuid::Uuid;
+
+#[doc(hidden)]
+pub use inventory;
+#[doc(hidden)]
+pub use serde;
+#[doc(hidden)]
+pub use serde_json;
+
+/// Rebuilds a boxed event from its JSON payload
+pub type DeserializeFn = fn(serde_json::Value) -> Result<Box<dyn Event>>;
+
+/// Deserializer submitted by `#[register_event]`
+///
+/// Collected into the `EventRegistry` when it is first used.
+#[doc(hidden)]
+pub struct EventRegistration {
+    event_type: &'static str,
+    deserialize: DeserializeFn,
+}
+
+impl EventRegistration {
+    pub const fn of<T>() -> Self
+    where
+        T: Event + StaticEventType + DeserializeOwned + 'static,
+    {
+        Self { event_type: T::EVENT_TYPE, deserialize: deserialize_as::<T> }
+    }
+}
+
+inventory::collect!(EventRegistration);
+
+fn deserialize_as<T: Event + DeserializeOwned + 'static>(value: serde_json::Value) -> Result<Box<dyn Event>> {
+    let event: T = serde_json::from_value(value)?;
+    Ok(Box::new(event))
+}
+
+/// Register an event type with the global `EventRegistry`
+#[macro_export]
+macro_rules! register_event_type {
+    ($ty:ty) => {
+        $crate::register_event_type!(<$ty as $crate::events::StaticEventType>::EVENT_TYPE, $ty)
+    };
+    ($name:expr, $ty:ty) => {
+        $crate::serde_support::EventRegistry::register($name, |value| {
+            let event: $ty = $crate::serde_support::serde_json::from_value(value)?;
+            Ok(Box::new(event) as Box<dyn $crate::events::Event>)
+        })
+    };
+}
+
+/// Global map from event type name to deserializer
+pub struct EventRegistry;
+
+impl EventRegistry {
+    fn entries() -> &'static DashMap<String, DeserializeFn> {
+        static ENTRIES: OnceLock<DashMap<String, DeserializeFn>> = OnceLock::new();
+        ENTRIES.get_or_init(|| {
+            let entries = DashMap::new();
+            Self::register_builtins(&entries);
+            let duplicates = Self::register_submitted(&entries, inventory::iter::<EventRegistration>);
+            Self::duplicates().lock().unwrap().extend(duplicates.into_iter().map(str::to_string));
+            entries
+        })
+    }
+    
+    /// Names a later registration tried to take, reported by `validate`
+    fn duplicates() -> &'static Mutex<BTreeSet<String>> {
+        static DUPLICATES: OnceLock<Mutex<BTreeSet<String>>> = OnceLock::new();
+        DUPLICATES.get_or_init(Default::default)
+    }
+    
+    /// Insert `deserialize` unless `event_type` is taken, returning false if it was
+    fn insert_first(entries: &DashMap<String, DeserializeFn>, event_type: &str, deserialize: DeserializeFn) -> bool {
+        match entries.entry(event_type.to_string()) {
+            Entry::Occupied(_) => {
+                warn!("Event type {:?} is registered more than once; keeping the first deserializer", event_type);
+                false
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(deserialize);
+                true
+            }
+        }
+    }
+    
+    /// Add `#[register_event]` deserializers, returning the names already taken
+    ///
+    /// A name can clash with a built-in event or another `#[register_event]`
+    /// type; the one registered first is kept.
+    fn register_submitted<'a>(
+        entries: &DashMap<String, DeserializeFn>,
+        registrations: impl IntoIterator<Item = &'a EventRegistration>,
+    ) -> Vec<&'static str> {
+        registrations.into_iter()
+            .filter(|registration| !Self::insert_first(entries, registration.event_type, registration.deserialize))
+            .map(|registration| registration.event_type)
+            .collect()
+    }
+    
+    fn register_builtins(entries: &DashMap<String, DeserializeFn>) {
+        macro_rules! builtin {
+            ($($ty:ty),* $(,)?) => {$(
+                entries.insert(<$ty as StaticEventType>::EVENT_TYPE.to_string(), |value| {
+                    let event: $ty = serde_json::from_value(value)?;
+                    Ok(Box::new(event) as Box<dyn Event>)
+                });
+            )*};
+        }
+        
+        builtin!(
+            MarketDataEvent,
+            AggregatedDataEvent,
+            FeatureEvent,
+            OrderBookEvent,
+            OrderBookDeltaEvent,
+            QuantumFeatureEvent,
+            SignalEvent,
+            OrderEvent,
+            FillEvent,
+            OrderUpdateEvent,
+            MetricsEvent,
+            PerformanceEvent,
+            HealthEvent,
+            ErrorEvent,
+            TransactionRolledBackEvent,
+            RetryAttemptEvent,
+            GaveUpEvent,
+            AttributedPnlEvent,
+        );
+    }
+    
+    /// Register the deserializer for an event type
+    ///
+    /// Like `#[register_event]`, the first registration of a name wins:
+    /// returns false, and leaves the existing deserializer in place, if the
+    /// name is already taken.
+    pub fn register(event_type: &str, deserialize: DeserializeFn) -> bool {
+        let registered = Self::insert_first(Self::entries(), event_type, deserialize);
+        if !registered {
+            Self::duplicates().lock().unwrap().insert(event_type.to_string());
+        }
+        registered
+    }
+    
+    /// Fail if any event type name was registered more than once
+    ///
+    /// Clashes are only logged when they happen, since the registry is built
+    /// lazily by whichever thread uses it first; call this at startup to make
+    /// them fatal.
+    pub fn validate() -> Result<()> {
+        Self::entries();
+        let duplicates = Self::duplicates().lock().unwrap();
+        if duplicates.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow!("Event types registered more than once: {:?}", duplicates))
+        }
+    }
+    
+    /// Check if an event type has a deserializer
+    pub fn is_registered(event_type: &str) -> bool {
+        Self::entries().contains_key(event_type)
+    }
+    
+    /// Names of all registered event types, sorted
+    pub fn registered_types() -> Vec<String> {
+        let mut types: Vec<String> = Self::entries().iter().map(|entry| entry.key().clone()).collect();
+        types.sort();
+        types
+    }
+    
+    /// Deserialize a payload of the given event type
+    pub fn deserialize(event_type: &str, payload: serde_json::Value) -> Result<Box<dyn Event>> {
+        let deserialize = *Self::entries()
+            .get(event_type)
+            .ok_or_else(|| anyhow!("Event type not registered: {}", event_type))?;
+        deserialize(payload)
+    }
+}
+
+/// Serializable form of an `EventEnvelope`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SerializableEnvelope {
+    pub id: Uuid,
+    pub timestamp_ns: i64,
+    pub priority: u8,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub causation_id: Option<Uuid>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<Uuid>,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+}
+
+impl SerializableEnvelope {
+    /// Rebuild the envelope using the registered deserializer
+    pub fn into_envelope(self) -> Result<EventEnvelope> {
+        let event = EventRegistry::deserialize(&self.event_type, self.payload)?;
+        let mut envelope = EventEnvelope::from_boxed(event, self.priority);
+        envelope.id = self.id;
+        envelope.timestamp_ns = self.timestamp_ns;
+        envelope.causation_id = self.causation_id;
+        envelope.correlation_id = self.correlation_id;
+        Ok(envelope)
+    }
+    
+    /// Rebuild the envelope, keeping unregistered event types as `RawEvent`
+    pub fn into_envelope_or_raw(self) -> Result<EventEnvelope> {
+        if EventRegistry::is_registered(&self.event_type) {
+            return self.into_envelope();
+        }
+        let event = RawEvent::new(&self.event_type, self.priority, self.payload);
+        let mut envelope = EventEnvelope::new(event, self.priority);
+        envelope.id = self.id;
+        envelope.timestamp_ns = self.timestamp_ns;
+        envelope.causation_id = self.causation_id;
+        envelope.correlation_id = self.correlation_id;
+        Ok(envelope)
+    }
+}
+
+impl EventEnvelope {
+    /// Convert to serializable form (`None` if the event has no JSON form)
+    pub fn to_serializable(&self) -> Option<SerializableEnvelope> {
+        Some(SerializableEnvelope {
+            id: self.id,
+            timestamp_ns: self.timestamp_ns,
+            priority: self.priority,
+            causation_id: self.causation_id,
+            correlation_id: self.correlation_id,
+            event_type: self.event_type_name().to_string(),
+            payload: self.event.to_json()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    
+    fn roundtrip(envelope: EventEnvelope) -> EventEnvelope {
+        let json = serde_json::to_string(&envelope.to_serializable().unwrap()).unwrap();
+        let restored: SerializableEnvelope = serde_json::from_str(&json).unwrap();
+        let restored = restored.into_envelope().unwrap();
+        
+        assert_eq!(restored.id, envelope.id);
+        assert_eq!(restored.timestamp_ns, envelope.timestamp_ns);
+        assert_eq!(restored.priority, envelope.priority);
+        assert_eq!(restored.event.event_type(), envelope.event.event_type());
+        assert_eq!(restored.event.to_json(), envelope.event.to_json());
+        restored
+    }
+    
+    #[test]
+    fn test_market_data_roundtrip() {
+        let restored = roundtrip(EventEnvelope::new(MarketDataEvent {
+            timestamp: 1,
+            symbol: "ES".to_string(),
+            price: 6000.0,
+            volume: 2.0,
+            bid_price: 5999.75,
+            bid_size: 3.0,
+            ask_price: 6000.25,
+            ask_size: 4.0,
+        }, 2));
+        
+        // Concrete type is restored, not a raw payload
+        let event = restored.event.downcast_ref::<MarketDataEvent>().unwrap();
+        assert_eq!(event.symbol, "ES");
+    }
+    
+    #[test]
+    fn test_signal_roundtrip() {
+        let restored = roundtrip(EventEnvelope::new(SignalEvent {
+            signal_id: Uuid::new_v4(),
+            timestamp: 1,
+            strategy_id: "momentum".to_string(),
+            symbol: "NQ".to_string(),
+            direction: SignalDirection::Short,
+            strength: 0.7,
+            target_price: Some(21000.0),
+            stop_loss: Some(21100.0),
+            metadata: HashMap::new(),
+        }, 3));
+        assert!(restored.event.downcast_ref::<SignalEvent>().is_some());
+    }
+    
+    #[test]
+    fn test_fill_roundtrip() {
+        let restored = roundtrip(EventEnvelope::new(FillEvent {
+            fill_id: Uuid::new_v4(),
+            order_id: Uuid::new_v4(),
+            signal_id: None,
+            timestamp: 1,
+            symbol: "CL".to_string(),
+            side: OrderSide::Buy,
+            filled_quantity: 2.0,
+            fill_price: 70.5,
+            commission: 1.0,
+            slippage_bps: 0.3,
+        }, 0));
+        assert!(restored.event.downcast_ref::<FillEvent>().is_some());
+    }
+    
+    #[test]
+    fn test_unregistered_type() {
+        let envelope = SerializableEnvelope {
+            id: Uuid::new_v4(),
+            timestamp_ns: 0,
+            priority: 5,
+            causation_id: None,
+            correlation_id: None,
+            event_type: "serde_support_test_unknown".to_string(),
+            payload: serde_json::Value::Null,
+        };
+        assert!(envelope.clone().into_envelope().is_err());
+        
+        // Manual registration makes it loadable
+        crate::register_event_type!("serde_support_test_unknown", MarketDataEvent);
+        assert!(EventRegistry::is_registered("serde_support_test_unknown"));
+        
+        // Registered, but payload does not match the type
+        assert!(envelope.into_envelope().is_err());
+    }
+    
+    #[test]
+    fn test_registered_types() {
+        let types = EventRegistry::registered_types();
+        assert!(types.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(types.iter().any(|t| t == MarketDataEvent::EVENT_TYPE));
+        assert!(types.iter().any(|t| t == AttributedPnlEvent::EVENT_TYPE));
+    }
+    
+    #[test]
+    fn test_submitted_name_clashes_with_builtin() {
+        let entries = DashMap::new();
+        EventRegistry::register_builtins(&entries);
+        let builtins = entries.len();
+        let duplicates = EventRegistry::register_submitted(&entries, &[EventRegistration::of::<FillEvent>()]);
+        assert_eq!(duplicates, vec![FillEvent::EVENT_TYPE]);
+        assert_eq!(entries.len(), builtins);
+    }
+    
+    #[test]
+    fn test_submitted_names_clash() {
+        let entries = DashMap::new();
+        let registrations = [EventRegistration::of::<FillEvent>(), EventRegistration::of::<FillEvent>()];
+        let duplicates = EventRegistry::register_submitted(&entries, &registrations);
+        assert_eq!(duplicates, vec![FillEvent::EVENT_TYPE]);
+        assert_eq!(entries.len(), 1);
+    }
+    
+    #[test]
+    fn test_register_keeps_first() {
+        assert!(!crate::register_event_type!(MarketDataEvent::EVENT_TYPE, FillEvent));
+        
+        // Still the built-in deserializer
+        let payload = serde_json::to_value(crate::test_fixtures::quote("ES", 6000.0)).unwrap();
+        let event = EventRegistry::deserialize(MarketDataEvent::EVENT_TYPE, payload).unwrap();
+        assert!(event.downcast_ref::<MarketDataEvent>().is_some());
+    }
+}
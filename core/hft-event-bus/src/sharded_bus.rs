@@ -0,0 +1,226 @@
+//! Event bus sharded by event type
+//!
+//! `EventBus` keeps every channel (and per-type statistics) in shared
+//! `DashMap`s, so publishers of unrelated types still meet on the same
+//! shard locks at very high rates. `ShardedEventBus` splits channels over
+//! independent shards chosen by an FNV-1a hash of the event type; each
+//! shard is a plain map behind its own lock that publishers only read.
+//!
+//! The sharded bus only does publish and subscribe: no middleware,
+//! recording, statistics or pattern subscriptions. Code written against
+//! `EventBusTrait` runs on either bus.
+
+use crate::bus::{EventBusTrait, PublishReceipt, CHANNEL_CAPACITY};
+use crate::events::{Event, EventEnvelope};
+use anyhow::Result;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, RwLock};
+use tokio::sync::broadcast;
+use tracing::debug;
+
+/// Shards used by `ShardedEventBus::default`
+pub const DEFAULT_SHARD_COUNT: usize = 16;
+
+/// Layout of a `ShardedEventBus`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShardingConfig {
+    /// Number of shards; must be a power of two
+    pub shard_count: usize,
+    /// Capacity of each event type's broadcast channel
+    pub channel_capacity: usize,
+}
+
+impl Default for ShardingConfig {
+    fn default() -> Self {
+        Self {
+            shard_count: DEFAULT_SHARD_COUNT,
+            channel_capacity: CHANNEL_CAPACITY,
+        }
+    }
+}
+
+/// Channels for the event types hashed to one shard
+#[derive(Default)]
+struct EventBusShard {
+    channels: RwLock<HashMap<String, broadcast::Sender<EventEnvelope>>>,
+}
+
+impl EventBusShard {
+    fn sender(&self, event_type: &str) -> Option<broadcast::Sender<EventEnvelope>> {
+        self.channels.read().unwrap().get(event_type).cloned()
+    }
+}
+
+/// Event bus whose channels are split over independent shards
+///
+/// Cloning yields a handle to the same channels.
+#[derive(Clone)]
+pub struct ShardedEventBus {
+    shards: Arc<Vec<EventBusShard>>,
+    shard_count: usize,
+    channel_capacity: usize,
+}
+
+impl ShardedEventBus {
+    /// Create bus with `shard_count` shards
+    ///
+    /// # Panics
+    ///
+    /// If `shard_count` is not a non-zero power of two.
+    pub fn new(shard_count: usize) -> Self {
+        Self::from_config(ShardingConfig {
+            shard_count,
+            ..ShardingConfig::default()
+        })
+    }
+
+    /// Create bus from `config`
+    ///
+    /// # Panics
+    ///
+    /// If `config.shard_count` is not a non-zero power of two.
+    pub fn from_config(config: ShardingConfig) -> Self {
+        assert!(
+            config.shard_count.is_power_of_two(),
+            "shard count {} is not a non-zero power of two",
+            config.shard_count
+        );
+        Self {
+            shards: Arc::new((0..config.shard_count).map(|_| EventBusShard::default()).collect()),
+            shard_count: config.shard_count,
+            channel_capacity: config.channel_capacity.max(1),
+        }
+    }
+
+    pub fn shard_count(&self) -> usize {
+        self.shard_count
+    }
+
+    /// Index of the shard holding `event_type`'s channel
+    pub fn shard_for(&self, event_type: &str) -> usize {
+        (fnv1a(event_type.as_bytes()) as usize) & (self.shard_count - 1)
+    }
+
+    /// Publish event with default priority
+    pub async fn publish<T: Event + Send + 'static>(&self, event: T) -> Result<PublishReceipt> {
+        Ok(self.send(EventEnvelope::new(event, 5)))
+    }
+
+    /// Publish an EventEnvelope directly
+    pub async fn publish_envelope(&self, envelope: EventEnvelope) -> Result<()> {
+        self.send(envelope);
+        Ok(())
+    }
+
+    /// Subscribe to a specific event type
+    pub async fn subscribe(&self, event_type: &str) -> broadcast::Receiver<EventEnvelope> {
+        let shard = &self.shards[self.shard_for(event_type)];
+        if let Some(sender) = shard.sender(event_type) {
+            return sender.subscribe();
+        }
+        let mut channels = shard.channels.write().unwrap();
+        channels
+            .entry(event_type.to_string())
+            .or_insert_with(|| {
+                debug!("Creating new sharded channel for subscription: {}", event_type);
+                broadcast::channel(self.channel_capacity).0
+            })
+            .subscribe()
+    }
+
+    /// Number of live receivers for `event_type`
+    pub fn subscriber_count(&self, event_type: &str) -> usize {
+        self.shards[self.shard_for(event_type)]
+            .sender(event_type)
+            .map_or(0, |sender| sender.receiver_count())
+    }
+
+    fn send(&self, envelope: EventEnvelope) -> PublishReceipt {
+        let (event_id, timestamp_ns) = (envelope.id, envelope.timestamp_ns);
+        let event_type = envelope.event.event_type();
+        let channels = self.shards[self.shard_for(event_type)].channels.read().unwrap();
+        let subscriber_count = match channels.get(event_type) {
+            // Sending only fails when there are no receivers
+            Some(sender) => sender.send(envelope).unwrap_or(0),
+            None => 0,
+        };
+        PublishReceipt::untracked(subscriber_count, event_id, timestamp_ns)
+    }
+}
+
+impl Default for ShardedEventBus {
+    fn default() -> Self {
+        Self::from_config(ShardingConfig::default())
+    }
+}
+
+impl EventBusTrait for ShardedEventBus {
+    fn publish<T: Event + Send + 'static>(&self, event: T) -> impl Future<Output = Result<PublishReceipt>> + Send {
+        ShardedEventBus::publish(self, event)
+    }
+
+    fn publish_envelope(&self, envelope: EventEnvelope) -> impl Future<Output = Result<()>> + Send {
+        ShardedEventBus::publish_envelope(self, envelope)
+    }
+
+    fn subscribe(&self, event_type: &str) -> impl Future<Output = broadcast::Receiver<EventEnvelope>> + Send {
+        ShardedEventBus::subscribe(self, event_type)
+    }
+}
+
+/// 64-bit FNV-1a hash
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+    bytes.iter().fold(OFFSET_BASIS, |hash, &byte| (hash ^ byte as u64).wrapping_mul(PRIME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fnv1a_reference_values() {
+        assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(fnv1a(b"foobar"), 0x8594_4171_f739_67e8);
+    }
+
+    #[test]
+    fn test_shard_assignment() {
+        let bus = ShardedEventBus::new(8);
+        for event_type in ["market_data", "signal", "order", "fill"] {
+            let shard = bus.shard_for(event_type);
+            assert!(shard < 8);
+            assert_eq!(shard, bus.shard_for(event_type));
+            assert_eq!(shard as u64, fnv1a(event_type.as_bytes()) % 8);
+        }
+        // A single shard takes everything
+        assert_eq!(ShardedEventBus::new(1).shard_for("market_data"), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "power of two")]
+    fn test_shard_count_must_be_power_of_two() {
+        ShardedEventBus::new(12);
+    }
+
+    #[tokio::test]
+    async fn test_channel_capacity_from_config() {
+        let bus = ShardedEventBus::from_config(ShardingConfig { shard_count: 4, channel_capacity: 2 });
+        let mut rx = bus.subscribe("health").await;
+        for i in 0..3 {
+            let event = crate::events::HealthEvent {
+                timestamp: i,
+                component: "gateway".to_string(),
+                status: crate::events::HealthStatus::Healthy,
+                message: String::new(),
+            };
+            assert_eq!(bus.publish(event).await.unwrap().subscriber_count, 1);
+        }
+        // The oldest event was evicted
+        assert!(matches!(rx.recv().await, Err(broadcast::error::RecvError::Lagged(1))));
+        assert_eq!(bus.subscriber_count("health"), 1);
+    }
+}
@@ -0,0 +1,147 @@
+//! JSON Schema validation for `SignalEvent::metadata`
+//!
+//! Each strategy can register a schema describing the metadata keys its
+//! signals must carry. Metadata is validated as a JSON object of strings:
+//!
+//! ```json
+//! {
+//!     "type": "object",
+//!     "required": ["reason"],
+//!     "properties": { "reason": { "enum": ["breakout", "mean_reversion"] } }
+//! }
+//! ```
+//!
+//! Signals from strategies without a registered schema are not checked.
+
+use crate::events::SignalEvent;
+use anyhow::{anyhow, Result};
+use jsonschema::JSONSchema;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// Signal metadata that does not match its strategy's schema
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("metadata of signal from {strategy_id} violates schema: {}", errors.join("; "))]
+pub struct ValidationError {
+    pub strategy_id: String,
+    /// One message per violation
+    pub errors: Vec<String>,
+}
+
+/// Metadata schemas indexed by strategy ID
+///
+/// Cloning yields a handle to the same registry.
+#[derive(Clone, Default)]
+pub struct SignalSchemaRegistry {
+    schemas: Arc<RwLock<HashMap<String, JSONSchema>>>,
+}
+
+impl SignalSchemaRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register (or replace) the schema for a strategy
+    ///
+    /// Fails if `schema_json` is not valid JSON or not a valid schema.
+    pub fn register(&self, strategy_id: &str, schema_json: &str) -> Result<()> {
+        let schema: serde_json::Value = serde_json::from_str(schema_json)?;
+        let compiled = JSONSchema::compile(&schema)
+            .map_err(|e| anyhow!("Invalid schema for strategy {}: {}", strategy_id, e))?;
+        self.schemas.write().unwrap().insert(strategy_id.to_string(), compiled);
+        Ok(())
+    }
+
+    /// Remove a strategy's schema, returning true if one was registered
+    pub fn unregister(&self, strategy_id: &str) -> bool {
+        self.schemas.write().unwrap().remove(strategy_id).is_some()
+    }
+
+    /// Check if a strategy has a schema
+    pub fn contains(&self, strategy_id: &str) -> bool {
+        self.schemas.read().unwrap().contains_key(strategy_id)
+    }
+
+    /// Validate a signal's metadata against its strategy's schema
+    pub fn validate(&self, signal: &SignalEvent) -> Result<(), ValidationError> {
+        let schemas = self.schemas.read().unwrap();
+        let Some(schema) = schemas.get(&signal.strategy_id) else {
+            return Ok(());
+        };
+
+        let metadata = serde_json::Value::Object(
+            signal.metadata.iter()
+                .map(|(key, value)| (key.clone(), serde_json::Value::String(value.clone())))
+                .collect(),
+        );
+        if let Err(errors) = schema.validate(&metadata) {
+            return Err(ValidationError {
+                strategy_id: signal.strategy_id.clone(),
+                errors: errors.map(|e| e.to_string()).collect(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::SignalDirection;
+    use uuid::Uuid;
+
+    const MOMENTUM_SCHEMA: &str = r#"{
+        "type": "object",
+        "required": ["reason", "confidence_source"],
+        "properties": {
+            "reason": { "enum": ["breakout", "mean_reversion"] },
+            "confidence_source": { "type": "string", "minLength": 1 }
+        }
+    }"#;
+
+    fn signal(strategy_id: &str, metadata: &[(&str, &str)]) -> SignalEvent {
+        SignalEvent {
+            signal_id: Uuid::new_v4(),
+            timestamp: 0,
+            strategy_id: strategy_id.to_string(),
+            symbol: "ES".to_string(),
+            direction: SignalDirection::Long,
+            strength: 0.5,
+            target_price: None,
+            stop_loss: None,
+            metadata: metadata.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+        }
+    }
+
+    #[test]
+    fn test_validate_metadata() {
+        let registry = SignalSchemaRegistry::new();
+        registry.register("momentum", MOMENTUM_SCHEMA).unwrap();
+        assert!(registry.contains("momentum"));
+
+        let valid = signal("momentum", &[("reason", "breakout"), ("confidence_source", "model"), ("extra", "ok")]);
+        assert!(registry.validate(&valid).is_ok());
+
+        let missing = signal("momentum", &[("reason", "breakout")]);
+        let err = registry.validate(&missing).unwrap_err();
+        assert_eq!(err.strategy_id, "momentum");
+        assert_eq!(err.errors.len(), 1);
+        assert!(err.errors[0].contains("confidence_source"));
+
+        let bad_values = signal("momentum", &[("reason", "hunch"), ("confidence_source", "")]);
+        assert_eq!(registry.validate(&bad_values).unwrap_err().errors.len(), 2);
+
+        // Unregistered strategies are not checked
+        assert!(registry.validate(&signal("other", &[])).is_ok());
+        assert!(registry.unregister("momentum"));
+        assert!(registry.validate(&missing).is_ok());
+    }
+
+    #[test]
+    fn test_register_invalid_schema() {
+        let registry = SignalSchemaRegistry::new();
+        assert!(registry.register("momentum", "not json").is_err());
+        assert!(registry.register("momentum", r#"{"type": "no_such_type"}"#).is_err());
+        assert!(!registry.contains("momentum"));
+    }
+}
@@ -0,0 +1,232 @@
+//! Per-event size budget
+//!
+//! A bus created with `EventBus::with_max_event_size` measures every
+//! published envelope as serialized JSON (its `SerializableEnvelope` form)
+//! and applies a `SizeBudgetPolicy` to envelopes over the limit. Events
+//! without a JSON form are not measured.
+
+use crate::events::EventEnvelope;
+use dashmap::DashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tracing::warn;
+
+/// Envelope larger than the bus's size budget
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("event is {actual} bytes, over the {limit} byte budget")]
+pub struct EventTooLargeError {
+    pub actual: usize,
+    pub limit: usize,
+}
+
+/// Shrinks an oversized envelope given the byte limit; `None` if it cannot
+pub type TruncateFn = Arc<dyn Fn(&EventEnvelope, usize) -> Option<EventEnvelope> + Send + Sync>;
+
+/// What to do with an envelope over the budget
+#[derive(Clone)]
+pub enum SizeBudgetPolicy {
+    /// Fail the publish with `EventTooLargeError`
+    Reject,
+    /// Log a warning and publish anyway
+    Warn,
+    /// Publish the envelope returned by the function instead; rejected if it
+    /// returns `None` or the replacement is still over the budget
+    Truncate(TruncateFn),
+}
+
+impl std::fmt::Debug for SizeBudgetPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SizeBudgetPolicy::Reject => f.write_str("Reject"),
+            SizeBudgetPolicy::Warn => f.write_str("Warn"),
+            SizeBudgetPolicy::Truncate(_) => f.write_str("Truncate(..)"),
+        }
+    }
+}
+
+/// Running total of measured bytes for one event type
+#[derive(Default)]
+struct SizeTotals {
+    bytes: AtomicU64,
+    count: AtomicU64,
+}
+
+/// Size limit, policy and per-type size totals of one bus
+pub(crate) struct SizeBudget {
+    limit: usize,
+    policy: SizeBudgetPolicy,
+    totals: DashMap<&'static str, SizeTotals>,
+}
+
+impl SizeBudget {
+    pub(crate) fn new(limit: usize, policy: SizeBudgetPolicy) -> Self {
+        Self {
+            limit,
+            policy,
+            totals: DashMap::new(),
+        }
+    }
+
+    /// Same limit and policy with no recorded sizes
+    pub(crate) fn fresh(&self) -> Self {
+        Self::new(self.limit, self.policy.clone())
+    }
+
+    /// Serialized size of `envelope`, or `None` if it has no JSON form
+    pub(crate) fn measure(envelope: &EventEnvelope) -> Option<usize> {
+        let serializable = envelope.to_serializable()?;
+        serde_json::to_vec(&serializable).ok().map(|json| json.len())
+    }
+
+    /// Apply the budget, returning the envelope to publish
+    ///
+    /// Only envelopes that are published count towards the average size.
+    pub(crate) fn enforce(&self, envelope: EventEnvelope) -> Result<EventEnvelope, EventTooLargeError> {
+        let Some(actual) = Self::measure(&envelope) else {
+            return Ok(envelope);
+        };
+        if actual <= self.limit {
+            self.record(envelope.event.event_type(), actual);
+            return Ok(envelope);
+        }
+
+        match &self.policy {
+            SizeBudgetPolicy::Reject => Err(EventTooLargeError { actual, limit: self.limit }),
+            SizeBudgetPolicy::Warn => {
+                warn!(
+                    "{} event {} is {} bytes, over the {} byte budget",
+                    envelope.event.event_type(),
+                    envelope.id,
+                    actual,
+                    self.limit
+                );
+                self.record(envelope.event.event_type(), actual);
+                Ok(envelope)
+            }
+            SizeBudgetPolicy::Truncate(truncate) => {
+                let Some(truncated) = truncate(&envelope, self.limit) else {
+                    return Err(EventTooLargeError { actual, limit: self.limit });
+                };
+                match Self::measure(&truncated) {
+                    Some(size) if size > self.limit => Err(EventTooLargeError { actual: size, limit: self.limit }),
+                    Some(size) => {
+                        self.record(truncated.event.event_type(), size);
+                        Ok(truncated)
+                    }
+                    None => Ok(truncated),
+                }
+            }
+        }
+    }
+
+    fn record(&self, event_type: &'static str, bytes: usize) {
+        let totals = self.totals.entry(event_type).or_default();
+        totals.bytes.fetch_add(bytes as u64, Ordering::Relaxed);
+        totals.count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Mean serialized size of published `event_type` envelopes (0 if none)
+    pub(crate) fn average(&self, event_type: &str) -> f64 {
+        match self.totals.get(event_type) {
+            Some(totals) => {
+                let count = totals.count.load(Ordering::Relaxed);
+                if count == 0 {
+                    0.0
+                } else {
+                    totals.bytes.load(Ordering::Relaxed) as f64 / count as f64
+                }
+            }
+            None => 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus::EventBus;
+    use crate::events::{HealthEvent, HealthStatus};
+
+    fn health(message: &str) -> HealthEvent {
+        HealthEvent {
+            timestamp: 0,
+            component: "feed".to_string(),
+            status: HealthStatus::Healthy,
+            message: message.to_string(),
+        }
+    }
+
+    /// Serialized size of `event` as `publish` would measure it
+    fn size_of(event: HealthEvent) -> usize {
+        SizeBudget::measure(&EventEnvelope::new(event, 5)).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_reject_at_boundary() {
+        let size = size_of(health("ok"));
+
+        let bus = EventBus::with_max_event_size(size);
+        let mut rx = bus.subscribe("health").await;
+        bus.publish(health("ok")).await.unwrap();
+        assert!(rx.try_recv().is_ok());
+
+        // One byte over
+        let err = bus.publish(health("ok!")).await.unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<EventTooLargeError>(),
+            Some(&EventTooLargeError { actual: size + 1, limit: size })
+        );
+        assert!(rx.try_recv().is_err());
+
+        let bus = EventBus::with_max_event_size(size - 1);
+        let err = bus.publish(health("ok")).await.unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<EventTooLargeError>(),
+            Some(&EventTooLargeError { actual: size, limit: size - 1 })
+        );
+        assert!(bus.publish_batch(&[health("")]).await.is_ok());
+        assert!(bus.publish_batch(&[health(""), health("ok")]).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_warn_and_truncate_policies() {
+        let limit = size_of(health("short"));
+
+        let bus = EventBus::with_size_budget_policy(limit, SizeBudgetPolicy::Warn);
+        let mut rx = bus.subscribe("health").await;
+        bus.publish(health("much longer than the budget")).await.unwrap();
+        assert!(rx.try_recv().is_ok());
+
+        let truncate: TruncateFn = Arc::new(|envelope, _limit| {
+            let event = envelope.event.downcast_ref::<HealthEvent>()?;
+            let mut shortened = event.clone();
+            shortened.message.truncate(5);
+            let mut replacement = EventEnvelope::new(shortened, envelope.priority);
+            replacement.timestamp_ns = envelope.timestamp_ns;
+            Some(replacement)
+        });
+        let bus = EventBus::with_size_budget_policy(limit, SizeBudgetPolicy::Truncate(truncate));
+        let mut rx = bus.subscribe("health").await;
+        bus.publish(health("much longer than the budget")).await.unwrap();
+        let received = rx.try_recv().unwrap();
+        assert_eq!(received.event.downcast_ref::<HealthEvent>().unwrap().message, "much ");
+
+        // Still too large after truncation
+        let noop: TruncateFn = Arc::new(|envelope, _limit| Some(envelope.clone()));
+        let bus = EventBus::with_size_budget_policy(limit, SizeBudgetPolicy::Truncate(noop));
+        assert!(bus.publish(health("much longer than the budget")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_average_event_size() {
+        let bus = EventBus::with_max_event_size(usize::MAX);
+        assert_eq!(bus.average_event_size_bytes("health"), 0.0);
+
+        bus.publish(health("a")).await.unwrap();
+        bus.publish(health("abc")).await.unwrap();
+        let expected = (size_of(health("a")) + size_of(health("abc"))) as f64 / 2.0;
+        assert_eq!(bus.average_event_size_bytes("health"), expected);
+
+        assert_eq!(EventBus::new().average_event_size_bytes("health"), 0.0);
+    }
+}
@@ -1,7 +1,16 @@
 //! Subscriber utilities and helpers
 
 use crate::events::{Event, EventEnvelope};
+use futures_util::future;
+use futures_util::stream::{self, Stream, StreamExt};
+use std::marker::PhantomData;
+use std::time::Duration;
 use tokio::sync::broadcast;
+use tokio::time::error::Elapsed;
+use tokio::time::Instant;
+
+/// Predicate applied by `FilteredSubscriber`
+pub type EnvelopePredicate = Box<dyn Fn(&EventEnvelope) -> bool + Send>;
 
 /// Helper for subscribing to specific event types
 pub struct Subscriber {
@@ -34,10 +43,724 @@ impl Subscriber {
         }
     }
     
+    /// Receive next event, waiting at most `duration`
+    pub async fn recv_timeout(&mut self, duration: Duration) -> Result<EventEnvelope, RecvTimeoutError> {
+        match tokio::time::timeout(duration, self.receiver.recv()).await {
+            Ok(result) => result.map_err(RecvTimeoutError::from),
+            Err(_) => Err(RecvTimeoutError::Timeout),
+        }
+    }
+    
+    /// Receive next event, waiting until `deadline` at the latest
+    ///
+    /// An event that is already queued is returned even if `deadline` has passed.
+    pub async fn recv_deadline(&mut self, deadline: Instant) -> Result<EventEnvelope, RecvTimeoutError> {
+        match tokio::time::timeout_at(deadline, self.receiver.recv()).await {
+            Ok(result) => result.map_err(RecvTimeoutError::from),
+            Err(_) => Err(RecvTimeoutError::Timeout),
+        }
+    }
+    
+    /// Receive next event, or `default_fn()` if none arrives within `duration`
+    ///
+    /// Lag is skipped over; a closed channel also yields the default.
+    pub async fn recv_or_default<F>(&mut self, duration: Duration, default_fn: F) -> EventEnvelope
+    where
+        F: Fn() -> EventEnvelope,
+    {
+        let deadline = Instant::now() + duration;
+        loop {
+            match self.recv_deadline(deadline).await {
+                Ok(envelope) => return envelope,
+                Err(RecvTimeoutError::Lagged(skipped)) => {
+                    tracing::warn!("Subscriber lagged, skipped {} events", skipped);
+                }
+                Err(RecvTimeoutError::Timeout | RecvTimeoutError::Closed) => return default_fn(),
+            }
+        }
+    }
+    
     /// Resubscribe (useful after lagging)
     pub fn resubscribe(&self) -> Self {
         Self {
             receiver: self.receiver.resubscribe(),
         }
     }
+    
+    /// Only deliver envelopes matching the predicate
+    pub fn filter<F>(self, predicate: F) -> FilteredSubscriber
+    where
+        F: Fn(&EventEnvelope) -> bool + Send + 'static,
+    {
+        FilteredSubscriber {
+            receiver: self.receiver,
+            predicate: Box::new(predicate),
+        }
+    }
+    
+    /// Only deliver market events for the given symbol
+    pub fn filter_symbol(self, symbol: impl Into<String>) -> FilteredSubscriber {
+        let symbol = symbol.into();
+        self.filter(move |envelope| {
+            envelope.event.as_market_event().and_then(|e| e.symbol()) == Some(symbol.as_str())
+        })
+    }
+}
+
+impl Subscriber {
+    /// Receive next event, skipping over lag (`None` once the channel closes)
+    async fn next_envelope(&mut self) -> Option<EventEnvelope> {
+        loop {
+            match self.receiver.recv().await {
+                Ok(event) => return Some(event),
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::warn!("Subscriber stream lagged, skipped {} events", skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    }
+    
+    /// Adapt into a `Stream` that ends when the channel closes
+    ///
+    /// Unlike `recv`, lagging does not end the stream.
+    pub fn into_stream(self) -> impl Stream<Item = EventEnvelope> + Send {
+        stream::unfold(self, |mut subscriber| async move {
+            let envelope = subscriber.next_envelope().await?;
+            Some((envelope, subscriber))
+        })
+    }
+    
+    /// Stream envelopes until `f` first returns `false`
+    pub fn take_while_async<F>(self, f: F) -> impl Stream<Item = EventEnvelope> + Send
+    where
+        F: Fn(&EventEnvelope) -> bool + Send + 'static,
+    {
+        self.into_stream().take_while(move |envelope| future::ready(f(envelope)))
+    }
+    
+    /// Stream envelopes after `f` first returns `false`
+    pub fn skip_while_async<F>(self, f: F) -> impl Stream<Item = EventEnvelope> + Send
+    where
+        F: Fn(&EventEnvelope) -> bool + Send + 'static,
+    {
+        self.into_stream().skip_while(move |envelope| future::ready(f(envelope)))
+    }
+    
+    /// Stream envelopes, yielding `Err(Elapsed)` whenever none arrives within `duration`
+    ///
+    /// The stream keeps waiting after a timeout and ends when the channel closes.
+    pub fn timeout(self, duration: Duration) -> impl Stream<Item = Result<EventEnvelope, Elapsed>> + Send {
+        stream::unfold(self, move |mut subscriber| async move {
+            match tokio::time::timeout(duration, subscriber.next_envelope()).await {
+                Ok(Some(envelope)) => Some((Ok(envelope), subscriber)),
+                Ok(None) => None,
+                Err(elapsed) => Some((Err(elapsed), subscriber)),
+            }
+        })
+    }
+}
+
+impl Subscriber {
+    /// Only deliver events of type `T`, mapped through `f`
+    pub fn map_event<T, R, F>(self, f: F) -> MappedSubscriber<R>
+    where
+        T: Event + 'static,
+        F: Fn(&T) -> R + Send + 'static,
+    {
+        MappedSubscriber {
+            inner: self,
+            map: Box::new(move |envelope: &EventEnvelope| envelope.event.downcast_ref::<T>().map(&f)),
+        }
+    }
+    
+    /// Only deliver events of type `T`
+    pub fn filter_type<T: Event + 'static>(self) -> TypedSubscriber<T> {
+        TypedSubscriber {
+            inner: self,
+            current: None,
+            _marker: PhantomData,
+        }
+    }
+    
+    /// Fold every event into a running state, starting from `initial`
+    pub fn aggregate<S, F>(self, initial: S, fold: F) -> AggregatingSubscriber<S>
+    where
+        S: Send + Clone + 'static,
+        F: Fn(&mut S, &EventEnvelope) + Send + 'static,
+    {
+        AggregatingSubscriber {
+            inner: self,
+            state: initial,
+            fold: Box::new(fold),
+        }
+    }
+    
+    /// Report gaps in sequence numbers (see `EventBus::enable_sequencing`)
+    pub fn assert_contiguous(self) -> ContiguousSubscriber {
+        ContiguousSubscriber {
+            receiver: self.receiver,
+            expected: None,
+            pending: None,
+        }
+    }
+}
+
+/// Why `Subscriber::recv_timeout` / `recv_deadline` returned no event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum RecvTimeoutError {
+    #[error("no event before the timeout")]
+    Timeout,
+    #[error("channel closed")]
+    Closed,
+    /// The subscriber fell behind and this many events were dropped; the
+    /// next receive continues with the oldest retained event
+    #[error("subscriber lagged, skipped {0} events")]
+    Lagged(u64),
+}
+
+impl From<broadcast::error::RecvError> for RecvTimeoutError {
+    fn from(e: broadcast::error::RecvError) -> Self {
+        match e {
+            broadcast::error::RecvError::Closed => Self::Closed,
+            broadcast::error::RecvError::Lagged(skipped) => Self::Lagged(skipped),
+        }
+    }
+}
+
+/// Sequence numbers of a channel skipped from `expected` to `got`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("sequence gap: expected {expected}, got {got}")]
+pub struct GapError {
+    pub expected: u64,
+    pub got: u64,
+}
+
+/// Subscriber that checks envelopes arrive with consecutive sequence numbers
+///
+/// Numbering starts from the first sequenced envelope received. Envelopes
+/// without a sequence number are passed through unchecked.
+pub struct ContiguousSubscriber {
+    receiver: broadcast::Receiver<EventEnvelope>,
+    /// Sequence number the next envelope should carry
+    expected: Option<u64>,
+    /// Envelope that revealed a gap, delivered by the next receive
+    pending: Option<EventEnvelope>,
+}
+
+impl ContiguousSubscriber {
+    /// Receive next event (`Ok(None)` once the channel closes)
+    ///
+    /// On a gap returns `GapError`; the envelope that revealed it is
+    /// returned by the next call, and checking resumes from there. Events
+    /// lost to lag show up as a gap.
+    pub async fn recv(&mut self) -> Result<Option<EventEnvelope>, GapError> {
+        if let Some(envelope) = self.pending.take() {
+            return Ok(Some(envelope));
+        }
+        loop {
+            match self.receiver.recv().await {
+                Ok(envelope) => return self.check(envelope).map(Some),
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::warn!("Contiguous subscriber lagged, skipped {} events", skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => return Ok(None),
+            }
+        }
+    }
+    
+    /// Try to receive without blocking (`Ok(None)` if nothing is queued)
+    pub fn try_recv(&mut self) -> Result<Option<EventEnvelope>, GapError> {
+        if let Some(envelope) = self.pending.take() {
+            return Ok(Some(envelope));
+        }
+        loop {
+            match self.receiver.try_recv() {
+                Ok(envelope) => return self.check(envelope).map(Some),
+                Err(broadcast::error::TryRecvError::Lagged(_)) => continue,
+                Err(_) => return Ok(None),
+            }
+        }
+    }
+    
+    fn check(&mut self, envelope: EventEnvelope) -> Result<EventEnvelope, GapError> {
+        let Some(got) = envelope.sequence_number else {
+            return Ok(envelope);
+        };
+        let expected = self.expected.replace(got + 1);
+        match expected {
+            Some(expected) if expected != got => {
+                self.pending = Some(envelope);
+                Err(GapError { expected, got })
+            }
+            _ => Ok(envelope),
+        }
+    }
+}
+
+/// Subscriber yielding values mapped from one event type
+pub struct MappedSubscriber<R> {
+    inner: Subscriber,
+    map: Box<dyn Fn(&EventEnvelope) -> Option<R> + Send>,
+}
+
+impl<R> MappedSubscriber<R> {
+    /// Receive next mapped event, skipping events of other types
+    pub async fn recv(&mut self) -> Option<R> {
+        loop {
+            let envelope = self.inner.recv().await?;
+            if let Some(mapped) = (self.map)(&envelope) {
+                return Some(mapped);
+            }
+        }
+    }
+    
+    /// Try to receive a mapped event without blocking
+    pub fn try_recv(&mut self) -> Option<R> {
+        loop {
+            let envelope = self.inner.try_recv()?;
+            if let Some(mapped) = (self.map)(&envelope) {
+                return Some(mapped);
+            }
+        }
+    }
+}
+
+/// Subscriber yielding only events of type `T`
+pub struct TypedSubscriber<T> {
+    inner: Subscriber,
+    /// Last received envelope (the returned `&T` borrows from it)
+    current: Option<EventEnvelope>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: Event + 'static> TypedSubscriber<T> {
+    /// Receive next event of type `T`, skipping events of other types
+    pub async fn recv(&mut self) -> Option<(EventEnvelope, &T)> {
+        loop {
+            let envelope = self.inner.recv().await?;
+            if envelope.event.downcast_ref::<T>().is_some() {
+                return Some(self.hold(envelope));
+            }
+        }
+    }
+    
+    /// Try to receive an event of type `T` without blocking
+    pub fn try_recv(&mut self) -> Option<(EventEnvelope, &T)> {
+        loop {
+            let envelope = self.inner.try_recv()?;
+            if envelope.event.downcast_ref::<T>().is_some() {
+                return Some(self.hold(envelope));
+            }
+        }
+    }
+    
+    fn hold(&mut self, envelope: EventEnvelope) -> (EventEnvelope, &T) {
+        let current = self.current.insert(envelope);
+        let event = current.event.downcast_ref::<T>().expect("checked by caller");
+        (current.clone(), event)
+    }
+}
+
+/// Subscriber folding each event into an accumulated state
+pub struct AggregatingSubscriber<S> {
+    inner: Subscriber,
+    state: S,
+    fold: Box<dyn Fn(&mut S, &EventEnvelope) + Send>,
+}
+
+impl<S: Clone> AggregatingSubscriber<S> {
+    /// Receive next event and return the state after folding it in
+    pub async fn recv_state(&mut self) -> Option<S> {
+        let envelope = self.inner.recv().await?;
+        (self.fold)(&mut self.state, &envelope);
+        Some(self.state.clone())
+    }
+    
+    /// Fold in the next event without blocking
+    pub fn try_recv_state(&mut self) -> Option<S> {
+        let envelope = self.inner.try_recv()?;
+        (self.fold)(&mut self.state, &envelope);
+        Some(self.state.clone())
+    }
+    
+    /// State accumulated so far
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+}
+
+/// Subscriber that discards envelopes not matching a predicate
+pub struct FilteredSubscriber {
+    receiver: broadcast::Receiver<EventEnvelope>,
+    predicate: EnvelopePredicate,
+}
+
+impl FilteredSubscriber {
+    /// Receive next matching event (`None` once the channel closes)
+    pub async fn recv(&mut self) -> Option<EventEnvelope> {
+        loop {
+            match self.receiver.recv().await {
+                Ok(event) if (self.predicate)(&event) => return Some(event),
+                Ok(_) => continue,
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::warn!("Filtered subscriber lagged, skipped {} events", skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    }
+    
+    /// Try to receive a matching event without blocking
+    pub fn try_recv(&mut self) -> Option<EventEnvelope> {
+        loop {
+            match self.receiver.try_recv() {
+                Ok(event) if (self.predicate)(&event) => return Some(event),
+                Ok(_) | Err(broadcast::error::TryRecvError::Lagged(_)) => continue,
+                Err(_) => return None,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus::EventBus;
+    use crate::events::MarketDataEvent;
+    use crate::test_fixtures::quote;
+    
+    #[tokio::test]
+    async fn test_filter_symbol() {
+        let bus = EventBus::new();
+        let mut filtered = Subscriber::new(bus.subscribe_market_data().await).filter_symbol("NQ");
+        let mut unfiltered = Subscriber::new(bus.subscribe_market_data().await);
+        
+        let symbols = ["ES", "NQ", "CL", "GC"];
+        for i in 0..1000 {
+            bus.publish(quote(symbols[i % symbols.len()], i as f64)).await.unwrap();
+        }
+        
+        let mut received = 0;
+        while let Some(envelope) = filtered.try_recv() {
+            let market = envelope.event.as_market_event().unwrap();
+            assert_eq!(market.symbol(), Some("NQ"));
+            received += 1;
+        }
+        assert_eq!(received, 250);
+        
+        let mut all = 0;
+        while unfiltered.try_recv().is_some() {
+            all += 1;
+        }
+        assert_eq!(all, 1000);
+    }
+    
+    #[tokio::test]
+    async fn test_filter_recv_skips_until_match() {
+        let bus = EventBus::new();
+        let mut filtered = Subscriber::new(bus.subscribe_market_data().await)
+            .filter(|envelope| envelope.priority == 0);
+        
+        bus.publish(quote("ES", 1.0)).await.unwrap();
+        bus.publish_with_priority(quote("ES", 2.0), 0).await.unwrap();
+        
+        let envelope = filtered.recv().await.unwrap();
+        assert_eq!(envelope.priority, 0);
+        assert!(filtered.try_recv().is_none());
+    }
+    
+    /// Subscriber over a closed channel holding prices 0..count
+    fn bounded(count: usize) -> Subscriber {
+        let (sender, receiver) = broadcast::channel(64);
+        for i in 0..count {
+            sender.send(EventEnvelope::new(quote("ES", i as f64), 5)).unwrap();
+        }
+        Subscriber::new(receiver)
+    }
+    
+    fn price(envelope: &EventEnvelope) -> f64 {
+        envelope.event.downcast_ref::<MarketDataEvent>().unwrap().price
+    }
+    
+    #[tokio::test]
+    async fn test_into_stream() {
+        let envelopes: Vec<EventEnvelope> = bounded(5).into_stream().collect().await;
+        assert_eq!(envelopes.iter().map(price).collect::<Vec<_>>(), vec![0.0, 1.0, 2.0, 3.0, 4.0]);
+    }
+    
+    #[tokio::test]
+    async fn test_take_and_skip_while_async() {
+        let taken: Vec<EventEnvelope> = bounded(6).take_while_async(|e| price(e) < 3.0).collect().await;
+        assert_eq!(taken.iter().map(price).collect::<Vec<_>>(), vec![0.0, 1.0, 2.0]);
+        
+        let skipped: Vec<EventEnvelope> = bounded(6).skip_while_async(|e| price(e) < 3.0).collect().await;
+        assert_eq!(skipped.iter().map(price).collect::<Vec<_>>(), vec![3.0, 4.0, 5.0]);
+    }
+    
+    #[tokio::test]
+    async fn test_timeout_stream() {
+        let (sender, receiver) = broadcast::channel(8);
+        sender.send(EventEnvelope::new(quote("ES", 1.0), 5)).unwrap();
+        sender.send(EventEnvelope::new(quote("ES", 2.0), 5)).unwrap();
+        
+        // Sender stays open, so the third poll times out
+        let results: Vec<Result<EventEnvelope, Elapsed>> = Subscriber::new(receiver)
+            .timeout(Duration::from_millis(10))
+            .take(3)
+            .collect()
+            .await;
+        assert_eq!(price(results[0].as_ref().unwrap()), 1.0);
+        assert_eq!(price(results[1].as_ref().unwrap()), 2.0);
+        assert!(results[2].is_err());
+        
+        drop(sender);
+    }
+    
+    async fn publish_mixed(bus: &EventBus) {
+        for i in 0..10 {
+            bus.publish(quote("ES", i as f64)).await.unwrap();
+            if i % 3 == 0 {
+                bus.publish(crate::events::HealthEvent {
+                    timestamp: i,
+                    component: "feed".to_string(),
+                    status: crate::events::HealthStatus::Healthy,
+                    message: String::new(),
+                }).await.unwrap();
+            }
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_map_event_mixed_stream() {
+        let bus = EventBus::new();
+        let mut prices = Subscriber::new(bus.subscribe_pattern("*").await)
+            .map_event(|event: &MarketDataEvent| event.price);
+        let mut components = Subscriber::new(bus.subscribe_pattern("*").await)
+            .map_event(|event: &crate::events::HealthEvent| event.timestamp);
+        
+        publish_mixed(&bus).await;
+        
+        let mut received = Vec::new();
+        while let Some(price) = prices.try_recv() {
+            received.push(price);
+        }
+        assert_eq!(received, (0..10).map(|i| i as f64).collect::<Vec<_>>());
+        
+        assert_eq!(components.recv().await, Some(0));
+        let rest: Vec<i64> = std::iter::from_fn(|| components.try_recv()).collect();
+        assert_eq!(rest, vec![3, 6, 9]);
+    }
+    
+    #[tokio::test]
+    async fn test_filter_type_mixed_stream() {
+        let bus = EventBus::new();
+        let mut health = Subscriber::new(bus.subscribe_pattern("*").await)
+            .filter_type::<crate::events::HealthEvent>();
+        
+        publish_mixed(&bus).await;
+        
+        let (envelope, event) = health.recv().await.unwrap();
+        assert_eq!(envelope.event.event_type(), "health");
+        assert_eq!(event.timestamp, 0);
+        
+        let mut timestamps = Vec::new();
+        while let Some((_, event)) = health.try_recv() {
+            timestamps.push(event.timestamp);
+        }
+        assert_eq!(timestamps, vec![3, 6, 9]);
+    }
+    
+    fn sequenced(n: u64) -> EventEnvelope {
+        let mut envelope = EventEnvelope::new(quote("ES", n as f64), 5);
+        envelope.sequence_number = Some(n);
+        envelope
+    }
+    
+    #[tokio::test]
+    async fn test_contiguous_detects_dropped_envelopes() {
+        let (tx, rx) = broadcast::channel(16);
+        let mut subscriber = Subscriber::new(rx).assert_contiguous();
+        
+        // 3 and 6..8 are dropped; the unsequenced envelope is not checked
+        for n in [1, 2, 4, 5] {
+            tx.send(sequenced(n)).unwrap();
+        }
+        tx.send(EventEnvelope::new(quote("ES", 0.0), 5)).unwrap();
+        tx.send(sequenced(9)).unwrap();
+        drop(tx);
+        
+        assert_eq!(subscriber.recv().await.unwrap().unwrap().sequence_number, Some(1));
+        assert_eq!(subscriber.recv().await.unwrap().unwrap().sequence_number, Some(2));
+        assert_eq!(subscriber.recv().await.unwrap_err(), GapError { expected: 3, got: 4 });
+        assert_eq!(subscriber.recv().await.unwrap().unwrap().sequence_number, Some(4));
+        assert_eq!(subscriber.try_recv().unwrap().unwrap().sequence_number, Some(5));
+        assert_eq!(subscriber.try_recv().unwrap().unwrap().sequence_number, None);
+        assert_eq!(subscriber.try_recv().unwrap_err(), GapError { expected: 6, got: 9 });
+        assert_eq!(subscriber.try_recv().unwrap().unwrap().sequence_number, Some(9));
+        assert!(subscriber.recv().await.unwrap().is_none());
+    }
+    
+    #[tokio::test]
+    async fn test_sequenced_channel() {
+        let bus = EventBus::new();
+        let mut plain = bus.subscribe("order").await;
+        assert!(bus.enable_sequencing("market_data"));
+        assert!(!bus.enable_sequencing("market_data"));
+        let mut subscriber = Subscriber::new(bus.subscribe_market_data().await).assert_contiguous();
+        
+        let publishers: Vec<_> = (0..4)
+            .map(|p| {
+                let bus = bus.clone();
+                tokio::spawn(async move {
+                    for i in 0..50 {
+                        bus.publish(quote(format!("P{}", p), i as f64)).await.unwrap();
+                    }
+                })
+            })
+            .collect();
+        for publisher in publishers {
+            publisher.await.unwrap();
+        }
+        bus.publish_unsequenced(quote("ES", 0.0)).await.unwrap();
+        
+        for n in 0..200 {
+            assert_eq!(subscriber.try_recv().unwrap().unwrap().sequence_number, Some(n));
+        }
+        assert_eq!(subscriber.try_recv().unwrap().unwrap().sequence_number, None);
+        
+        // Other channels are not numbered
+        bus.publish(crate::events::OrderEvent {
+            order_id: uuid::Uuid::nil(),
+            signal_id: None,
+            timestamp: 0,
+            symbol: "ES".to_string(),
+            side: crate::events::OrderSide::Buy,
+            order_type: crate::events::OrderType::Market,
+            quantity: 1.0,
+            price: None,
+        })
+        .await
+        .unwrap();
+        assert_eq!(plain.recv().await.unwrap().sequence_number, None);
+    }
+    
+    #[tokio::test(start_paused = true)]
+    async fn test_recv_timeout() {
+        let (tx, rx) = broadcast::channel(2);
+        let mut subscriber = Subscriber::new(rx);
+        
+        let start = Instant::now();
+        assert_eq!(subscriber.recv_timeout(Duration::from_millis(100)).await.unwrap_err(), RecvTimeoutError::Timeout);
+        assert_eq!(start.elapsed(), Duration::from_millis(100));
+        
+        let envelope = EventEnvelope::new(quote("ES", 1.0), 5);
+        let id = envelope.id;
+        tx.send(envelope).unwrap();
+        assert_eq!(subscriber.recv_timeout(Duration::from_millis(100)).await.unwrap().id, id);
+        assert_eq!(start.elapsed(), Duration::from_millis(100));
+        
+        for i in 0..3 {
+            tx.send(EventEnvelope::new(quote("ES", i as f64), 5)).unwrap();
+        }
+        assert_eq!(subscriber.recv_timeout(Duration::from_millis(100)).await.unwrap_err(), RecvTimeoutError::Lagged(1));
+        assert!(subscriber.recv_timeout(Duration::from_millis(100)).await.is_ok());
+        assert!(subscriber.recv_timeout(Duration::from_millis(100)).await.is_ok());
+        
+        drop(tx);
+        assert_eq!(subscriber.recv_timeout(Duration::from_millis(100)).await.unwrap_err(), RecvTimeoutError::Closed);
+        assert_eq!(start.elapsed(), Duration::from_millis(100));
+    }
+    
+    #[tokio::test(start_paused = true)]
+    async fn test_recv_deadline() {
+        let (tx, rx) = broadcast::channel(16);
+        let mut subscriber = Subscriber::new(rx);
+        let deadline = Instant::now() + Duration::from_millis(50);
+        let waiter = tokio::spawn(async move {
+            let result = subscriber.recv_deadline(deadline).await;
+            (result, subscriber)
+        });
+        
+        // The waiting task does not hold up the runtime
+        tokio::time::advance(Duration::from_millis(49)).await;
+        assert!(!waiter.is_finished());
+        let envelope = EventEnvelope::new(quote("ES", 1.0), 5);
+        let id = envelope.id;
+        tx.send(envelope).unwrap();
+        let (result, mut subscriber) = waiter.await.unwrap();
+        assert_eq!(result.unwrap().id, id);
+        
+        // A queued event wins over an expired deadline
+        tokio::time::advance(Duration::from_millis(10)).await;
+        tx.send(EventEnvelope::new(quote("ES", 2.0), 5)).unwrap();
+        assert!(subscriber.recv_deadline(deadline).await.is_ok());
+        let start = Instant::now();
+        assert_eq!(subscriber.recv_deadline(deadline).await.unwrap_err(), RecvTimeoutError::Timeout);
+        assert_eq!(start.elapsed(), Duration::ZERO);
+        
+        let deadline = Instant::now() + Duration::from_millis(30);
+        assert_eq!(subscriber.recv_deadline(deadline).await.unwrap_err(), RecvTimeoutError::Timeout);
+        assert_eq!(Instant::now(), deadline);
+    }
+    
+    #[tokio::test(start_paused = true)]
+    async fn test_recv_or_default() {
+        let (tx, rx) = broadcast::channel(16);
+        let mut subscriber = Subscriber::new(rx);
+        let fallback = EventEnvelope::new(quote("DEFAULT", 0.0), 0);
+        let fallback_id = fallback.id;
+        
+        let start = Instant::now();
+        let envelope = subscriber.recv_or_default(Duration::from_millis(20), || fallback.clone()).await;
+        assert_eq!(envelope.id, fallback_id);
+        assert_eq!(start.elapsed(), Duration::from_millis(20));
+        
+        let sent = EventEnvelope::new(quote("ES", 1.0), 5);
+        let sent_id = sent.id;
+        tx.send(sent).unwrap();
+        let envelope = subscriber.recv_or_default(Duration::from_millis(20), || fallback.clone()).await;
+        assert_eq!(envelope.id, sent_id);
+        assert_eq!(start.elapsed(), Duration::from_millis(20));
+    }
+    
+    #[tokio::test]
+    async fn test_aggregate_volume() {
+        let bus = EventBus::new();
+        let mut total = Subscriber::new(bus.subscribe_market_data().await).aggregate(0.0, |volume: &mut f64, envelope| {
+            if let Some(md) = envelope.event.downcast_ref::<MarketDataEvent>() {
+                *volume += md.volume;
+            }
+        });
+        assert_eq!(*total.state(), 0.0);
+        
+        let mut expected = 0.0;
+        for i in 1..=10 {
+            let mut event = quote("ES", 100.0);
+            event.volume = i as f64;
+            bus.publish(event).await.unwrap();
+            
+            expected += i as f64;
+            assert_eq!(total.recv_state().await, Some(expected));
+            assert_eq!(*total.state(), expected);
+        }
+        assert_eq!(expected, 55.0);
+        assert_eq!(total.try_recv_state(), None);
+        
+        // Structured state, e.g. VWAP inputs
+        let mut vwap = Subscriber::new(bus.subscribe_market_data().await).aggregate((0.0, 0.0), |(notional, volume): &mut (f64, f64), envelope| {
+            if let Some(md) = envelope.event.downcast_ref::<MarketDataEvent>() {
+                *notional += md.price * md.volume;
+                *volume += md.volume;
+            }
+        });
+        for (price, volume) in [(100.0, 1.0), (102.0, 3.0)] {
+            let mut event = quote("ES", price);
+            event.volume = volume;
+            bus.publish(event).await.unwrap();
+        }
+        vwap.try_recv_state().unwrap();
+        let (notional, volume) = vwap.try_recv_state().unwrap();
+        assert_eq!(notional / volume, 101.5);
+    }
 }
@@ -0,0 +1,17 @@
+//! Event fixtures shared by unit tests
+
+use crate::events::MarketDataEvent;
+
+/// One-lot print at `price` inside a quarter-point quote, five lots a side
+pub(crate) fn quote(symbol: impl Into<String>, price: f64) -> MarketDataEvent {
+    MarketDataEvent {
+        timestamp: 0,
+        symbol: symbol.into(),
+        price,
+        volume: 1.0,
+        bid_price: price - 0.25,
+        bid_size: 5.0,
+        ask_price: price + 0.25,
+        ask_size: 5.0,
+    }
+}
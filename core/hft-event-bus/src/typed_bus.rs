@@ -4,10 +4,66 @@
 //! the MarketEvent trait for zero-allocation event processing.
 
 use market_data_engine::types::{MarketEvent, EventType, TradeV2, QuoteV2};
-use crate::fast_channel::{FastChannel, SendError};
+use crate::fast_channel::{FastChannel, SendError, TrySendError};
+use crate::fast_channel::AsyncReceiver;
 use dashmap::DashMap;
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
 use std::any::TypeId;
+use std::thread;
+use std::time::Duration;
+
+/// Default capacity of channels created by the bus
+const CHANNEL_CAPACITY: usize = 100_000;
+
+/// How often pipeline threads check whether their output was dropped
+const PIPELINE_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Fan-out receivers registered by one `subscribe_n` / `subscribe_load_balanced` call
+struct Tap<E> {
+    senders: Vec<flume::Sender<E>>,
+    /// Round-robin cursor for load-balanced taps (`None` copies to every receiver)
+    next: Option<AtomicUsize>,
+    /// `E::clone`, captured where `E: Clone` is known
+    clone: fn(&E) -> E,
+}
+
+impl<E> Tap<E> {
+    /// Hand `event` to the receivers without blocking
+    ///
+    /// Returns how many copies were dropped because a receiver was full.
+    fn deliver(&self, event: &E) -> u64 {
+        match &self.next {
+            None => self.senders.iter()
+                .filter(|sender| !sender.is_disconnected())
+                .filter(|sender| matches!(sender.try_send((self.clone)(event)), Err(flume::TrySendError::Full(_))))
+                .count() as u64,
+            Some(next) => {
+                // Skip receivers that have been dropped
+                for _ in 0..self.senders.len() {
+                    let index = next.fetch_add(1, Ordering::Relaxed) % self.senders.len();
+                    match self.senders[index].try_send((self.clone)(event)) {
+                        Ok(()) => return 0,
+                        Err(flume::TrySendError::Full(_)) => return 1,
+                        Err(flume::TrySendError::Disconnected(_)) => {}
+                    }
+                }
+                0
+            }
+        }
+    }
+    
+    /// True once every receiver has been dropped
+    fn is_disconnected(&self) -> bool {
+        self.senders.iter().all(|sender| sender.is_disconnected())
+    }
+}
+
+/// Registered fan-out hooks for one event type
+struct TapList<E> {
+    taps: RwLock<Vec<Tap<E>>>,
+}
 
 /// Typed event bus for MarketEvent types
 ///
@@ -16,6 +72,21 @@ use std::any::TypeId;
 /// - Zero-allocation transmission
 /// - Lock-free concurrent access
 ///
+/// # Subscription modes
+/// - [`subscribe`](Self::subscribe): receivers share the type's channel and
+///   compete for events — whichever receiver is free takes the next one.
+/// - [`subscribe_n`](Self::subscribe_n): every receiver gets its own copy of
+///   every event (fan-out).
+/// - [`subscribe_load_balanced`](Self::subscribe_load_balanced): each event is
+///   delivered to exactly one receiver, assigned in strict round-robin order.
+/// - [`subscribe_map`](Self::subscribe_map) /
+///   [`subscribe_filter_map`](Self::subscribe_filter_map): like `subscribe`,
+///   but a background thread transforms events before they reach the receiver.
+///
+/// [`pipe`](Self::pipe) and [`pipe_batch`](Self::pipe_batch) go the other way:
+/// a background thread transforms events of one type and publishes the
+/// results back onto the bus as another type.
+///
 /// # Example
 /// ```
 /// use hft_event_bus::typed_bus::TypedEventBus;
@@ -35,8 +106,108 @@ pub struct TypedEventBus {
     /// Channels indexed by TypeId
     channels: Arc<DashMap<TypeId, Arc<dyn std::any::Any + Send + Sync>>>,
     
+    /// Fan-out hooks indexed by TypeId (values are `TapList<E>`)
+    taps: Arc<DashMap<TypeId, Arc<dyn std::any::Any + Send + Sync>>>,
+    
     /// Statistics
     stats: Arc<DashMap<TypeId, TypedEventStats>>,
+    
+    /// Channel capacity per event type (set through `TypedEventBusBuilder`)
+    capacities: Arc<HashMap<TypeId, usize>>,
+    
+    /// Capacity of channels for types without an entry in `capacities`
+    default_capacity: usize,
+}
+
+/// Builder configuring channel capacities per event type
+///
+/// ```ignore
+/// let bus = TypedEventBus::builder()
+///     .capacity_for::<TradeV2>(1_000_000)
+///     .default_capacity(10_000)
+///     .build();
+/// ```
+#[derive(Debug, Clone)]
+pub struct TypedEventBusBuilder {
+    capacities: HashMap<TypeId, usize>,
+    default_capacity: usize,
+}
+
+impl TypedEventBusBuilder {
+    pub fn new() -> Self {
+        Self {
+            capacities: HashMap::new(),
+            default_capacity: CHANNEL_CAPACITY,
+        }
+    }
+    
+    /// Set the channel capacity for event type `E`
+    pub fn capacity_for<E: MarketEvent>(mut self, capacity: usize) -> Self {
+        self.capacities.insert(TypeId::of::<E>(), capacity.max(1));
+        self
+    }
+    
+    /// Set the capacity for types without their own (default 100,000)
+    pub fn default_capacity(mut self, capacity: usize) -> Self {
+        self.default_capacity = capacity.max(1);
+        self
+    }
+    
+    pub fn build(self) -> TypedEventBus {
+        TypedEventBus {
+            capacities: Arc::new(self.capacities),
+            default_capacity: self.default_capacity,
+            ..TypedEventBus::new()
+        }
+    }
+}
+
+impl Default for TypedEventBusBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Background thread of a [`TypedEventBus::pipe`] or [`TypedEventBus::pipe_batch`]
+///
+/// Dropping the handle also stops the pipe, without waiting for the thread.
+pub struct PipeHandle {
+    stop: Arc<AtomicBool>,
+    thread: Option<thread::JoinHandle<()>>,
+}
+
+impl PipeHandle {
+    /// Stop the pipe and wait for its thread to exit
+    ///
+    /// An event the thread already took is still transformed and published;
+    /// a partially collected batch is discarded.
+    pub fn stop(mut self) {
+        self.stop.store(true, Ordering::Release);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+    
+    /// Whether the pipe thread is still running
+    pub fn is_running(&self) -> bool {
+        self.thread.as_ref().is_some_and(|thread| !thread.is_finished())
+    }
+}
+
+impl Drop for PipeHandle {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Release);
+    }
+}
+
+/// Outcome of a batch publish that stopped early
+///
+/// `remaining` holds the events that were not sent (the first one is the event
+/// the channel rejected), in their original order, so callers can retry.
+#[derive(Debug)]
+pub struct PartialSendResult<E> {
+    pub sent: usize,
+    pub remaining: Vec<E>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -44,6 +215,9 @@ pub struct TypedEventStats {
     pub published: u64,
     pub received: u64,
     pub subscribers: usize,
+    /// Fan-out copies dropped because a `subscribe_n` or
+    /// `subscribe_load_balanced` receiver was full
+    pub dropped: u64,
 }
 
 impl TypedEventBus {
@@ -51,10 +225,33 @@ impl TypedEventBus {
     pub fn new() -> Self {
         Self {
             channels: Arc::new(DashMap::new()),
+            taps: Arc::new(DashMap::new()),
             stats: Arc::new(DashMap::new()),
+            capacities: Arc::new(HashMap::new()),
+            default_capacity: CHANNEL_CAPACITY,
         }
     }
     
+    /// Builder for a bus with per-type channel capacities
+    pub fn builder() -> TypedEventBusBuilder {
+        TypedEventBusBuilder::new()
+    }
+    
+    /// Capacity configured for `E` with `capacity_for` (`None` if `E` uses the default)
+    pub fn channel_capacity<E: MarketEvent>(&self) -> Option<usize> {
+        self.capacities.get(&TypeId::of::<E>()).copied()
+    }
+    
+    /// Capacity of channels for types without their own
+    pub fn default_capacity(&self) -> usize {
+        self.default_capacity
+    }
+    
+    /// Capacity of channels created for `E`
+    fn capacity_of<E: MarketEvent>(&self) -> usize {
+        self.channel_capacity::<E>().unwrap_or(self.default_capacity)
+    }
+    
     /// Publish event (zero-copy)
     #[inline]
     pub fn publish<E: MarketEvent>(&self, event: E) -> Result<(), SendError<E>> {
@@ -63,8 +260,16 @@ impl TypedEventBus {
         // Get or create channel
         let channel = self.get_or_create_channel::<E>();
         
-        // Send event
-        let result = channel.send(event);
+        // Fan out to subscribe_n / load-balanced receivers first
+        let tapped = self.run_taps(&event);
+        
+        // Send event (skip the shared channel if only taps are listening,
+        // otherwise it would fill up and block publishers)
+        let result = if tapped && channel.receiver_count() == 0 {
+            Ok(())
+        } else {
+            channel.send(event)
+        };
         
         // Update stats
         if result.is_ok() {
@@ -76,6 +281,126 @@ impl TypedEventBus {
         result
     }
     
+    /// Publish event from an async task
+    ///
+    /// Same as `publish`, but waits for capacity without blocking the
+    /// runtime's worker thread when the channel is full.
+    pub async fn publish_async<E: MarketEvent>(&self, event: E) -> Result<(), SendError<E>> {
+        let channel = self.get_or_create_channel::<E>();
+        let tapped = self.run_taps(&event);
+        
+        let result = if tapped && channel.receiver_count() == 0 {
+            Ok(())
+        } else {
+            channel.async_sender().send(event).await
+        };
+        
+        if result.is_ok() {
+            self.stats.entry(TypeId::of::<E>())
+                .or_insert_with(TypedEventStats::default)
+                .published += 1;
+        }
+        
+        result
+    }
+    
+    /// Publish a batch of events, looking up the channel and fan-out hooks once
+    ///
+    /// Never blocks: if the shared channel fills up (or has been closed) the
+    /// batch stops and the unsent events are returned in a
+    /// [`PartialSendResult`]. Fan-out receivers only see events the shared
+    /// channel accepted, so retrying the remainder does not duplicate them.
+    pub fn publish_batch<E: MarketEvent + Clone>(&self, events: Vec<E>) -> Result<usize, PartialSendResult<E>> {
+        let channel = self.get_or_create_channel::<E>();
+        let list = self.tap_list::<E>();
+        let guard = list.as_ref().map(|list| list.taps.read().unwrap());
+        let taps: &[Tap<E>] = match &guard {
+            Some(taps) => taps,
+            None => &[],
+        };
+        
+        // Same rule as `publish`: skip the shared channel when only taps listen
+        let skip_channel = !taps.is_empty() && channel.receiver_count() == 0;
+        
+        let mut sent = 0;
+        let mut dropped = 0;
+        let mut events = events.into_iter();
+        let mut failed = None;
+        for event in events.by_ref() {
+            if skip_channel {
+                dropped += taps.iter().map(|tap| tap.deliver(&event)).sum::<u64>();
+            } else if taps.is_empty() {
+                if let Err(e) = channel.try_send(event) {
+                    failed = Some(e);
+                    break;
+                }
+            } else {
+                let copy = event.clone();
+                if let Err(e) = channel.try_send(event) {
+                    failed = Some(e);
+                    break;
+                }
+                dropped += taps.iter().map(|tap| tap.deliver(&copy)).sum::<u64>();
+            }
+            sent += 1;
+        }
+        
+        let disconnected = taps.iter().any(Tap::is_disconnected);
+        drop(guard);
+        if let Some(list) = &list {
+            self.finish_taps(list, dropped, disconnected);
+        }
+        
+        if sent > 0 {
+            self.stats.entry(TypeId::of::<E>())
+                .or_insert_with(TypedEventStats::default)
+                .published += sent as u64;
+        }
+        
+        match failed {
+            None => Ok(sent),
+            Some(TrySendError::Full(event) | TrySendError::Disconnected(event)) => {
+                let mut remaining = Vec::with_capacity(events.len() + 1);
+                remaining.push(event);
+                remaining.extend(events);
+                Err(PartialSendResult { sent, remaining })
+            }
+        }
+    }
+    
+    /// Take up to `max` events from the type's shared channel without blocking
+    pub fn subscribe_batch<E: MarketEvent>(&self, max: usize) -> Vec<E> {
+        let channel = self.get_or_create_channel::<E>();
+        let mut events = Vec::with_capacity(max.min(channel.len()));
+        while events.len() < max {
+            match channel.try_recv() {
+                Ok(event) => events.push(event),
+                Err(_) => break,
+            }
+        }
+        
+        if !events.is_empty() {
+            self.stats.entry(TypeId::of::<E>())
+                .or_insert_with(TypedEventStats::default)
+                .received += events.len() as u64;
+        }
+        events
+    }
+    
+    /// Subscribe to event type with a receiver that can be awaited
+    ///
+    /// Shares the type's channel with `subscribe`, so receivers compete for
+    /// events.
+    pub fn subscribe_async<E: MarketEvent>(&self) -> AsyncReceiver<E> {
+        let channel = self.get_or_create_channel::<E>();
+        
+        self.stats.entry(TypeId::of::<E>())
+            .or_insert_with(TypedEventStats::default)
+            .subscribers += 1;
+        
+        channel.async_receiver()
+    }
+    
     /// Subscribe to event type
     pub fn subscribe<E: MarketEvent>(&self) -> flume::Receiver<E> {
         let type_id = TypeId::of::<E>();
@@ -89,13 +414,222 @@ impl TypedEventBus {
         channel.receiver()
     }
     
+    /// Subscribe `n` independent receivers, each receiving every event
+    pub fn subscribe_n<E: MarketEvent + Clone + 'static>(&self, n: usize) -> Vec<flume::Receiver<E>> {
+        let capacity = self.capacity_of::<E>();
+        let (senders, receivers): (Vec<_>, Vec<_>) = (0..n)
+            .map(|_| flume::bounded::<E>(capacity))
+            .unzip();
+        
+        self.add_tap::<E>(Tap { senders, next: None, clone: E::clone });
+        
+        self.stats.entry(TypeId::of::<E>())
+            .or_insert_with(TypedEventStats::default)
+            .subscribers += n;
+        
+        receivers
+    }
+    
+    /// Subscribe `n` receivers that split the stream between them
+    ///
+    /// Each event is delivered to exactly one receiver, rotating through the
+    /// receivers in order and skipping any that have been dropped. Unlike
+    /// [`subscribe`](Self::subscribe), the split is deterministic and does not
+    /// depend on which consumer polls first.
+    pub fn subscribe_load_balanced<E: MarketEvent + Clone + 'static>(&self, n: usize) -> Vec<flume::Receiver<E>> {
+        let capacity = self.capacity_of::<E>();
+        let (senders, receivers): (Vec<_>, Vec<_>) = (0..n)
+            .map(|_| flume::bounded::<E>(capacity))
+            .unzip();
+        
+        if n > 0 {
+            self.add_tap::<E>(Tap { senders, next: Some(AtomicUsize::new(0)), clone: E::clone });
+        }
+        
+        self.stats.entry(TypeId::of::<E>())
+            .or_insert_with(TypedEventStats::default)
+            .subscribers += n;
+        
+        receivers
+    }
+    
+    /// Subscribe to a transformed stream of `E`
+    ///
+    /// A background thread takes events from the type's shared channel (like
+    /// [`subscribe`](Self::subscribe)), applies `f` and forwards the result.
+    /// The thread exits once the returned receiver is dropped.
+    pub fn subscribe_map<E, F, R>(&self, f: F) -> flume::Receiver<R>
+    where
+        E: MarketEvent + Send + 'static,
+        F: Fn(E) -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        self.subscribe_filter_map(move |event: E| Some(f(event)))
+    }
+    
+    /// Subscribe to a transformed stream of `E`, dropping events mapped to `None`
+    pub fn subscribe_filter_map<E, F, R>(&self, f: F) -> flume::Receiver<R>
+    where
+        E: MarketEvent + Send + 'static,
+        F: Fn(E) -> Option<R> + Send + 'static,
+        R: Send + 'static,
+    {
+        let source = self.subscribe::<E>();
+        let (tx, rx) = flume::bounded(source.capacity().unwrap_or(self.default_capacity));
+        
+        thread::spawn(move || loop {
+            match source.recv_timeout(PIPELINE_POLL_INTERVAL) {
+                Ok(event) => {
+                    if let Some(mapped) = f(event) {
+                        if tx.send(mapped).is_err() {
+                            break;
+                        }
+                    }
+                }
+                Err(flume::RecvTimeoutError::Timeout) => {
+                    if tx.is_disconnected() {
+                        break;
+                    }
+                }
+                Err(flume::RecvTimeoutError::Disconnected) => break,
+            }
+        });
+        
+        rx
+    }
+    
+    /// Publish `transform(event)` for each `E` taken from the shared channel
+    ///
+    /// Like [`subscribe_map`](Self::subscribe_map), the pipe competes with
+    /// other `subscribe` receivers for `E`; `None` results are dropped.
+    /// Publishing blocks while the `F` channel is full, as with `publish`.
+    ///
+    /// # Panics
+    ///
+    /// If `E` and `F` are the same type (the pipe would consume its own output).
+    pub fn pipe<E, F, T>(&self, transform: T) -> PipeHandle
+    where
+        E: MarketEvent + Send + 'static,
+        F: MarketEvent + Send + 'static,
+        T: Fn(E) -> Option<F> + Send + 'static,
+    {
+        self.spawn_pipe(transform)
+    }
+    
+    /// Publish `transform(batch)` for every `batch_size` consecutive `E`s
+    ///
+    /// Same as [`pipe`](Self::pipe) otherwise.
+    ///
+    /// # Panics
+    ///
+    /// If `batch_size` is zero or `E` and `F` are the same type.
+    pub fn pipe_batch<E, F, T>(&self, batch_size: usize, transform: T) -> PipeHandle
+    where
+        E: MarketEvent + Send + 'static,
+        F: MarketEvent + Send + 'static,
+        T: Fn(Vec<E>) -> Option<F> + Send + 'static,
+    {
+        assert!(batch_size > 0, "pipe batch size must be non-zero");
+        let mut batch = Vec::with_capacity(batch_size);
+        self.spawn_pipe(move |event: E| {
+            batch.push(event);
+            if batch.len() < batch_size {
+                return None;
+            }
+            transform(std::mem::replace(&mut batch, Vec::with_capacity(batch_size)))
+        })
+    }
+    
+    fn spawn_pipe<E, F, T>(&self, mut step: T) -> PipeHandle
+    where
+        E: MarketEvent + Send + 'static,
+        F: MarketEvent + Send + 'static,
+        T: FnMut(E) -> Option<F> + Send + 'static,
+    {
+        assert!(
+            TypeId::of::<E>() != TypeId::of::<F>(),
+            "a pipe cannot publish to its own input type"
+        );
+        let source = self.subscribe::<E>();
+        let bus = self.clone();
+        let stop = Arc::new(AtomicBool::new(false));
+        let stopped = stop.clone();
+        
+        let thread = thread::spawn(move || {
+            while !stopped.load(Ordering::Acquire) {
+                match source.recv_timeout(PIPELINE_POLL_INTERVAL) {
+                    Ok(event) => {
+                        if let Some(output) = step(event) {
+                            if bus.publish(output).is_err() {
+                                break;
+                            }
+                        }
+                    }
+                    Err(flume::RecvTimeoutError::Timeout) => {}
+                    Err(flume::RecvTimeoutError::Disconnected) => break,
+                }
+            }
+        });
+        
+        PipeHandle { stop, thread: Some(thread) }
+    }
+    
+    /// Register a fan-out hook for event type `E`
+    fn add_tap<E: MarketEvent>(&self, tap: Tap<E>) {
+        let arc_any = self.taps.entry(TypeId::of::<E>())
+            .or_insert_with(|| {
+                Arc::new(TapList::<E> { taps: RwLock::new(Vec::new()) }) as Arc<dyn std::any::Any + Send + Sync>
+            })
+            .clone();
+        
+        let list = arc_any.downcast::<TapList<E>>()
+            .expect("Type mismatch in tap registry");
+        list.taps.write().unwrap().push(tap);
+    }
+    
+    /// Invoke fan-out hooks for an event, returning true if any are registered
+    #[inline]
+    fn run_taps<E: MarketEvent>(&self, event: &E) -> bool {
+        let list = match self.tap_list::<E>() {
+            Some(list) => list,
+            None => return false,
+        };
+        
+        let taps = list.taps.read().unwrap();
+        let dropped = taps.iter().map(|tap| tap.deliver(event)).sum();
+        let disconnected = taps.iter().any(Tap::is_disconnected);
+        let tapped = !taps.is_empty();
+        drop(taps);
+        
+        self.finish_taps(&list, dropped, disconnected);
+        tapped
+    }
+    
+    /// Count copies the fan-out hooks dropped and prune hooks nobody listens to
+    fn finish_taps<E: MarketEvent>(&self, list: &TapList<E>, dropped: u64, disconnected: bool) {
+        if dropped > 0 {
+            self.stats.entry(TypeId::of::<E>())
+                .or_insert_with(TypedEventStats::default)
+                .dropped += dropped;
+        }
+        if disconnected {
+            list.taps.write().unwrap().retain(|tap| !tap.is_disconnected());
+        }
+    }
+    
+    /// Fan-out hooks registered for event type `E`, if any
+    fn tap_list<E: MarketEvent>(&self) -> Option<Arc<TapList<E>>> {
+        let arc_any = self.taps.get(&TypeId::of::<E>())?.clone();
+        Some(arc_any.downcast::<TapList<E>>().expect("Type mismatch in tap registry"))
+    }
+    
     /// Get or create channel for event type
     fn get_or_create_channel<E: MarketEvent>(&self) -> Arc<FastChannel<E>> {
         let type_id = TypeId::of::<E>();
         
         let arc_any = self.channels.entry(type_id)
             .or_insert_with(|| {
-                let channel = FastChannel::<E>::bounded(100_000);
+                let channel = FastChannel::<E>::bounded(self.capacity_of::<E>());
                 Arc::new(channel) as Arc<dyn std::any::Any + Send + Sync>
             })
             .clone();
@@ -126,7 +660,10 @@ impl Clone for TypedEventBus {
     fn clone(&self) -> Self {
         Self {
             channels: self.channels.clone(),
+            taps: self.taps.clone(),
             stats: self.stats.clone(),
+            capacities: self.capacities.clone(),
+            default_capacity: self.default_capacity,
         }
     }
 }
@@ -226,4 +763,305 @@ mod tests {
         let trade = rx.recv().unwrap();
         assert_eq!(trade.trade_id, 1);
     }
+    
+    #[test]
+    fn test_typed_bus_subscribe_n() {
+        let bus = TypedEventBus::new();
+        let receivers = bus.subscribe_n::<TradeV2>(3);
+        
+        for id in 0..5 {
+            bus.publish(create_test_trade(id)).unwrap();
+        }
+        
+        // Every receiver sees every event, in order
+        for rx in &receivers {
+            let ids: Vec<u64> = rx.try_iter().map(|t| t.trade_id).collect();
+            assert_eq!(ids, vec![0, 1, 2, 3, 4]);
+        }
+        assert_eq!(bus.stats::<TradeV2>().unwrap().subscribers, 3);
+    }
+    
+    #[test]
+    fn test_typed_bus_load_balanced() {
+        let bus = TypedEventBus::new();
+        let workers = bus.subscribe_load_balanced::<TradeV2>(3);
+        
+        for id in 0..9 {
+            bus.publish(create_test_trade(id)).unwrap();
+        }
+        
+        let mut seen = Vec::new();
+        for (worker, rx) in workers.iter().enumerate() {
+            let ids: Vec<u64> = rx.try_iter().map(|t| t.trade_id).collect();
+            
+            // Round-robin: worker k gets events k, k+3, k+6
+            assert_eq!(ids, vec![worker as u64, worker as u64 + 3, worker as u64 + 6]);
+            seen.extend(ids);
+        }
+        
+        // Each event delivered to exactly one worker
+        seen.sort_unstable();
+        assert_eq!(seen, (0..9).collect::<Vec<u64>>());
+    }
+    
+    #[test]
+    fn test_fan_out_never_blocks_and_prunes_dropped_receivers() {
+        let bus = TypedEventBus::builder().capacity_for::<TradeV2>(2).build();
+        let copies = bus.subscribe_n::<TradeV2>(2);
+        let workers = bus.subscribe_load_balanced::<TradeV2>(2);
+        drop(workers);
+        
+        // A full receiver costs a dropped copy instead of blocking the publisher
+        for id in 0..3 {
+            bus.publish(create_test_trade(id)).unwrap();
+        }
+        assert_eq!(bus.stats::<TradeV2>().unwrap().dropped, 2);
+        let ids: Vec<u64> = copies[0].try_iter().map(|t| t.trade_id).collect();
+        assert_eq!(ids, vec![0, 1]);
+        
+        // The load-balanced tap lost all its receivers and was pruned
+        assert_eq!(bus.tap_list::<TradeV2>().unwrap().taps.read().unwrap().len(), 1);
+        
+        drop(copies);
+        bus.publish(create_test_trade(3)).unwrap();
+        assert!(bus.tap_list::<TradeV2>().unwrap().taps.read().unwrap().is_empty());
+    }
+    
+    #[test]
+    fn test_typed_bus_taps_and_shared_channel() {
+        let bus = TypedEventBus::new();
+        let shared = bus.subscribe::<TradeV2>();
+        let copies = bus.subscribe_n::<TradeV2>(2);
+        
+        bus.publish(create_test_trade(7)).unwrap();
+        
+        assert_eq!(shared.recv().unwrap().trade_id, 7);
+        for rx in &copies {
+            assert_eq!(rx.recv().unwrap().trade_id, 7);
+        }
+    }
+    
+    #[test]
+    fn test_typed_bus_subscribe_map() {
+        let bus = TypedEventBus::new();
+        let spreads = bus.subscribe_map(|quote: QuoteV2| {
+            (quote.ask_price.to_float() - quote.bid_price.to_float(), quote.timestamp.nanos())
+        });
+        
+        for id in 0..3 {
+            bus.publish(create_test_quote(id)).unwrap();
+        }
+        
+        for id in 0..3 {
+            let (spread, ts) = spreads.recv_timeout(Duration::from_secs(1)).unwrap();
+            assert!((spread - 0.25).abs() < 1e-9);
+            assert_eq!(ts, id as i64);
+        }
+    }
+    
+    #[test]
+    fn test_typed_bus_subscribe_filter_map() {
+        let bus = TypedEventBus::new();
+        let even_ids = bus.subscribe_filter_map(|trade: TradeV2| {
+            (trade.trade_id % 2 == 0).then_some(trade.trade_id)
+        });
+        
+        for id in 0..10 {
+            bus.publish(create_test_trade(id)).unwrap();
+        }
+        
+        let ids: Vec<u64> = (0..5)
+            .map(|_| even_ids.recv_timeout(Duration::from_secs(1)).unwrap())
+            .collect();
+        assert_eq!(ids, vec![0, 2, 4, 6, 8]);
+    }
+    
+    #[test]
+    fn test_typed_bus_pipeline_thread_exits() {
+        let bus = TypedEventBus::new();
+        let channel = bus.get_or_create_channel::<TradeV2>();
+        
+        let mapped = bus.subscribe_map(|trade: TradeV2| trade.trade_id);
+        assert_eq!(channel.receiver_count(), 1);
+        
+        drop(mapped);
+        
+        // The worker notices the dropped output and releases its source receiver
+        let deadline = std::time::Instant::now() + Duration::from_secs(1);
+        while channel.receiver_count() > 0 {
+            assert!(std::time::Instant::now() < deadline, "pipeline thread did not exit");
+            thread::sleep(PIPELINE_POLL_INTERVAL);
+        }
+    }
+    
+    fn trade_at(id: u64, price: f64) -> TradeV2 {
+        TradeV2 {
+            price: Price::from_float(price),
+            ..create_test_trade(id)
+        }
+    }
+    
+    #[test]
+    fn test_typed_bus_pipe() {
+        let bus = TypedEventBus::new();
+        let quotes = bus.subscribe::<QuoteV2>();
+        
+        // Quote a quarter either side of every even trade
+        let pipe = bus.pipe(|trade: TradeV2| {
+            (trade.trade_id % 2 == 0).then(|| QuoteV2 {
+                bid_price: Price::from_float(trade.price.to_float() - 0.25),
+                ask_price: Price::from_float(trade.price.to_float() + 0.25),
+                ..create_test_quote(trade.trade_id)
+            })
+        });
+        assert!(pipe.is_running());
+        
+        for id in 0..6 {
+            bus.publish(trade_at(id, 100.0 + id as f64)).unwrap();
+        }
+        for id in [0, 2, 4] {
+            let quote = quotes.recv_timeout(Duration::from_secs(1)).unwrap();
+            assert_eq!(quote.timestamp.nanos(), id as i64);
+            assert!((quote.bid_price.to_float() - (99.75 + id as f64)).abs() < 1e-9);
+            assert!((quote.ask_price.to_float() - (100.25 + id as f64)).abs() < 1e-9);
+        }
+        
+        // Stopping releases the pipe's trade receiver
+        pipe.stop();
+        assert_eq!(bus.get_or_create_channel::<TradeV2>().receiver_count(), 0);
+        assert!(quotes.try_recv().is_err());
+    }
+    
+    #[test]
+    fn test_typed_bus_pipe_batch_bars() {
+        let bus = TypedEventBus::new();
+        let bars = bus.subscribe::<QuoteV2>();
+        
+        // There is no bar event type, so bars travel as quotes:
+        // bid = low, ask = high, stamped with the last trade
+        let pipe = bus.pipe_batch(5, |trades: Vec<TradeV2>| {
+            let prices = trades.iter().map(|t| t.price.to_float());
+            let low = prices.clone().fold(f64::INFINITY, f64::min);
+            let high = prices.fold(f64::NEG_INFINITY, f64::max);
+            let last = trades.last()?;
+            Some(QuoteV2 {
+                bid_price: Price::from_float(low),
+                ask_price: Price::from_float(high),
+                ..create_test_quote(last.trade_id)
+            })
+        });
+        
+        let prices = [100.0, 101.0, 99.5, 100.5, 100.25, 102.0, 101.75, 103.5, 102.25, 102.5, 104.0];
+        for (id, price) in prices.iter().enumerate() {
+            bus.publish(trade_at(id as u64, *price)).unwrap();
+        }
+        
+        for (last_id, low, high) in [(4, 99.5, 101.0), (9, 101.75, 103.5)] {
+            let bar = bars.recv_timeout(Duration::from_secs(1)).unwrap();
+            assert_eq!(bar.timestamp.nanos(), last_id);
+            assert!((bar.bid_price.to_float() - low).abs() < 1e-9);
+            assert!((bar.ask_price.to_float() - high).abs() < 1e-9);
+        }
+        
+        // The 11th trade starts a bar that never completes
+        assert!(bars.recv_timeout(PIPELINE_POLL_INTERVAL * 5).is_err());
+        pipe.stop();
+    }
+    
+    #[test]
+    #[should_panic(expected = "own input type")]
+    fn test_typed_bus_pipe_rejects_loop() {
+        TypedEventBus::new().pipe(|trade: TradeV2| Some(trade));
+    }
+    
+    #[test]
+    fn test_typed_bus_publish_batch() {
+        let bus = TypedEventBus::new();
+        let rx = bus.subscribe::<TradeV2>();
+        let fan_out = bus.subscribe_n::<TradeV2>(1);
+        
+        let trades: Vec<TradeV2> = (0..256).map(create_test_trade).collect();
+        assert_eq!(bus.publish_batch(trades).unwrap(), 256);
+        assert_eq!(bus.stats::<TradeV2>().unwrap().published, 256);
+        assert_eq!(fan_out[0].len(), 256);
+        
+        let first = bus.subscribe_batch::<TradeV2>(100);
+        assert_eq!(first.len(), 100);
+        assert_eq!(first[99].trade_id, 99);
+        assert_eq!(rx.len(), 156);
+        assert_eq!(bus.subscribe_batch::<TradeV2>(1_000).len(), 156);
+        assert!(bus.subscribe_batch::<TradeV2>(10).is_empty());
+        assert_eq!(bus.stats::<TradeV2>().unwrap().received, 256);
+    }
+    
+    #[test]
+    fn test_typed_bus_publish_batch_partial() {
+        let bus = TypedEventBus::new();
+        let _rx = bus.subscribe::<TradeV2>();
+        
+        // Fill the shared channel to one slot below capacity
+        let filler: Vec<TradeV2> = (0..CHANNEL_CAPACITY as u64 - 1).map(create_test_trade).collect();
+        assert_eq!(bus.publish_batch(filler).unwrap(), CHANNEL_CAPACITY - 1);
+        
+        let batch: Vec<TradeV2> = (0..5).map(|i| create_test_trade(1_000_000 + i)).collect();
+        let partial = bus.publish_batch(batch).unwrap_err();
+        assert_eq!(partial.sent, 1);
+        let remaining: Vec<u64> = partial.remaining.iter().map(|t| t.trade_id).collect();
+        assert_eq!(remaining, vec![1_000_001, 1_000_002, 1_000_003, 1_000_004]);
+        
+        // Retrying after draining sends the rest
+        assert_eq!(bus.subscribe_batch::<TradeV2>(10).len(), 10);
+        assert_eq!(bus.publish_batch(partial.remaining).unwrap(), 4);
+    }
+    
+    #[tokio::test]
+    async fn test_async_publish_subscribe() {
+        let bus = TypedEventBus::new();
+        let rx = bus.subscribe_async::<TradeV2>();
+        
+        let publisher = bus.clone();
+        let producer = tokio::spawn(async move {
+            for i in 0..1_000 {
+                publisher.publish_async(create_test_trade(i)).await.unwrap();
+            }
+        });
+        
+        for i in 0..1_000 {
+            assert_eq!(rx.recv().await.unwrap().trade_id, i);
+        }
+        producer.await.unwrap();
+        
+        let stats = bus.stats::<TradeV2>().unwrap();
+        assert_eq!(stats.published, 1_000);
+        assert_eq!(stats.subscribers, 1);
+    }
+    
+    #[test]
+    fn test_per_type_capacity_backpressure() {
+        let bus = TypedEventBus::builder()
+            .capacity_for::<QuoteV2>(10)
+            .default_capacity(1_000)
+            .build();
+        assert_eq!(bus.channel_capacity::<QuoteV2>(), Some(10));
+        assert_eq!(bus.channel_capacity::<TradeV2>(), None);
+        assert_eq!(bus.default_capacity(), 1_000);
+        assert_eq!(TypedEventBus::new().default_capacity(), CHANNEL_CAPACITY);
+        
+        let _quotes = bus.subscribe::<QuoteV2>();
+        let _trades = bus.subscribe::<TradeV2>();
+        
+        // Quotes fill up after 10, trades use the default
+        let partial = bus.publish_batch((0..15).map(create_test_quote).collect()).unwrap_err();
+        assert_eq!(partial.sent, 10);
+        assert_eq!(partial.remaining.len(), 5);
+        assert_eq!(bus.publish_batch((0..1_000).map(create_test_trade).collect()).unwrap(), 1_000);
+        assert!(bus.publish_batch(vec![create_test_trade(1_000)]).is_err());
+        
+        // Fan-out receivers get the same per-type capacity
+        let fanned = bus.subscribe_n::<QuoteV2>(1);
+        assert_eq!(fanned[0].capacity(), Some(10));
+        
+        // Clones share the configuration
+        assert_eq!(bus.clone().channel_capacity::<QuoteV2>(), Some(10));
+    }
 }
@@ -0,0 +1,73 @@
+//! Publisher-side backpressure against slow and blocked workers
+
+mod common;
+
+use common::quote;
+use hft_event_bus::{BackpressureEventBus, MarketDataEvent, StaticEventType};
+use std::time::{Duration, Instant};
+
+#[tokio::test]
+async fn test_timeout_when_all_workers_blocked() {
+    let bus = BackpressureEventBus::new(2);
+    let mut workers = Vec::new();
+    for _ in 0..3 {
+        workers.push(bus.subscribe(MarketDataEvent::EVENT_TYPE).await);
+    }
+
+    for i in 0..2 {
+        let receipt = bus.publish_with_timeout(quote("ES", i as f64), Duration::from_millis(5)).await.unwrap();
+        assert_eq!(receipt.subscriber_count, 3);
+    }
+
+    let timeout = Duration::from_millis(20);
+    let start = Instant::now();
+    let err = bus.publish_with_timeout(quote("ES", 2.0), timeout).await.unwrap_err();
+    let elapsed = start.elapsed();
+
+    assert_eq!(err.event_type, MarketDataEvent::EVENT_TYPE);
+    assert_eq!((err.subscribers, err.blocked), (3, 3));
+    // One deadline shared by all workers, not one timeout each
+    assert!(elapsed >= timeout, "returned early: {:?}", elapsed);
+    assert!(elapsed < timeout * 3, "waited per worker: {:?}", elapsed);
+}
+
+#[tokio::test]
+async fn test_partial_delivery_reports_blocked_workers() {
+    let bus = BackpressureEventBus::new(1);
+    let mut fast = bus.subscribe(MarketDataEvent::EVENT_TYPE).await;
+    let _blocked = bus.subscribe(MarketDataEvent::EVENT_TYPE).await;
+
+    bus.publish_with_timeout(quote("ES", 1.0), Duration::from_millis(5)).await.unwrap();
+    assert_eq!(fast.recv().await.unwrap().event.downcast_ref::<MarketDataEvent>().unwrap().price, 1.0);
+
+    let err = bus.publish_with_timeout(quote("ES", 2.0), Duration::from_millis(5)).await.unwrap_err();
+    assert_eq!((err.subscribers, err.blocked), (2, 1));
+    // Workers with room still got the event
+    assert_eq!(fast.recv().await.unwrap().event.downcast_ref::<MarketDataEvent>().unwrap().price, 2.0);
+}
+
+#[tokio::test]
+async fn test_waits_for_slow_worker() {
+    let bus = BackpressureEventBus::new(1);
+    let mut worker = bus.subscribe(MarketDataEvent::EVENT_TYPE).await;
+
+    let consumer = tokio::spawn(async move {
+        let mut prices = Vec::new();
+        while let Some(envelope) = worker.recv().await {
+            prices.push(envelope.event.downcast_ref::<MarketDataEvent>().unwrap().price);
+            if prices.len() == 5 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(2)).await;
+        }
+        prices
+    });
+
+    // Each publish waits for the worker to free its single slot
+    for i in 0..5 {
+        let receipt = bus.publish_with_timeout(quote("ES", i as f64), Duration::from_secs(1)).await.unwrap();
+        assert_eq!(receipt.subscriber_count, 1);
+    }
+    assert_eq!(consumer.await.unwrap(), vec![0.0, 1.0, 2.0, 3.0, 4.0]);
+    assert_eq!(bus.dropped_count(), 0);
+}
@@ -0,0 +1,85 @@
+//! Integration tests against a real broker (see `docker-compose.yml`)
+//!
+//! Ignored by default; set `KAFKA_BROKERS` to use a broker other than
+//! `localhost:9092`.
+#![cfg(feature = "kafka")]
+
+mod common;
+
+use common::quote;
+use hft_event_bus::{EventBus, KafkaBridge, KafkaBridgeConfig, MarketDataEvent, StaticEventType};
+use std::time::Duration;
+use uuid::Uuid;
+
+fn config(prefix: &str, outbound: &[&str], inbound: &[&str]) -> KafkaBridgeConfig {
+    KafkaBridgeConfig {
+        brokers: std::env::var("KAFKA_BROKERS").unwrap_or_else(|_| "localhost:9092".to_string()),
+        topic_prefix: prefix.to_string(),
+        consumer_group: format!("hft-event-bus-test-{}", Uuid::new_v4()),
+        outbound_types: outbound.iter().map(|t| t.to_string()).collect(),
+        inbound_types: inbound.iter().map(|t| t.to_string()).collect(),
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires a Kafka broker"]
+async fn test_events_cross_between_buses() {
+    let prefix = format!("test-{}", Uuid::new_v4());
+    let source = EventBus::new();
+    let sink = EventBus::new();
+    let producer = KafkaBridge::new(source.clone(), config(&prefix, &[MarketDataEvent::EVENT_TYPE], &[])).unwrap();
+    let consumer = KafkaBridge::new(sink.clone(), config(&prefix, &[], &[MarketDataEvent::EVENT_TYPE])).unwrap();
+    let _producing = producer.run();
+    let _consuming = consumer.run();
+    let mut received = sink.subscribe_market_data().await;
+    
+    // The consumer starts at the latest offset, so keep publishing until one arrives
+    let envelope = tokio::time::timeout(Duration::from_secs(30), async {
+        loop {
+            source.publish(quote("ES", 6000.0)).await.unwrap();
+            if let Ok(Ok(envelope)) = tokio::time::timeout(Duration::from_millis(500), received.recv()).await {
+                return envelope;
+            }
+        }
+    })
+    .await
+    .expect("no event crossed the bridge");
+    
+    let event = envelope.event.downcast_ref::<MarketDataEvent>().unwrap();
+    assert_eq!(event.symbol, "ES");
+    assert_eq!(event.price, 6000.0);
+}
+
+#[tokio::test]
+#[ignore = "requires a Kafka broker"]
+async fn test_bidirectional_bridge_does_not_echo() {
+    let prefix = format!("test-{}", Uuid::new_v4());
+    let types = [MarketDataEvent::EVENT_TYPE];
+    let left = EventBus::new();
+    let right = EventBus::new();
+    let left_bridge = KafkaBridge::new(left.clone(), config(&prefix, &types, &types)).unwrap();
+    let right_bridge = KafkaBridge::new(right.clone(), config(&prefix, &types, &types)).unwrap();
+    let _left = left_bridge.run();
+    let _right = right_bridge.run();
+    let mut on_left = left.subscribe_market_data().await;
+    let mut on_right = right.subscribe_market_data().await;
+    
+    let crossed = tokio::time::timeout(Duration::from_secs(30), async {
+        loop {
+            left.publish(quote("ES", 1.0)).await.unwrap();
+            if let Ok(Ok(envelope)) = tokio::time::timeout(Duration::from_millis(500), on_right.recv()).await {
+                return envelope;
+            }
+        }
+    })
+    .await
+    .expect("no event crossed the bridge");
+    assert_eq!(crossed.event.downcast_ref::<MarketDataEvent>().unwrap().price, 1.0);
+    
+    // Left only ever sees its own publishes, never copies bounced back by the right bridge
+    tokio::time::sleep(Duration::from_secs(2)).await;
+    let mut seen = std::collections::HashSet::new();
+    while let Ok(envelope) = on_left.try_recv() {
+        assert!(seen.insert(envelope.id), "event echoed back to its source bus");
+    }
+}
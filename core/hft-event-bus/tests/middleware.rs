@@ -0,0 +1,102 @@
+//! Publish-time middleware on a live bus
+
+mod common;
+
+use common::quote;
+use hft_event_bus::{
+    EventBus, EventEnvelope, LoggingMiddleware, MarketDataEvent, MiddlewareResult, RateLimitMiddleware,
+    SharedClock, StaticEventType,
+};
+use tracing::Level;
+
+fn price(envelope: &EventEnvelope) -> f64 {
+    envelope.event.downcast_ref::<MarketDataEvent>().unwrap().price
+}
+
+#[tokio::test]
+async fn test_rate_limit_per_second() {
+    let clock = SharedClock::new();
+    let bus = EventBus::with_shared_clock(clock.clone());
+    bus.add_middleware(RateLimitMiddleware::new(3));
+    let mut rx = bus.subscribe(MarketDataEvent::EVENT_TYPE).await;
+
+    for i in 0..5 {
+        bus.publish(quote("ES", i as f64)).await.unwrap();
+    }
+    // Budget resets in the next second
+    clock.advance_to(1_000_000_000);
+    let receipt = bus.publish(quote("ES", 5.0)).await.unwrap();
+    assert_eq!(receipt.subscriber_count, 1);
+
+    let mut received = Vec::new();
+    while let Ok(envelope) = rx.try_recv() {
+        received.push(price(&envelope));
+    }
+    assert_eq!(received, vec![0.0, 1.0, 2.0, 5.0]);
+
+    let stats = bus.get_stats().into_iter().find(|(t, _)| t == MarketDataEvent::EVENT_TYPE).unwrap().1;
+    assert_eq!(stats.published, 4);
+    assert_eq!(stats.filtered, 2);
+}
+
+#[tokio::test]
+async fn test_logging_passes_events_through() {
+    let bus = EventBus::new();
+    bus.add_middleware(LoggingMiddleware::new(Level::INFO));
+    bus.add_middleware(LoggingMiddleware::new(Level::TRACE));
+    let mut rx = bus.subscribe(MarketDataEvent::EVENT_TYPE).await;
+
+    let receipt = bus.publish(quote("ES", 6000.0)).await.unwrap();
+    let envelope = rx.recv().await.unwrap();
+    assert_eq!(envelope.id, receipt.event_id);
+    assert_eq!(price(&envelope), 6000.0);
+}
+
+#[tokio::test]
+async fn test_chained_middleware() {
+    let bus = EventBus::with_shared_clock(SharedClock::new());
+    // Drop NQ, reprice ES, then cap the (post-filter) rate
+    bus.add_middleware(|envelope: &mut EventEnvelope| {
+        match envelope.event.downcast_ref::<MarketDataEvent>() {
+            Some(tick) if tick.symbol == "NQ" => MiddlewareResult::Drop,
+            _ => MiddlewareResult::Continue,
+        }
+    });
+    bus.add_middleware(|envelope: &mut EventEnvelope| {
+        match envelope.event.downcast_ref::<MarketDataEvent>() {
+            Some(tick) => {
+                let mut repriced = EventEnvelope::new(quote(&tick.symbol, tick.price * 2.0), 1);
+                repriced.timestamp_ns = envelope.timestamp_ns;
+                MiddlewareResult::Transform(repriced)
+            }
+            None => MiddlewareResult::Continue,
+        }
+    });
+    bus.add_middleware(RateLimitMiddleware::new(2));
+    bus.add_middleware(LoggingMiddleware::new(Level::DEBUG));
+
+    let mut rx = bus.subscribe(MarketDataEvent::EVENT_TYPE).await;
+    let mut typed = bus.subscribe_typed::<MarketDataEvent>();
+
+    bus.publish(quote("NQ", 21000.0)).await.unwrap();
+    let delivered = bus
+        .publish_batch(&[quote("ES", 1.0), quote("NQ", 2.0), quote("ES", 3.0), quote("ES", 4.0)])
+        .await
+        .unwrap();
+    assert_eq!(delivered, 2);
+
+    let first = rx.recv().await.unwrap();
+    assert_eq!(price(&first), 2.0);
+    assert_eq!(first.priority, 1);
+    assert_eq!(price(&rx.recv().await.unwrap()), 6.0);
+    assert!(rx.try_recv().is_err());
+
+    // Typed subscribers see the transformed events only
+    assert_eq!(typed.recv().await.unwrap().price, 2.0);
+    assert_eq!(typed.recv().await.unwrap().price, 6.0);
+    assert!(typed.try_recv().is_none());
+
+    bus.clear_middleware();
+    bus.publish(quote("NQ", 21000.0)).await.unwrap();
+    assert_eq!(price(&rx.recv().await.unwrap()), 21000.0);
+}
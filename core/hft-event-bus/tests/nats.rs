@@ -0,0 +1,224 @@
+//! Integration tests against a real NATS server
+//!
+//! Ignored by default. Each test starts its own `nats-server` with
+//! JetStream enabled (set `NATS_SERVER` to the binary if it is not on
+//! `PATH`):
+//!
+//!   cargo test --features nats --test nats -- --ignored
+#![cfg(feature = "nats")]
+
+mod common;
+
+use common::quote;
+use hft_event_bus::{EventBus, EventEnvelope, MarketDataEvent, NatsBridge, NatsBridgeConfig, StaticEventType};
+use std::net::{TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::process::{Child, Command, Stdio};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+/// `nats-server` child process, killed on drop
+struct NatsServer {
+    child: Child,
+    port: u16,
+    store_dir: PathBuf,
+}
+
+impl NatsServer {
+    fn spawn(port: u16, store_dir: &PathBuf) -> Child {
+        let binary = std::env::var("NATS_SERVER").unwrap_or_else(|_| "nats-server".to_string());
+        let child = Command::new(binary)
+            .args(["-js", "-a", "127.0.0.1", "-p", &port.to_string(), "-sd"])
+            .arg(store_dir)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("failed to start nats-server");
+        
+        for _ in 0..100 {
+            if TcpStream::connect(("127.0.0.1", port)).is_ok() {
+                return child;
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        panic!("nats-server did not start on port {}", port);
+    }
+    
+    fn url(&self) -> String {
+        format!("nats://127.0.0.1:{}", self.port)
+    }
+    
+    /// Kill the server and start it again on the same port and store
+    fn restart(&mut self) {
+        self.child.kill().unwrap();
+        self.child.wait().unwrap();
+        self.child = Self::spawn(self.port, &self.store_dir);
+    }
+}
+
+impl Drop for NatsServer {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        let _ = std::fs::remove_dir_all(&self.store_dir);
+    }
+}
+
+/// Start a JetStream-enabled server on a free port
+fn setup() -> NatsServer {
+    let port = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+    let store_dir = std::env::temp_dir().join(format!("hft-event-bus-nats-{}", Uuid::new_v4()));
+    let child = NatsServer::spawn(port, &store_dir);
+    NatsServer { child, port, store_dir }
+}
+
+fn config(prefix: &str, outbound: &[&str], inbound: &[&str], durable_name: Option<&str>) -> NatsBridgeConfig {
+    NatsBridgeConfig {
+        subject_prefix: prefix.to_string(),
+        outbound_types: outbound.iter().map(|t| t.to_string()).collect(),
+        inbound_subjects: inbound.iter().map(|t| format!("{}.{}", prefix, t)).collect(),
+        durable_name: durable_name.map(str::to_string),
+    }
+}
+
+/// Keep publishing on `source` until an event arrives on `received`
+///
+/// The consumer only delivers messages published after it is created, so
+/// early publishes may be lost.
+async fn publish_until_received(
+    source: &EventBus,
+    received: &mut broadcast::Receiver<EventEnvelope>,
+    price: f64,
+) -> EventEnvelope {
+    tokio::time::timeout(Duration::from_secs(30), async {
+        loop {
+            source.publish(quote("ES", price)).await.unwrap();
+            if let Ok(Ok(envelope)) = tokio::time::timeout(Duration::from_millis(500), received.recv()).await {
+                return envelope;
+            }
+        }
+    })
+    .await
+    .expect("no event crossed the bridge")
+}
+
+#[tokio::test]
+#[ignore = "requires nats-server"]
+async fn test_events_cross_between_buses() {
+    let server = setup();
+    let types = [MarketDataEvent::EVENT_TYPE];
+    let source = EventBus::new();
+    let sink = EventBus::new();
+    let publisher = NatsBridge::new(
+        source.clone(),
+        async_nats::connect(server.url()).await.unwrap(),
+        config("test", &types, &[], None),
+    );
+    let consumer = NatsBridge::new(
+        sink.clone(),
+        async_nats::connect(server.url()).await.unwrap(),
+        config("test", &[], &types, None),
+    );
+    let _publishing = publisher.run();
+    let _consuming = consumer.run();
+    let mut received = sink.subscribe_market_data().await;
+    
+    let envelope = publish_until_received(&source, &mut received, 6000.0).await;
+    let event = envelope.event.downcast_ref::<MarketDataEvent>().unwrap();
+    assert_eq!(event.symbol, "ES");
+    assert_eq!(event.price, 6000.0);
+}
+
+#[tokio::test]
+#[ignore = "requires nats-server"]
+async fn test_bidirectional_bridge_does_not_echo() {
+    let server = setup();
+    let types = [MarketDataEvent::EVENT_TYPE];
+    let left = EventBus::new();
+    let right = EventBus::new();
+    let left_bridge = NatsBridge::new(
+        left.clone(),
+        async_nats::connect(server.url()).await.unwrap(),
+        config("test", &types, &types, None),
+    );
+    let right_bridge = NatsBridge::new(
+        right.clone(),
+        async_nats::connect(server.url()).await.unwrap(),
+        config("test", &types, &types, None),
+    );
+    let _left = left_bridge.run();
+    let _right = right_bridge.run();
+    let mut on_left = left.subscribe_market_data().await;
+    let mut on_right = right.subscribe_market_data().await;
+    
+    let crossed = publish_until_received(&left, &mut on_right, 1.0).await;
+    assert_eq!(crossed.event.downcast_ref::<MarketDataEvent>().unwrap().price, 1.0);
+    
+    // Left only ever sees its own publishes, never copies bounced back by the right bridge
+    tokio::time::sleep(Duration::from_secs(2)).await;
+    let mut seen = std::collections::HashSet::new();
+    while let Ok(envelope) = on_left.try_recv() {
+        assert!(seen.insert(envelope.id), "event echoed back to its source bus");
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires nats-server"]
+async fn test_bridge_survives_server_restart() {
+    let mut server = setup();
+    let types = [MarketDataEvent::EVENT_TYPE];
+    let source = EventBus::new();
+    let sink = EventBus::new();
+    let publisher = NatsBridge::new(
+        source.clone(),
+        async_nats::connect(server.url()).await.unwrap(),
+        config("test", &types, &[], None),
+    );
+    // A durable consumer is kept in the server's store across the restart
+    let consumer = NatsBridge::new(
+        sink.clone(),
+        async_nats::connect(server.url()).await.unwrap(),
+        config("test", &[], &types, Some("restart-test")),
+    );
+    let _publishing = publisher.run();
+    let _consuming = consumer.run();
+    let mut received = sink.subscribe_market_data().await;
+    publish_until_received(&source, &mut received, 1.0).await;
+    
+    server.restart();
+    
+    // Skip anything still in flight from before the restart
+    let envelope = tokio::time::timeout(Duration::from_secs(30), async {
+        loop {
+            let envelope = publish_until_received(&source, &mut received, 2.0).await;
+            if envelope.event.downcast_ref::<MarketDataEvent>().unwrap().price == 2.0 {
+                return envelope;
+            }
+        }
+    })
+    .await
+    .expect("no event crossed the bridge after the restart");
+    assert_eq!(envelope.event.downcast_ref::<MarketDataEvent>().unwrap().price, 2.0);
+}
+
+#[tokio::test]
+#[ignore = "requires nats-server"]
+async fn test_stop() {
+    let server = setup();
+    let types = [MarketDataEvent::EVENT_TYPE];
+    let bus = EventBus::new();
+    let bridge = NatsBridge::new(
+        bus.clone(),
+        async_nats::connect(server.url()).await.unwrap(),
+        config("test", &types, &types, None),
+    );
+    let handle = bridge.run();
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    
+    bridge.stop();
+    let result = tokio::time::timeout(Duration::from_secs(5), handle)
+        .await
+        .expect("bridge did not stop");
+    result.unwrap().unwrap();
+}
@@ -0,0 +1,80 @@
+//! Event types declared with `#[register_event]` load through the `EventRegistry`
+
+use hft_event_bus::{register_event, Event, EventEnvelope, EventRegistry, SerializableEnvelope, StaticEventType};
+
+#[register_event]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpreadAlertEvent {
+    pub symbol: String,
+    pub spread_bps: f64,
+}
+
+#[register_event(name = "venue_halt", priority = 0)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct VenueHalted {
+    pub venue: String,
+    pub reason: Option<String>,
+}
+
+#[register_event]
+#[derive(Debug, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RegimeChangeEvent {
+    Trending { strength: f64 },
+    MeanReverting,
+}
+
+/// Serialize to JSON and rebuild through the registry
+fn roundtrip<T: Event + Clone + 'static>(event: T) -> T {
+    let envelope = EventEnvelope::new(event.clone(), event.priority());
+    let json = serde_json::to_string(&envelope.to_serializable().unwrap()).unwrap();
+    let restored: SerializableEnvelope = serde_json::from_str(&json).unwrap();
+    let restored = restored.into_envelope().unwrap();
+
+    assert_eq!(restored.id, envelope.id);
+    assert_eq!(restored.priority, envelope.priority);
+    restored.event.downcast_ref::<T>().unwrap().clone()
+}
+
+#[test]
+fn test_event_type_names() {
+    assert_eq!(SpreadAlertEvent::EVENT_TYPE, "spread_alert");
+    assert_eq!(VenueHalted::EVENT_TYPE, "venue_halt");
+    assert_eq!(RegimeChangeEvent::EVENT_TYPE, "regime_change");
+
+    let halted = VenueHalted { venue: "CME".to_string(), reason: None };
+    assert_eq!(halted.event_type(), "venue_halt");
+    assert_eq!(halted.priority(), 0);
+    assert_eq!(RegimeChangeEvent::MeanReverting.priority(), 5);
+}
+
+#[test]
+fn test_registered_without_startup_calls() {
+    let types = EventRegistry::registered_types();
+    for event_type in ["spread_alert", "venue_halt", "regime_change", "market_data"] {
+        assert!(types.iter().any(|t| t == event_type), "{} not registered", event_type);
+    }
+    EventRegistry::validate().unwrap();
+}
+
+#[test]
+fn test_roundtrip() {
+    let alert = SpreadAlertEvent { symbol: "ES".to_string(), spread_bps: 1.5 };
+    assert_eq!(roundtrip(alert.clone()), alert);
+
+    let halted = VenueHalted { venue: "CME".to_string(), reason: Some("circuit breaker".to_string()) };
+    assert_eq!(roundtrip(halted.clone()), halted);
+
+    let regime = RegimeChangeEvent::Trending { strength: 0.8 };
+    assert_eq!(roundtrip(regime.clone()), regime);
+    assert_eq!(roundtrip(RegimeChangeEvent::MeanReverting), RegimeChangeEvent::MeanReverting);
+}
+
+#[test]
+fn test_payload_uses_serde_attributes() {
+    let json = RegimeChangeEvent::Trending { strength: 0.8 }.to_json().unwrap();
+    assert_eq!(json, serde_json::json!({ "kind": "trending", "strength": 0.8 }));
+
+    let event = EventRegistry::deserialize("regime_change", serde_json::json!({ "kind": "mean_reverting" })).unwrap();
+    assert_eq!(event.downcast_ref::<RegimeChangeEvent>(), Some(&RegimeChangeEvent::MeanReverting));
+}
@@ -0,0 +1,107 @@
+//! Publish/subscribe behaviour shared by `EventBus` and `ShardedEventBus`
+
+mod common;
+
+use common::quote;
+use hft_event_bus::{
+    EventBus, EventBusTrait, EventEnvelope, HealthEvent, HealthStatus, MarketDataEvent, ShardedEventBus,
+    ShardingConfig, StaticEventType,
+};
+use std::collections::HashMap;
+
+fn health() -> HealthEvent {
+    HealthEvent {
+        timestamp: 0,
+        component: "gateway".to_string(),
+        status: HealthStatus::Healthy,
+        message: String::new(),
+    }
+}
+
+fn sharded() -> ShardedEventBus {
+    ShardedEventBus::from_config(ShardingConfig { shard_count: 4, channel_capacity: 10_000 })
+}
+
+async fn fan_out<B: EventBusTrait>(bus: B) {
+    let receipt = bus.publish(quote("ES", 1.0)).await.unwrap();
+    assert_eq!(receipt.subscriber_count, 0);
+
+    let mut first = bus.subscribe(MarketDataEvent::EVENT_TYPE).await;
+    let mut second = bus.subscribe(MarketDataEvent::EVENT_TYPE).await;
+    let receipt = bus.publish(quote("ES", 2.0)).await.unwrap();
+    assert_eq!(receipt.subscriber_count, 2);
+    assert_eq!(first.recv().await.unwrap().id, receipt.event_id);
+    assert_eq!(second.recv().await.unwrap().id, receipt.event_id);
+}
+
+async fn types_isolated<B: EventBusTrait>(bus: B) {
+    let mut market = bus.subscribe(MarketDataEvent::EVENT_TYPE).await;
+    let mut health_rx = bus.subscribe(HealthEvent::EVENT_TYPE).await;
+
+    bus.publish(quote("ES", 1.0)).await.unwrap();
+    assert!(market.recv().await.is_ok());
+    assert!(health_rx.try_recv().is_err());
+
+    bus.publish(health()).await.unwrap();
+    assert!(health_rx.recv().await.is_ok());
+    assert!(market.try_recv().is_err());
+}
+
+async fn publish_envelope_keeps_id<B: EventBusTrait>(bus: B) {
+    let mut rx = bus.subscribe(HealthEvent::EVENT_TYPE).await;
+    let envelope = EventEnvelope::new(health(), 1);
+    let id = envelope.id;
+    bus.publish_envelope(envelope).await.unwrap();
+    assert_eq!(rx.recv().await.unwrap().id, id);
+}
+
+async fn concurrent_publishers_keep_order<B: EventBusTrait>(bus: B) {
+    let mut rx = bus.subscribe(MarketDataEvent::EVENT_TYPE).await;
+    let publishers: Vec<_> = (0..8)
+        .map(|p| {
+            let bus = bus.clone();
+            tokio::spawn(async move {
+                for i in 0..100 {
+                    bus.publish(quote(format!("P{}", p), i as f64)).await.unwrap();
+                }
+            })
+        })
+        .collect();
+    for publisher in publishers {
+        publisher.await.unwrap();
+    }
+
+    // Each publisher's events arrive in the order it sent them
+    let mut last: HashMap<String, f64> = HashMap::new();
+    for _ in 0..800 {
+        let envelope = rx.recv().await.unwrap();
+        let event = envelope.event.downcast_ref::<MarketDataEvent>().unwrap();
+        let previous = last.insert(event.symbol.clone(), event.price);
+        assert!(previous.map_or(event.price == 0.0, |previous| event.price == previous + 1.0));
+    }
+    assert_eq!(last.len(), 8);
+}
+
+#[tokio::test]
+async fn test_fan_out() {
+    fan_out(EventBus::new()).await;
+    fan_out(sharded()).await;
+}
+
+#[tokio::test]
+async fn test_types_isolated() {
+    types_isolated(EventBus::new()).await;
+    types_isolated(sharded()).await;
+}
+
+#[tokio::test]
+async fn test_publish_envelope_keeps_id() {
+    publish_envelope_keeps_id(EventBus::new()).await;
+    publish_envelope_keeps_id(sharded()).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_concurrent_publishers_keep_order() {
+    concurrent_publishers_keep_order(EventBus::new()).await;
+    concurrent_publishers_keep_order(sharded()).await;
+}